@@ -0,0 +1,457 @@
+//! Chapters of audiobooks and podcasts, read from the `CHAPTERxxx` Vorbis comments of Ogg,
+//! Opus and FLAC files, and from the chapter track or the Nero `chpl` atom of MP4 files.
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub start: Duration,
+    pub title: String,
+}
+
+/// The chapter playing at `position`, none before the first one. Positions are only known to
+/// the second, so a chapter starts at the second it starts in.
+pub fn index_at(chapters: &[Chapter], position: Duration) -> Option<usize> {
+    chapters
+        .iter()
+        .rposition(|chapter| chapter.start.as_secs() <= position.as_secs())
+}
+
+/// Chapters from comments like `CHAPTER001=00:05:30.000` and `CHAPTER001NAME=Intro`.
+pub fn from_comments<'a>(comments: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<Chapter> {
+    let mut found: BTreeMap<u32, (Option<Duration>, Option<String>)> = BTreeMap::new();
+    for (key, value) in comments {
+        let key = key.to_uppercase();
+        let Some(rest) = key.strip_prefix("CHAPTER") else {
+            continue;
+        };
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        let Ok(number) = rest[..digits].parse::<u32>() else {
+            continue;
+        };
+        let entry = found.entry(number).or_default();
+        match &rest[digits..] {
+            "" => entry.0 = parse_time(value),
+            "NAME" => entry.1 = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    let mut chapters: Vec<Chapter> = found
+        .into_iter()
+        .filter_map(|(number, (start, title))| {
+            Some(Chapter {
+                start: start?,
+                title: title.unwrap_or_else(|| format!("Chapter {}", number)),
+            })
+        })
+        .collect();
+    chapters.sort_by_key(|chapter| chapter.start);
+    chapters
+}
+
+// hh:mm:ss.sss, the hours and the fraction can be left out
+fn parse_time(time: &str) -> Option<Duration> {
+    let mut seconds = 0.0;
+    for part in time.trim().split(':') {
+        let part: f64 = part.parse().ok()?;
+        seconds = seconds * 60.0 + part;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Chapters of an MP4 file, from its chapter track as written by iTunes or else from the
+/// `chpl` atom as written by Nero and ffmpeg.
+pub fn read_mp4(path: &Path) -> Result<Vec<Chapter>> {
+    read_mp4_from(&mut BufReader::new(File::open(path)?))
+}
+
+fn read_mp4_from<R: Read + Seek>(reader: &mut R) -> Result<Vec<Chapter>> {
+    let moov = read_moov(reader)?;
+    let mut chapters = chapter_track(reader, &moov).unwrap_or_default();
+    if chapters.is_empty() {
+        chapters = find(&moov, &[b"udta", b"chpl"])
+            .map(parse_chpl)
+            .unwrap_or_default();
+    }
+    Ok(chapters)
+}
+
+// The atoms of the movie are all read, the media data is only read for the chapter titles.
+fn read_moov<R: Read + Seek>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut header = [0; 8];
+    loop {
+        reader.read_exact(&mut header)?;
+        let kind = [header[4], header[5], header[6], header[7]];
+        let (size, header_size) =
+            match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+                0 => bail!("no moov atom"),
+                1 => {
+                    let mut large = [0; 8];
+                    reader.read_exact(&mut large)?;
+                    (u64::from_be_bytes(large), 16)
+                }
+                size => (u64::from(size), 8),
+            };
+        let Some(body_size) = size.checked_sub(header_size) else {
+            bail!("invalid atom size");
+        };
+        if &kind == b"moov" {
+            let mut moov = Vec::new();
+            reader.take(body_size).read_to_end(&mut moov)?;
+            return Ok(moov);
+        }
+        reader.seek(SeekFrom::Current(i64::try_from(body_size)?))?;
+    }
+}
+
+// The atoms inside `data`, as their type and body.
+fn atoms(mut data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    std::iter::from_fn(move || {
+        let size = usize::try_from(read_u32(data, 0)?).ok()?;
+        let (size, header_size) = match size {
+            0 => (data.len(), 8),
+            1 => (usize::try_from(read_u64(data, 8)?).ok()?, 16),
+            size => (size, 8),
+        };
+        if size < header_size || size > data.len() {
+            return None;
+        }
+        let atom = (&data[4..8], &data[header_size..size]);
+        data = &data[size..];
+        Some(atom)
+    })
+}
+
+fn find<'a>(data: &'a [u8], path: &[&[u8; 4]]) -> Option<&'a [u8]> {
+    path.iter().try_fold(data, |data, kind| {
+        atoms(data).find(|(k, _)| k == kind).map(|(_, body)| body)
+    })
+}
+
+fn read_u8(data: &[u8], at: usize) -> Option<u8> {
+    data.get(at).copied()
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+// Entries of a table atom that starts with version, flags and the number of entries.
+fn table(data: &[u8], entry_size: usize) -> impl Iterator<Item = &[u8]> {
+    let count = read_u32(data, 4).unwrap_or(0) as usize;
+    data.get(8..)
+        .unwrap_or_default()
+        .chunks_exact(entry_size)
+        .take(count)
+}
+
+// Version, flags, one more word with version 1, the number of chapters, and for each the
+// start in 100 ns and the title after its length.
+fn parse_chpl(data: &[u8]) -> Vec<Chapter> {
+    let mut at = if read_u8(data, 0) == Some(1) { 8 } else { 4 };
+    let count = read_u8(data, at).unwrap_or(0);
+    at += 1;
+    let mut chapters = Vec::new();
+    for _ in 0..count {
+        let (Some(start), Some(len)) = (read_u64(data, at), read_u8(data, at + 8)) else {
+            break;
+        };
+        let Some(title) = data.get(at + 9..at + 9 + usize::from(len)) else {
+            break;
+        };
+        chapters.push(Chapter {
+            start: Duration::from_nanos(start.saturating_mul(100)),
+            title: String::from_utf8_lossy(title).into_owned(),
+        });
+        at += 9 + usize::from(len);
+    }
+    chapters
+}
+
+// The text track that the `chap` reference of another track points to, one sample per chapter.
+fn chapter_track<R: Read + Seek>(reader: &mut R, moov: &[u8]) -> Option<Vec<Chapter>> {
+    let traks: Vec<&[u8]> = atoms(moov)
+        .filter(|(kind, _)| kind == b"trak")
+        .map(|(_, body)| body)
+        .collect();
+    let ids: Vec<u32> = traks
+        .iter()
+        .filter_map(|trak| find(trak, &[b"tref", b"chap"]))
+        .flat_map(|chap| chap.chunks_exact(4).filter_map(|id| read_u32(id, 0)))
+        .collect();
+    let trak = traks.iter().find(|trak| {
+        find(trak, &[b"tkhd"]).is_some_and(|tkhd| {
+            let at = if read_u8(tkhd, 0) == Some(1) { 20 } else { 12 };
+            read_u32(tkhd, at).is_some_and(|id| ids.contains(&id))
+        })
+    })?;
+
+    let mdhd = find(trak, &[b"mdia", b"mdhd"])?;
+    let timescale = read_u32(mdhd, if read_u8(mdhd, 0) == Some(1) { 20 } else { 12 })?;
+    if timescale == 0 {
+        return None;
+    }
+    let stbl = find(trak, &[b"mdia", b"minf", b"stbl"])?;
+
+    let stsz = find(stbl, &[b"stsz"])?;
+    let sample_size = read_u32(stsz, 4)?;
+    let sample_count = read_u32(stsz, 8)?;
+    let sizes: Vec<u32> = if sample_size == 0 {
+        stsz.get(12..)?
+            .chunks_exact(4)
+            .take(sample_count as usize)
+            .filter_map(|size| read_u32(size, 0))
+            .collect()
+    } else {
+        // the samples have to fit in the file
+        let len = reader.seek(SeekFrom::End(0)).ok()?;
+        let count = u64::from(sample_count).min(len / u64::from(sample_size));
+        vec![sample_size; usize::try_from(count).ok()?]
+    };
+
+    // one start for each sample, the counts of the time table may claim more
+    let mut starts = Vec::new();
+    let mut time = 0_u64;
+    'entries: for entry in table(find(stbl, &[b"stts"])?, 8) {
+        for _ in 0..read_u32(entry, 0)? {
+            if starts.len() == sizes.len() {
+                break 'entries;
+            }
+            starts.push(time);
+            time += u64::from(read_u32(entry, 4)?);
+        }
+    }
+
+    let chunks: Vec<u64> = match find(stbl, &[b"stco"]) {
+        Some(stco) => table(stco, 4)
+            .filter_map(|offset| read_u32(offset, 0).map(u64::from))
+            .collect(),
+        None => table(find(stbl, &[b"co64"])?, 8)
+            .filter_map(|offset| read_u64(offset, 0))
+            .collect(),
+    };
+    // the first chunk of a run of chunks with the same number of samples, and that number
+    let runs: Vec<(u32, u32)> = table(find(stbl, &[b"stsc"])?, 12)
+        .filter_map(|entry| Some((read_u32(entry, 0)?, read_u32(entry, 4)?)))
+        .collect();
+
+    let mut offsets = Vec::new();
+    let mut sizes_left = sizes.iter();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let samples = runs
+            .iter()
+            .rev()
+            .find(|(first, _)| *first as usize <= index + 1)
+            .map_or(0, |(_, samples)| *samples);
+        let mut offset = *chunk;
+        for _ in 0..samples {
+            let size = *sizes_left.next()?;
+            offsets.push((offset, size));
+            offset += u64::from(size);
+        }
+    }
+
+    let mut chapters = Vec::new();
+    for (start, (offset, size)) in starts.into_iter().zip(offsets) {
+        reader.seek(SeekFrom::Start(offset)).ok()?;
+        let mut sample = Vec::new();
+        reader.take(u64::from(size)).read_to_end(&mut sample).ok()?;
+        chapters.push(Chapter {
+            start: Duration::from_secs_f64(start as f64 / f64::from(timescale)),
+            title: sample_text(&sample),
+        });
+    }
+    Some(chapters)
+}
+
+// The length of the text and the text, in UTF-16 when it starts with a byte order mark.
+fn sample_text(sample: &[u8]) -> String {
+    let len = read_u16(sample, 0).map_or(0, usize::from);
+    let text = sample.get(2..2 + len).unwrap_or_default();
+    match text.strip_prefix(&[0xfe, 0xff]) {
+        Some(utf16) => String::from_utf16_lossy(
+            &utf16
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        None => String::from_utf8_lossy(text).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    fn atom(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut atom = u32::try_from(body.len() + 8)
+            .unwrap()
+            .to_be_bytes()
+            .to_vec();
+        atom.extend_from_slice(kind);
+        atom.extend_from_slice(body);
+        atom
+    }
+
+    fn words(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
+    // a file with the chapters "Uno" and "Dos" in a chapter track, and `chpl` too
+    fn chapter_track_file(stts: &[u32], stsz: &[u32], chpl: &[u8]) -> Vec<u8> {
+        let mut samples = Vec::new();
+        for title in ["Uno", "Dos"] {
+            samples.extend(3_u16.to_be_bytes());
+            samples.extend(title.as_bytes());
+        }
+        // after the free atom and the header of mdat
+        let offset = 16;
+        let audio = atom(
+            b"trak",
+            &[
+                atom(b"tkhd", &words(&[0, 0, 0, 1])),
+                atom(b"tref", &atom(b"chap", &words(&[2]))),
+            ]
+            .concat(),
+        );
+        let stbl = [
+            atom(b"stts", &words(stts)),
+            atom(b"stsz", &words(stsz)),
+            atom(b"stsc", &words(&[0, 1, 1, 2, 1])),
+            atom(b"stco", &words(&[0, 1, offset])),
+        ]
+        .concat();
+        let text = atom(
+            b"trak",
+            &[
+                atom(b"tkhd", &words(&[0, 0, 0, 2])),
+                atom(
+                    b"mdia",
+                    &[
+                        atom(b"mdhd", &words(&[0, 0, 0, 1000, 5000])),
+                        atom(b"minf", &atom(b"stbl", &stbl)),
+                    ]
+                    .concat(),
+                ),
+            ]
+            .concat(),
+        );
+        let mut file = atom(b"free", &[]);
+        file.extend(atom(b"mdat", &samples));
+        file.extend(atom(
+            b"moov",
+            &[audio, text, atom(b"udta", &atom(b"chpl", chpl))].concat(),
+        ));
+        file
+    }
+
+    #[test]
+    fn test_chapters_from_comments() {
+        let chapters = from_comments([
+            ("CHAPTER002", "00:10:30.500"),
+            ("CHAPTER001", "00:00:00.000"),
+            ("CHAPTER001NAME", "Opening"),
+            ("chapter003", "1:02:03"),
+            ("CHAPTER004NAME", "No start"),
+            ("TITLE", "Book"),
+        ]);
+        assert_eq!(
+            chapters,
+            vec![
+                Chapter {
+                    start: Duration::ZERO,
+                    title: "Opening".to_string()
+                },
+                Chapter {
+                    start: Duration::from_millis(630_500),
+                    title: "Chapter 2".to_string()
+                },
+                Chapter {
+                    start: Duration::from_secs(3723),
+                    title: "Chapter 3".to_string()
+                },
+            ]
+        );
+        assert_eq!(index_at(&chapters, Duration::from_secs(700)), Some(1));
+    }
+
+    #[test]
+    fn test_read_mp4_chapters() {
+        let mut chpl = words(&[0x0100_0000, 0]);
+        chpl.push(2);
+        for (start, title) in [(0_u64, "One"), (300_000_000, "Two")] {
+            chpl.extend(start.to_be_bytes());
+            chpl.push(3);
+            chpl.extend(title.as_bytes());
+        }
+        let mut file = atom(b"ftyp", b"M4B ");
+        file.extend(atom(b"moov", &atom(b"udta", &atom(b"chpl", &chpl))));
+        let chapters = read_mp4_from(&mut Cursor::new(&file)).unwrap();
+        assert_eq!(chapters[1].start, Duration::from_secs(30));
+        assert_eq!(chapters[1].title, "Two");
+
+        // the chapter track wins over chpl
+        let file = chapter_track_file(&[0, 2, 1, 1000, 1, 4000], &[0, 0, 2, 5, 5], &chpl);
+        let chapters = read_mp4_from(&mut Cursor::new(&file)).unwrap();
+        assert_eq!(
+            chapters,
+            vec![
+                Chapter {
+                    start: Duration::ZERO,
+                    title: "Uno".to_string()
+                },
+                Chapter {
+                    start: Duration::from_secs(1),
+                    title: "Dos".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample_counts() {
+        let uno_dos = |file: &[u8]| {
+            read_mp4_from(&mut Cursor::new(file))
+                .unwrap()
+                .into_iter()
+                .map(|chapter| (chapter.start, chapter.title))
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![
+            (Duration::ZERO, "Uno".to_string()),
+            (Duration::from_secs(1), "Dos".to_string()),
+        ];
+        // far more samples in the time table than there are
+        let file = chapter_track_file(&[0, 1, u32::MAX, 1000], &[0, 0, 2, 5, 5], &[]);
+        assert_eq!(uno_dos(&file), expected);
+        // and far more of the same size than fit in the file
+        let file = chapter_track_file(&[0, 1, u32::MAX, 1000], &[0, 5, u32::MAX], &[]);
+        assert_eq!(uno_dos(&file), expected);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("1:02:03.5"),
+            Some(Duration::from_millis(3_723_500))
+        );
+        assert_eq!(parse_time("90"), Some(Duration::from_secs(90)));
+        for time in ["inf", "1e300", "-1", "NaN", "1:x"] {
+            assert_eq!(parse_time(time), None, "{}", time);
+        }
+    }
+}
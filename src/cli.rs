@@ -0,0 +1,345 @@
+//! ## Cli
+//!
+//! Subcommands that control a running termusic through the server socket, like
+//! `termusic next` or `termusic status --json`.
+use crate::cue;
+use crate::server::{Client, PlayerState, Request, Response, SleepTimer};
+use crate::utils::filetype_supported;
+use anyhow::{anyhow, bail, Result};
+use std::path::Path;
+
+/// Exit code when the command was done.
+pub const EXIT_OK: i32 = 0;
+/// Exit code when the player couldn't do what was asked, like adding a missing file.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code for a wrong command line.
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code when no termusic is running.
+pub const EXIT_NOT_RUNNING: i32 = 3;
+
+const COMMANDS: [&str; 11] = [
+    "play", "pause", "toggle", "next", "prev", "previous", "seek", "volume", "add", "sleep",
+    "status",
+];
+
+enum Action {
+    Send(Request),
+    // seconds from the start of the track
+    SeekTo(i64),
+    VolumeBy(i32),
+    Add(Vec<String>),
+    Status { json: bool },
+}
+
+/// Tells whether the argument is a command. A music directory that happens to have the name
+/// of a command, like `./next`, is opened rather than taken for the command.
+pub fn is_command(arg: &str) -> bool {
+    COMMANDS.contains(&arg) && !Path::new(arg).exists()
+}
+
+/// Runs the command and returns the exit code.
+pub fn run(command: &str, args: &[String]) -> i32 {
+    run_with(command, args, Client::connect)
+}
+
+fn run_with(command: &str, args: &[String], connect: impl FnOnce() -> Result<Client>) -> i32 {
+    let action = match parse(command, args) {
+        Ok(action) => action,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return EXIT_USAGE;
+        }
+    };
+    let mut client = match connect() {
+        Ok(client) => client,
+        Err(_) => {
+            eprintln!("Error: termusic is not running");
+            return EXIT_NOT_RUNNING;
+        }
+    };
+    match execute(&mut client, action) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn parse(command: &str, args: &[String]) -> Result<Action> {
+    let arg = args.first().map(String::as_str);
+    let action = match (command, arg) {
+        ("play", None) => Action::Send(Request::Play),
+        ("pause", None) => Action::Send(Request::Pause),
+        ("toggle", None) => Action::Send(Request::TogglePause),
+        ("next", None) => Action::Send(Request::Next),
+        ("prev" | "previous", None) => Action::Send(Request::Previous),
+        ("seek", Some(position)) => match parse_offset(position)? {
+            (true, offset) => Action::Send(Request::Seek(offset)),
+            (false, position) => Action::SeekTo(position),
+        },
+        ("volume", Some(volume)) => match parse_offset(volume)? {
+            (true, offset) => Action::VolumeBy(i32::try_from(offset)?),
+            (false, volume) => Action::Send(Request::SetVolume(i32::try_from(volume)?)),
+        },
+        ("add", Some(_)) => return Ok(Action::Add(args.to_vec())),
+        ("sleep", Some("off")) => Action::Send(Request::SetSleepTimer(None)),
+        ("sleep", Some(timer)) => Action::Send(Request::SetSleepTimer(Some(
+            timer.parse::<SleepTimer>().map_err(|e| anyhow!(e))?,
+        ))),
+        ("status", None) => Action::Status { json: false },
+        ("status", Some("--json")) => return Ok(Action::Status { json: true }),
+        ("seek", None) => bail!("'seek' needs a position like 30, +10 or -10"),
+        ("volume", None) => bail!("'volume' needs a value like 60, +5 or -5"),
+        ("add", None) => bail!("'add' needs a file or directory"),
+        ("sleep", None) => bail!("'sleep' needs minutes like 30, end, end+2 or off"),
+        (command, Some(arg)) => bail!("unexpected argument '{}' for '{}'", arg, command),
+        (command, None) => bail!("unknown command '{}'", command),
+    };
+    if args.len() > 1 {
+        bail!("too many arguments for '{}'", command);
+    }
+    Ok(action)
+}
+
+// Returns whether the number has a sign, which makes it relative, and its value.
+fn parse_offset(arg: &str) -> Result<(bool, i64)> {
+    let relative = arg.starts_with('+') || arg.starts_with('-');
+    let value = arg
+        .parse()
+        .map_err(|_| anyhow!("'{}' is not a whole number", arg))?;
+    Ok((relative, value))
+}
+
+fn execute(client: &mut Client, action: Action) -> Result<()> {
+    match action {
+        Action::Send(request) => {
+            client.request(&request)?;
+        }
+        Action::SeekTo(position) => {
+            let state = state(client)?;
+            client.request(&Request::Seek(position - state.position))?;
+        }
+        Action::VolumeBy(offset) => {
+            let state = state(client)?;
+            client.request(&Request::SetVolume(state.volume + offset))?;
+        }
+        Action::Add(paths) => {
+            let mut files = Vec::new();
+            for path in &paths {
+                files.extend(audio_files(Path::new(path))?);
+            }
+            client.request(&Request::Queue(files))?;
+        }
+        Action::Status { json } => {
+            let state = state(client)?;
+            if json {
+                println!("{}", serde_json::to_string(&state)?);
+            } else {
+                print_state(&state);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn state(client: &mut Client) -> Result<PlayerState> {
+    match client.request(&Request::Status)? {
+        Response::State(state) => Ok(state),
+        _ => bail!("unexpected answer from termusic"),
+    }
+}
+
+// The file itself, or the audio files and cue sheets right inside a directory, with absolute
+// paths since the server runs somewhere else.
+fn audio_files(path: &Path) -> Result<Vec<String>> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| anyhow!("cannot add {}: {}", path.display(), e))?;
+    if !path.is_dir() {
+        return Ok(vec![path.to_string_lossy().to_string()]);
+    }
+
+    let mut files: Vec<String> = std::fs::read_dir(&path)?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .filter(|file| filetype_supported(file) || cue::is_cue(file))
+        .collect();
+    let split = cue::split_files(files.iter().map(Path::new));
+    files.retain(|file| !split.contains(Path::new(file)));
+    if files.is_empty() {
+        bail!("no audio files in {}", path.display());
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn print_state(state: &PlayerState) {
+    println!("Status: {}", state.status);
+    if let Some(file) = &state.file {
+        let title = state.title.as_deref().unwrap_or("Unknown Title");
+        let artist = state.artist.as_deref().unwrap_or("Unknown Artist");
+        println!("Track: {} - {}", artist, title);
+        if let Some(album) = &state.album {
+            println!("Album: {}", album);
+        }
+        println!("File: {}", file);
+        if let Some(title) = &state.stream_title {
+            println!("On air: {}", title);
+        }
+        println!(
+            "Position: {}:{:02} / {}:{:02}",
+            state.position / 60,
+            state.position % 60,
+            state.duration / 60,
+            state.duration % 60,
+        );
+    }
+    println!("Volume: {}", state.volume);
+    println!("Speed: {:.1}", f64::from(state.speed) / 10.0);
+    println!("Gapless: {}", state.gapless);
+    println!("Loop: {}", state.loop_mode.display(false));
+    if let Some(timer) = &state.sleep_timer {
+        println!("Sleep: {}", timer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |command, arguments: &[&str]| parse(command, &args(arguments)).unwrap();
+        assert!(matches!(parse("play", &[]), Action::Send(Request::Play)));
+        assert!(matches!(parse("pause", &[]), Action::Send(Request::Pause)));
+        assert!(matches!(
+            parse("toggle", &[]),
+            Action::Send(Request::TogglePause)
+        ));
+        assert!(matches!(parse("next", &[]), Action::Send(Request::Next)));
+        assert!(matches!(
+            parse("prev", &[]),
+            Action::Send(Request::Previous)
+        ));
+        assert!(matches!(
+            parse("previous", &[]),
+            Action::Send(Request::Previous)
+        ));
+        assert!(matches!(parse("seek", &["30"]), Action::SeekTo(30)));
+        assert!(matches!(
+            parse("seek", &["+10"]),
+            Action::Send(Request::Seek(10))
+        ));
+        assert!(matches!(
+            parse("seek", &["-10"]),
+            Action::Send(Request::Seek(-10))
+        ));
+        assert!(matches!(
+            parse("volume", &["60"]),
+            Action::Send(Request::SetVolume(60))
+        ));
+        assert!(matches!(parse("volume", &["-5"]), Action::VolumeBy(-5)));
+        assert!(matches!(
+            parse("add", &["a.mp3", "b"]),
+            Action::Add(paths) if paths == args(&["a.mp3", "b"])
+        ));
+        assert!(matches!(
+            parse("sleep", &["off"]),
+            Action::Send(Request::SetSleepTimer(None))
+        ));
+        assert!(matches!(
+            parse("sleep", &["30"]),
+            Action::Send(Request::SetSleepTimer(Some(SleepTimer::Seconds(1800))))
+        ));
+        assert!(matches!(
+            parse("sleep", &["end+2"]),
+            Action::Send(Request::SetSleepTimer(Some(SleepTimer::Tracks(3))))
+        ));
+        assert!(matches!(
+            parse("status", &[]),
+            Action::Status { json: false }
+        ));
+        assert!(matches!(
+            parse("status", &["--json"]),
+            Action::Status { json: true }
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (command, arguments, error) in [
+            (
+                "seek",
+                &[][..],
+                "'seek' needs a position like 30, +10 or -10",
+            ),
+            ("seek", &["ten"], "'ten' is not a whole number"),
+            ("volume", &[], "'volume' needs a value like 60, +5 or -5"),
+            ("volume", &["+1.5"], "'+1.5' is not a whole number"),
+            ("add", &[], "'add' needs a file or directory"),
+            (
+                "sleep",
+                &[],
+                "'sleep' needs minutes like 30, end, end+2 or off",
+            ),
+            (
+                "sleep",
+                &["0"],
+                "'0' is not a number of minutes, end or end+N",
+            ),
+            ("next", &["2"], "unexpected argument '2' for 'next'"),
+            (
+                "status",
+                &["--yaml"],
+                "unexpected argument '--yaml' for 'status'",
+            ),
+            ("seek", &["+5", "+5"], "too many arguments for 'seek'"),
+            ("stop", &[], "unknown command 'stop'"),
+        ] {
+            let result = parse(command, &args(arguments));
+            assert_eq!(result.err().map(|e| e.to_string()).as_deref(), Some(error));
+        }
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let unused = || -> Result<Client> { panic!("connected for a wrong command line") };
+        assert_eq!(run_with("seek", &[], unused), EXIT_USAGE);
+        assert_eq!(run_with("stop", &[], unused), EXIT_USAGE);
+
+        let not_running = || -> Result<Client> { bail!("no socket") };
+        assert_eq!(run_with("next", &[], not_running), EXIT_NOT_RUNNING);
+
+        // the file is checked before anything is sent
+        let connected = || Client::from_stream(UnixStream::pair()?.0);
+        let missing = args(&["/nonexistent/termusic.mp3"]);
+        assert_eq!(run_with("add", &missing, connected), EXIT_FAILURE);
+
+        let (socket, mut server) = UnixStream::pair().unwrap();
+        let answer = std::thread::spawn(move || {
+            let mut request = String::new();
+            BufReader::new(server.try_clone()?).read_line(&mut request)?;
+            server.write_all(b"\"ok\"\n")
+        });
+        assert_eq!(
+            run_with("next", &[], || Client::from_stream(socket)),
+            EXIT_OK
+        );
+        answer.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_is_command() {
+        assert!(is_command("next"));
+        assert!(!is_command("./next"));
+        assert!(!is_command("stop"));
+    }
+}
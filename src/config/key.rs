@@ -13,7 +13,9 @@ pub const CONTROL_ALT_SHIFT: KeyModifiers = KeyModifiers::from_bits_truncate(
     KeyModifiers::ALT.bits() | KeyModifiers::CONTROL.bits() | KeyModifiers::SHIFT.bits(),
 );
 
+// Keys added in newer versions get their default binding when missing from config.toml
 #[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Keys {
     pub global_esc: BindingForEvent,
     pub global_quit: BindingForEvent,
@@ -42,6 +44,21 @@ pub struct Keys {
     pub global_key_editor_open: BindingForEvent,
     pub global_layout_treeview: BindingForEvent,
     pub global_layout_database: BindingForEvent,
+    pub global_layout_playlists: BindingForEvent,
+    pub global_layout_podcasts: BindingForEvent,
+    pub global_equalizer_open: BindingForEvent,
+    pub global_output_device_open: BindingForEvent,
+    pub global_stats_open: BindingForEvent,
+    pub global_rating_up: BindingForEvent,
+    pub global_rating_down: BindingForEvent,
+    pub global_favourite_toggle: BindingForEvent,
+    pub global_named_playlist_append: BindingForEvent,
+    pub global_radio_browser_open: BindingForEvent,
+    pub global_sleep_timer_open: BindingForEvent,
+    pub global_player_ab_loop: BindingForEvent,
+    pub global_player_chapter_next: BindingForEvent,
+    pub global_player_chapter_previous: BindingForEvent,
+    pub global_chapter_list_open: BindingForEvent,
     pub library_load_dir: BindingForEvent,
     pub library_delete: BindingForEvent,
     pub library_yank: BindingForEvent,
@@ -49,6 +66,7 @@ pub struct Keys {
     pub library_search: BindingForEvent,
     pub library_search_youtube: BindingForEvent,
     pub library_tag_editor_open: BindingForEvent,
+    pub library_replaygain_scan: BindingForEvent,
     pub playlist_delete: BindingForEvent,
     pub playlist_delete_all: BindingForEvent,
     pub playlist_shuffle: BindingForEvent,
@@ -58,7 +76,21 @@ pub struct Keys {
     pub playlist_search: BindingForEvent,
     pub playlist_swap_down: BindingForEvent,
     pub playlist_swap_up: BindingForEvent,
+    pub playlist_export: BindingForEvent,
     pub database_add_all: BindingForEvent,
+    pub database_sort: BindingForEvent,
+    pub named_playlist_new: BindingForEvent,
+    pub named_playlist_rename: BindingForEvent,
+    pub named_playlist_duplicate: BindingForEvent,
+    pub named_playlist_delete: BindingForEvent,
+    pub named_playlist_load: BindingForEvent,
+    pub named_playlist_export: BindingForEvent,
+    pub podcast_add: BindingForEvent,
+    pub podcast_delete: BindingForEvent,
+    pub podcast_refresh: BindingForEvent,
+    pub podcast_refresh_all: BindingForEvent,
+    pub podcast_download: BindingForEvent,
+    pub podcast_mark_played: BindingForEvent,
     pub global_player_toggle_gapless: BindingForEvent,
 }
 
@@ -167,6 +199,152 @@ impl BindingForEvent {
     }
 }
 
+impl Keys {
+    /// Fails when a key is bound twice where both bindings apply. The keys of a view go before
+    /// the global ones, so the keys only have to differ among the global ones and within a view.
+    pub fn check_duplicates(&self) -> Result<()> {
+        let groups = [
+            vec![
+                ("global_esc", &self.global_esc),
+                ("global_quit", &self.global_quit),
+                ("global_left", &self.global_left),
+                ("global_down", &self.global_down),
+                ("global_up", &self.global_up),
+                ("global_right", &self.global_right),
+                ("global_goto_top", &self.global_goto_top),
+                ("global_goto_bottom", &self.global_goto_bottom),
+                (
+                    "global_player_toggle_pause",
+                    &self.global_player_toggle_pause,
+                ),
+                ("global_player_next", &self.global_player_next),
+                ("global_player_previous", &self.global_player_previous),
+                (
+                    "global_player_volume_plus_1",
+                    &self.global_player_volume_plus_1,
+                ),
+                (
+                    "global_player_volume_plus_2",
+                    &self.global_player_volume_plus_2,
+                ),
+                (
+                    "global_player_volume_minus_1",
+                    &self.global_player_volume_minus_1,
+                ),
+                (
+                    "global_player_volume_minus_2",
+                    &self.global_player_volume_minus_2,
+                ),
+                ("global_help", &self.global_help),
+                (
+                    "global_player_seek_forward",
+                    &self.global_player_seek_forward,
+                ),
+                (
+                    "global_player_seek_backward",
+                    &self.global_player_seek_backward,
+                ),
+                (
+                    "global_lyric_adjust_forward",
+                    &self.global_lyric_adjust_forward,
+                ),
+                (
+                    "global_lyric_adjust_backward",
+                    &self.global_lyric_adjust_backward,
+                ),
+                ("global_player_speed_up", &self.global_player_speed_up),
+                ("global_player_speed_down", &self.global_player_speed_down),
+                ("global_lyric_cycle", &self.global_lyric_cycle),
+                ("global_color_editor_open", &self.global_color_editor_open),
+                ("global_key_editor_open", &self.global_key_editor_open),
+                ("global_layout_treeview", &self.global_layout_treeview),
+                ("global_layout_database", &self.global_layout_database),
+                ("global_layout_playlists", &self.global_layout_playlists),
+                ("global_layout_podcasts", &self.global_layout_podcasts),
+                ("global_equalizer_open", &self.global_equalizer_open),
+                ("global_output_device_open", &self.global_output_device_open),
+                ("global_stats_open", &self.global_stats_open),
+                ("global_rating_up", &self.global_rating_up),
+                ("global_rating_down", &self.global_rating_down),
+                ("global_favourite_toggle", &self.global_favourite_toggle),
+                (
+                    "global_named_playlist_append",
+                    &self.global_named_playlist_append,
+                ),
+                ("global_radio_browser_open", &self.global_radio_browser_open),
+                ("global_sleep_timer_open", &self.global_sleep_timer_open),
+                ("global_player_ab_loop", &self.global_player_ab_loop),
+                (
+                    "global_player_chapter_next",
+                    &self.global_player_chapter_next,
+                ),
+                (
+                    "global_player_chapter_previous",
+                    &self.global_player_chapter_previous,
+                ),
+                ("global_chapter_list_open", &self.global_chapter_list_open),
+                (
+                    "global_player_toggle_gapless",
+                    &self.global_player_toggle_gapless,
+                ),
+            ],
+            vec![
+                ("library_load_dir", &self.library_load_dir),
+                ("library_delete", &self.library_delete),
+                ("library_yank", &self.library_yank),
+                ("library_paste", &self.library_paste),
+                ("library_search", &self.library_search),
+                ("library_search_youtube", &self.library_search_youtube),
+                ("library_tag_editor_open", &self.library_tag_editor_open),
+                ("library_replaygain_scan", &self.library_replaygain_scan),
+            ],
+            vec![
+                ("playlist_delete", &self.playlist_delete),
+                ("playlist_delete_all", &self.playlist_delete_all),
+                ("playlist_shuffle", &self.playlist_shuffle),
+                ("playlist_mode_cycle", &self.playlist_mode_cycle),
+                ("playlist_play_selected", &self.playlist_play_selected),
+                ("playlist_add_front", &self.playlist_add_front),
+                ("playlist_search", &self.playlist_search),
+                ("playlist_swap_down", &self.playlist_swap_down),
+                ("playlist_swap_up", &self.playlist_swap_up),
+                ("playlist_export", &self.playlist_export),
+            ],
+            vec![
+                ("database_add_all", &self.database_add_all),
+                ("database_sort", &self.database_sort),
+            ],
+            vec![
+                ("named_playlist_new", &self.named_playlist_new),
+                ("named_playlist_rename", &self.named_playlist_rename),
+                ("named_playlist_duplicate", &self.named_playlist_duplicate),
+                ("named_playlist_delete", &self.named_playlist_delete),
+                ("named_playlist_load", &self.named_playlist_load),
+                ("named_playlist_export", &self.named_playlist_export),
+            ],
+            vec![
+                ("podcast_add", &self.podcast_add),
+                ("podcast_delete", &self.podcast_delete),
+                ("podcast_refresh", &self.podcast_refresh),
+                ("podcast_refresh_all", &self.podcast_refresh_all),
+                ("podcast_download", &self.podcast_download),
+                ("podcast_mark_played", &self.podcast_mark_played),
+            ],
+        ];
+        for group in &groups {
+            for (index, (name, binding)) in group.iter().enumerate() {
+                if let Some((other, _)) = group[..index]
+                    .iter()
+                    .find(|(_, other)| other.key_event() == binding.key_event())
+                {
+                    bail!("{} is bound to both {} and {}", binding, other, name);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Default for Keys {
     #[allow(clippy::too_many_lines)]
     fn default() -> Self {
@@ -300,6 +478,10 @@ impl Default for Keys {
                 code: Key::Char('t'),
                 modifiers: KeyModifiers::NONE,
             },
+            library_replaygain_scan: BindingForEvent {
+                code: Key::Char('R'),
+                modifiers: KeyModifiers::SHIFT,
+            },
             playlist_delete: BindingForEvent {
                 code: Key::Char('d'),
                 modifiers: KeyModifiers::NONE,
@@ -336,6 +518,10 @@ impl Default for Keys {
                 code: Key::Char('k'),
                 modifiers: KeyModifiers::CONTROL,
             },
+            playlist_export: BindingForEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            },
             global_layout_treeview: BindingForEvent {
                 code: Key::Char('1'),
                 modifiers: KeyModifiers::NONE,
@@ -344,10 +530,122 @@ impl Default for Keys {
                 code: Key::Char('2'),
                 modifiers: KeyModifiers::NONE,
             },
+            global_layout_playlists: BindingForEvent {
+                code: Key::Char('3'),
+                modifiers: KeyModifiers::NONE,
+            },
+            global_layout_podcasts: BindingForEvent {
+                code: Key::Char('4'),
+                modifiers: KeyModifiers::NONE,
+            },
+            global_equalizer_open: BindingForEvent {
+                code: Key::Char('E'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_output_device_open: BindingForEvent {
+                code: Key::Char('O'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_stats_open: BindingForEvent {
+                code: Key::Char('S'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_rating_up: BindingForEvent {
+                code: Key::Char('>'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_rating_down: BindingForEvent {
+                code: Key::Char('<'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_favourite_toggle: BindingForEvent {
+                code: Key::Char('*'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_named_playlist_append: BindingForEvent {
+                code: Key::Char('A'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_radio_browser_open: BindingForEvent {
+                code: Key::Char('W'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            global_sleep_timer_open: BindingForEvent {
+                code: Key::Char('z'),
+                modifiers: KeyModifiers::NONE,
+            },
+            global_player_ab_loop: BindingForEvent {
+                code: Key::Char('i'),
+                modifiers: KeyModifiers::NONE,
+            },
+            global_player_chapter_next: BindingForEvent {
+                code: Key::Char(']'),
+                modifiers: KeyModifiers::NONE,
+            },
+            global_player_chapter_previous: BindingForEvent {
+                code: Key::Char('['),
+                modifiers: KeyModifiers::NONE,
+            },
+            global_chapter_list_open: BindingForEvent {
+                code: Key::Char('c'),
+                modifiers: KeyModifiers::NONE,
+            },
             database_add_all: BindingForEvent {
                 code: Key::Char('L'),
                 modifiers: KeyModifiers::SHIFT,
             },
+            database_sort: BindingForEvent {
+                code: Key::Char('o'),
+                modifiers: KeyModifiers::NONE,
+            },
+            named_playlist_new: BindingForEvent {
+                code: Key::Char('a'),
+                modifiers: KeyModifiers::NONE,
+            },
+            named_playlist_rename: BindingForEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            },
+            named_playlist_duplicate: BindingForEvent {
+                code: Key::Char('y'),
+                modifiers: KeyModifiers::NONE,
+            },
+            named_playlist_delete: BindingForEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            },
+            named_playlist_load: BindingForEvent {
+                code: Key::Char('L'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            named_playlist_export: BindingForEvent {
+                code: Key::Char('x'),
+                modifiers: KeyModifiers::NONE,
+            },
+            podcast_add: BindingForEvent {
+                code: Key::Char('a'),
+                modifiers: KeyModifiers::NONE,
+            },
+            podcast_delete: BindingForEvent {
+                code: Key::Char('d'),
+                modifiers: KeyModifiers::NONE,
+            },
+            podcast_refresh: BindingForEvent {
+                code: Key::Char('r'),
+                modifiers: KeyModifiers::NONE,
+            },
+            podcast_refresh_all: BindingForEvent {
+                code: Key::Char('R'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            podcast_download: BindingForEvent {
+                code: Key::Char('D'),
+                modifiers: KeyModifiers::SHIFT,
+            },
+            podcast_mark_played: BindingForEvent {
+                code: Key::Char('m'),
+                modifiers: KeyModifiers::NONE,
+            },
             global_player_toggle_gapless: BindingForEvent {
                 code: Key::Char('g'),
                 modifiers: KeyModifiers::CONTROL,
@@ -355,3 +653,29 @@ impl Default for Keys {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_check_duplicates() {
+        let mut keys = Keys::default();
+        assert!(keys.check_duplicates().is_ok());
+
+        // a view may take a global key
+        keys.playlist_shuffle = keys.global_quit.clone();
+        assert!(keys.check_duplicates().is_ok());
+
+        keys.playlist_shuffle = keys.playlist_delete.clone();
+        assert_eq!(
+            keys.check_duplicates().unwrap_err().to_string(),
+            "d is bound to both playlist_delete and playlist_shuffle"
+        );
+
+        let mut keys = Keys::default();
+        keys.global_lyric_cycle = keys.global_help.clone();
+        assert!(keys.check_duplicates().is_err());
+    }
+}
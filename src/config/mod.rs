@@ -24,7 +24,7 @@
 mod key;
 mod theme;
 
-use crate::player::Loop;
+use crate::player::{EqualizerPreset, Loop, ReplayGain};
 use crate::ui::components::Xywh;
 use anyhow::{anyhow, Result};
 pub use key::{BindingForEvent, Keys};
@@ -35,7 +35,10 @@ pub use theme::{load_alacritty, ColorTermusic, StyleColorSymbol};
 
 pub const MUSIC_DIR: &str = "~/Music";
 
+// Missing fields fall back to their default, so that a config.toml written
+// by an older version still loads after new settings are added.
 #[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Settings {
     pub music_dir: String,
     #[serde(skip_serializing)]
@@ -43,14 +46,47 @@ pub struct Settings {
     pub loop_mode: Loop,
     pub volume: i32,
     pub speed: i32,
+    /// Keep the pitch when playing faster or slower, for the default and mpv backends
+    pub pitch_correction: bool,
     pub add_playlist_front: bool,
     pub gapless: bool,
+    /// Seconds the next track fades in over the end of the current one, 0 to disable
+    pub crossfade: u32,
+    pub replaygain: ReplayGain,
+    /// Extra gain in dB applied on top of the `ReplayGain` value
+    pub replaygain_preamp: f32,
+    /// Name of the selected equalizer preset
+    pub equalizer: String,
+    /// Name of the audio output device, empty for the default one
+    pub output_device: String,
+    /// Stop the music when the interface quits, instead of playing on in the background
+    pub quit_server_on_exit: bool,
+    /// Go on with the track that played when the server quit, from where it was
+    pub resume_on_startup: bool,
+    /// Tracks at least this many minutes long go on where they were left off when they are
+    /// played again, 0 to disable
+    pub remember_position_minutes: u32,
+    /// Keep the A-B loop of a file, so that it is back when the file plays again
+    pub remember_ab_loop: bool,
+    /// Address the server takes MPD clients on, empty to disable. Only used when built with
+    /// the `mpd` feature
+    pub mpd_address: String,
+    /// Base url of the radio-browser api the station browser searches, a mirror or a local
+    /// server with the same api works as well
+    pub radio_browser_url: String,
+    /// Where podcast episodes are downloaded to, in a folder for each podcast
+    pub podcast_dir: String,
+    /// Write ratings into the tags of mp3, flac, ogg and opus files, next to the library
+    /// database
+    pub rating_write_to_file: bool,
     pub disable_exit_confirmation: bool,
     pub playlist_display_symbol: bool,
     pub theme_selected: String,
     pub album_photo_xywh: Xywh,
     pub style_color_symbol: StyleColorSymbol,
     pub keys: Keys,
+    // tables have to come after plain values in toml
+    pub equalizer_presets: Vec<EqualizerPreset>,
 }
 
 impl Default for Settings {
@@ -61,8 +97,23 @@ impl Default for Settings {
             loop_mode: Loop::Queue,
             volume: 70,
             speed: 10,
+            pitch_correction: true,
             add_playlist_front: false,
             gapless: true,
+            crossfade: 0,
+            replaygain: ReplayGain::Off,
+            replaygain_preamp: 0.0,
+            equalizer: "Flat".to_string(),
+            output_device: String::new(),
+            quit_server_on_exit: false,
+            resume_on_startup: false,
+            remember_position_minutes: 20,
+            remember_ab_loop: true,
+            mpd_address: "127.0.0.1:6600".to_string(),
+            radio_browser_url: "https://de1.api.radio-browser.info".to_string(),
+            podcast_dir: "~/Music/podcasts".to_string(),
+            rating_write_to_file: false,
+            equalizer_presets: EqualizerPreset::defaults(),
             disable_exit_confirmation: false,
             playlist_display_symbol: true,
             keys: Keys::default(),
@@ -0,0 +1,342 @@
+//! CUE sheets, which split one audio file, usually a whole album, into its tracks. A track of
+//! a sheet is found at `cue:///path/of/album.cue/3`, with the path of the audio file instead
+//! of the sheet's when the sheet is embedded in its `CUESHEET` tag.
+use crate::track::{Segment, Track};
+use crate::utils::filetype_supported;
+use anyhow::{anyhow, bail, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const SCHEME: &str = "cue://";
+// an index is given in minutes, seconds and frames of a CD, 75 per second
+const FRAMES_PER_SECOND: u64 = 75;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct CueSheet {
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub genre: Option<String>,
+    pub tracks: Vec<CueTrack>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct CueTrack {
+    pub number: u32,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    /// The audio file as named in the sheet, relative to it
+    pub file: String,
+    /// `INDEX 01`, the pregap before it belongs to the track before
+    pub start: Duration,
+    /// The start of the next track in the same file, none for the last one
+    pub end: Option<Duration>,
+}
+
+impl CueSheet {
+    /// Reads the sheet, which is most often UTF-8 but can be Latin-1 as well.
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let text = String::from_utf8(bytes)
+            .unwrap_or_else(|e| e.into_bytes().iter().map(|b| char::from(*b)).collect());
+        let sheet = Self::parse(&text);
+        if sheet.tracks.is_empty() {
+            bail!("no tracks in {}", path.display());
+        }
+        Ok(sheet)
+    }
+
+    /// Commands that are not understood are left out, and so are tracks without `INDEX 01`.
+    pub fn parse(text: &str) -> Self {
+        let mut sheet = Self::default();
+        let mut file = String::new();
+        let mut track: Option<CueTrack> = None;
+        let mut tracks = Vec::new();
+        for line in text.trim_start_matches('\u{feff}').lines() {
+            let (command, args) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            match command.to_uppercase().as_str() {
+                "FILE" => file = file_name(args),
+                "TRACK" => {
+                    tracks.extend(track.take());
+                    let number = args.split_whitespace().next().and_then(|n| n.parse().ok());
+                    track = number.map(|number| CueTrack {
+                        number,
+                        ..CueTrack::default()
+                    });
+                }
+                "TITLE" => match track.as_mut() {
+                    Some(track) => track.title = Some(unquote(args)),
+                    None => sheet.title = Some(unquote(args)),
+                },
+                "PERFORMER" => match track.as_mut() {
+                    Some(track) => track.performer = Some(unquote(args)),
+                    None => sheet.performer = Some(unquote(args)),
+                },
+                "REM" => {
+                    if let Some(genre) = args.strip_prefix("GENRE ") {
+                        sheet.genre = Some(unquote(genre));
+                    }
+                }
+                "INDEX" => {
+                    let mut args = args.split_whitespace();
+                    if let (Some(track), Some("01"), Some(start)) = (
+                        track.as_mut(),
+                        args.next(),
+                        args.next().and_then(parse_time),
+                    ) {
+                        // a track can start in the file after the one of its pregap
+                        track.file = file.clone();
+                        track.start = start;
+                    }
+                }
+                _ => {}
+            }
+        }
+        tracks.extend(track);
+
+        tracks.retain(|track| !track.file.is_empty());
+        for i in 1..tracks.len() {
+            if tracks[i].file == tracks[i - 1].file {
+                tracks[i - 1].end = Some(tracks[i].start);
+            }
+        }
+        sheet.tracks = tracks;
+        sheet
+    }
+}
+
+// FILE "Some Album.flac" WAVE
+fn file_name(args: &str) -> String {
+    let args = args.trim();
+    if args.starts_with('"') {
+        return unquote(args);
+    }
+    args.rsplit_once(' ')
+        .map_or(args, |(name, _file_type)| name)
+        .to_string()
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    match value.strip_prefix('"') {
+        Some(value) => value.split('"').next().unwrap_or(value).to_string(),
+        None => value.to_string(),
+    }
+}
+
+// mm:ss:ff, `None` for values that don't fit
+fn parse_time(time: &str) -> Option<Duration> {
+    let mut parts = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (minutes, seconds, frames) = (parts.next()??, parts.next()??, parts.next()??);
+    let seconds = minutes.checked_mul(60)?.checked_add(seconds)?;
+    let millis = frames.checked_mul(1000)? / FRAMES_PER_SECOND;
+    Duration::from_secs(seconds).checked_add(Duration::from_millis(millis))
+}
+
+pub fn is_cue_sheet(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("cue"))
+}
+
+/// Whether the location is a track of a CUE sheet, rather than a file.
+pub fn is_cue_track(location: &str) -> bool {
+    location.starts_with(SCHEME)
+}
+
+/// A sheet or the track of one, which plays one or more tracks.
+pub fn is_cue(location: &str) -> bool {
+    is_cue_track(location) || is_cue_sheet(Path::new(location))
+}
+
+/// Where the track with the number is found, `path` being the sheet or the audio file it is
+/// embedded in.
+pub fn location(path: &Path, number: u32) -> String {
+    format!("{}{}/{}", SCHEME, path.display(), number)
+}
+
+fn parse_location(location: &str) -> Option<(PathBuf, u32)> {
+    let (path, number) = location.strip_prefix(SCHEME)?.rsplit_once('/')?;
+    Some((PathBuf::from(path), number.parse().ok()?))
+}
+
+/// The track of a sheet at `location`.
+pub fn read_track(location: &str) -> Result<Track> {
+    let (path, number) =
+        parse_location(location).ok_or_else(|| anyhow!("invalid location {}", location))?;
+    read_tracks(&path)?
+        .into_iter()
+        .find(|track| track.file() == Some(location))
+        .ok_or_else(|| anyhow!("no track {} in {}", number, path.display()))
+}
+
+/// The tracks a file plays: the ones of a sheet, or of the sheet embedded in an audio file, or
+/// else the file itself.
+pub fn read_tracks(path: &Path) -> Result<Vec<Track>> {
+    if is_cue_sheet(path) {
+        let sheet = CueSheet::read(path)?;
+        let mut audio: HashMap<&str, Option<Track>> = HashMap::new();
+        let mut tracks = Vec::new();
+        for cue_track in &sheet.tracks {
+            let whole = audio.entry(cue_track.file.as_str()).or_insert_with(|| {
+                audio_path(path, &cue_track.file).and_then(|file| Track::read_from_path(file).ok())
+            });
+            if let Some(whole) = whole {
+                tracks.push(split(whole.clone(), path, &sheet, cue_track));
+            }
+        }
+        if tracks.is_empty() {
+            bail!("cannot find the audio files of {}", path.display());
+        }
+        return Ok(tracks);
+    }
+
+    let track = Track::read_from_path(path)?;
+    match track.cuesheet().map(CueSheet::parse) {
+        Some(sheet) if !sheet.tracks.is_empty() => Ok(sheet
+            .tracks
+            .iter()
+            .map(|cue_track| split(track.clone(), path, &sheet, cue_track))
+            .collect()),
+        _ => Ok(vec![track]),
+    }
+}
+
+fn split(whole: Track, path: &Path, sheet: &CueSheet, cue_track: &CueTrack) -> Track {
+    let title = cue_track
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Track {:02}", cue_track.number));
+    let modified = path
+        .metadata()
+        .and_then(|m| m.modified())
+        .unwrap_or(std::time::UNIX_EPOCH);
+    let mut track = whole.into_cue_track(
+        &location(path, cue_track.number),
+        &format!("{:02} {}", cue_track.number, title),
+        Segment {
+            start: cue_track.start,
+            end: cue_track.end,
+        },
+    );
+    track.set_title(&title);
+    if let Some(performer) = cue_track.performer.as_ref().or(sheet.performer.as_ref()) {
+        track.set_artist(performer);
+    }
+    if let Some(album) = &sheet.title {
+        track.set_album(album);
+    }
+    if let Some(genre) = &sheet.genre {
+        track.set_genre(genre);
+    }
+    // a changed sheet changes its tracks as much as a changed audio file
+    track.last_modified = track.last_modified.max(modified);
+    track
+}
+
+// Rips often keep the sheet of the wav file they were encoded from, so a file with the same
+// name and another extension does as well.
+fn audio_path(sheet: &Path, name: &str) -> Option<PathBuf> {
+    let dir = sheet.parent()?;
+    let path = dir.join(name);
+    if path.exists() {
+        return Some(path);
+    }
+    let stem = path.file_stem()?.to_owned();
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .find(|p| p.file_stem() == Some(&stem) && filetype_supported(&p.to_string_lossy()))
+}
+
+/// The audio files that the sheets among `files` split into tracks. They are left out where
+/// the tracks of the sheets are, so that they don't show up twice.
+pub fn split_files<'a>(files: impl IntoIterator<Item = &'a Path>) -> HashSet<PathBuf> {
+    files
+        .into_iter()
+        .filter(|path| is_cue_sheet(path))
+        .filter_map(|path| {
+            let sheet = CueSheet::read(path).ok()?;
+            Some(
+                sheet
+                    .tracks
+                    .into_iter()
+                    .filter_map(|track| audio_path(path, &track.file))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_cue_sheet() {
+        let sheet = CueSheet::parse(
+            "\u{feff}REM GENRE Jazz\r\n\
+             PERFORMER \"Miles Davis\"\r\n\
+             TITLE \"Kind of Blue\"\r\n\
+             FILE \"Kind of Blue.wav\" WAVE\r\n  \
+               TRACK 01 AUDIO\r\n    \
+                 TITLE \"So What\"\r\n    \
+                 INDEX 01 00:00:00\r\n  \
+               TRACK 02 AUDIO\r\n    \
+                 TITLE \"Freddie Freeloader\"\r\n    \
+                 PERFORMER \"Miles Davis Sextet\"\r\n    \
+                 INDEX 00 09:20:40\r\n    \
+                 INDEX 01 09:22:15\r\n\
+             FILE second.flac WAVE\r\n  \
+               TRACK 03 AUDIO\r\n    \
+                 INDEX 01 00:00:00\r\n",
+        );
+        assert_eq!(sheet.title.as_deref(), Some("Kind of Blue"));
+        assert_eq!(sheet.performer.as_deref(), Some("Miles Davis"));
+        assert_eq!(sheet.genre.as_deref(), Some("Jazz"));
+        assert_eq!(sheet.tracks.len(), 3);
+
+        let start = Duration::from_secs(9 * 60 + 22) + Duration::from_millis(200);
+        assert_eq!(sheet.tracks[0].file, "Kind of Blue.wav");
+        assert_eq!(sheet.tracks[0].end, Some(start));
+        assert_eq!(sheet.tracks[1].start, start);
+        assert_eq!(sheet.tracks[1].end, None);
+        assert_eq!(
+            sheet.tracks[1].performer.as_deref(),
+            Some("Miles Davis Sextet")
+        );
+        assert_eq!(sheet.tracks[2].file, "second.flac");
+        assert_eq!(sheet.tracks[2].title, None);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("09:22:15"),
+            Some(Duration::from_secs(9 * 60 + 22) + Duration::from_millis(200))
+        );
+        assert_eq!(parse_time("00:00:00"), Some(Duration::ZERO));
+        assert_eq!(parse_time("1:2"), None);
+        assert_eq!(parse_time("a:00:00"), None);
+
+        let max = u64::MAX.to_string();
+        assert_eq!(parse_time(&format!("{}:00:00", max)), None);
+        assert_eq!(parse_time(&format!("00:00:{}", max)), None);
+        // the frames add a second to the longest duration
+        assert_eq!(parse_time(&format!("00:{}:75", max)), None);
+        assert!(parse_time(&format!("00:{}:74", max)).is_some());
+    }
+
+    #[test]
+    fn test_location() {
+        let location = location(Path::new("/music/Kind of Blue.cue"), 2);
+        assert_eq!(location, "cue:///music/Kind of Blue.cue/2");
+        assert!(is_cue_track(&location));
+        assert_eq!(
+            parse_location(&location),
+            Some((PathBuf::from("/music/Kind of Blue.cue"), 2))
+        );
+    }
+}
@@ -3,6 +3,7 @@
 #![warn(clippy::all, clippy::correctness)]
 // #![warn(rust_2018_idioms)]
 
+mod chapter;
 /**
  * MIT License
  *
@@ -26,12 +27,21 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+mod cli;
 mod config;
+mod cue;
 #[cfg(feature = "discord")]
 mod discord;
 mod invidious;
+mod named_playlist;
 mod player;
 mod playlist;
+mod podcast;
+mod radio_browser;
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+mod replaygain;
+mod server;
+mod smart_playlist;
 mod songtag;
 mod sqlite;
 mod track;
@@ -62,6 +72,34 @@ fn main() {
         process::exit(0);
     }
 
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    if let Some(index) = args
+        .iter()
+        .position(|arg| arg == "-r" || arg == "--replaygain-scan")
+    {
+        match args.get(index + 1) {
+            Some(dir) => replaygain_scan(Path::new(dir)),
+            None => {
+                eprintln!("Error: '--replaygain-scan' needs a file or directory");
+                process::exit(1);
+            }
+        }
+    }
+
+    if args.iter().any(|arg| arg == "--server") {
+        if let Err(e) = server::run(&config) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        process::exit(0);
+    }
+
+    if let Some(command) = args.first() {
+        if cli::is_command(command) {
+            process::exit(cli::run(command, &args[1..]));
+        }
+    }
+
     if let Some(dir) = args.first() {
         let mut path = Path::new(dir).to_path_buf();
 
@@ -83,7 +121,32 @@ fn main() {
         }
     }
 
-    UI::new(&config).run();
+    match UI::new(&config) {
+        Ok(mut ui) => ui.run(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+fn replaygain_scan(path: &Path) {
+    if !path.exists() {
+        eprintln!("Error: '{}' doesn't exist", path.display());
+        process::exit(1);
+    }
+
+    let (tagged, failed) = replaygain::scan(path, |index, total, name| {
+        println!("[{}/{}] {}", index, total, name);
+    });
+
+    for (file, error) in &failed {
+        eprintln!("Failed: {}: {}", file, error);
+    }
+    println!("ReplayGain written to {} files.", tagged);
+
+    process::exit(i32::from(!failed.is_empty()));
 }
 
 fn display_help() {
@@ -92,6 +155,7 @@ fn display_help() {
 Termusic help:
 
 Usage: termusic [OPTIONS] [MUSIC_DIRECTORY]
+       termusic COMMAND
 
 With no MUSIC_DIRECTORY, use `~/.config/termusic/config.toml`
 
@@ -99,6 +163,41 @@ With no MUSIC_DIRECTORY, use `~/.config/termusic/config.toml`
 Options:
     -h, --help        Print this message and exit.
     -v, --version     Print version and exit.
+    -r, --replaygain-scan <PATH>
+                      Measure loudness of the files under PATH, write
+                      ReplayGain tags and exit. Not available with the mpv
+                      and gstreamer backends, which don't need it to play.
+    --server          Play in the background without the interface. The
+                      interface starts one when none is running, and
+                      attaches to it otherwise.
+
+
+Commands, for a running termusic. A MUSIC_DIRECTORY with the name of a
+command, like ./next, is opened rather than taken for the command:
+    play              Resume playback.
+    pause             Pause playback.
+    toggle            Toggle between play and pause.
+    next              Play the next track.
+    prev              Play the previous track.
+    seek <SECONDS>    Seek to a position, or by an offset with +SECONDS or
+                      -SECONDS.
+    volume <VOLUME>   Set the volume from 0 to 100, or change it with +STEP
+                      or -STEP.
+    add <PATH>...     Add files, or the audio files of directories, to the
+                      end of the playlist.
+    sleep <TIMER>     Pause after a number of minutes up to a day, fading out
+                      over the last one, at the end of the current track
+                      with end, or of the second track after it with end+2.
+                      off cancels the timer.
+    status [--json]   Print the state of the player. The JSON object has the
+                      fields status, volume, speed, gapless, loop_mode,
+                      position, duration, file, title, artist and album.
+
+Exit codes of commands:
+    0  Done.
+    1  The player couldn't do it, like adding a file that doesn't exist.
+    2  Wrong command line.
+    3  termusic is not running.
   "
     );
 
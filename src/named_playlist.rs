@@ -0,0 +1,288 @@
+//! Playlists with a name, next to the play queue. Each one is an m3u8 file in
+//! the `playlists` folder of the config dir, named after the playlist.
+use crate::config::get_app_config_path;
+use crate::playlist::PlaylistItem;
+use crate::track::Track;
+use crate::utils::get_pin_yin;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const NAMED_PLAYLIST_DIR: &str = "playlists";
+const NAMED_PLAYLIST_EXT: &str = "m3u8";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaylistEntry {
+    pub file: String,
+    /// "Artist - Title" as kept in `#EXTINF`, empty when unknown
+    pub info: String,
+    /// In seconds, -1 when unknown
+    pub duration: i64,
+}
+
+impl PlaylistEntry {
+    pub fn from_file(file: &str) -> Self {
+        match Track::read_from_path(file) {
+            Ok(track) => Self::from(&track),
+            Err(_) => Self {
+                file: file.to_string(),
+                info: String::new(),
+                duration: -1,
+            },
+        }
+    }
+
+    /// What is shown for the entry, the file name when there is no info.
+    pub fn display(&self) -> String {
+        if !self.info.is_empty() {
+            return self.info.clone();
+        }
+        Path::new(&self.file).file_name().map_or_else(
+            || self.file.clone(),
+            |name| name.to_string_lossy().to_string(),
+        )
+    }
+}
+
+impl From<&Track> for PlaylistEntry {
+    fn from(track: &Track) -> Self {
+        let info = match (track.artist(), track.title()) {
+            (Some(artist), Some(title)) => format!("{} - {}", artist, title),
+            (None, Some(title)) => title.to_string(),
+            _ => String::new(),
+        };
+        Self {
+            file: track.file().unwrap_or_default().to_string(),
+            info,
+            duration: track.duration().as_secs() as i64,
+        }
+    }
+}
+
+impl From<&PlaylistEntry> for PlaylistItem {
+    // The tags of the file when it can be read, as the info mixes artist and title.
+    fn from(entry: &PlaylistEntry) -> Self {
+        if let Ok(track) = Track::read_from_path(&entry.file) {
+            return Self::from(&track);
+        }
+        Self {
+            location: entry.file.clone(),
+            title: (!entry.info.is_empty()).then(|| entry.info.clone()),
+            artist: None,
+            duration: u64::try_from(entry.duration).ok().map(Duration::from_secs),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NamedPlaylist {
+    pub name: String,
+    pub entries: Vec<PlaylistEntry>,
+}
+
+impl NamedPlaylist {
+    /// The names of all playlists, sorted.
+    pub fn names() -> Result<Vec<String>> {
+        let dir = Self::dir()?;
+        let mut names: Vec<String> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == NAMED_PLAYLIST_EXT)
+            })
+            .filter_map(|path| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+            })
+            .collect();
+        names.sort_by_cached_key(|name| get_pin_yin(name));
+        Ok(names)
+    }
+
+    pub fn load(name: &str) -> Result<Self> {
+        let path = Self::path(name)?;
+        let content = fs::read_to_string(&path)?;
+        Ok(Self {
+            name: name.to_string(),
+            entries: parse_m3u8(&content, &Self::dir()?),
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(Self::path(&self.name)?, to_m3u8(&self.entries))?;
+        Ok(())
+    }
+
+    /// Creates an empty playlist, unless there is one with that name.
+    pub fn create(name: &str) -> Result<()> {
+        Self::check_name(name)?;
+        if Self::path(name)?.exists() {
+            bail!("playlist {} exists already", name);
+        }
+        Self {
+            name: name.to_string(),
+            entries: vec![],
+        }
+        .save()
+    }
+
+    pub fn rename(name: &str, new_name: &str) -> Result<()> {
+        Self::check_name(new_name)?;
+        let new_path = Self::path(new_name)?;
+        if new_path.exists() {
+            bail!("playlist {} exists already", new_name);
+        }
+        fs::rename(Self::path(name)?, new_path)?;
+        Ok(())
+    }
+
+    pub fn delete(name: &str) -> Result<()> {
+        fs::remove_file(Self::path(name)?)?;
+        Ok(())
+    }
+
+    /// Copies the playlist to "<name> copy", with a number when that is taken,
+    /// and returns the name of the copy.
+    pub fn duplicate(name: &str) -> Result<String> {
+        let mut playlist = Self::load(name)?;
+        let mut copy = format!("{} copy", name);
+        let mut number = 2;
+        while Self::path(&copy)?.exists() {
+            copy = format!("{} copy {}", name, number);
+            number += 1;
+        }
+        playlist.name.clone_from(&copy);
+        playlist.save()?;
+        Ok(copy)
+    }
+
+    pub fn append(name: &str, entries: Vec<PlaylistEntry>) -> Result<()> {
+        let mut playlist = Self::load(name)?;
+        playlist.entries.extend(entries);
+        playlist.save()
+    }
+
+    /// Points the entries of all playlists at the new place of moved files.
+    pub fn relocate(moved: &HashMap<String, String>) -> Result<()> {
+        if moved.is_empty() {
+            return Ok(());
+        }
+        for name in Self::names()? {
+            let mut playlist = Self::load(&name)?;
+            let mut changed = false;
+            for entry in &mut playlist.entries {
+                if let Some(file) = moved.get(&entry.file) {
+                    entry.file.clone_from(file);
+                    changed = true;
+                }
+            }
+            if changed {
+                playlist.save()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_name(name: &str) -> Result<()> {
+        if name.trim().is_empty() {
+            bail!("playlist name is empty");
+        }
+        if name.starts_with('.') || name.contains('/') || name.contains('\\') {
+            bail!("playlist name can't start with . or contain slashes");
+        }
+        Ok(())
+    }
+
+    fn dir() -> Result<PathBuf> {
+        let mut path = get_app_config_path()?;
+        path.push(NAMED_PLAYLIST_DIR);
+        if !path.exists() {
+            fs::create_dir_all(&path)?;
+        }
+        Ok(path)
+    }
+
+    fn path(name: &str) -> Result<PathBuf> {
+        let mut path = Self::dir()?;
+        path.push(format!("{}.{}", name, NAMED_PLAYLIST_EXT));
+        Ok(path)
+    }
+}
+
+// Relative paths are taken from `base`, `#EXTINF` lines belong to the path after them.
+fn parse_m3u8(content: &str, base: &Path) -> Vec<PlaylistEntry> {
+    let mut entries = vec![];
+    let mut extinf: Option<(i64, String)> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("#EXTINF:") {
+            let (duration, info) = value.split_once(',').unwrap_or((value, ""));
+            extinf = Some((duration.trim().parse().unwrap_or(-1), info.to_string()));
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let file = if Path::new(line).is_relative() {
+            base.join(line).to_string_lossy().to_string()
+        } else {
+            line.to_string()
+        };
+        let (duration, info) = extinf.take().unwrap_or((-1, String::new()));
+        entries.push(PlaylistEntry {
+            file,
+            info,
+            duration,
+        });
+    }
+    entries
+}
+
+fn to_m3u8(entries: &[PlaylistEntry]) -> String {
+    let mut content = String::from("#EXTM3U\n");
+    for entry in entries {
+        writeln!(content, "#EXTINF:{},{}", entry.duration, entry.info).ok();
+        writeln!(content, "{}", entry.file).ok();
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_m3u8_round_trip() {
+        let entries = vec![
+            PlaylistEntry {
+                file: "/music/Miles Davis/So What.flac".to_string(),
+                info: "Miles Davis - So What".to_string(),
+                duration: 562,
+            },
+            PlaylistEntry {
+                file: "/music/unknown.mp3".to_string(),
+                info: String::new(),
+                duration: -1,
+            },
+        ];
+        let content = to_m3u8(&entries);
+        assert_eq!(
+            content,
+            "#EXTM3U\n#EXTINF:562,Miles Davis - So What\n/music/Miles Davis/So What.flac\n\
+             #EXTINF:-1,\n/music/unknown.mp3\n"
+        );
+        assert_eq!(parse_m3u8(&content, Path::new("/playlists")), entries);
+
+        let entries = parse_m3u8(
+            "#EXTM3U\r\n#PLAYLIST:Other\r\nsong.ogg\r\n#EXTINF:10,A, B - C\r\n/a.mp3\r\n",
+            Path::new("/playlists"),
+        );
+        assert_eq!(entries[0].file, "/playlists/song.ogg");
+        assert_eq!(entries[0].duration, -1);
+        assert_eq!(entries[1].info, "A, B - C");
+        assert_eq!(entries[1].duration, 10);
+    }
+}
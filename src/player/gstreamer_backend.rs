@@ -21,7 +21,7 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use super::{PlayerMsg, PlayerTrait};
+use super::{PlayerMsg, PlayerTrait, ReplayGain};
 use crate::config::Settings;
 use anyhow::Result;
 use gst::ClockTime;
@@ -74,6 +74,15 @@ impl GStreamer {
             .expect("Could not create autoaudiosink element.");
 
         playbin.set_property("audio-sink", &sink);
+
+        // rgvolume reads the ReplayGain tags from the stream, it's in gst-plugins-good
+        if config.replaygain != ReplayGain::Off {
+            if let Ok(rgvolume) = gst::ElementFactory::make("rgvolume", Some("replaygain")) {
+                rgvolume.set_property("album-mode", config.replaygain == ReplayGain::Album);
+                rgvolume.set_property("pre-amp", f64::from(config.replaygain_preamp));
+                playbin.set_property("audio-filter", &rgvolume);
+            }
+        }
         // Set flags to show Audio and Video but ignore Subtitles
         let flags = playbin.property_value("flags");
         let flags_class = FlagsClass::new(flags.type_()).unwrap();
@@ -11,12 +11,13 @@ use anyhow::Result;
 #[cfg(feature = "mpv")]
 use mpv_backend::MpvBackend;
 pub use playlist::Playlist;
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+pub use rusty_backend::{Source, Symphonia};
 use serde::{Deserialize, Serialize};
 use std::sync::mpsc::{self, Receiver, Sender};
-#[cfg(not(any(feature = "mpv", feature = "gst")))]
 use std::time::Duration;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub enum Status {
     Running,
     Stopped,
@@ -33,7 +34,7 @@ impl std::fmt::Display for Status {
     }
 }
 
-#[derive(Clone, Copy, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub enum Loop {
     Single,
     Playlist,
@@ -59,12 +60,82 @@ impl Loop {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum ReplayGain {
+    Off,
+    Track,
+    Album,
+}
+
+impl std::fmt::Display for ReplayGain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Track => write!(f, "track"),
+            Self::Album => write!(f, "album"),
+        }
+    }
+}
+
+/// Center frequencies in Hz of the equalizer bands.
+pub const EQUALIZER_BANDS: [f32; 10] = [
+    31.0, 62.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EqualizerPreset {
+    pub name: String,
+    /// Gain in dB for each of the `EQUALIZER_BANDS`, missing ones are flat
+    pub gains: Vec<f32>,
+    /// Tracks with one of these genres switch to this preset while they play
+    #[serde(default)]
+    pub genres: Vec<String>,
+}
+
+impl EqualizerPreset {
+    fn new(name: &str, gains: [f32; 10]) -> Self {
+        Self {
+            name: name.to_string(),
+            gains: gains.to_vec(),
+            genres: Vec::new(),
+        }
+    }
+
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("Flat", [0.0; 10]),
+            Self::new(
+                "Bass Boost",
+                [6.0, 5.0, 4.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            ),
+            Self::new(
+                "Treble Boost",
+                [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 4.0, 5.0, 6.0],
+            ),
+            Self::new(
+                "Vocal",
+                [-2.0, -2.0, -1.0, 0.0, 2.0, 4.0, 4.0, 2.0, 0.0, -1.0],
+            ),
+            Self::new("Rock", [4.0, 3.0, 2.0, 0.0, -1.0, -1.0, 0.0, 2.0, 3.0, 4.0]),
+            Self::new(
+                "Classical",
+                [3.0, 2.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0],
+            ),
+        ]
+    }
+}
+
 // #[allow(unused)]
 pub enum PlayerMsg {
     Eos,
     AboutToFinish,
     CurrentTrackUpdated,
     Progress(i64, i64),
+    /// The song title an internet radio sent along with the audio
+    StreamTitle(String),
+    /// A stream opened away from the server loop, ready to be queued
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    StreamOpened(usize, Symphonia),
 }
 
 pub struct GeneralPlayer {
@@ -81,23 +152,31 @@ pub struct GeneralPlayer {
     pub config: Settings,
     next_track: Option<Track>,
     #[cfg(not(any(feature = "mpv", feature = "gst")))]
-    next_track_duration: Duration,
+    // none for streams
+    next_track_duration: Option<Duration>,
+    // the track that played when the server quit last time, with its position
+    resume_point: Option<(String, i64)>,
 }
 
 impl GeneralPlayer {
-    pub fn new(config: &Settings) -> Self {
+    pub fn new(config: &Settings) -> Result<Self> {
         let (message_tx, message_rx): (Sender<PlayerMsg>, Receiver<PlayerMsg>) = mpsc::channel();
         #[cfg(all(feature = "gst", not(feature = "mpv")))]
         let player = gstreamer_backend::GStreamer::new(config, message_tx.clone());
         #[cfg(feature = "mpv")]
         let player = MpvBackend::new(config, message_tx.clone());
         #[cfg(not(any(feature = "mpv", feature = "gst")))]
-        let player = rusty_backend::Player::new(config, message_tx.clone());
+        let player = rusty_backend::Player::new(config, message_tx.clone())?;
         let mut playlist = Playlist::default();
         if let Ok(p) = Playlist::new() {
             playlist = p;
         }
-        Self {
+        let resume_point = if config.resume_on_startup {
+            playlist.resume()
+        } else {
+            None
+        };
+        let mut this = Self {
             player,
             message_tx,
             message_rx,
@@ -106,9 +185,102 @@ impl GeneralPlayer {
             config: config.clone(),
             next_track: None,
             #[cfg(not(any(feature = "mpv", feature = "gst")))]
-            next_track_duration: Duration::from_secs(0),
+            next_track_duration: None,
+            resume_point,
+        };
+        this.apply_equalizer();
+        Ok(this)
+    }
+
+    /// The preset of the current track's genre if there is one, the selected preset otherwise.
+    pub fn equalizer_preset(&self) -> Option<&EqualizerPreset> {
+        let presets = &self.config.equalizer_presets;
+        self.playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::genre)
+            .and_then(|genre| {
+                presets
+                    .iter()
+                    .find(|p| p.genres.iter().any(|g| g.eq_ignore_ascii_case(genre)))
+            })
+            .or_else(|| presets.iter().find(|p| p.name == self.config.equalizer))
+    }
+
+    pub fn set_equalizer(&mut self, name: &str) {
+        self.config.equalizer = name.to_string();
+        self.apply_equalizer();
+    }
+
+    // only the default backend has an equalizer
+    fn apply_equalizer(&mut self) {
+        #[cfg(not(any(feature = "mpv", feature = "gst")))]
+        {
+            let gains = self
+                .equalizer_preset()
+                .map(|p| p.gains.clone())
+                .unwrap_or_default();
+            self.player.set_equalizer(&gains);
+        }
+    }
+    /// Names of the audio output devices that can be picked.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn output_devices() -> Vec<String> {
+        rusty_backend::output_device_names()
+    }
+
+    /// Switches the output device, empty for the default one, and goes on with the current
+    /// track from where it was.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn set_output_device(&mut self, name: &str) -> Result<()> {
+        let position = self.player.elapsed();
+        let paused = self.is_paused();
+        self.player
+            .set_output_device(name)
+            .map_err(|e| anyhow::anyhow!("cannot open output device {}: {}", name, e))?;
+
+        // the track queued for gapless playback went away with the old sink
+        self.next_track = None;
+        if let Some(track) = &self.playlist.current_track {
+            if let Some(file) = track.audio_file() {
+                self.player.set_replaygain(self.replaygain_factor(track));
+                self.player.set_segment(track.segment());
+                self.player.add_and_play(file);
+                self.player.seek_to(position);
+                self.player.sink.message_on_end();
+            }
+        }
+        self.apply_equalizer();
+        if paused {
+            self.player.pause();
         }
+        Ok(())
     }
+
+    /// Queues a stream once it is opened.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn stream_opened(&mut self, id: usize, decoder: Symphonia) {
+        self.player.stream_opened(id, decoder);
+    }
+
+    /// Where the current track is, more precise than the progress messages.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn elapsed(&self) -> Duration {
+        self.player.elapsed()
+    }
+
+    /// Loops the part between the two positions of the current track, none plays on. The
+    /// other backends leave it to the progress messages.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn set_ab_loop(&mut self, ab_loop: Option<(Duration, Duration)>) {
+        self.player.set_ab_loop(ab_loop);
+    }
+
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn output_device_lost(&self) -> bool {
+        self.player.output_device_lost()
+    }
+
     pub fn toggle_gapless(&mut self) {
         self.player.gapless = !self.player.gapless;
     }
@@ -118,8 +290,21 @@ impl GeneralPlayer {
             self.set_status(Status::Running);
         }
         self.handle_current_track();
-        if let Some(file) = self.playlist.get_current_track() {
+        self.apply_equalizer();
+        let file = self
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::audio_file)
+            .map(ToString::to_string);
+        if let Some(file) = file {
             if !self.has_next_track() {
+                #[cfg(not(any(feature = "mpv", feature = "gst")))]
+                if let Some(track) = &self.playlist.current_track {
+                    let factor = self.replaygain_factor(track);
+                    self.player.set_replaygain(factor);
+                    self.player.set_segment(track.segment());
+                }
                 self.add_and_play(&file);
                 // eprintln!("completely new track added");
                 #[cfg(not(any(feature = "mpv", feature = "gst")))]
@@ -134,7 +319,7 @@ impl GeneralPlayer {
                 // eprintln!("next track played");
                 #[cfg(not(any(feature = "mpv", feature = "gst")))]
                 {
-                    self.player.total_duration = Some(self.next_track_duration);
+                    self.player.total_duration = self.next_track_duration;
                     self.player.sink.message_on_end();
                     self.message_tx
                         .send(PlayerMsg::CurrentTrackUpdated)
@@ -161,14 +346,38 @@ impl GeneralPlayer {
         }
     }
 
+    /// How long the next track fades in over the end of the current one. Tracks of the same
+    /// album are played gapless instead, so that albums mixed as one piece stay untouched.
+    pub fn crossfade(&self) -> Duration {
+        #[cfg(not(any(feature = "mpv", feature = "gst")))]
+        if let (Some(current), Some(next)) =
+            (&self.playlist.current_track, self.playlist.tracks.front())
+        {
+            return crossfade_between(
+                current,
+                next,
+                Duration::from_secs(self.config.crossfade.into()),
+            );
+        }
+        // only the default backend can crossfade
+        Duration::ZERO
+    }
+
     pub fn enqueue_next(&mut self) {
         if self.next_track.is_none() {
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            let crossfade = self.crossfade();
             if let Some(track) = self.playlist.tracks.get(0) {
                 self.next_track = Some(track.clone());
-                if let Some(file) = track.file() {
+                if let Some(file) = track.audio_file() {
                     #[cfg(not(any(feature = "mpv", feature = "gst")))]
-                    if let Some(d) = self.player.enqueue_next(file) {
-                        self.next_track_duration = d;
+                    {
+                        self.player.set_replaygain(self.replaygain_factor(track));
+                        self.player.set_segment(track.segment());
+                    }
+                    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+                    {
+                        self.next_track_duration = self.player.enqueue_next(file, crossfade);
                         // eprintln!("next track queued");
                     }
                     #[cfg(all(feature = "gst", not(feature = "mpv")))]
@@ -189,6 +398,14 @@ impl GeneralPlayer {
         }
     }
 
+    // mpv and gstreamer read the ReplayGain tags by themselves
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    fn replaygain_factor(&self, track: &Track) -> f32 {
+        track
+            .replaygain()
+            .factor(self.config.replaygain, self.config.replaygain_preamp)
+    }
+
     pub fn has_next_track(&mut self) -> bool {
         self.next_track.is_some()
     }
@@ -198,6 +415,15 @@ impl GeneralPlayer {
         self.player.skip_one();
     }
 
+    /// The position the track goes on from when it was the one playing at the last quit. Only
+    /// the first track played counts.
+    pub fn take_resume_point(&mut self, file: &str) -> Option<i64> {
+        self.resume_point
+            .take()
+            .filter(|(resume_file, _)| resume_file == file)
+            .map(|(_, position)| position)
+    }
+
     pub fn set_status(&mut self, status: Status) {
         self.status = status;
     }
@@ -287,3 +513,46 @@ pub trait PlayerTrait {
     fn speed(&self) -> i32;
     fn stop(&mut self);
 }
+
+// No crossfade at all between two tracks of one album.
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+fn crossfade_between(current: &Track, next: &Track, crossfade: Duration) -> Duration {
+    if current.album().is_some() && current.album() == next.album() {
+        return Duration::ZERO;
+    }
+    crossfade
+}
+
+#[cfg(all(test, not(any(feature = "mpv", feature = "gst"))))]
+mod tests {
+    use super::*;
+    use crate::playlist::PlaylistItem;
+    use pretty_assertions::assert_eq;
+
+    fn track(album: Option<&str>) -> Track {
+        let mut track = Track::from_playlist_item(&PlaylistItem {
+            location: "/music/track.flac".to_string(),
+            title: None,
+            artist: None,
+            duration: None,
+        });
+        if let Some(album) = album {
+            track.set_album(album);
+        }
+        track
+    }
+
+    #[test]
+    fn test_crossfade_between() {
+        let crossfade = Duration::from_secs(5);
+        let (a, b) = (track(Some("Kind of Blue")), track(Some("Blue Train")));
+        assert_eq!(crossfade_between(&a, &b, crossfade), crossfade);
+        assert_eq!(crossfade_between(&a, &a.clone(), crossfade), Duration::ZERO);
+        // two tracks without an album are not from the same one
+        assert_eq!(
+            crossfade_between(&track(None), &track(None), crossfade),
+            crossfade
+        );
+        assert_eq!(crossfade_between(&a, &track(None), crossfade), crossfade);
+    }
+}
@@ -23,7 +23,7 @@
  */
 mod libmpv;
 
-use super::{PlayerMsg, PlayerTrait};
+use super::{PlayerMsg, PlayerTrait, ReplayGain};
 use crate::config::Settings;
 use anyhow::Result;
 use libmpv::Mpv;
@@ -70,9 +70,19 @@ impl MpvBackend {
         mpv.set_property("volume", i64::from(volume))
             .expect("Error setting volume");
         mpv.set_property("speed", speed as f64 / 10.0).ok();
+        mpv.set_property("audio-pitch-correction", config.pitch_correction)
+            .ok();
         let gapless_setting = if gapless { "yes" } else { "no" };
         mpv.set_property("gapless-audio", gapless_setting)
             .expect("gapless setting failed");
+        let replaygain_setting = match config.replaygain {
+            ReplayGain::Off => "no",
+            ReplayGain::Track => "track",
+            ReplayGain::Album => "album",
+        };
+        mpv.set_property("replaygain", replaygain_setting).ok();
+        mpv.set_property("replaygain-preamp", f64::from(config.replaygain_preamp))
+            .ok();
 
         let mut duration: i64 = 0;
         // let mut time_pos: i64 = 0;
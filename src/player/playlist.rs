@@ -1,7 +1,10 @@
+use crate::playlist::PlaylistItem;
+use crate::server::PlaylistEdit;
+use crate::utils::is_stream;
 use crate::{config::get_app_config_path, track::Track};
 // use anyhow::{anyhow, bail, Result};
 use anyhow::Result;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::fs::File;
 // use std::io::{BufRead, BufReader, Write};
 use std::io::{BufRead, BufReader, Write};
@@ -48,7 +51,9 @@ impl Playlist {
 
         let mut playlist_items = VecDeque::new();
         for line in &lines {
-            if let Ok(s) = Track::read_from_path(line) {
+            if is_stream(line) {
+                playlist_items.push_back(Self::stream_track(line));
+            } else if let Ok(s) = Track::read_from_path(line) {
                 playlist_items.push_back(s);
             };
         }
@@ -69,6 +74,46 @@ impl Playlist {
         Ok(())
     }
 
+    /// Writes the current track and the seconds it played to `resume.log`, next to the
+    /// playlist. Nothing is written when no track plays.
+    pub fn save_resume_point(&self, position: i64) -> Result<()> {
+        let mut path = get_app_config_path()?;
+        path.push("resume.log");
+        let mut file = File::create(path.as_path())?;
+        if let Some(current) = self.current_track.as_ref().and_then(Track::file) {
+            writeln!(&mut file, "{}", current)?;
+            writeln!(&mut file, "{}", position)?;
+        }
+
+        Ok(())
+    }
+
+    /// Puts the track of `resume.log` in front of the playlist, so that it plays first, and
+    /// returns it with the seconds it had played.
+    pub fn resume(&mut self) -> Option<(String, i64)> {
+        let mut path = get_app_config_path().ok()?;
+        path.push("resume.log");
+        let content = std::fs::read_to_string(path).ok()?;
+        let mut lines = content.lines();
+        let file = lines.next()?.to_string();
+        let position = lines.next()?.trim().parse().ok()?;
+        self.put_in_front(&file)?;
+        Some((file, position))
+    }
+
+    // With loop mode playlist the current track went to the back, with single it stays in
+    // front and with queue it is gone.
+    fn put_in_front(&mut self, file: &str) -> Option<()> {
+        if self.tracks.back().and_then(Track::file) == Some(file) {
+            let track = self.tracks.pop_back()?;
+            self.tracks.push_front(track);
+        } else if self.tracks.front().and_then(Track::file) != Some(file) {
+            let track = self.tracks_from_files(&[file.to_string()]).pop_front()?;
+            self.tracks.push_front(track);
+        }
+        Some(())
+    }
+
     pub fn up(&mut self) {
         if self.tracks.is_empty() {
             return;
@@ -171,6 +216,94 @@ impl Playlist {
         }
     }
 
+    /// File paths of the tracks, as they are written to `playlist.log`.
+    pub fn files(&self) -> Vec<String> {
+        self.tracks
+            .iter()
+            .filter_map(Track::file)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Applies edits that were made on the `base` files. Tracks of the base that started
+    /// playing since are not in the playlist any more, so the positions are moved up by as
+    /// many, and the tracks to remove or move are looked for by their file.
+    pub fn apply_edits(&mut self, base: &[String], edits: &[PlaylistEdit]) {
+        let files = self.files();
+        let mut played = (0..base.len())
+            .find(|start| files.starts_with(&base[*start..]))
+            .unwrap_or(if files.is_empty() { base.len() } else { 0 });
+
+        for edit in edits {
+            match edit {
+                PlaylistEdit::Add { index, files } => {
+                    let at = index.saturating_sub(played).min(self.tracks.len());
+                    for (i, track) in self.tracks_from_files(files).into_iter().enumerate() {
+                        self.tracks.insert(at + i, track);
+                    }
+                }
+                PlaylistEdit::Remove { index, files } => {
+                    for file in files {
+                        if *index < played {
+                            played -= 1;
+                        } else if let Some(at) = self.find_near(file, index - played) {
+                            self.tracks.remove(at);
+                        }
+                    }
+                }
+                PlaylistEdit::Move { from, to, file } => {
+                    if *from < played {
+                        continue;
+                    }
+                    if let Some(track) = self
+                        .find_near(file, from - played)
+                        .and_then(|at| self.tracks.remove(at))
+                    {
+                        let at = to.saturating_sub(played).min(self.tracks.len());
+                        self.tracks.insert(at, track);
+                    }
+                }
+            }
+        }
+    }
+
+    // The position of the track with the file that is the closest to `index`.
+    fn find_near(&self, file: &str, index: usize) -> Option<usize> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, track)| track.file() == Some(file))
+            .min_by_key(|(i, _)| i.abs_diff(index))
+            .map(|(i, _)| i)
+    }
+
+    /// Tracks for the given files. The ones already in the playlist are reused, so that their
+    /// tags are not read again. Files that cannot be read are left out, streams are kept.
+    pub fn tracks_from_files(&self, files: &[String]) -> VecDeque<Track> {
+        let known: HashMap<&str, &Track> = self
+            .current_track
+            .iter()
+            .chain(self.tracks.iter())
+            .filter_map(|track| track.file().map(|file| (file, track)))
+            .collect();
+        files
+            .iter()
+            .filter_map(|file| match known.get(file.as_str()) {
+                Some(track) => Some((*track).clone()),
+                None if is_stream(file) => Some(Self::stream_track(file)),
+                None => Track::read_from_path(file).ok(),
+            })
+            .collect()
+    }
+
+    // all there is to know about a stream is its url
+    fn stream_track(url: &str) -> Track {
+        Track::from_playlist_item(&PlaylistItem {
+            location: url.to_string(),
+            ..PlaylistItem::default()
+        })
+    }
+
     pub fn get_current_track(&mut self) -> Option<String> {
         let mut result = None;
         if let Some(track) = &self.current_track {
@@ -181,3 +314,70 @@ impl Playlist {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // streams, so that there are no files to read
+    fn urls(names: &str) -> Vec<String> {
+        names
+            .chars()
+            .map(|c| format!("http://radio/{}", c))
+            .collect()
+    }
+
+    fn playlist(names: &str) -> Playlist {
+        let mut playlist = Playlist::default();
+        playlist.tracks = playlist.tracks_from_files(&urls(names));
+        playlist
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let mut list = playlist("abc");
+        list.apply_edits(
+            &urls("abc"),
+            &PlaylistEdit::diff(&urls("abc"), &urls("adbc")),
+        );
+        assert_eq!(list.files(), urls("adbc"));
+        list.apply_edits(
+            &urls("adbc"),
+            &PlaylistEdit::diff(&urls("adbc"), &urls("abdc")),
+        );
+        assert_eq!(list.files(), urls("abdc"));
+    }
+
+    #[test]
+    fn test_apply_edits_after_next_track() {
+        // "a" started playing before the edits made on "abc" arrived
+        let edit = |old: &str, new: &str| {
+            let mut list = playlist("bc");
+            list.apply_edits(&urls(old), &PlaylistEdit::diff(&urls(old), &urls(new)));
+            list.files()
+        };
+        assert_eq!(edit("abc", "abcd"), urls("bcd"));
+        assert_eq!(edit("abc", "axbc"), urls("xbc"));
+        assert_eq!(edit("abc", "ac"), urls("c"));
+        assert_eq!(edit("abc", "bc"), urls("bc"));
+        assert_eq!(edit("abc", "acb"), urls("cb"));
+        assert_eq!(edit("abc", ""), urls(""));
+    }
+
+    #[test]
+    fn test_put_in_front() {
+        let resume = |names: &str| {
+            let mut list = playlist(names);
+            list.put_in_front(&urls("a")[0]).unwrap();
+            list.files()
+        };
+        // loop mode playlist
+        assert_eq!(resume("bca"), urls("abc"));
+        // loop mode single
+        assert_eq!(resume("abc"), urls("abc"));
+        // loop mode queue
+        assert_eq!(resume("bc"), urls("abc"));
+        assert_eq!(resume(""), urls("a"));
+    }
+}
@@ -0,0 +1,266 @@
+//! AIFF and AIFF-C, which Symphonia has no reader for. The samples are PCM like in a wav file,
+//! only big-endian, so they are handed as packets to its PCM decoder.
+use std::io::{Seek, SeekFrom};
+use symphonia::core::{
+    audio::Channels,
+    codecs::{self, CodecParameters, CodecType},
+    errors::{end_of_stream_error, seek_error, unsupported_error, Result, SeekErrorKind},
+    formats::{Cue, FormatOptions, FormatReader, Packet, SeekMode, SeekTo, SeekedTo, Track},
+    io::{MediaSource, MediaSourceStream, ReadBytes},
+    meta::{Metadata, MetadataLog},
+    probe::{Descriptor, Instantiate, QueryDescriptor},
+    support_format,
+    units::TimeBase,
+};
+
+const MAX_FRAMES_PER_PACKET: u64 = 1152;
+
+/// Reads the sound data of an AIFF or AIFF-C file.
+pub struct AiffReader {
+    reader: MediaSourceStream,
+    tracks: Vec<Track>,
+    cues: Vec<Cue>,
+    metadata: MetadataLog,
+    frame_len: u64,
+    data_start_pos: u64,
+    data_end_pos: u64,
+}
+
+// the COMM chunk, with what the samples are
+struct Common {
+    channels: u16,
+    frames: u32,
+    bits_per_sample: u16,
+    sample_rate: u32,
+    codec: CodecType,
+}
+
+impl QueryDescriptor for AiffReader {
+    fn query() -> &'static [Descriptor] {
+        &[support_format!(
+            "aiff",
+            "Audio Interchange File Format",
+            &["aiff", "aif", "aifc"],
+            &["audio/aiff", "audio/x-aiff"],
+            &[b"FORM"]
+        )]
+    }
+
+    fn score(_context: &[u8]) -> u8 {
+        255
+    }
+}
+
+impl FormatReader for AiffReader {
+    fn try_new(mut source: MediaSourceStream, _options: &FormatOptions) -> Result<Self> {
+        if source.read_quad_bytes()? != *b"FORM" {
+            return unsupported_error("aiff: missing form marker");
+        }
+        let _form_len = source.read_be_u32()?;
+        let compressed = match &source.read_quad_bytes()? {
+            b"AIFF" => false,
+            b"AIFC" => true,
+            _ => return unsupported_error("aiff: form is not aiff"),
+        };
+
+        let mut common = None;
+        // the sound data can come before the description of its samples
+        let mut sound = None;
+        while common.is_none() || sound.is_none() {
+            let id = source.read_quad_bytes()?;
+            let len = u64::from(source.read_be_u32()?);
+            // chunks are padded to an even length
+            let padded_len = len + len % 2;
+            match &id {
+                b"COMM" => {
+                    common = Some(read_common(&mut source, compressed)?);
+                    let read = if compressed { 22 } else { 18 };
+                    source.ignore_bytes(padded_len.saturating_sub(read))?;
+                }
+                b"SSND" => {
+                    let offset = u64::from(source.read_be_u32()?);
+                    let _block_size = source.read_be_u32()?;
+                    let start = source.pos() + offset;
+                    sound = Some((start, len.saturating_sub(8 + offset)));
+                    if common.is_some() {
+                        break;
+                    }
+                    source.ignore_bytes(padded_len.saturating_sub(8))?;
+                }
+                _ => source.ignore_bytes(padded_len)?,
+            }
+        }
+        let (Some(common), Some((data_start_pos, data_len))) = (common, sound) else {
+            return unsupported_error("aiff: missing sound data");
+        };
+        if source.pos() < data_start_pos {
+            source.ignore_bytes(data_start_pos - source.pos())?;
+        } else if source.pos() > data_start_pos {
+            source.seek(SeekFrom::Start(data_start_pos))?;
+        }
+
+        let bytes_per_sample = u64::from(common.bits_per_sample).div_ceil(8);
+        let frame_len = u64::from(common.channels) * bytes_per_sample;
+        if frame_len == 0 {
+            return unsupported_error("aiff: no samples");
+        }
+        // streams written on the fly can leave the length of the sound data out
+        let data_len = match u64::from(common.frames) * frame_len {
+            len if data_len == 0 || len < data_len => len,
+            _ => data_len,
+        };
+
+        let mut codec_params = CodecParameters::new();
+        codec_params
+            .for_codec(common.codec)
+            .with_sample_rate(common.sample_rate)
+            .with_time_base(TimeBase::new(1, common.sample_rate))
+            .with_n_frames(data_len / frame_len)
+            .with_max_frames_per_packet(MAX_FRAMES_PER_PACKET)
+            .with_channels(channels(common.channels)?);
+        if common.bits_per_sample > 0 {
+            codec_params
+                .with_bits_per_coded_sample(u32::try_from(bytes_per_sample * 8).unwrap_or(0))
+                .with_bits_per_sample(u32::from(common.bits_per_sample));
+        }
+
+        Ok(Self {
+            reader: source,
+            tracks: vec![Track::new(0, codec_params)],
+            cues: Vec::new(),
+            metadata: MetadataLog::default(),
+            frame_len,
+            data_start_pos,
+            data_end_pos: data_start_pos + data_len,
+        })
+    }
+
+    fn next_packet(&mut self) -> Result<Packet> {
+        let pos = self.reader.pos();
+        let frames_left = self.data_end_pos.saturating_sub(pos) / self.frame_len;
+        if frames_left == 0 {
+            return end_of_stream_error();
+        }
+        let dur = frames_left.min(MAX_FRAMES_PER_PACKET);
+        let buf = self
+            .reader
+            .read_boxed_slice_exact(usize::try_from(dur * self.frame_len).unwrap_or(0))?;
+        let ts = (pos - self.data_start_pos) / self.frame_len;
+        Ok(Packet::new_from_boxed_slice(0, ts, dur, buf))
+    }
+
+    fn metadata(&mut self) -> Metadata<'_> {
+        self.metadata.metadata()
+    }
+
+    fn cues(&self) -> &[Cue] {
+        &self.cues
+    }
+
+    fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
+        let params = &self.tracks[0].codec_params;
+        let ts = match to {
+            SeekTo::TimeStamp { ts, .. } => ts,
+            SeekTo::Time { time, .. } => match params.time_base {
+                Some(time_base) => time_base.calc_timestamp(time),
+                None => return seek_error(SeekErrorKind::Unseekable),
+            },
+        };
+        if params.n_frames.is_some_and(|frames| ts > frames) {
+            return seek_error(SeekErrorKind::OutOfRange);
+        }
+
+        // packets start at whole packets, as they do without seeking
+        let actual_ts = ts / MAX_FRAMES_PER_PACKET * MAX_FRAMES_PER_PACKET;
+        let pos = self.data_start_pos + actual_ts * self.frame_len;
+        if self.reader.is_seekable() {
+            self.reader.seek(SeekFrom::Start(pos))?;
+        } else if pos >= self.reader.pos() {
+            self.reader.ignore_bytes(pos - self.reader.pos())?;
+        } else {
+            return seek_error(SeekErrorKind::ForwardOnly);
+        }
+        Ok(SeekedTo {
+            track_id: 0,
+            actual_ts,
+            required_ts: ts,
+        })
+    }
+
+    fn into_inner(self: Box<Self>) -> MediaSourceStream {
+        self.reader
+    }
+}
+
+fn read_common(source: &mut MediaSourceStream, compressed: bool) -> Result<Common> {
+    let channels = source.read_be_u16()?;
+    let frames = source.read_be_u32()?;
+    let bits_per_sample = source.read_be_u16()?;
+    let mut rate = [0; 10];
+    source.read_buf_exact(&mut rate)?;
+    let sample_rate = extended_to_u32(rate);
+    if sample_rate == 0 {
+        return unsupported_error("aiff: invalid sample rate");
+    }
+
+    let compression = if compressed {
+        source.read_quad_bytes()?
+    } else {
+        *b"NONE"
+    };
+    let codec = match (&compression, bits_per_sample) {
+        (b"NONE" | b"twos" | b"sowt", 1..=8) => codecs::CODEC_TYPE_PCM_S8,
+        (b"NONE" | b"twos", 9..=16) => codecs::CODEC_TYPE_PCM_S16BE,
+        (b"NONE" | b"twos" | b"in24", 17..=24) => codecs::CODEC_TYPE_PCM_S24BE,
+        (b"NONE" | b"twos" | b"in32", 25..=32) => codecs::CODEC_TYPE_PCM_S32BE,
+        (b"sowt", 9..=16) => codecs::CODEC_TYPE_PCM_S16LE,
+        (b"sowt", 17..=24) => codecs::CODEC_TYPE_PCM_S24LE,
+        (b"sowt", 25..=32) => codecs::CODEC_TYPE_PCM_S32LE,
+        (b"raw ", 1..=8) => codecs::CODEC_TYPE_PCM_U8,
+        (b"fl32" | b"FL32", _) => codecs::CODEC_TYPE_PCM_F32BE,
+        (b"fl64" | b"FL64", _) => codecs::CODEC_TYPE_PCM_F64BE,
+        (b"alaw" | b"ALAW", _) => codecs::CODEC_TYPE_PCM_ALAW,
+        (b"ulaw" | b"ULAW", _) => codecs::CODEC_TYPE_PCM_MULAW,
+        _ => return unsupported_error("aiff: unsupported compression"),
+    };
+    // the samples of floats and of A-law and µ-law have a size of their own
+    let bits_per_sample = match codec {
+        codecs::CODEC_TYPE_PCM_F32BE => 32,
+        codecs::CODEC_TYPE_PCM_F64BE => 64,
+        codecs::CODEC_TYPE_PCM_ALAW | codecs::CODEC_TYPE_PCM_MULAW => 8,
+        _ => bits_per_sample,
+    };
+    Ok(Common {
+        channels,
+        frames,
+        bits_per_sample,
+        sample_rate,
+        codec,
+    })
+}
+
+// The sample rate is an 80-bit extended float: sign and exponent, then a 64-bit mantissa with
+// an explicit integer bit.
+fn extended_to_u32(bytes: [u8; 10]) -> u32 {
+    let exponent = i32::from(u16::from_be_bytes([bytes[0], bytes[1]]) & 0x7fff) - 16383;
+    let mut mantissa = [0; 8];
+    mantissa.copy_from_slice(&bytes[2..]);
+    let mantissa = u64::from_be_bytes(mantissa);
+    if !(0..32).contains(&exponent) {
+        return 0;
+    }
+    u32::try_from(mantissa >> (63 - exponent)).unwrap_or(0)
+}
+
+fn channels(count: u16) -> Result<Channels> {
+    match count {
+        1..=32 => Ok(Channels::from_bits_truncate(
+            u32::try_from((1_u64 << count) - 1).unwrap_or(u32::MAX),
+        )),
+        _ => unsupported_error("aiff: invalid channel count"),
+    }
+}
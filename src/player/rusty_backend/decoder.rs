@@ -1,18 +1,40 @@
+use super::aiff::AiffReader;
+use super::http::HttpStream;
+use super::opus::OpusDecoder;
 use super::Source;
+use crate::track::Segment;
+use lazy_static::lazy_static;
 use std::{fmt, fs::File, time::Duration};
 use symphonia::{
     core::{
         audio::{AudioBufferRef, SampleBuffer, SignalSpec},
-        codecs::{self, CodecParameters},
+        codecs::{self, CodecParameters, CodecRegistry},
         errors::Error,
         formats::{FormatOptions, FormatReader, SeekMode, SeekTo},
-        io::{MediaSourceStream, MediaSourceStreamOptions},
+        io::{MediaSource, MediaSourceStream, MediaSourceStreamOptions},
         meta::MetadataOptions,
-        probe::Hint,
+        probe::{Hint, Probe},
         units::{Time, TimeBase},
     },
-    default::get_probe,
+    default::{register_enabled_codecs, register_enabled_formats},
 };
+
+lazy_static! {
+    // Symphonia's own formats and codecs, along with the ones it has none for.
+    static ref PROBE: Probe = {
+        let mut probe = Probe::default();
+        register_enabled_formats(&mut probe);
+        probe.register_all::<AiffReader>();
+        probe
+    };
+    static ref CODECS: CodecRegistry = {
+        let mut registry = CodecRegistry::new();
+        register_enabled_codecs(&mut registry);
+        registry.register_all::<OpusDecoder>();
+        registry
+    };
+}
+
 // Decoder errors are not considered fatal.
 // The correct action is to just get a new packet and try again.
 // But a decode error in more than 3 consecutive packets is fatal.
@@ -24,16 +46,34 @@ pub struct Symphonia {
     format: Box<dyn FormatReader>,
     buffer: SampleBuffer<i16>,
     spec: SignalSpec,
-    duration: Duration,
+    // none for streams
+    duration: Option<Duration>,
     elapsed: Duration,
+    // the part of the file that plays, positions are counted from its start
+    start: Duration,
+    end: Option<Duration>,
+    // samples of the buffer that are heard, the ones after the end of the segment are not
+    buffer_end: usize,
 }
 
 impl Symphonia {
     pub fn new(file: File, gapless: bool) -> Result<Self, SymphoniaDecoderError> {
-        let source = Box::new(file);
+        Self::from_source(Box::new(file), &Hint::default(), gapless)
+    }
+
+    /// Decodes an http stream, which has no duration and can't seek.
+    pub fn new_stream(stream: HttpStream, gapless: bool) -> Result<Self, SymphoniaDecoderError> {
+        let hint = stream.hint();
+        Self::from_source(Box::new(stream), &hint, gapless)
+    }
 
+    fn from_source(
+        source: Box<dyn MediaSource>,
+        hint: &Hint,
+        gapless: bool,
+    ) -> Result<Self, SymphoniaDecoderError> {
         let mss = MediaSourceStream::new(source, MediaSourceStreamOptions::default());
-        match Self::init(mss, gapless) {
+        match Self::init(mss, hint, gapless) {
             Err(e) => match e {
                 Error::IoError(e) => Err(SymphoniaDecoderError::IoError(e.to_string())),
                 Error::DecodeError(e) => Err(SymphoniaDecoderError::DecodeError(e)),
@@ -51,10 +91,12 @@ impl Symphonia {
 
     fn init(
         mss: MediaSourceStream,
+        hint: &Hint,
         gapless: bool,
     ) -> symphonia::core::errors::Result<Option<Self>> {
-        let mut probed = get_probe().format(
-            &Hint::default(),
+        let seekable = mss.is_seekable();
+        let mut probed = PROBE.format(
+            hint,
             mss,
             &FormatOptions {
                 prebuild_seek_index: true,
@@ -70,12 +112,12 @@ impl Symphonia {
             None => return Ok(None),
         };
 
-        let mut decoder = symphonia::default::get_codecs().make(
+        let mut decoder = CODECS.make(
             &track.codec_params,
             &codecs::DecoderOptions { verify: true },
         )?;
 
-        let duration = Self::get_duration(&track.codec_params);
+        let duration = seekable.then(|| Self::get_duration(&track.codec_params));
 
         let mut decode_errors: usize = 0;
         let decode_result = loop {
@@ -95,6 +137,7 @@ impl Symphonia {
         };
         let spec = decode_result.spec().to_owned();
         let buffer = Self::get_buffer(decode_result, &spec);
+        let buffer_end = buffer.len();
 
         Ok(Some(Self {
             decoder,
@@ -104,9 +147,102 @@ impl Symphonia {
             spec,
             duration,
             elapsed: Duration::from_secs(0),
+            start: Duration::ZERO,
+            end: None,
+            buffer_end,
         }))
     }
 
+    /// Plays only the segment of the file, as if there was nothing else in it. The start is
+    /// found to the sample, so that the segments of a file play gapless one after the other.
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.start = segment.start;
+        self.end = segment.end;
+        self.duration = self.duration.map(|duration| {
+            segment
+                .end
+                .unwrap_or(duration)
+                .saturating_sub(segment.start)
+        });
+        if segment.start.is_zero() {
+            return self;
+        }
+        if let Ok(seeked_to) = self.format.seek(
+            SeekMode::Accurate,
+            SeekTo::Time {
+                time: Self::time(segment.start),
+                track_id: None,
+            },
+        ) {
+            self.decoder.reset();
+            // the packet seeked to starts a bit before, the samples up to the start are dropped
+            let skip = seeked_to.required_ts.saturating_sub(seeked_to.actual_ts);
+            self.current_frame_offset = self.buffer_end;
+            if self.next_buffer() {
+                let channels = self.spec.channels.count();
+                self.current_frame_offset =
+                    (usize::try_from(skip).unwrap_or(usize::MAX) * channels).min(self.buffer_end);
+            }
+        }
+        self
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn time(time: Duration) -> Time {
+        let nanos_per_sec = 1_000_000_000.0;
+        Time::new(
+            time.as_secs(),
+            f64::from(time.subsec_nanos()) / nanos_per_sec,
+        )
+    }
+
+    // Decodes the next packet into the buffer, false at the end of the file or the segment.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn next_buffer(&mut self) -> bool {
+        let mut decode_errors: usize = 0;
+        let decoded = loop {
+            match self.format.next_packet() {
+                Ok(packet) => match self.decoder.decode(&packet) {
+                    Ok(decoded) => {
+                        let ts = packet.ts();
+                        if let Some(track) = self.format.default_track() {
+                            if let Some(tb) = track.codec_params.time_base {
+                                let t = tb.calc_time(ts);
+                                self.elapsed = Duration::from_secs(t.seconds)
+                                    + Duration::from_secs_f64(t.frac);
+                            }
+                        }
+                        break decoded;
+                    }
+                    Err(e) => match e {
+                        Error::DecodeError(_) => {
+                            decode_errors += 1;
+                            if decode_errors > MAX_DECODE_ERRORS {
+                                return false;
+                            }
+                        }
+                        _ => return false,
+                    },
+                },
+                Err(_) => return false,
+            }
+        };
+        self.spec = decoded.spec().to_owned();
+        self.buffer = Self::get_buffer(decoded, &self.spec);
+        self.current_frame_offset = 0;
+        self.buffer_end = self.buffer.len();
+        if let Some(end) = self.end {
+            if self.elapsed >= end {
+                return false;
+            }
+            let frames = ((end - self.elapsed).as_secs_f64() * f64::from(self.spec.rate)) as usize;
+            self.buffer_end = self
+                .buffer_end
+                .min(frames.saturating_mul(self.spec.channels.count()));
+        }
+        true
+    }
+
     fn get_duration(params: &CodecParameters) -> Duration {
         // if let Some(n_frames) = params.n_frames {
         //     if let Some(tb) = params.time_base {
@@ -151,7 +287,7 @@ impl Symphonia {
 impl Source for Symphonia {
     #[inline]
     fn current_frame_len(&self) -> Option<usize> {
-        Some(self.buffer.samples().len())
+        Some(self.buffer_end)
     }
 
     #[inline]
@@ -167,25 +303,21 @@ impl Source for Symphonia {
 
     #[inline]
     fn total_duration(&self) -> Option<Duration> {
-        Some(self.duration)
+        self.duration
     }
 
     #[inline]
     fn elapsed(&mut self) -> Duration {
-        self.elapsed
+        self.elapsed.saturating_sub(self.start)
     }
 
     #[inline]
     #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
     fn seek(&mut self, time: Duration) -> Option<Duration> {
-        let nanos_per_sec = 1_000_000_000.0;
         match self.format.seek(
             SeekMode::Coarse,
             SeekTo::Time {
-                time: Time::new(
-                    time.as_secs(),
-                    f64::from(time.subsec_nanos()) / nanos_per_sec,
-                ),
+                time: Self::time(self.start + time),
                 track_id: None,
             },
         ) {
@@ -193,9 +325,12 @@ impl Source for Symphonia {
                 let base = TimeBase::new(1, self.sample_rate());
                 let time = base.calc_time(seeked_to.actual_ts);
 
-                Some(Duration::from_millis(
-                    time.seconds * 1000 + ((time.frac * 60. * 1000.).round() as u64),
-                ))
+                Some(
+                    Duration::from_millis(
+                        time.seconds * 1000 + ((time.frac * 60. * 1000.).round() as u64),
+                    )
+                    .saturating_sub(self.start),
+                )
             }
             Err(_) => None,
         }
@@ -207,38 +342,11 @@ impl Iterator for Symphonia {
 
     #[inline]
     fn next(&mut self) -> Option<i16> {
-        if self.current_frame_offset == self.buffer.len() {
-            let mut decode_errors: usize = 0;
-            let decoded = loop {
-                match self.format.next_packet() {
-                    Ok(packet) => match self.decoder.decode(&packet) {
-                        Ok(decoded) => {
-                            let ts = packet.ts();
-                            if let Some(track) = self.format.default_track() {
-                                if let Some(tb) = track.codec_params.time_base {
-                                    let t = tb.calc_time(ts);
-                                    self.elapsed = Duration::from_secs(t.seconds)
-                                        + Duration::from_secs_f64(t.frac);
-                                }
-                            }
-                            break decoded;
-                        }
-                        Err(e) => match e {
-                            Error::DecodeError(_) => {
-                                decode_errors += 1;
-                                if decode_errors > MAX_DECODE_ERRORS {
-                                    return None;
-                                }
-                            }
-                            _ => return None,
-                        },
-                    },
-                    Err(_) => return None,
-                }
-            };
-            self.spec = decoded.spec().to_owned();
-            self.buffer = Self::get_buffer(decoded, &self.spec);
-            self.current_frame_offset = 0;
+        if self.current_frame_offset >= self.buffer_end {
+            // the end of the segment is in the middle of the buffer
+            if self.buffer_end < self.buffer.len() || !self.next_buffer() {
+                return None;
+            }
         }
 
         let sample = self.buffer.samples()[self.current_frame_offset];
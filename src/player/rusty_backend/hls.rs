@@ -0,0 +1,383 @@
+//! HLS streams for the default backend. The segments of the media playlist are downloaded one
+//! after the other, and the audio taken out of them goes to the buffer of an
+//! [`HttpStream`](super::http::HttpStream) as one continuous stream. For live streams the
+//! playlist is loaded again as new segments come up.
+use super::http::{agent, Shared, RECONNECT_ATTEMPTS, RECONNECT_DELAY};
+use crate::playlist::hls::{self, MediaPlaylist};
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const TS_PACKET_SIZE: usize = 188;
+const TS_SYNC: u8 = 0x47;
+// a bound for the size of a segment or playlist, which are read at once
+const MAX_DOWNLOAD: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Codec {
+    Aac,
+    Mp3,
+}
+
+impl Codec {
+    // From the frame header of packed audio segments.
+    fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [0xff, b, ..] if b & 0xf6 == 0xf0 => Some(Self::Aac),
+            [0xff, b, ..] if b & 0xe0 == 0xe0 => Some(Self::Mp3),
+            _ => None,
+        }
+    }
+
+    const fn content_type(self) -> &'static str {
+        match self {
+            Self::Aac => "audio/aac",
+            Self::Mp3 => "audio/mpeg",
+        }
+    }
+}
+
+/// Starts the download of the HLS stream whose playlist `content` was loaded from `url`, and
+/// returns the content type of the audio in it.
+pub fn start(url: &str, content: &str, shared: &Arc<Shared>) -> io::Result<String> {
+    let (media_url, playlist) = media_playlist(url, content)?;
+    if playlist.encrypted {
+        return Err(io::Error::other("encrypted HLS streams are not supported"));
+    }
+    let start = playlist.start_sequence();
+    let first = playlist
+        .segments
+        .iter()
+        .find(|segment| segment.sequence >= start)
+        .ok_or_else(|| io::Error::other("the HLS playlist has no segments"))?;
+
+    // the first segment tells what kind of audio the stream has
+    let mut demuxer = Demuxer::default();
+    let audio = demuxer.demux(&fetch(&first.uri)?.1)?;
+    let content_type = demuxer
+        .codec
+        .map_or("audio/aac", Codec::content_type)
+        .to_string();
+    let next = first.sequence + 1;
+    let shared = Arc::clone(shared);
+    thread::spawn(move || download(&media_url, playlist, next, &audio, demuxer, &shared));
+    Ok(content_type)
+}
+
+// The url and content of the media playlist, which is the variant picked from a master
+// playlist, or the playlist itself.
+fn media_playlist(url: &str, content: &str) -> io::Result<(String, MediaPlaylist)> {
+    if !hls::is_master(content) {
+        return Ok((url.to_string(), hls::parse_media(content, url)));
+    }
+    let variants = hls::parse_master(content, url);
+    let variant = hls::select_variant(&variants)
+        .ok_or_else(|| io::Error::other("the HLS playlist has no streams"))?;
+    let uri = variant.audio.as_ref().unwrap_or(&variant.uri);
+    let (url, content) = fetch(uri)?;
+    let content = String::from_utf8_lossy(&content);
+    Ok((url.clone(), hls::parse_media(&content, &url)))
+}
+
+fn download(
+    url: &str,
+    mut playlist: MediaPlaylist,
+    mut next: u64,
+    first_audio: &[u8],
+    mut demuxer: Demuxer,
+    shared: &Shared,
+) {
+    if !shared.push(first_audio) {
+        return;
+    }
+    loop {
+        let first = next;
+        for segment in playlist.segments.iter().filter(|s| s.sequence >= first) {
+            let result =
+                fetch_retrying(&segment.uri, shared).and_then(|(_, data)| demuxer.demux(&data));
+            match result {
+                Ok(audio) => {
+                    if !shared.push(&audio) {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    shared.finish(Some(e.to_string()));
+                    return;
+                }
+            }
+            next = segment.sequence + 1;
+        }
+        if playlist.ended {
+            shared.finish(None);
+            return;
+        }
+
+        // a live stream, whose playlist has the new segments some time later
+        thread::sleep((playlist.target_duration / 2).max(Duration::from_millis(500)));
+        if shared.is_closed() {
+            return;
+        }
+        match fetch_retrying(url, shared) {
+            Ok((url, content)) => {
+                playlist = hls::parse_media(&String::from_utf8_lossy(&content), &url);
+            }
+            Err(e) => {
+                shared.finish(Some(e.to_string()));
+                return;
+            }
+        }
+    }
+}
+
+// The url after redirects, with the body.
+fn fetch(url: &str) -> io::Result<(String, Vec<u8>)> {
+    let response = agent()
+        .get(url)
+        .call()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    let url = response.get_url().to_string();
+    let mut data = vec![];
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD)
+        .read_to_end(&mut data)?;
+    Ok((url, data))
+}
+
+fn fetch_retrying(url: &str, shared: &Shared) -> io::Result<(String, Vec<u8>)> {
+    let mut result = fetch(url);
+    for _ in 0..RECONNECT_ATTEMPTS {
+        if result.is_ok() || shared.is_closed() {
+            break;
+        }
+        thread::sleep(RECONNECT_DELAY);
+        result = fetch(url);
+    }
+    result
+}
+
+// Takes the audio out of the segments, which are MPEG transport streams or packed audio.
+#[derive(Default)]
+struct Demuxer {
+    ts: TsDemuxer,
+    codec: Option<Codec>,
+}
+
+impl Demuxer {
+    fn demux(&mut self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut audio = vec![];
+        if data.first() == Some(&TS_SYNC) && data.get(TS_PACKET_SIZE).is_none_or(|&b| b == TS_SYNC)
+        {
+            self.ts.demux(data, &mut audio);
+            self.codec = self.ts.codec;
+        } else {
+            // packed audio has an ID3 tag with a timestamp in front
+            let data = strip_id3(data);
+            self.codec = self.codec.or_else(|| Codec::detect(data));
+            audio.extend_from_slice(data);
+        }
+        if self.codec.is_none() {
+            return Err(io::Error::other(
+                "unsupported HLS segments, only MPEG-TS, AAC and MP3 ones play",
+            ));
+        }
+        Ok(audio)
+    }
+}
+
+fn strip_id3(mut data: &[u8]) -> &[u8] {
+    while data.len() >= 10 && data.starts_with(b"ID3") {
+        // the size is in 7 bit bytes, without the header and footer
+        let size = data[6..10]
+            .iter()
+            .fold(0, |size, &b| (size << 7) | usize::from(b & 0x7f));
+        let footer = if data[5] & 0x10 == 0 { 0 } else { 10 };
+        data = data.get(10 + size + footer..).unwrap_or_default();
+    }
+    data
+}
+
+// The audio of the first program of a transport stream. The packets of the program tables
+// tell which packets have the audio, whose PES headers are left out.
+#[derive(Default)]
+struct TsDemuxer {
+    pmt_pid: Option<u16>,
+    audio_pid: Option<u16>,
+    codec: Option<Codec>,
+}
+
+impl TsDemuxer {
+    fn demux(&mut self, data: &[u8], audio: &mut Vec<u8>) {
+        for packet in data.chunks_exact(TS_PACKET_SIZE) {
+            if packet[0] != TS_SYNC {
+                continue;
+            }
+            let pid = u16::from(packet[1] & 0x1f) << 8 | u16::from(packet[2]);
+            let unit_start = packet[1] & 0x40 != 0;
+            let control = (packet[3] >> 4) & 0x3;
+            // without payload
+            if control & 0x1 == 0 {
+                continue;
+            }
+            let start = if control & 0x2 == 0 {
+                4
+            } else {
+                5 + usize::from(packet[4])
+            };
+            let Some(payload) = packet.get(start..) else {
+                continue;
+            };
+            if pid == 0 {
+                self.read_pat(payload);
+            } else if Some(pid) == self.pmt_pid {
+                self.read_pmt(payload);
+            } else if Some(pid) == self.audio_pid {
+                push_pes(payload, unit_start, audio);
+            }
+        }
+    }
+
+    // The program association table, with the pid of the program map table.
+    fn read_pat(&mut self, payload: &[u8]) -> Option<()> {
+        let section = section(payload)?;
+        // after the stream id, version and section numbers come the programs
+        for program in section.get(5..)?.chunks_exact(4) {
+            if program[0] != 0 || program[1] != 0 {
+                self.pmt_pid = Some(u16::from(program[2] & 0x1f) << 8 | u16::from(program[3]));
+                break;
+            }
+        }
+        Some(())
+    }
+
+    // The program map table, with the pid of the audio.
+    fn read_pmt(&mut self, payload: &[u8]) -> Option<()> {
+        let section = section(payload)?;
+        // the program info is after the program number, version, section numbers and pcr pid
+        let info_len =
+            usize::from(u16::from(section.get(7)? & 0x0f) << 8 | u16::from(*section.get(8)?));
+        let mut streams = section.get(9 + info_len..)?;
+        while streams.len() >= 5 {
+            let pid = u16::from(streams[1] & 0x1f) << 8 | u16::from(streams[2]);
+            let es_info_len =
+                usize::from(u16::from(streams[3] & 0x0f) << 8 | u16::from(streams[4]));
+            let codec = match streams[0] {
+                0x0f => Some(Codec::Aac),
+                0x03 | 0x04 => Some(Codec::Mp3),
+                _ => None,
+            };
+            if codec.is_some() {
+                self.audio_pid = Some(pid);
+                self.codec = codec;
+                break;
+            }
+            streams = streams.get(5 + es_info_len..)?;
+        }
+        Some(())
+    }
+}
+
+// The table in a packet of the PAT or PMT, from after its length to before its crc.
+fn section(payload: &[u8]) -> Option<&[u8]> {
+    let pointer = usize::from(*payload.first()?);
+    let section = payload.get(1 + pointer..)?;
+    let len = usize::from(u16::from(section.get(1)? & 0x0f) << 8 | u16::from(*section.get(2)?));
+    section.get(3..(3 + len).checked_sub(4)?)
+}
+
+// A PES packet starts with a header in the packet where `unit_start` is set.
+fn push_pes(payload: &[u8], unit_start: bool, audio: &mut Vec<u8>) {
+    if !unit_start {
+        audio.extend_from_slice(payload);
+        return;
+    }
+    if payload.len() < 9 || payload[..3] != [0, 0, 1] {
+        return;
+    }
+    let header_len = 9 + usize::from(payload[8]);
+    if let Some(data) = payload.get(header_len..) {
+        audio.extend_from_slice(data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(pid: u16, unit_start: bool, payload: &[u8]) -> Vec<u8> {
+        let mut packet = vec![
+            TS_SYNC,
+            (u8::from(unit_start) << 6) | (pid >> 8) as u8,
+            pid as u8,
+            0x10,
+        ];
+        if payload.len() < TS_PACKET_SIZE - 4 {
+            // filled up with an adaptation field
+            let fill = TS_PACKET_SIZE - 5 - payload.len();
+            packet[3] = 0x30;
+            packet.push(fill as u8);
+            packet.extend(std::iter::repeat_n(0xff, fill));
+        }
+        packet.extend(payload);
+        packet
+    }
+
+    fn table(table_id: u8, body: &[u8]) -> Vec<u8> {
+        let len = body.len() + 4;
+        let mut table = vec![0, table_id, 0xb0 | (len >> 8) as u8, len as u8];
+        table.extend(body);
+        // the crc is not checked
+        table.extend([0; 4]);
+        table
+    }
+
+    // A transport stream with a program of video on pid 0x100 and `audio` on pid 0x101.
+    fn transport_stream(audio: &[u8]) -> Vec<u8> {
+        let mut ts = packet(0, true, &table(0, &[0, 1, 0xc1, 0, 0, 0, 1, 0xe0, 0x20]));
+        ts.extend(packet(
+            0x20,
+            true,
+            &table(
+                2,
+                &[
+                    0, 1, 0xc1, 0, 0, 0xe1, 0x00, 0xf0, 0, 0x1b, 0xe1, 0x00, 0xf0, 0, 0x0f, 0xe1,
+                    0x01, 0xf0, 0,
+                ],
+            ),
+        ));
+        ts.extend(packet(
+            0x100,
+            true,
+            &[0, 0, 1, 0xe0, 0, 0, 0x80, 0, 0, 0xaa],
+        ));
+        let (first, rest) = audio.split_at(audio.len().min(100));
+        let mut pes = vec![0, 0, 1, 0xc0, 0, 0, 0x80, 0x80, 5, 0x21, 0, 1, 0, 1];
+        pes.extend(first);
+        ts.extend(packet(0x101, true, &pes));
+        for chunk in rest.chunks(TS_PACKET_SIZE - 4) {
+            ts.extend(packet(0x101, false, chunk));
+        }
+        ts
+    }
+
+    #[test]
+    fn demux_transport_stream() {
+        let audio: Vec<u8> = (0..500).map(|i| (i % 251) as u8).collect();
+        let mut demuxer = Demuxer::default();
+        assert_eq!(demuxer.demux(&transport_stream(&audio)).unwrap(), audio);
+        assert_eq!(demuxer.codec, Some(Codec::Aac));
+    }
+
+    #[test]
+    fn demux_packed_audio() {
+        let mut segment = b"ID3\x04\x00\x00\x00\x00\x00\x03abc".to_vec();
+        segment.extend([0xff, 0xf1, 0x50, 0x80]);
+        let mut demuxer = Demuxer::default();
+        assert_eq!(demuxer.demux(&segment).unwrap(), [0xff, 0xf1, 0x50, 0x80]);
+        assert_eq!(demuxer.codec, Some(Codec::Aac));
+
+        assert!(Demuxer::default().demux(b"\0\0\0\x20ftypiso6").is_err());
+    }
+}
@@ -0,0 +1,645 @@
+//! Reading audio over http(s), for internet radio and other progressive streams.
+//!
+//! A thread downloads into a bounded buffer that the decoder reads from. It takes out the
+//! ICY metadata that shoutcast and icecast servers mix into the audio, and connects again
+//! when the connection drops: files go on where they broke off, live streams just go on.
+//! Files on servers that take range requests can seek, the download then starts over from
+//! the new position. HLS playlists are handed to [`hls`](super::hls), which fills the same
+//! buffer.
+use super::hls;
+use crate::playlist::is_content_hls;
+use std::collections::VecDeque;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use symphonia::core::io::MediaSource;
+use symphonia::core::probe::Hint;
+use ureq::{Agent, AgentBuilder};
+
+// how much is downloaded ahead of the decoder
+const BUFFER_CAPACITY: usize = 1024 * 1024;
+// how much is downloaded before playback starts
+const PREBUFFER: usize = 64 * 1024;
+const PREBUFFER_TIMEOUT: Duration = Duration::from_secs(10);
+const TIMEOUT: Duration = Duration::from_secs(15);
+pub const RECONNECT_ATTEMPTS: u32 = 5;
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const CHUNK_SIZE: usize = 16 * 1024;
+// a bound for playlists, which are read at once
+const MAX_PLAYLIST_SIZE: u64 = 1024 * 1024;
+const PLAYLIST_CONTENT_TYPES: [&str; 4] = [
+    "application/vnd.apple.mpegurl",
+    "application/x-mpegurl",
+    "audio/mpegurl",
+    "audio/x-mpegurl",
+];
+
+pub fn agent() -> Agent {
+    AgentBuilder::new()
+        .timeout_connect(TIMEOUT)
+        .timeout_read(TIMEOUT)
+        .build()
+}
+
+#[derive(Default)]
+struct State {
+    buffer: VecDeque<u8>,
+    // where the first byte of the buffer is in the file
+    position: u64,
+    // where the download has to start over, after a seek out of the buffer
+    seek: Option<u64>,
+    // no more data is coming, with the reason when it failed
+    finished: bool,
+    error: Option<String>,
+    // the stream was dropped, the download stops
+    closed: bool,
+}
+
+/// The buffer between the download and the decoder.
+#[derive(Default)]
+pub struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl Shared {
+    /// Waits for room in the buffer, false when the stream was dropped meanwhile. The data
+    /// is thrown away when a seek came in meanwhile.
+    pub fn push(&self, data: &[u8]) -> bool {
+        for chunk in data.chunks(CHUNK_SIZE) {
+            let mut state = self.state.lock().unwrap();
+            while !state.closed
+                && state.seek.is_none()
+                && state.buffer.len() + chunk.len() > BUFFER_CAPACITY
+            {
+                state = self.changed.wait(state).unwrap();
+            }
+            if state.closed {
+                return false;
+            }
+            if state.seek.is_some() {
+                return true;
+            }
+            state.buffer.extend(chunk);
+            self.changed.notify_all();
+        }
+        true
+    }
+
+    pub fn finish(&self, error: Option<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.finished = true;
+        state.error = error;
+        self.changed.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    fn take_seek(&self) -> Option<u64> {
+        self.state.lock().unwrap().seek.take()
+    }
+
+    // After the download ended, waits for a seek that needs it again. None when the stream
+    // was dropped.
+    fn wait_seek(&self) -> Option<u64> {
+        let mut state = self.state.lock().unwrap();
+        while !state.closed && state.seek.is_none() {
+            state = self.changed.wait(state).unwrap();
+        }
+        state.seek.take().filter(|_| !state.closed)
+    }
+}
+
+/// The audio of an http url, as a source for the decoder.
+pub struct HttpStream {
+    shared: Arc<Shared>,
+    content_type: Option<String>,
+    // the length of a file that can seek, none for live streams
+    length: Option<u64>,
+}
+
+impl HttpStream {
+    /// Connects to the url and waits for the first bit of audio to arrive. The titles sent
+    /// along with the audio of internet radios are passed to `on_title`.
+    pub fn open<F>(url: &str, on_title: F) -> io::Result<Self>
+    where
+        F: Fn(String) + Send + 'static,
+    {
+        let connection = Connection::open(url, 0)?;
+        let shared = Arc::new(Shared::default());
+        let (content_type, length) = if connection.is_playlist() {
+            let url = connection.url.clone();
+            let content = connection.into_text()?;
+            if !is_content_hls(&content) {
+                return Err(io::Error::other("not an HLS playlist"));
+            }
+            (Some(hls::start(&url, &content, &shared)?), None)
+        } else {
+            let content_type = connection.content_type.clone();
+            let length = connection.length.filter(|_| connection.ranges);
+            let download_shared = Arc::clone(&shared);
+            let url = url.to_string();
+            thread::spawn(move || download(&url, &download_shared, connection, &on_title));
+            (content_type, length)
+        };
+
+        let deadline = Instant::now() + PREBUFFER_TIMEOUT;
+        let mut state = shared.state.lock().unwrap();
+        while !state.finished && state.buffer.len() < PREBUFFER {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            state = shared
+                .changed
+                .wait_timeout(state, deadline - now)
+                .unwrap()
+                .0;
+        }
+        if state.buffer.is_empty() {
+            if let Some(e) = &state.error {
+                return Err(io::Error::other(e.clone()));
+            }
+        }
+        drop(state);
+
+        Ok(Self {
+            shared,
+            content_type,
+            length,
+        })
+    }
+
+    /// What the content type of the response tells about the format.
+    pub fn hint(&self) -> Hint {
+        let mut hint = Hint::new();
+        if let Some(content_type) = &self.content_type {
+            hint.mime_type(content_type);
+            let extension = match content_type.as_str() {
+                "audio/mpeg" | "audio/mp3" => Some("mp3"),
+                "audio/aac" | "audio/aacp" | "audio/x-aac" => Some("aac"),
+                "audio/ogg" | "application/ogg" | "audio/vorbis" => Some("ogg"),
+                "audio/flac" | "audio/x-flac" => Some("flac"),
+                _ => None,
+            };
+            if let Some(extension) = extension {
+                hint.with_extension(extension);
+            }
+        }
+        hint
+    }
+}
+
+impl Read for HttpStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.buffer.is_empty() && !state.finished {
+            state = self.shared.changed.wait(state).unwrap();
+        }
+        if state.buffer.is_empty() {
+            return match &state.error {
+                Some(e) => Err(io::Error::other(e.clone())),
+                None => Ok(0),
+            };
+        }
+        let len = buf.len().min(state.buffer.len());
+        for (byte, value) in buf.iter_mut().zip(state.buffer.drain(..len)) {
+            *byte = value;
+        }
+        state.position += len as u64;
+        self.shared.changed.notify_all();
+        Ok(len)
+    }
+}
+
+impl Seek for HttpStream {
+    // Within the buffer the bytes in between are skipped, anywhere else the download starts
+    // over from there.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let Some(length) = self.length else {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "live streams can't seek",
+            ));
+        };
+        let mut state = self.shared.state.lock().unwrap();
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => state.position.checked_add_signed(offset),
+        }
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek before the start"))?
+        .min(length);
+        match target
+            .checked_sub(state.position)
+            .and_then(|ahead| usize::try_from(ahead).ok())
+            .filter(|ahead| *ahead <= state.buffer.len())
+        {
+            Some(ahead) => {
+                state.buffer.drain(..ahead);
+            }
+            None => {
+                state.buffer.clear();
+                state.finished = false;
+                state.error = None;
+                state.seek = Some(target);
+            }
+        }
+        state.position = target;
+        self.shared.changed.notify_all();
+        Ok(target)
+    }
+}
+
+impl MediaSource for HttpStream {
+    fn is_seekable(&self) -> bool {
+        self.length.is_some()
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        self.length
+    }
+}
+
+impl Drop for HttpStream {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().closed = true;
+        self.shared.changed.notify_all();
+    }
+}
+
+struct Connection {
+    // after redirects
+    url: String,
+    reader: IcyReader<Box<dyn Read + Send>>,
+    content_type: Option<String>,
+    // the length of the whole file, none for live streams
+    length: Option<u64>,
+    // the server takes range requests, so the file can seek
+    ranges: bool,
+}
+
+impl Connection {
+    // Asks for the audio from `offset` on, which only files can do.
+    fn open(url: &str, offset: u64) -> io::Result<Self> {
+        let mut request = agent().get(url).set("Icy-MetaData", "1");
+        if offset > 0 {
+            request = request.set("Range", &format!("bytes={}-", offset));
+        }
+        let response = request
+            .call()
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        if offset > 0 && response.status() != 206 {
+            return Err(io::Error::other(
+                "the server can't go on where the download broke off",
+            ));
+        }
+
+        let metaint = response
+            .header("icy-metaint")
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(0);
+        let content_type = response
+            .header("content-type")
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_lowercase());
+        // a live stream has no length, or says so with icy headers
+        let length = match response
+            .header("icy-name")
+            .or_else(|| response.header("icy-br"))
+        {
+            Some(_) => None,
+            None => response
+                .header("content-length")
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(|length| length + offset),
+        };
+        let ranges = response.status() == 206
+            || response
+                .header("accept-ranges")
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("bytes"));
+        Ok(Self {
+            ranges,
+            url: response.get_url().to_string(),
+            reader: IcyReader::new(Box::new(response.into_reader()), metaint),
+            content_type,
+            length,
+        })
+    }
+
+    // An HLS playlist, or another kind that is played as one.
+    fn is_playlist(&self) -> bool {
+        let path = self.url.split(['?', '#']).next().unwrap_or_default();
+        self.content_type
+            .as_deref()
+            .is_some_and(|content_type| PLAYLIST_CONTENT_TYPES.contains(&content_type))
+            || path.to_lowercase().ends_with(".m3u8")
+    }
+
+    fn into_text(self) -> io::Result<String> {
+        let mut text = String::new();
+        self.reader
+            .inner
+            .take(MAX_PLAYLIST_SIZE)
+            .read_to_string(&mut text)?;
+        Ok(text)
+    }
+}
+
+fn download<F>(url: &str, shared: &Shared, mut connection: Connection, on_title: &F)
+where
+    F: Fn(String),
+{
+    let mut chunk = [0_u8; CHUNK_SIZE];
+    // audio bytes so far, to go on from there after a drop
+    let mut received: u64 = 0;
+    let mut seek = None;
+    loop {
+        if let Some(offset) = seek.take().or_else(|| shared.take_seek()) {
+            match Connection::open(url, offset) {
+                Ok(new_connection) => {
+                    connection = new_connection;
+                    received = offset;
+                }
+                Err(e) => {
+                    shared.finish(Some(e.to_string()));
+                    match shared.wait_seek() {
+                        Some(offset) => seek = Some(offset),
+                        None => return,
+                    }
+                    continue;
+                }
+            }
+        }
+        let error = match connection.reader.read(&mut chunk) {
+            Ok((0, _)) if connection.length.is_some_and(|length| received >= length) => {
+                shared.finish(None);
+                // the decoder may still seek back into the file
+                match shared.wait_seek() {
+                    Some(offset) => seek = Some(offset),
+                    None => return,
+                }
+                continue;
+            }
+            Ok((0, _)) => "the connection was closed".to_string(),
+            Ok((len, title)) => {
+                if let Some(title) = title {
+                    on_title(title);
+                }
+                if !shared.push(&chunk[..len]) {
+                    return;
+                }
+                received += len as u64;
+                continue;
+            }
+            Err(e) => e.to_string(),
+        };
+
+        match reconnect(url, shared, connection.length.map(|_| received)) {
+            Some(new_connection) => connection = new_connection,
+            None if shared.is_closed() => return,
+            None => {
+                shared.finish(Some(error));
+                match shared.wait_seek() {
+                    Some(offset) => seek = Some(offset),
+                    None => return,
+                }
+            }
+        }
+    }
+}
+
+// Tries to connect again a few times, from `offset` for files and from where the stream
+// is now for live ones. None when it didn't work, or the stream is not needed anymore.
+fn reconnect(url: &str, shared: &Shared, offset: Option<u64>) -> Option<Connection> {
+    for _ in 0..RECONNECT_ATTEMPTS {
+        thread::sleep(RECONNECT_DELAY);
+        if shared.is_closed() {
+            return None;
+        }
+        if let Ok(connection) = Connection::open(url, offset.unwrap_or(0)) {
+            return Some(connection);
+        }
+    }
+    None
+}
+
+/// Takes the metadata blocks out of an ICY stream, which come after every `metaint` bytes of
+/// audio. A block is a byte with its length in 16 bytes, then text like
+/// `StreamTitle='Artist - Title';StreamUrl='';` padded with zeros.
+struct IcyReader<R> {
+    inner: R,
+    // no metadata when zero
+    metaint: usize,
+    // audio bytes before the next metadata block
+    remaining: usize,
+}
+
+impl<R: Read> IcyReader<R> {
+    const fn new(inner: R, metaint: usize) -> Self {
+        Self {
+            inner,
+            metaint,
+            remaining: metaint,
+        }
+    }
+
+    // Reads audio into `buf`, along with the title of a metadata block that came first.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<(usize, Option<String>)> {
+        if self.metaint == 0 {
+            return Ok((self.inner.read(buf)?, None));
+        }
+        let mut title = None;
+        if self.remaining == 0 {
+            let mut len = [0_u8; 1];
+            if self.inner.read(&mut len)? == 0 {
+                return Ok((0, None));
+            }
+            let mut metadata = vec![0_u8; usize::from(len[0]) * 16];
+            self.inner.read_exact(&mut metadata)?;
+            title = parse_stream_title(&String::from_utf8_lossy(&metadata));
+            self.remaining = self.metaint;
+        }
+        let len = buf.len().min(self.remaining);
+        let len = self.inner.read(&mut buf[..len])?;
+        self.remaining -= len;
+        Ok((len, title))
+    }
+}
+
+fn parse_stream_title(metadata: &str) -> Option<String> {
+    let start = metadata.find("StreamTitle='")? + "StreamTitle='".len();
+    let rest = &metadata[start..];
+    let end = rest
+        .find("';")
+        .unwrap_or_else(|| rest.trim_end_matches('\0').len());
+    let title = rest[..end].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    fn icy_block(text: &str) -> Vec<u8> {
+        let blocks = text.len().div_ceil(16);
+        let mut block = vec![u8::try_from(blocks).unwrap()];
+        block.extend(text.as_bytes());
+        block.resize(1 + blocks * 16, 0);
+        block
+    }
+
+    #[test]
+    fn icy_metadata() {
+        let mut data = b"abcd".to_vec();
+        data.extend(icy_block("StreamTitle='Artist - Song';StreamUrl='';"));
+        data.extend(b"efgh");
+        data.push(0);
+        data.extend(b"ij");
+        let mut reader = IcyReader::new(io::Cursor::new(data), 4);
+        let mut audio: Vec<u8> = vec![];
+        let mut titles: Vec<String> = vec![];
+        let mut buf = [0_u8; 3];
+        loop {
+            let (len, title) = reader.read(&mut buf).unwrap();
+            if len == 0 {
+                break;
+            }
+            audio.extend(&buf[..len]);
+            titles.extend(title);
+        }
+        assert_eq!(audio, b"abcdefghij");
+        assert_eq!(titles, vec!["Artist - Song".to_string()]);
+    }
+
+    // Serves each of the responses to one connection. The last one stays open, like a radio.
+    fn serve(responses: Vec<Vec<u8>>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/stream", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut last = None;
+            let count = responses.len();
+            for (i, response) in responses.into_iter().enumerate() {
+                let (mut socket, _) = listener.accept().unwrap();
+                let mut request = [0_u8; 1024];
+                let len = socket.read(&mut request).unwrap();
+                tx.send(String::from_utf8_lossy(&request[..len]).to_string())
+                    .unwrap();
+                socket.write_all(&response).ok();
+                if i + 1 == count {
+                    last = Some(socket);
+                }
+            }
+            listener.accept().ok();
+            drop(last);
+        });
+        (url, rx)
+    }
+
+    #[test]
+    fn radio_stream() {
+        let mut response =
+            format!("HTTP/1.1 200 OK\r\nContent-Type: audio/mpeg\r\nicy-name: Test\r\nicy-metaint: {}\r\n\r\n", PREBUFFER)
+                .into_bytes();
+        let audio: Vec<u8> = (0..PREBUFFER + 2).map(|i| (i % 251) as u8).collect();
+        response.extend(&audio[..PREBUFFER]);
+        response.extend(icy_block("StreamTitle='Now Playing';"));
+        response.extend(&audio[PREBUFFER..]);
+        let (url, requests) = serve(vec![response]);
+        let (title_tx, titles) = mpsc::channel();
+        let mut stream = HttpStream::open(&url, move |title| title_tx.send(title).unwrap())
+            .expect("stream should open");
+        assert!(requests.recv().unwrap().contains("Icy-MetaData: 1"));
+        let mut read = vec![0_u8; audio.len()];
+        stream.read_exact(&mut read).unwrap();
+        assert_eq!(read, audio);
+        assert_eq!(titles.recv().unwrap(), "Now Playing");
+    }
+
+    #[test]
+    fn file_resumes_after_drop() {
+        let first =
+            b"HTTP/1.1 200 OK\r\nContent-Type: audio/ogg\r\nContent-Length: 10\r\n\r\n01234"
+                .to_vec();
+        let second =
+            b"HTTP/1.1 206 Partial Content\r\nContent-Type: audio/ogg\r\nContent-Length: 5\r\n\r\n56789"
+                .to_vec();
+        let (url, requests) = serve(vec![first, second]);
+        let mut stream = HttpStream::open(&url, |_| {}).expect("stream should open");
+        let mut audio: Vec<u8> = vec![];
+        stream.read_to_end(&mut audio).unwrap();
+        assert_eq!(audio, b"0123456789");
+        requests.recv().unwrap();
+        assert!(requests.recv().unwrap().contains("Range: bytes=5-"));
+    }
+
+    #[test]
+    fn file_seeks() {
+        let first = b"HTTP/1.1 200 OK\r\nContent-Type: audio/ogg\r\nAccept-Ranges: bytes\r\nContent-Length: 10\r\n\r\n0123456789"
+            .to_vec();
+        let second =
+            b"HTTP/1.1 206 Partial Content\r\nContent-Type: audio/ogg\r\nContent-Length: 8\r\n\r\n23456789"
+                .to_vec();
+        let (url, requests) = serve(vec![first, second]);
+        let mut stream = HttpStream::open(&url, |_| {}).expect("stream should open");
+        assert_eq!(stream.byte_len(), Some(10));
+        // within the buffer
+        assert_eq!(stream.seek(SeekFrom::Start(4)).unwrap(), 4);
+        let mut audio: Vec<u8> = vec![];
+        stream.read_to_end(&mut audio).unwrap();
+        assert_eq!(audio, b"456789");
+        // back before it
+        assert_eq!(stream.seek(SeekFrom::Current(-8)).unwrap(), 2);
+        audio.clear();
+        stream.read_to_end(&mut audio).unwrap();
+        assert_eq!(audio, b"23456789");
+        requests.recv().unwrap();
+        assert!(requests.recv().unwrap().contains("Range: bytes=2-"));
+    }
+
+    #[test]
+    fn hls_stream() {
+        let response = |content_type: &str, body: &[u8]| {
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+                content_type,
+                body.len()
+            )
+            .into_bytes();
+            response.extend(body);
+            response
+        };
+        let master =
+            b"#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\"\nmedia.m3u8\n";
+        let media = b"#EXTM3U\n#EXT-X-TARGETDURATION:2\n#EXTINF:2,\nfirst.aac\n#EXTINF:2,\n/second.aac\n#EXT-X-ENDLIST\n";
+        let (url, requests) = serve(vec![
+            response("application/vnd.apple.mpegurl", master),
+            response("application/vnd.apple.mpegurl", media),
+            response(
+                "audio/aac",
+                b"ID3\x04\x00\x00\x00\x00\x00\x01x\xff\xf1first",
+            ),
+            response("audio/aac", b"\xff\xf1second"),
+        ]);
+        let mut stream = HttpStream::open(&url, |_| {}).expect("stream should open");
+        let mut audio: Vec<u8> = vec![];
+        stream.read_to_end(&mut audio).unwrap();
+        assert_eq!(audio, b"\xff\xf1first\xff\xf1second");
+        let paths: Vec<String> = requests
+            .iter()
+            .take(4)
+            .map(|request| request.split(' ').nth(1).unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            ["/stream", "/media.m3u8", "/first.aac", "/second.aac"]
+        );
+    }
+}
@@ -1,12 +1,16 @@
 #![cfg_attr(test, deny(missing_docs))]
 
+mod aiff;
 mod conversions;
+mod opus;
 mod sink;
 mod stream;
 
 pub mod buffer;
 pub mod decoder;
 pub mod dynamic_mixer;
+pub mod hls;
+pub mod http;
 pub mod queue;
 pub mod source;
 
@@ -18,82 +22,201 @@ pub use cpal::{
 pub use decoder::Symphonia;
 pub use sink::Sink;
 pub use source::Source;
-pub use stream::{OutputStream, OutputStreamHandle, PlayError, StreamError};
+pub use stream::{output_device_names, OutputStream, OutputStreamHandle, PlayError, StreamError};
 
 use std::fs::File;
 use std::path::Path;
 use std::sync::mpsc::Sender;
+use std::thread;
 use std::time::Duration;
 
 use super::{PlayerMsg, PlayerTrait};
 use crate::config::Settings;
-use anyhow::Result;
+use crate::track::Segment;
+use crate::utils::is_stream;
+use anyhow::{anyhow, Result};
+use http::HttpStream;
 
 static VOLUME_STEP: u16 = 5;
+// seeking forward stops this far before the end of the track
 static SEEK_STEP: f64 = 5.0;
 
 pub struct Player {
-    _stream: OutputStream,
+    stream: OutputStream,
     handle: OutputStreamHandle,
+    // set when the configured output device couldn't be opened and the default one is used
+    device_fallback: bool,
     pub sink: Sink,
     pub total_duration: Option<Duration>,
     // total_duration_next: Option<Duration>,
     volume: u16,
     speed: i32,
+    pitch_correction: bool,
     pub gapless: bool,
+    replaygain: f32,
+    segment: Option<Segment>,
+    ab_loop: Option<(Duration, Duration)>,
+    // the stream being opened: its number, ReplayGain factor and crossfade
+    opening_stream: Option<(usize, f32, Duration)>,
+    streams_opened: usize,
     // pub current_item: Option<String>,
     // pub next_item: Option<String>,
     pub message_tx: Sender<PlayerMsg>,
 }
 
 impl Player {
-    pub fn new(config: &Settings, tx: Sender<PlayerMsg>) -> Self {
-        let (stream, handle) = OutputStream::try_default().unwrap();
+    pub fn new(config: &Settings, tx: Sender<PlayerMsg>) -> Result<Self> {
+        let mut device_fallback = false;
+        let (stream, handle) = OutputStream::try_from_name(&config.output_device)
+            .or_else(|_| {
+                device_fallback = true;
+                OutputStream::try_default()
+            })
+            .map_err(|e| anyhow!("cannot open an audio output device: {}", e))?;
         let gapless = config.gapless;
-        let sink = Sink::try_new(&handle, gapless, tx.clone()).unwrap();
+        let sink = Sink::try_new(&handle, gapless, tx.clone())?;
         let volume = config.volume.try_into().unwrap();
         sink.set_volume(f32::from(volume) / 100.0);
+        sink.set_pitch_correction(config.pitch_correction);
         let speed = config.speed;
 
         let mut this = Self {
-            _stream: stream,
+            stream,
             handle,
+            device_fallback,
             sink,
             total_duration: None,
             volume,
             speed,
+            pitch_correction: config.pitch_correction,
             gapless,
+            replaygain: 1.0,
+            segment: None,
+            ab_loop: None,
+            opening_stream: None,
+            streams_opened: 0,
             message_tx: tx,
         };
         this.set_speed(speed);
-        this
+        Ok(this)
     }
 
     pub fn enqueue(&mut self, item: &str) {
-        let p1 = Path::new(item);
-        if let Ok(file) = File::open(p1) {
-            if let Ok(decoder) = Symphonia::new(file, self.gapless) {
-                self.total_duration = decoder.total_duration();
-                self.sink.append(decoder);
-                self.set_speed(self.speed);
-                // self.sink.message_on_end();
-            }
+        if is_stream(item) {
+            self.total_duration = None;
+            self.open_stream(item, Duration::ZERO);
+            return;
+        }
+        if let Some(decoder) = self.decoder(item) {
+            self.total_duration = decoder.total_duration();
+            self.sink.append(decoder.amplify(self.replaygain));
+            self.set_speed(self.speed);
+            // self.sink.message_on_end();
         }
     }
 
-    pub fn enqueue_next(&mut self, item: &str) -> Option<Duration> {
-        let mut duration = None;
-        let p1 = Path::new(item);
-        if let Ok(file) = File::open(p1) {
-            if let Ok(decoder) = Symphonia::new(file, self.gapless) {
-                duration = decoder.total_duration();
-                self.sink.append(decoder);
-                // self.sink.message_on_end();
-            }
+    /// Queues the track to play after the current one, fading in over the last `crossfade` of
+    /// the current one. A zero `crossfade` just plays them one after the other.
+    pub fn enqueue_next(&mut self, item: &str, crossfade: Duration) -> Option<Duration> {
+        if is_stream(item) {
+            self.open_stream(item, crossfade);
+            return None;
         }
+        let decoder = self.decoder(item)?;
+        let duration = decoder.total_duration();
+        self.sink
+            .append_with_crossfade(decoder.amplify(self.replaygain), crossfade);
+        // self.sink.message_on_end();
         duration
     }
 
+    // Connecting to a stream and waiting for its first audio can take many seconds, so it is
+    // done on a thread of its own that hands the decoder back with `PlayerMsg::StreamOpened`.
+    // The titles sent along by internet radios go to the message channel too.
+    fn open_stream(&mut self, url: &str, crossfade: Duration) {
+        self.streams_opened = self.streams_opened.wrapping_add(1);
+        let id = self.streams_opened;
+        self.opening_stream = Some((id, self.replaygain, crossfade));
+        let url = url.to_string();
+        let gapless = self.gapless;
+        let tx = self.message_tx.clone();
+        thread::spawn(move || {
+            let title_tx = tx.clone();
+            let Ok(stream) = HttpStream::open(&url, move |title| {
+                title_tx.send(PlayerMsg::StreamTitle(title)).ok();
+            }) else {
+                return;
+            };
+            if let Ok(decoder) = Symphonia::new_stream(stream, gapless) {
+                tx.send(PlayerMsg::StreamOpened(id, decoder)).ok();
+            }
+        });
+    }
+
+    /// Queues the stream that `open_stream` got ready, unless the player moved on meanwhile.
+    pub fn stream_opened(&mut self, id: usize, decoder: Symphonia) {
+        let Some((_, replaygain, crossfade)) =
+            self.opening_stream.filter(|(opening, _, _)| *opening == id)
+        else {
+            return;
+        };
+        self.opening_stream = None;
+        self.sink
+            .append_with_crossfade(decoder.amplify(replaygain), crossfade);
+        self.set_speed(self.speed);
+        self.sink.message_on_end();
+    }
+
+    fn decoder(&self, item: &str) -> Option<Symphonia> {
+        let file = File::open(Path::new(item)).ok()?;
+        let decoder = Symphonia::new(file, self.gapless).ok()?;
+        Some(match self.segment {
+            Some(segment) => decoder.segment(segment),
+            None => decoder,
+        })
+    }
+
+    /// Sets the `ReplayGain` factor for the tracks queued from now on. Tracks already in the
+    /// sink keep their own factor, so the gapless next track doesn't change the current one.
+    pub fn set_replaygain(&mut self, factor: f32) {
+        self.replaygain = factor;
+    }
+
+    /// Sets the part of the file to play for the tracks queued from now on, none for all of it.
+    pub fn set_segment(&mut self, segment: Option<Segment>) {
+        self.segment = segment;
+    }
+
+    /// Plays the part from `a` to `b` of the current track over and over, none plays on.
+    pub fn set_ab_loop(&mut self, ab_loop: Option<(Duration, Duration)>) {
+        self.ab_loop = ab_loop;
+        self.sink.set_ab_loop(ab_loop);
+    }
+
+    /// Changes the equalizer gains, in dB. Takes effect on the playing track right away.
+    pub fn set_equalizer(&mut self, gains: &[f32]) {
+        self.sink.set_equalizer(gains);
+    }
+
+    /// Switches to the output device with the given name, or the default one if the name is
+    /// empty. The sink is rebuilt, so the current track has to be queued again.
+    pub fn set_output_device(&mut self, name: &str) -> Result<(), StreamError> {
+        let (stream, handle) = OutputStream::try_from_name(name)?;
+        // the old sink ends its track when dropped, that is not the end of the track
+        self.sink.cancel_message_on_end();
+        self.stream = stream;
+        self.handle = handle;
+        self.device_fallback = false;
+        self.stop();
+        Ok(())
+    }
+
+    /// Returns true when the output device went away, or when the configured one couldn't be
+    /// opened at start and the default one is used instead.
+    pub fn output_device_lost(&self) -> bool {
+        self.device_fallback || self.stream.is_device_lost()
+    }
+
     fn play(&mut self, current_item: &str) {
         // self.stop();
         self.enqueue(current_item);
@@ -102,10 +225,13 @@ impl Player {
     fn stop(&mut self) {
         // self.current_item = None;
         // self.next_item = None;
+        self.opening_stream = None;
         self.sink = Sink::try_new(&self.handle, self.gapless, self.message_tx.clone()).unwrap();
         self.sink.set_volume(f32::from(self.volume) / 100.0);
+        self.sink.set_pitch_correction(self.pitch_correction);
+        self.sink.set_ab_loop(self.ab_loop);
     }
-    fn elapsed(&self) -> Duration {
+    pub fn elapsed(&self) -> Duration {
         self.sink.elapsed()
     }
     fn duration(&self) -> Option<f64> {
@@ -113,23 +239,23 @@ impl Player {
             .map(|duration| duration.as_secs_f64() - 0.29)
     }
 
-    fn seek_fw(&mut self) {
-        let new_pos = self.elapsed().as_secs_f64() + SEEK_STEP;
+    fn seek_fw(&mut self, secs: f64) {
+        let new_pos = self.elapsed().as_secs_f64() + secs;
         if let Some(duration) = self.duration() {
             if new_pos < duration - SEEK_STEP {
                 self.seek_to(Duration::from_secs_f64(new_pos));
             }
         }
     }
-    fn seek_bw(&mut self) {
-        let mut new_pos = self.elapsed().as_secs_f64() - SEEK_STEP;
+    fn seek_bw(&mut self, secs: f64) {
+        let mut new_pos = self.elapsed().as_secs_f64() - secs;
         if new_pos < 0.0 {
             new_pos = 0.0;
         }
 
         self.seek_to(Duration::from_secs_f64(new_pos));
     }
-    fn seek_to(&self, time: Duration) {
+    pub fn seek_to(&self, time: Duration) {
         self.sink.seek(time);
         self.get_progress().ok();
     }
@@ -191,13 +317,14 @@ impl PlayerTrait for Player {
         self.sink.is_paused()
     }
 
+    #[allow(clippy::cast_precision_loss)]
     fn seek(&mut self, secs: i64) -> Result<()> {
         if secs.is_positive() {
-            self.seek_fw();
+            self.seek_fw(secs as f64);
             return Ok(());
         }
 
-        self.seek_bw();
+        self.seek_bw(secs.unsigned_abs() as f64);
         Ok(())
     }
 
@@ -208,7 +335,8 @@ impl PlayerTrait for Player {
     )]
     fn get_progress(&self) -> Result<()> {
         let position = self.elapsed().as_secs() as i64;
-        let duration = self.duration().unwrap_or(99.0) as i64;
+        // streams have no duration
+        let duration = self.duration().map_or(0, |duration| duration as i64);
         self.message_tx
             .send(PlayerMsg::Progress(position, duration))?;
         Ok(())
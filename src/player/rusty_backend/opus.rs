@@ -0,0 +1,197 @@
+//! Opus, which Symphonia demuxes from Ogg and WebM files but has no decoder for.
+use symphonia::core::{
+    audio::{AsAudioBufferRef, AudioBuffer, AudioBufferRef, Channels, Signal, SignalSpec},
+    codecs::{
+        CodecDescriptor, CodecParameters, Decoder, DecoderOptions, FinalizeResult, CODEC_TYPE_OPUS,
+    },
+    errors::{unsupported_error, Error, Result},
+    formats::Packet,
+    support_codec,
+};
+
+// Opus always decodes at 48 kHz, in frames of up to 120 ms.
+const SAMPLE_RATE: u32 = 48_000;
+const MAX_FRAMES: usize = 5760;
+
+/// Decodes Opus packets with the pure Rust decoder of `opus-decoder`.
+pub struct OpusDecoder {
+    params: CodecParameters,
+    decoder: Streams,
+    channels: usize,
+    // interleaved as decoded, then split into the channels of the buffer
+    pcm: Vec<i16>,
+    buf: AudioBuffer<i16>,
+    // samples at the start left over from the encoder, which are not heard
+    pre_skip: usize,
+    // none for the usual gain of 0 dB
+    gain: Option<f32>,
+}
+
+enum Streams {
+    Single(Box<opus_decoder::OpusDecoder>),
+    // surround sound, coded as several streams of one or two channels
+    Multi(opus_decoder::OpusMultistreamDecoder),
+}
+
+// The identification header that Ogg and WebM files carry as extra data.
+#[derive(Debug, PartialEq)]
+struct OpusHead {
+    channels: usize,
+    pre_skip: usize,
+    // in dB, in Q7.8
+    gain: i16,
+    // the number of streams and of stereo ones, and which output channel is which decoded one
+    mapping: Option<(usize, usize, Vec<u8>)>,
+}
+
+impl OpusHead {
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 19 || !data.starts_with(b"OpusHead") {
+            return None;
+        }
+        let channels = usize::from(data[9]);
+        let mapping = match data[18] {
+            0 => None,
+            _ => Some((
+                usize::from(*data.get(19)?),
+                usize::from(*data.get(20)?),
+                data.get(21..21 + channels)?.to_vec(),
+            )),
+        };
+        Some(Self {
+            channels,
+            pre_skip: usize::from(u16::from_le_bytes([data[10], data[11]])),
+            gain: i16::from_le_bytes([data[16], data[17]]),
+            mapping,
+        })
+    }
+}
+
+impl Decoder for OpusDecoder {
+    fn try_new(params: &CodecParameters, _options: &DecoderOptions) -> Result<Self> {
+        let head = params.extra_data.as_deref().and_then(OpusHead::parse);
+        let head = match head {
+            Some(head) => head,
+            // a stream without the header has one or two channels and nothing to skip
+            None => OpusHead {
+                channels: params.channels.map_or(2, Channels::count),
+                pre_skip: 0,
+                gain: 0,
+                mapping: None,
+            },
+        };
+        let decoder = match &head.mapping {
+            None if (1..=2).contains(&head.channels) => {
+                opus_decoder::OpusDecoder::new(SAMPLE_RATE, head.channels)
+                    .map(|decoder| Streams::Single(Box::new(decoder)))
+            }
+            Some((streams, coupled, mapping)) => opus_decoder::OpusMultistreamDecoder::new(
+                SAMPLE_RATE,
+                head.channels,
+                *streams,
+                *coupled,
+                mapping,
+            )
+            .map(Streams::Multi),
+            None => return unsupported_error("opus: invalid channel count"),
+        };
+        let Ok(decoder) = decoder else {
+            return unsupported_error("opus: invalid header");
+        };
+        let channels = match params.channels {
+            Some(channels) if channels.count() == head.channels => channels,
+            _ => match u32::try_from(head.channels) {
+                Ok(count @ 1..=32) => {
+                    Channels::from_bits_truncate(u32::try_from((1_u64 << count) - 1).unwrap_or(0))
+                }
+                _ => return unsupported_error("opus: invalid channel count"),
+            },
+        };
+
+        Ok(Self {
+            params: params.clone(),
+            decoder,
+            channels: head.channels,
+            pcm: vec![0; MAX_FRAMES * head.channels],
+            buf: AudioBuffer::new(MAX_FRAMES as u64, SignalSpec::new(SAMPLE_RATE, channels)),
+            pre_skip: head.pre_skip,
+            gain: (head.gain != 0).then(|| 10_f32.powf(f32::from(head.gain) / 256.0 / 20.0)),
+        })
+    }
+
+    fn supported_codecs() -> &'static [CodecDescriptor] {
+        &[support_codec!(CODEC_TYPE_OPUS, "opus", "Opus")]
+    }
+
+    fn reset(&mut self) {
+        match &mut self.decoder {
+            Streams::Single(decoder) => decoder.reset(),
+            Streams::Multi(decoder) => decoder.reset(),
+        }
+    }
+
+    fn codec_params(&self) -> &CodecParameters {
+        &self.params
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn decode(&mut self, packet: &Packet) -> Result<AudioBufferRef<'_>> {
+        let decoded = match &mut self.decoder {
+            Streams::Single(decoder) => decoder.decode(&packet.data, &mut self.pcm, false),
+            Streams::Multi(decoder) => decoder.decode(&packet.data, &mut self.pcm, false),
+        };
+        let Ok(frames) = decoded else {
+            return Err(Error::DecodeError("opus: invalid packet"));
+        };
+        let skip = self.pre_skip.min(frames);
+        self.pre_skip -= skip;
+
+        self.buf.clear();
+        self.buf.render_reserved(Some(frames - skip));
+        for channel in 0..self.channels {
+            let samples = self.pcm[skip * self.channels..frames * self.channels]
+                .iter()
+                .skip(channel)
+                .step_by(self.channels);
+            for (sample, decoded) in self.buf.chan_mut(channel).iter_mut().zip(samples) {
+                *sample = match self.gain {
+                    Some(gain) => (f32::from(*decoded) * gain) as i16,
+                    None => *decoded,
+                };
+            }
+        }
+        self.buf
+            .trim(packet.trim_start() as usize, packet.trim_end() as usize);
+        Ok(self.buf.as_audio_buffer_ref())
+    }
+
+    fn finalize(&mut self) -> FinalizeResult {
+        FinalizeResult::default()
+    }
+
+    fn last_decoded(&self) -> AudioBufferRef<'_> {
+        self.buf.as_audio_buffer_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_opus_head() {
+        let mut head = b"OpusHead\x01\x06\x38\x01\x80\xbb\x00\x00\x00\x01\x01".to_vec();
+        head.extend([4, 2, 0, 4, 1, 2, 3, 5]);
+        assert_eq!(
+            OpusHead::parse(&head),
+            Some(OpusHead {
+                channels: 6,
+                pre_skip: 312,
+                gain: 256,
+                mapping: Some((4, 2, vec![0, 4, 1, 2, 3, 5])),
+            })
+        );
+        assert_eq!(OpusHead::parse(&head[..20]), None);
+    }
+}
@@ -36,6 +36,11 @@ where
 
     let output = SourcesQueueOutput {
         current: Box::new(Empty::<S>::new()) as Box<_>,
+        current_duration: None,
+        current_samples: 0,
+        fading_out: None,
+        fade_position: 0,
+        fade_length: 0,
         signal_after_end: None,
         input: input.clone(),
         sample_cache: VecDeque::new(),
@@ -47,10 +52,39 @@ where
 
 // TODO: consider reimplementing this with `from_factory`
 
+// How often, in frames, the remaining time of the current sound is checked for a crossfade.
+const CROSSFADE_CHECK_FRAMES: usize = 512;
+
+struct Sound<S> {
+    source: Box<dyn Source<Item = S> + Send>,
+    signal_after_end: Option<Sender<()>>,
+    duration: Option<Duration>,
+    // how long this sound fades in over the end of the previous one
+    crossfade: Duration,
+}
+
+impl<S> Sound<S>
+where
+    S: Sample + Send + 'static,
+{
+    fn new<T>(source: T, signal_after_end: Option<Sender<()>>, crossfade: Duration) -> Self
+    where
+        T: Source<Item = S> + Send + 'static,
+    {
+        // taken before playback starts, so it is the length of the media whatever speed is set later
+        let duration = source.total_duration();
+        Self {
+            source: Box::new(source) as Box<_>,
+            signal_after_end,
+            duration,
+            crossfade,
+        }
+    }
+}
+
 /// The input of the queue.
-#[allow(clippy::type_complexity)]
 pub struct SourcesQueueInput<S> {
-    next_sounds: Mutex<Vec<(Box<dyn Source<Item = S> + Send>, Option<Sender<()>>)>>,
+    next_sounds: Mutex<Vec<Sound<S>>>,
 
     // See constructor.
     keep_alive_if_empty: AtomicBool,
@@ -70,7 +104,7 @@ where
         self.next_sounds
             .lock()
             .unwrap()
-            .push((Box::new(source) as Box<_>, None));
+            .push(Sound::new(source, None, Duration::ZERO));
     }
 
     /// Adds a new source to the end of the queue.
@@ -78,6 +112,20 @@ where
     /// The `Receiver` will be signalled when the sound has finished playing.
     #[inline]
     pub fn append_with_signal<T>(&self, source: T) -> Receiver<()>
+    where
+        T: Source<Item = S> + Send + 'static,
+    {
+        self.append_with_crossfade(source, Duration::ZERO)
+    }
+
+    /// Adds a new source to the end of the queue, fading in while the previous one fades out
+    /// during its last `crossfade`. Falls back to gapless playback when the two sources don't
+    /// have the same channels and sample rate.
+    ///
+    /// The `Receiver` will be signalled when the sound has finished playing, or as soon as the
+    /// next sound starts fading in.
+    #[inline]
+    pub fn append_with_crossfade<T>(&self, source: T, crossfade: Duration) -> Receiver<()>
     where
         T: Source<Item = S> + Send + 'static,
     {
@@ -85,7 +133,7 @@ where
         self.next_sounds
             .lock()
             .unwrap()
-            .push((Box::new(source) as Box<_>, Some(tx)));
+            .push(Sound::new(source, Some(tx), crossfade));
         rx
     }
 
@@ -110,6 +158,14 @@ where
 pub struct SourcesQueueOutput<S> {
     // The current iterator that produces samples.
     current: Box<dyn Source<Item = S> + Send>,
+    current_duration: Option<Duration>,
+    // Samples produced by `current` so far, to check for a crossfade every now and then.
+    current_samples: usize,
+
+    // The previous sound while it fades out under `current`.
+    fading_out: Option<Box<dyn Source<Item = S> + Send>>,
+    fade_position: usize,
+    fade_length: usize,
 
     // Signal this sender before picking from `next`.
     signal_after_end: Option<Sender<()>>,
@@ -195,9 +251,24 @@ where
             if !self.sample_cache.is_empty() {
                 return self.sample_cache.pop_front().unwrap();
             }
-            // Basic situation that will happen most of the time.
-            if let Some(sample) = self.current.next() {
-                return Some(sample);
+            if self.fading_out.is_some() {
+                if let Some(sample) = self.next_crossfaded() {
+                    return Some(sample);
+                }
+            } else {
+                let frames = CROSSFADE_CHECK_FRAMES * usize::from(self.current.channels());
+                if self.current_samples > 0 && self.current_samples.is_multiple_of(frames) {
+                    self.start_crossfade();
+                    if self.fading_out.is_some() {
+                        continue;
+                    }
+                }
+
+                // Basic situation that will happen most of the time.
+                if let Some(sample) = self.current.next() {
+                    self.current_samples += 1;
+                    return Some(sample);
+                }
             }
 
             // Since `self.current` has finished, we need to pick the next sound.
@@ -228,16 +299,22 @@ where
             let _ = signal_after_end.send(());
         }
 
-        let (next, signal_after_end) = {
+        let Sound {
+            source: next,
+            signal_after_end,
+            duration,
+            ..
+        } = {
             let mut next = self.input.next_sounds.lock().unwrap();
 
             if next.len() == 0 {
                 if self.input.keep_alive_if_empty.load(Ordering::Acquire) {
                     // Play a short silence in order to avoid spinlocking.
                     let silence = Zero::<S>::new(1, 44100); // TODO: meh
-                    (
-                        Box::new(silence.take_duration(Duration::from_millis(10))) as Box<_>,
+                    Sound::new(
+                        silence.take_duration(Duration::from_millis(10)),
                         None,
+                        Duration::ZERO,
                     )
                 } else {
                     return Err(());
@@ -268,8 +345,136 @@ where
         };
 
         self.current = next;
+        self.current_duration = duration;
+        self.current_samples = 0;
 
         self.signal_after_end = signal_after_end;
         Ok(())
     }
+
+    // Starts fading in the next sound when the current one is about to end and the next one
+    // asks for a crossfade long enough.
+    fn start_crossfade(&mut self) {
+        let remaining = match self.current_duration {
+            Some(duration) => duration.saturating_sub(self.current.elapsed()),
+            None => return,
+        };
+        let channels = self.current.channels();
+        let sample_rate = self.current.sample_rate();
+
+        let sound = {
+            let mut next = self.input.next_sounds.lock().unwrap();
+            match next.first() {
+                Some(sound)
+                    if !sound.crossfade.is_zero()
+                        && remaining <= sound.crossfade
+                        && sound.source.channels() == channels
+                        && sound.source.sample_rate() == sample_rate =>
+                {
+                    next.remove(0)
+                }
+                _ => return,
+            }
+        };
+
+        // the previous sound is over as far as the listener is concerned
+        if let Some(signal_after_end) = self.signal_after_end.take() {
+            let _ = signal_after_end.send(());
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let frames = (remaining.as_secs_f64() * f64::from(sample_rate)) as usize;
+        self.fade_length = (frames * usize::from(channels)).max(1);
+        self.fade_position = 0;
+        self.fading_out = Some(std::mem::replace(&mut self.current, sound.source));
+        self.current_duration = sound.duration;
+        self.current_samples = 0;
+        self.signal_after_end = sound.signal_after_end;
+    }
+
+    // Mixes the sound fading out with the current one, with equal power gains so the loudness
+    // stays the same during the crossfade. When the current sound is shorter than the fade, the
+    // rest of the one fading out still plays. Returns `None` when both are over.
+    fn next_crossfaded(&mut self) -> Option<S> {
+        let outgoing_source = self.fading_out.as_mut()?;
+        // both have the same channels, the current one may have been replaced when it ended
+        let channels = usize::from(outgoing_source.channels());
+        let outgoing = outgoing_source.next();
+        let incoming = self.current.next();
+        if incoming.is_none() {
+            if outgoing.is_none() {
+                self.fading_out = None;
+                return None;
+            }
+            // sounds don't have to keep returning `None` once they are over
+            self.current = Box::new(Empty::<S>::new());
+        }
+
+        // gains change once per frame so that all channels get the same
+        #[allow(clippy::cast_precision_loss)]
+        let progress =
+            (self.fade_position - self.fade_position % channels) as f32 / self.fade_length as f32;
+        let angle = progress.min(1.0) * std::f32::consts::FRAC_PI_2;
+        self.fade_position += 1;
+
+        let sample = match (incoming, outgoing) {
+            (Some(incoming), Some(outgoing)) => outgoing
+                .amplify(angle.cos())
+                .saturating_add(incoming.amplify(angle.sin())),
+            (Some(incoming), None) => incoming.amplify(angle.sin()),
+            (None, outgoing) => outgoing?.amplify(angle.cos()),
+        };
+
+        if self.fade_position >= self.fade_length && self.fade_position.is_multiple_of(channels) {
+            self.fading_out = None;
+            self.current_samples = self.fade_position;
+        }
+        Some(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::rusty_backend::buffer::SamplesBuffer;
+
+    // mono at 1 kHz, so that a sample lasts a millisecond
+    fn constant(len: usize) -> SamplesBuffer<f32> {
+        SamplesBuffer::new(1, 1000, vec![1.0; len])
+    }
+
+    #[test]
+    fn test_crossfade_gains() {
+        let (input, output) = queue(false, true);
+        input.append(constant(1024));
+        let _end = input.append_with_crossfade(constant(2048), Duration::from_secs(5));
+        let samples: Vec<f32> = output.collect();
+
+        // the fade starts at the first check, 512 samples in, and lasts as long as the
+        // buffer says is left, its elapsed time being always zero
+        assert_eq!(samples.len(), 512 + 2048);
+        for (i, sample) in samples[512..512 + 1024].iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let angle = i as f32 / 1024.0 * std::f32::consts::FRAC_PI_2;
+            let outgoing = if i < 512 { angle.cos() } else { 0.0 };
+            assert!(
+                (sample - outgoing - angle.sin()).abs() < 1e-5,
+                "sample {}",
+                i
+            );
+        }
+        assert!(samples[512 + 1024..].iter().all(|s| (s - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_crossfade_into_shorter_sound() {
+        let (input, output) = queue(false, true);
+        input.append(constant(1024));
+        let _end = input.append_with_crossfade(constant(300), Duration::from_secs(5));
+        let samples: Vec<f32> = output.collect();
+
+        // the sound fading out plays to its end after the short one is over
+        assert_eq!(samples.len(), 1024);
+        assert!(samples[812..].iter().all(|s| *s > 0.0));
+    }
 }
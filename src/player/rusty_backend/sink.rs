@@ -23,11 +23,13 @@ pub struct Sink {
 
     controls: Arc<Controls>,
     sound_count: Arc<AtomicUsize>,
+    next_id: AtomicUsize,
 
     detached: bool,
 
     elapsed: Arc<RwLock<Duration>>,
     message_tx: Sender<PlayerMsg>,
+    message_on_end_cancelled: Arc<AtomicBool>,
 }
 
 struct Controls {
@@ -36,7 +38,13 @@ struct Controls {
     seek: Mutex<Option<Duration>>,
     stopped: AtomicBool,
     speed: Mutex<f32>,
+    pitch_correction: AtomicBool,
+    equalizer: Mutex<Vec<f32>>,
     do_skip: AtomicBool,
+    // id of the source that is heard on its own, or fading in during a crossfade
+    active: AtomicUsize,
+    // the part that plays over and over, from A to B
+    ab_loop: Mutex<Option<(Duration, Duration)>>,
 }
 
 #[allow(unused)]
@@ -70,12 +78,18 @@ impl Sink {
                 stopped: AtomicBool::new(false),
                 seek: Mutex::new(None),
                 speed: Mutex::new(1.0),
+                pitch_correction: AtomicBool::new(true),
+                equalizer: Mutex::new(Vec::new()),
                 do_skip: AtomicBool::new(false),
+                active: AtomicUsize::new(0),
+                ab_loop: Mutex::new(None),
             }),
             sound_count: Arc::new(AtomicUsize::new(0)),
+            next_id: AtomicUsize::new(0),
             detached: false,
             elapsed: Arc::new(RwLock::new(Duration::from_secs(0))),
             message_tx: tx,
+            message_on_end_cancelled: Arc::new(AtomicBool::new(false)),
         };
         (sink, queue_rx)
     }
@@ -83,31 +97,61 @@ impl Sink {
     /// Appends a sound to the queue of sounds to play.
     #[inline]
     pub fn append<S>(&self, source: S)
+    where
+        S: Source + Send + 'static,
+        S::Item: Sample + Send,
+    {
+        self.append_with_crossfade(source, Duration::ZERO);
+    }
+
+    /// Appends a sound to the queue of sounds to play, fading in over the last `crossfade` of
+    /// the sound before it.
+    pub fn append_with_crossfade<S>(&self, source: S, crossfade: Duration)
     where
         S: Source + Send + 'static,
         S::Item: Sample + Send,
     {
         let controls = self.controls.clone();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut started = false;
 
         let elapsed = self.elapsed.clone();
+        let equalizer = self.controls.equalizer.lock().unwrap().clone();
         let source = source
+            .equalizer(&equalizer)
+            .time_stretch(1.0)
             .speed(1.0)
             .pausable(false)
             .amplify(1.0)
             .skippable()
             .stoppable()
             .periodic_access(Duration::from_millis(50), move |src| {
+                // the latest source to start takes over, the one fading out under it
+                // only follows volume, pause and speed
+                if !started {
+                    started = true;
+                    controls.active.store(id, Ordering::SeqCst);
+                }
+                let active = controls.active.load(Ordering::SeqCst) == id;
+
                 if controls.stopped.load(Ordering::SeqCst) {
                     src.stop();
-                } else if controls.do_skip.load(Ordering::SeqCst) {
+                } else if active && controls.do_skip.load(Ordering::SeqCst) {
                     src.inner_mut().skip();
                     controls.do_skip.store(false, Ordering::SeqCst);
                 } else {
-                    if let Some(seek_time) = controls.seek.lock().unwrap().take() {
-                        src.seek(seek_time).unwrap();
-                        // src.seek(seek_time);
+                    if active {
+                        if let Some(seek_time) = controls.seek.lock().unwrap().take() {
+                            src.seek(seek_time).unwrap();
+                            // src.seek(seek_time);
+                        }
+                        if let Some((a, b)) = *controls.ab_loop.lock().unwrap() {
+                            if src.elapsed() >= b {
+                                src.seek(a);
+                            }
+                        }
+                        *elapsed.write().unwrap() = src.elapsed();
                     }
-                    *elapsed.write().unwrap() = src.elapsed();
 
                     // src.inner_mut().set_factor(*controls.volume.lock().unwrap());
                     // Workaround for buffer underrun issue
@@ -121,17 +165,38 @@ impl Sink {
                         .inner_mut()
                         .inner_mut()
                         .set_paused(controls.pause.load(Ordering::SeqCst));
+                    // either the pitch follows the speed, or it is kept by stretching the sound
+                    let speed = *controls.speed.lock().unwrap();
+                    let (resample, stretch) = if controls.pitch_correction.load(Ordering::SeqCst) {
+                        (1.0, speed)
+                    } else {
+                        (speed, 1.0)
+                    };
+                    src.inner_mut()
+                        .inner_mut()
+                        .inner_mut()
+                        .inner_mut()
+                        .set_factor(resample);
+                    src.inner_mut()
+                        .inner_mut()
+                        .inner_mut()
+                        .inner_mut()
+                        .inner_mut()
+                        .set_factor(stretch);
                     src.inner_mut()
                         .inner_mut()
                         .inner_mut()
                         .inner_mut()
-                        .set_factor(*controls.speed.lock().unwrap());
+                        .inner_mut()
+                        .inner_mut()
+                        .set_gains(&controls.equalizer.lock().unwrap());
                 }
             })
             .convert_samples();
         self.sound_count.fetch_add(1, Ordering::Relaxed);
         let source = Done::new(source, self.sound_count.clone());
-        *self.sleep_until_end.lock().unwrap() = Some(self.queue_tx.append_with_signal(source));
+        *self.sleep_until_end.lock().unwrap() =
+            Some(self.queue_tx.append_with_crossfade(source, crossfade));
     }
 
     /// Gets the volume of the sound.
@@ -152,6 +217,12 @@ impl Sink {
         *self.controls.volume.lock().unwrap() = value;
     }
 
+    /// Changes the equalizer gains, in dB for each of the `EQUALIZER_BANDS`.
+    #[inline]
+    pub fn set_equalizer(&self, gains: &[f32]) {
+        *self.controls.equalizer.lock().unwrap() = gains.to_vec();
+    }
+
     /// Resumes playback of a paused sink.
     ///
     /// No effect if not paused.
@@ -185,6 +256,11 @@ impl Sink {
         *self.controls.seek.lock().unwrap() = Some(seek_time);
     }
 
+    /// Plays the part from `a` to `b` of the current sound over and over, none plays on.
+    pub fn set_ab_loop(&self, ab_loop: Option<(Duration, Duration)>) {
+        *self.controls.ab_loop.lock().unwrap() = ab_loop;
+    }
+
     /// Gets if a sink is paused
     ///
     /// Sinks can be paused and resumed using `pause()` and `play()`. This returns `true` if the
@@ -245,6 +321,14 @@ impl Sink {
         *self.controls.speed.lock().unwrap() = value;
     }
 
+    /// Sets whether a speed other than `1.0` keeps the pitch of the sound.
+    #[inline]
+    pub fn set_pitch_correction(&self, value: bool) {
+        self.controls
+            .pitch_correction
+            .store(value, Ordering::SeqCst);
+    }
+
     /// Removes all currently loaded `Source`s from the `Sink`, and pauses it.
     ///
     /// See `pause()` for information about pausing a `Sink`.
@@ -263,14 +347,24 @@ impl Sink {
         self.controls.do_skip.store(true, Ordering::SeqCst);
     }
 
+    /// Keeps `message_on_end` from sending its message, so the sink can be dropped without
+    /// the player moving on to the next track.
+    pub fn cancel_message_on_end(&self) {
+        self.message_on_end_cancelled.store(true, Ordering::SeqCst);
+    }
+
     // Spawns a new thread to sleep until the sound ends, and then sends the SoundEnded
     // message through the given Sender.
     pub fn message_on_end(&self) {
         // let tx1 = Sender::clone(&self.message_tx);
         let tx1 = self.message_tx.clone();
+        let cancelled = self.message_on_end_cancelled.clone();
         if let Some(sleep_until_end) = self.sleep_until_end.lock().unwrap().take() {
             std::thread::spawn(move || {
                 let _ = sleep_until_end.recv();
+                if cancelled.load(Ordering::SeqCst) {
+                    return;
+                }
                 if let Err(e) = tx1.send(PlayerMsg::Eos) {
                     eprintln!("Error is: {}", e);
                 }
@@ -0,0 +1,240 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use super::{Sample, Source};
+use crate::player::EQUALIZER_BANDS;
+use cpal::Sample as CpalSample;
+
+// about one octave wide
+const BAND_Q: f32 = 1.41;
+
+/// Internal function that builds a `Equalizer` object.
+pub fn equalizer<I>(input: I, gains: &[f32]) -> Equalizer<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    let mut equalizer = Equalizer {
+        input,
+        gains: Vec::new(),
+        sample_rate: 0,
+        filters: Vec::new(),
+        pre_gain: 1.0,
+        channel: 0,
+    };
+    equalizer.set_gains(gains);
+    equalizer
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    // peaking filter from the Audio EQ Cookbook, coefficients only so the state is kept
+    fn set_peaking(&mut self, freq: f32, gain_db: f32, sample_rate: u32) {
+        #[allow(clippy::cast_precision_loss)]
+        let rate = sample_rate as f32;
+        if gain_db == 0.0 || freq >= rate / 2.0 {
+            *self = Self {
+                b0: 1.0,
+                ..Self::default()
+            };
+            return;
+        }
+
+        let a = 10_f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * PI * freq / rate;
+        let alpha = w0.sin() / (2.0 * BAND_Q);
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha / a;
+
+        self.b0 = (1.0 + alpha * a) / a0;
+        self.b1 = -2.0 * cos / a0;
+        self.b2 = (1.0 - alpha * a) / a0;
+        self.a1 = -2.0 * cos / a0;
+        self.a2 = (1.0 - alpha / a) / a0;
+    }
+
+    #[inline]
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
+
+/// Filter that boosts or cuts the frequency bands of `EQUALIZER_BANDS` by the given gains in dB.
+#[derive(Clone, Debug)]
+pub struct Equalizer<I> {
+    input: I,
+    gains: Vec<f32>,
+    sample_rate: u32,
+    // one filter per band, for each channel
+    filters: Vec<Vec<Biquad>>,
+    // lowers the input by the largest boost, so that boosted bands have room and don't clip
+    pre_gain: f32,
+    channel: usize,
+}
+
+impl<I> Equalizer<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    /// Modifies the gains, in dB. Missing bands are left flat.
+    pub fn set_gains(&mut self, gains: &[f32]) {
+        let channels = usize::from(self.input.channels());
+        let sample_rate = self.input.sample_rate();
+        if gains == self.gains && sample_rate == self.sample_rate && channels == self.filters.len()
+        {
+            return;
+        }
+
+        self.gains = gains.to_vec();
+        self.sample_rate = sample_rate;
+        let max_boost = gains.iter().copied().fold(0.0, f32::max);
+        self.pre_gain = 10_f32.powf(-max_boost / 20.0);
+        if channels != self.filters.len() {
+            self.filters = vec![vec![Biquad::default(); EQUALIZER_BANDS.len()]; channels];
+            self.channel = 0;
+        }
+
+        for filters in &mut self.filters {
+            for (band, filter) in filters.iter_mut().enumerate() {
+                let gain = gains.get(band).copied().unwrap_or(0.0);
+                filter.set_peaking(EQUALIZER_BANDS[band], gain, sample_rate);
+            }
+        }
+    }
+
+    fn is_flat(&self) -> bool {
+        self.gains.iter().all(|g| *g == 0.0)
+    }
+}
+
+impl<I> Iterator for Equalizer<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        let value = self.input.next()?;
+        if self.is_flat() || self.filters.is_empty() {
+            return Some(value);
+        }
+
+        let channel = self.channel;
+        self.channel = (channel + 1) % self.filters.len();
+
+        let mut sample = value.to_f32() * self.pre_gain;
+        for filter in &mut self.filters[channel] {
+            sample = filter.process(sample);
+        }
+        Some(CpalSample::from(&sample.clamp(-1.0, 1.0)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Equalizer<I>
+where
+    I: Source + ExactSizeIterator,
+    I::Item: Sample,
+{
+}
+
+impl<I> Source for Equalizer<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    #[inline]
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+
+    #[inline]
+    fn elapsed(&mut self) -> Duration {
+        self.input.elapsed()
+    }
+
+    fn seek(&mut self, time: Duration) -> Option<Duration> {
+        self.input.seek(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::rusty_backend::buffer::SamplesBuffer;
+
+    const RATE: u32 = 44100;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn sine(freq: f32, amplitude: f32) -> SamplesBuffer<f32> {
+        let samples: Vec<f32> = (0..RATE / 2)
+            .map(|i| amplitude * (2.0 * PI * freq * i as f32 / RATE as f32).sin())
+            .collect();
+        SamplesBuffer::new(1, RATE, samples)
+    }
+
+    // the peak once the filters have settled
+    fn peak(source: impl Iterator<Item = f32>) -> f32 {
+        source
+            .skip(RATE as usize / 4)
+            .fold(0.0, |peak, s| peak.max(s.abs()))
+    }
+
+    #[test]
+    fn test_flat_is_unchanged() {
+        let flat = equalizer(sine(440.0, 0.5), &[0.0; 10]);
+        assert!(flat.zip(sine(440.0, 0.5)).all(|(a, b)| a == b));
+
+        let mut filter = Biquad::default();
+        filter.set_peaking(1000.0, 0.0, RATE);
+        assert!((filter.process(0.25) - 0.25).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_boosted_band() {
+        let mut gains = [0.0; 10];
+        // the 1 kHz band
+        gains[5] = 6.0;
+        let boosted = peak(equalizer(sine(1000.0, 0.9), &gains));
+        let other = peak(equalizer(sine(62.0, 0.9), &gains));
+        let gain_db = 20.0 * (boosted / other).log10();
+        assert!((gain_db - 6.0).abs() < 0.5, "gain {} dB", gain_db);
+        // the headroom keeps the boosted band from clipping
+        assert!(boosted < 0.95, "peak {}", boosted);
+    }
+}
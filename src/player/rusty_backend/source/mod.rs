@@ -7,6 +7,7 @@ use super::Sample;
 pub use self::amplify::Amplify;
 pub use self::done::Done;
 pub use self::empty::Empty;
+pub use self::equalizer::Equalizer;
 pub use self::fadein::FadeIn;
 pub use self::pausable::Pausable;
 pub use self::periodic::PeriodicAccess;
@@ -15,12 +16,14 @@ pub use self::skippable::Skippable;
 pub use self::speed::Speed;
 pub use self::stoppable::Stoppable;
 pub use self::take::TakeDuration;
+pub use self::time_stretch::TimeStretch;
 pub use self::uniform::UniformSourceIterator;
 pub use self::zero::Zero;
 
 mod amplify;
 mod done;
 mod empty;
+mod equalizer;
 mod fadein;
 mod pausable;
 mod periodic;
@@ -29,6 +32,7 @@ mod skippable;
 mod speed;
 mod stoppable;
 mod take;
+mod time_stretch;
 mod uniform;
 mod zero;
 
@@ -148,6 +152,15 @@ where
         amplify::amplify(self, value)
     }
 
+    /// Boosts or cuts frequency bands by the given gains in dB.
+    #[inline]
+    fn equalizer(self, gains: &[f32]) -> Equalizer<Self>
+    where
+        Self: Sized,
+    {
+        equalizer::equalizer(self, gains)
+    }
+
     /// Fades in the sound.
     #[inline]
     fn fade_in(self, duration: Duration) -> FadeIn<Self>
@@ -220,4 +233,13 @@ where
     {
         speed::speed(self, ratio)
     }
+
+    /// Changes the play speed of the sound while keeping its pitch.
+    #[inline]
+    fn time_stretch(self, ratio: f32) -> TimeStretch<Self>
+    where
+        Self: Sized,
+    {
+        time_stretch::time_stretch(self, ratio)
+    }
 }
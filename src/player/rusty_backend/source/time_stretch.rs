@@ -0,0 +1,421 @@
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use super::{Sample, Source};
+use cpal::Sample as CpalSample;
+
+// length of the segments that are overlapped, in ms
+const SEGMENT_MS: u32 = 40;
+// how far a segment may be moved to line up with the previous one, in ms
+const SEARCH_MS: u32 = 8;
+
+/// Internal function that builds a `TimeStretch` object.
+pub fn time_stretch<I>(input: I, factor: f32) -> TimeStretch<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    TimeStretch {
+        input,
+        factor,
+        active: false,
+        channels: 0,
+        sample_rate: 0,
+        window: Vec::new(),
+        search: 0,
+        buffer: Vec::new(),
+        position: 0.0,
+        previous: 0,
+        first: true,
+        overlap: Vec::new(),
+        output: VecDeque::new(),
+    }
+}
+
+/// Filter that changes the speed of the sound without changing its pitch, with WSOLA
+/// (waveform similarity overlap-add).
+///
+/// Segments are taken from the input every `hop * factor` frames and put back together every
+/// `hop` frames. Each segment is moved a little so that it lines up with the end of the one
+/// before, which keeps the overlaps free of phase cancellation.
+#[derive(Clone, Debug)]
+pub struct TimeStretch<I> {
+    input: I,
+    factor: f32,
+    // the input is only touched once a factor other than 1.0 has been set
+    active: bool,
+    // the layout the window and buffer are set up for
+    channels: usize,
+    sample_rate: u32,
+    window: Vec<f32>,
+    search: usize,
+    // interleaved input not used up yet
+    buffer: Vec<f32>,
+    // where the next segment would start without the search, in frames of `buffer`
+    position: f64,
+    // where the last segment started, in frames of `buffer`
+    previous: usize,
+    first: bool,
+    // windowed second half of the last segment
+    overlap: Vec<f32>,
+    output: VecDeque<f32>,
+}
+
+#[allow(unused)]
+impl<I> TimeStretch<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    /// Modifies the speed factor.
+    #[inline]
+    pub fn set_factor(&mut self, factor: f32) {
+        self.factor = factor;
+    }
+
+    /// Returns a reference to the inner source.
+    #[inline]
+    pub fn inner(&self) -> &I {
+        &self.input
+    }
+
+    /// Returns a mutable reference to the inner source.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    /// Returns the inner source.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn activate(&mut self) {
+        let rate = self.input.sample_rate();
+        let length = ((rate * SEGMENT_MS / 1000) as usize).max(2) & !1;
+        self.channels = usize::from(self.input.channels().max(1));
+        self.sample_rate = rate;
+        // periodic hann window, two halves overlapped add up to 1
+        self.window = (0..length)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / length as f32).cos())
+            .collect();
+        self.search = (rate * SEARCH_MS / 1000) as usize;
+        self.reset();
+        self.active = true;
+    }
+
+    // A stream can go on with other channels or another sample rate.
+    fn format_changed(&self) -> bool {
+        self.input.sample_rate() != self.sample_rate
+            || usize::from(self.input.channels().max(1)) != self.channels
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.position = 0.0;
+        self.previous = 0;
+        self.first = true;
+        self.overlap = vec![0.0; self.window.len() / 2 * self.channels];
+        self.output.clear();
+    }
+
+    fn buffered_frames(&self) -> usize {
+        self.buffer.len() / self.channels
+    }
+
+    // Reads the input until `frames` frames are buffered. Returns false if it ended before.
+    fn fill(&mut self, frames: usize) -> bool {
+        while self.buffered_frames() < frames {
+            for _ in 0..self.channels {
+                match self.input.next() {
+                    Some(sample) => self.buffer.push(sample.to_f32()),
+                    None => return false,
+                }
+            }
+        }
+        true
+    }
+
+    // Mono sum of a frame of the buffer.
+    fn frame(&self, frame: usize) -> f32 {
+        let start = frame * self.channels;
+        self.buffer[start..start + self.channels].iter().sum()
+    }
+
+    // The start around `position` whose first half looks the most like what naturally follows
+    // the previous segment.
+    fn best_start(&self, position: usize) -> usize {
+        let half = self.window.len() / 2;
+        let target = self.previous + half;
+        let mut best = position;
+        let mut best_score = f32::MIN;
+        for start in position.saturating_sub(self.search)..=position + self.search {
+            let score: f32 = (0..half)
+                .map(|i| self.frame(start + i) * self.frame(target + i))
+                .sum();
+            if score > best_score {
+                best_score = score;
+                best = start;
+            }
+        }
+        best
+    }
+
+    // Adds the next `hop` frames to the output. Returns false once the input has ended and
+    // everything has been played.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn process(&mut self) -> bool {
+        let length = self.window.len();
+        let half = length / 2;
+        let channels = self.channels;
+        let position = self.position as usize;
+
+        let needed = (position + self.search + length).max(self.previous + length);
+        if !self.fill(needed) {
+            // the last overlap fades out
+            self.output.extend(self.overlap.drain(..));
+            return !self.output.is_empty();
+        }
+
+        let start = if self.first {
+            position
+        } else {
+            self.best_start(position)
+        };
+        for i in 0..half {
+            for c in 0..channels {
+                let sample = self.buffer[(start + i) * channels + c];
+                let faded_in = if self.first {
+                    sample
+                } else {
+                    sample * self.window[i]
+                };
+                self.output
+                    .push_back(self.overlap[i * channels + c] + faded_in);
+                self.overlap[i * channels + c] =
+                    self.buffer[(start + half + i) * channels + c] * self.window[half + i];
+            }
+        }
+        self.first = false;
+        self.previous = start;
+        self.position += half as f64 * f64::from(self.factor);
+
+        // what comes before both the next search and the next target isn't needed any more
+        let used = (self.position as usize)
+            .saturating_sub(self.search)
+            .min(self.previous);
+        self.buffer.drain(..used * channels);
+        self.previous -= used;
+        self.position -= used as f64;
+        true
+    }
+}
+
+impl<I> Iterator for TimeStretch<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if !self.active {
+            if (self.factor - 1.0).abs() < f32::EPSILON {
+                return self.input.next();
+            }
+            self.activate();
+        }
+
+        if self.output.is_empty() {
+            if self.format_changed() {
+                self.activate();
+            }
+            if !self.process() {
+                return None;
+            }
+        }
+        self.output
+            .pop_front()
+            .map(|sample| CpalSample::from(&sample.clamp(-1.0, 1.0)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.active {
+            (0, None)
+        } else {
+            self.input.size_hint()
+        }
+    }
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+impl<I> Source for TimeStretch<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    #[inline]
+    fn current_frame_len(&self) -> Option<usize> {
+        if self.active {
+            None
+        } else {
+            self.input.current_frame_len()
+        }
+    }
+
+    #[inline]
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        self.input
+            .total_duration()
+            .map(|duration| duration.div_f32(self.factor))
+    }
+
+    #[inline]
+    fn elapsed(&mut self) -> Duration {
+        self.input.elapsed()
+    }
+
+    fn seek(&mut self, time: Duration) -> Option<Duration> {
+        if self.active {
+            self.reset();
+        }
+        self.input.seek(time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::rusty_backend::buffer::SamplesBuffer;
+    use pretty_assertions::assert_eq;
+
+    const RATE: u32 = 44100;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn sine(freq: f32, frames: u32) -> SamplesBuffer<f32> {
+        let samples: Vec<f32> = (0..frames)
+            .map(|i| 0.5 * (2.0 * PI * freq * i as f32 / RATE as f32).sin())
+            .collect();
+        SamplesBuffer::new(1, RATE, samples)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn frequency(samples: &[f32]) -> f32 {
+        let crossings = samples
+            .windows(2)
+            .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+            .count();
+        crossings as f32 * RATE as f32 / samples.len() as f32
+    }
+
+    #[test]
+    fn test_unchanged_at_normal_speed() {
+        let stretched: Vec<f32> = time_stretch(sine(440.0, RATE), 1.0).collect();
+        let original: Vec<f32> = sine(440.0, RATE).collect();
+        assert_eq!(stretched, original);
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_faster_keeps_pitch() {
+        let frames = RATE;
+        let stretched: Vec<f32> = time_stretch(sine(440.0, frames), 1.5).collect();
+
+        let expected = frames as f32 / 1.5;
+        // off by no more than a segment, which the end can leave out
+        let segment = (RATE * SEGMENT_MS / 1000) as f32;
+        assert!(
+            (stretched.len() as f32 - expected).abs() < segment,
+            "{} samples",
+            stretched.len()
+        );
+        let freq = frequency(&stretched);
+        assert!((freq - 440.0).abs() < 440.0 * 0.02, "{} Hz", freq);
+    }
+
+    // mono for its first 100 ms, then stereo
+    struct Switching(usize);
+
+    impl Iterator for Switching {
+        type Item = f32;
+
+        fn next(&mut self) -> Option<f32> {
+            self.0 += 1;
+            (self.0 <= 22050).then_some(0.25)
+        }
+    }
+
+    impl Source for Switching {
+        fn current_frame_len(&self) -> Option<usize> {
+            None
+        }
+
+        fn channels(&self) -> u16 {
+            if self.0 < 4410 {
+                1
+            } else {
+                2
+            }
+        }
+
+        fn sample_rate(&self) -> u32 {
+            RATE
+        }
+
+        fn total_duration(&self) -> Option<Duration> {
+            None
+        }
+
+        fn elapsed(&mut self) -> Duration {
+            Duration::ZERO
+        }
+
+        fn seek(&mut self, time: Duration) -> Option<Duration> {
+            Some(time)
+        }
+    }
+
+    #[test]
+    fn test_follows_format_change() {
+        let mut stretch = time_stretch(Switching(0), 1.5);
+        stretch.by_ref().take(100).for_each(drop);
+        assert_eq!(stretch.channels, 1);
+        stretch.by_ref().for_each(drop);
+        assert_eq!(stretch.channels, 2);
+        assert_eq!(stretch.window.len(), (RATE * SEGMENT_MS / 1000) as usize);
+    }
+
+    #[test]
+    fn test_seek_resets() {
+        let mut stretch = time_stretch(sine(440.0, RATE), 1.5);
+        stretch.by_ref().take(5000).for_each(drop);
+        assert!(!stretch.buffer.is_empty() && !stretch.first);
+
+        stretch.seek(Duration::from_millis(100));
+        assert!(stretch.buffer.is_empty() && stretch.output.is_empty() && stretch.first);
+        assert!(stretch.overlap.iter().all(|sample| *sample == 0.0));
+    }
+}
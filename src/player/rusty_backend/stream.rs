@@ -1,5 +1,6 @@
 // use std::io::{Read, Seek};
 // use std::marker::Sync;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 use std::{error, fmt};
 
@@ -17,6 +18,7 @@ use cpal::Sample;
 pub struct OutputStream {
     mixer: Arc<DynamicMixerController<f32>>,
     _stream: cpal::Stream,
+    device_lost: Arc<AtomicBool>,
 }
 
 /// More flexible handle to a `OutputStream` that provides playback.
@@ -30,11 +32,13 @@ impl OutputStream {
     pub fn try_from_device(
         device: &cpal::Device,
     ) -> Result<(Self, OutputStreamHandle), StreamError> {
-        let (mixer, stream) = device.try_new_output_stream()?;
+        let device_lost = Arc::new(AtomicBool::new(false));
+        let (mixer, stream) = device.try_new_output_stream(device_lost.clone())?;
         stream.play()?;
         let out = Self {
             mixer,
             _stream: stream,
+            device_lost,
         };
         let handle = OutputStreamHandle {
             mixer: Arc::downgrade(&out.mixer),
@@ -64,6 +68,34 @@ impl OutputStream {
                 .ok_or(original_err)
         })
     }
+
+    /// Returns a new stream & handle using the output device with the given name, or the
+    /// default one if the name is empty.
+    pub fn try_from_name(name: &str) -> Result<(Self, OutputStreamHandle), StreamError> {
+        if name.is_empty() {
+            return Self::try_default();
+        }
+
+        let device = cpal::default_host()
+            .output_devices()
+            .map_err(|_| StreamError::NoDevice)?
+            .find(|d| d.name().is_ok_and(|n| n == name))
+            .ok_or(StreamError::NoDevice)?;
+        Self::try_from_device(&device)
+    }
+
+    /// Returns true once the device went away, for example when it was unplugged.
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::Relaxed)
+    }
+}
+
+/// Names of the output devices of the default host.
+pub fn output_device_names() -> Vec<String> {
+    cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
 }
 
 #[allow(unused)]
@@ -186,10 +218,12 @@ pub trait CpalDeviceExt {
     fn new_output_stream_with_format(
         &self,
         format: cpal::SupportedStreamConfig,
+        device_lost: Arc<AtomicBool>,
     ) -> Result<(Arc<DynamicMixerController<f32>>, cpal::Stream), cpal::BuildStreamError>;
 
     fn try_new_output_stream(
         &self,
+        device_lost: Arc<AtomicBool>,
     ) -> Result<(Arc<DynamicMixerController<f32>>, cpal::Stream), StreamError>;
 }
 
@@ -197,11 +231,17 @@ impl CpalDeviceExt for cpal::Device {
     fn new_output_stream_with_format(
         &self,
         format: cpal::SupportedStreamConfig,
+        device_lost: Arc<AtomicBool>,
     ) -> Result<(Arc<DynamicMixerController<f32>>, cpal::Stream), cpal::BuildStreamError> {
         let (mixer_tx, mut mixer_rx) =
             dynamic_mixer::mixer::<f32>(format.channels(), format.sample_rate().0);
 
-        let error_callback = |err| eprintln!("an error occurred on output stream: {}", err);
+        let error_callback = move |err| match err {
+            cpal::StreamError::DeviceNotAvailable => device_lost.store(true, Ordering::Relaxed),
+            cpal::StreamError::BackendSpecific { .. } => {
+                eprintln!("an error occurred on output stream: {}", err);
+            }
+        };
 
         match format.sample_format() {
             cpal::SampleFormat::F32 => self.build_output_stream::<f32, _, _>(
@@ -235,15 +275,19 @@ impl CpalDeviceExt for cpal::Device {
 
     fn try_new_output_stream(
         &self,
+        device_lost: Arc<AtomicBool>,
     ) -> Result<(Arc<DynamicMixerController<f32>>, cpal::Stream), StreamError> {
         // Determine the format to use for the new stream.
         let default_format = self.default_output_config()?;
 
-        self.new_output_stream_with_format(default_format)
+        self.new_output_stream_with_format(default_format, device_lost.clone())
             .or_else(|err| {
                 // look through all supported formats to see if another works
                 supported_output_formats(self)?
-                    .find_map(|format| self.new_output_stream_with_format(format).ok())
+                    .find_map(|format| {
+                        self.new_output_stream_with_format(format, device_lost.clone())
+                            .ok()
+                    })
                     // return original error if nothing works
                     .ok_or(StreamError::BuildStreamError(err))
             })
@@ -1,19 +1,13 @@
+use super::{escape, PlaylistItem};
+use quick_xml::events::attributes::Attributes;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use std::error::Error;
-
-#[derive(Clone)]
-pub struct PlaylistItem {
-    pub title: String,
-    pub url: String,
-}
+use std::time::Duration;
 
 pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
     let mut list = vec![];
-    let mut item = PlaylistItem {
-        title: String::from(""),
-        url: String::from(""),
-    };
+    let mut item = PlaylistItem::default();
 
     let mut reader = Reader::from_str(content);
     reader.trim_text(true);
@@ -23,48 +17,27 @@ pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
         match reader.read_event(&mut buf) {
             Ok(Event::Empty(ref e)) => {
                 xml_stack.push(reader.decode(e.name())?.to_lowercase());
-
-                let path = xml_stack.join("/");
-                for a in e.attributes() {
-                    let a = a?;
-                    let key = reader.decode(a.key)?.to_lowercase();
-                    let value = reader.decode(&a.value)?;
-                    if path == "asx/entry/ref" && key == "href" {
-                        item.url = value.to_string();
-                    }
-                }
-
+                read_attributes(&xml_stack.join("/"), e.attributes(), &reader, &mut item)?;
                 xml_stack.pop();
             }
             Ok(Event::Start(ref e)) => {
                 xml_stack.push(reader.decode(e.name())?.to_lowercase());
-
-                let path = xml_stack.join("/");
-                for a in e.attributes() {
-                    let a = a?;
-                    let key = reader.decode(a.key)?.to_lowercase();
-                    let value = reader.decode(&a.value)?;
-                    if path == "asx/entry/ref" && key == "href" {
-                        item.url = value.to_string();
-                    }
-                }
+                read_attributes(&xml_stack.join("/"), e.attributes(), &reader, &mut item)?;
             }
             Ok(Event::End(_)) => {
                 let path = xml_stack.join("/");
                 if path == "asx/entry" {
-                    list.push(item.clone());
-                    item.title = String::from("");
-                    item.url = String::from("");
+                    list.push(std::mem::take(&mut item));
                 }
                 xml_stack.pop();
             }
             Ok(Event::Text(e)) => {
                 let path = xml_stack.join("/");
                 if path == "asx/entry/title" {
-                    item.title = e
-                        .unescape_and_decode(&reader)
-                        .unwrap_or_else(|_| String::from(""))
-                        .clone();
+                    item.title = e.unescape_and_decode(&reader).ok();
+                }
+                if path == "asx/entry/author" {
+                    item.artist = e.unescape_and_decode(&reader).ok();
                 }
             }
             Ok(Event::Eof) => break,
@@ -79,3 +52,60 @@ pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
 
     Ok(list)
 }
+
+fn read_attributes(
+    path: &str,
+    attributes: Attributes,
+    reader: &Reader<&[u8]>,
+    item: &mut PlaylistItem,
+) -> Result<(), Box<dyn Error>> {
+    for a in attributes {
+        let a = a?;
+        let key = reader.decode(a.key)?.to_lowercase();
+        let value = a.unescape_and_decode_value(reader)?;
+        if path == "asx/entry/ref" && key == "href" {
+            item.location = value;
+        } else if path == "asx/entry/duration" && key == "value" {
+            item.duration = parse_duration(&value);
+        }
+    }
+    Ok(())
+}
+
+// ASX durations look like 00:03:25.50, hours and fractions may be left out
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut secs = 0.0;
+    for part in value.trim().split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+    (secs >= 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+pub fn encode(items: &[PlaylistItem]) -> String {
+    let mut content = String::from("<asx version=\"3.0\">\n");
+    for item in items {
+        content.push_str("  <entry>\n");
+        if let Some(title) = &item.title {
+            content.push_str(&format!("    <title>{}</title>\n", escape(title)));
+        }
+        if let Some(artist) = &item.artist {
+            content.push_str(&format!("    <author>{}</author>\n", escape(artist)));
+        }
+        if let Some(duration) = item.duration {
+            let secs = duration.as_secs();
+            content.push_str(&format!(
+                "    <duration value=\"{:02}:{:02}:{:02}\" />\n",
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60
+            ));
+        }
+        content.push_str(&format!(
+            "    <ref href=\"{}\" />\n",
+            escape(&item.location)
+        ));
+        content.push_str("  </entry>\n");
+    }
+    content.push_str("</asx>\n");
+    content
+}
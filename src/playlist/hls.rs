@@ -0,0 +1,281 @@
+//! HLS playlists, the `.m3u8` files of http live streaming. A master playlist lists the
+//! variants of a stream in different qualities, a media playlist lists the segments to play
+//! one after the other. Live streams keep a window of the last segments, which slides on
+//! each time the playlist is loaded again.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// One of the streams a master playlist offers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variant {
+    pub uri: String,
+    /// Bits per second, zero when unknown
+    pub bandwidth: u64,
+    /// Like `mp4a.40.2,avc1.4d401f`
+    pub codecs: Option<String>,
+    /// The uri of the separate audio rendition of the variant, if it has one
+    pub audio: Option<String>,
+}
+
+impl Variant {
+    // Without a video codec, as radio stations have it.
+    fn is_audio_only(&self) -> bool {
+        self.codecs.as_deref().is_some_and(|codecs| {
+            codecs
+                .split(',')
+                .all(|codec| codec.trim().starts_with("mp4a") || codec.trim() == "mp3")
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub uri: String,
+    /// The media sequence number, which tells the segments apart while the window slides
+    pub sequence: u64,
+    pub duration: Duration,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaPlaylist {
+    pub target_duration: Duration,
+    pub segments: Vec<Segment>,
+    /// No more segments are coming, set for files but not for live streams
+    pub ended: bool,
+    /// The segments are encrypted, which is not supported
+    pub encrypted: bool,
+}
+
+impl MediaPlaylist {
+    /// Where playback starts: the beginning of a file, or three segments before the end of a
+    /// live stream, as the spec asks for.
+    pub fn start_sequence(&self) -> u64 {
+        let start = if self.ended {
+            0
+        } else {
+            self.segments.len().saturating_sub(3)
+        };
+        self.segments
+            .get(start)
+            .map_or(0, |segment| segment.sequence)
+    }
+}
+
+pub fn is_master(content: &str) -> bool {
+    content.contains("#EXT-X-STREAM-INF")
+}
+
+/// The variants of a master playlist, with their uris resolved against `base`.
+pub fn parse_master(content: &str, base: &str) -> Vec<Variant> {
+    // audio renditions by group id, the default one of a group wins
+    let mut audio: HashMap<String, (String, bool)> = HashMap::new();
+    let mut variants = vec![];
+    let mut stream_inf: Option<HashMap<String, String>> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(attributes) = line.strip_prefix("#EXT-X-MEDIA:") {
+            let attributes = parse_attributes(attributes);
+            if attributes.get("TYPE").map(String::as_str) != Some("AUDIO") {
+                continue;
+            }
+            if let (Some(group), Some(uri)) = (attributes.get("GROUP-ID"), attributes.get("URI")) {
+                let default = attributes.get("DEFAULT").map(String::as_str) == Some("YES");
+                let replace = audio
+                    .get(group)
+                    .is_none_or(|(_, was_default)| default && !was_default);
+                if replace {
+                    audio.insert(group.clone(), (resolve(base, uri), default));
+                }
+            }
+        } else if let Some(attributes) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+            stream_inf = Some(parse_attributes(attributes));
+        } else if !line.is_empty() && !line.starts_with('#') {
+            if let Some(attributes) = stream_inf.take() {
+                variants.push(Variant {
+                    uri: resolve(base, line),
+                    bandwidth: attributes
+                        .get("BANDWIDTH")
+                        .and_then(|bandwidth| bandwidth.parse().ok())
+                        .unwrap_or(0),
+                    codecs: attributes.get("CODECS").cloned(),
+                    audio: attributes.get("AUDIO").cloned(),
+                });
+            }
+        }
+    }
+    for variant in &mut variants {
+        variant.audio = variant
+            .audio
+            .take()
+            .and_then(|group| audio.get(&group).map(|(uri, _)| uri.clone()));
+    }
+    variants
+}
+
+/// The variant to play: the best audio only one, or else the smallest one, as only the audio
+/// of it is played.
+pub fn select_variant(variants: &[Variant]) -> Option<&Variant> {
+    variants
+        .iter()
+        .filter(|variant| variant.is_audio_only())
+        .max_by_key(|variant| variant.bandwidth)
+        .or_else(|| variants.iter().min_by_key(|variant| variant.bandwidth))
+}
+
+/// The segments of a media playlist, with their uris resolved against `base`.
+pub fn parse_media(content: &str, base: &str) -> MediaPlaylist {
+    let mut playlist = MediaPlaylist::default();
+    let mut sequence = 0;
+    let mut duration = Duration::ZERO;
+    for line in content.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+            playlist.target_duration = parse_seconds(value);
+        } else if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+            sequence = value.parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("#EXTINF:") {
+            duration = parse_seconds(value.split(',').next().unwrap_or_default());
+        } else if let Some(attributes) = line.strip_prefix("#EXT-X-KEY:") {
+            let method = parse_attributes(attributes).remove("METHOD");
+            playlist.encrypted = method.is_some_and(|method| method != "NONE");
+        } else if line == "#EXT-X-ENDLIST" {
+            playlist.ended = true;
+        } else if !line.is_empty() && !line.starts_with('#') {
+            playlist.segments.push(Segment {
+                uri: resolve(base, line),
+                sequence,
+                duration,
+            });
+            sequence += 1;
+            duration = Duration::ZERO;
+        }
+    }
+    playlist
+}
+
+/// The uri relative to the url of the playlist it is in.
+pub fn resolve(base: &str, uri: &str) -> String {
+    if uri.contains("://") {
+        return uri.to_string();
+    }
+    let base = base.split(['?', '#']).next().unwrap_or(base);
+    let (scheme, rest) = base.split_once("://").unwrap_or(("http", base));
+    let host_end = rest.find('/').unwrap_or(rest.len());
+    let host = &rest[..host_end];
+    if let Some(uri) = uri.strip_prefix("//") {
+        return format!("{}://{}", scheme, uri);
+    }
+    if uri.starts_with('/') {
+        return format!("{}://{}{}", scheme, host, uri);
+    }
+    let path = &rest[host_end..];
+    let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+    let dir = if dir.is_empty() { "/" } else { dir };
+    format!("{}://{}{}{}", scheme, host, dir, uri)
+}
+
+fn parse_seconds(value: &str) -> Duration {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map_or(Duration::ZERO, Duration::from_secs_f64)
+}
+
+// `KEY=value,KEY="quoted, value"` as the tags have them.
+fn parse_attributes(list: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut rest = list.trim();
+    while let Some((key, value)) = rest.split_once('=') {
+        let (value, next) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                let next = quoted[end..].trim_start_matches('"');
+                (
+                    &quoted[..end],
+                    next.split_once(',').map_or("", |(_, next)| next),
+                )
+            }
+            None => value.split_once(',').unwrap_or((value, "")),
+        };
+        attributes.insert(key.trim().to_string(), value.trim().to_string());
+        rest = next.trim();
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn master_playlist() {
+        let content = "#EXTM3U\n\
+            #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"en\",URI=\"audio/en.m3u8\"\n\
+            #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"main\",DEFAULT=YES,URI=\"audio/main.m3u8\"\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\",AUDIO=\"aac\"\n\
+            video/low.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.5\"\n\
+            https://cdn.example.com/radio/64k.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=128000,CODECS=\"mp4a.40.2\"\n\
+            /radio/128k.m3u8?token=1\n";
+        let variants = parse_master(content, "http://example.com/live/master.m3u8?x=1");
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[0].uri, "http://example.com/live/video/low.m3u8");
+        assert_eq!(
+            variants[0].audio.as_deref(),
+            Some("http://example.com/live/audio/main.m3u8")
+        );
+        assert_eq!(variants[0].codecs.as_deref(), Some("avc1.4d401f,mp4a.40.2"));
+        assert_eq!(variants[1].uri, "https://cdn.example.com/radio/64k.m3u8");
+        let selected = select_variant(&variants).unwrap();
+        assert_eq!(selected.uri, "http://example.com/radio/128k.m3u8?token=1");
+        assert_eq!(selected.bandwidth, 128_000);
+        assert_eq!(selected.audio, None);
+
+        // without audio only variants the smallest one is taken
+        assert_eq!(
+            select_variant(&variants[..1]).map(|variant| variant.uri.as_str()),
+            Some("http://example.com/live/video/low.m3u8")
+        );
+    }
+
+    #[test]
+    fn media_playlist() {
+        let content = "#EXTM3U\n\
+            #EXT-X-VERSION:3\n\
+            #EXT-X-TARGETDURATION:10\n\
+            #EXT-X-MEDIA-SEQUENCE:2680\n\
+            #EXTINF:9.975,\n\
+            segment2680.ts\n\
+            #EXTINF:9.975,title\n\
+            segment2681.ts\n\
+            #EXTINF:10.0,\n\
+            segment2682.ts\n\
+            #EXTINF:10.0,\n\
+            segment2683.ts\n";
+        let playlist = parse_media(content, "https://example.com/radio/128k.m3u8");
+        assert_eq!(playlist.target_duration, Duration::from_secs(10));
+        assert!(!playlist.ended);
+        assert!(!playlist.encrypted);
+        assert_eq!(playlist.segments.len(), 4);
+        assert_eq!(
+            playlist.segments[1],
+            Segment {
+                uri: "https://example.com/radio/segment2681.ts".to_string(),
+                sequence: 2681,
+                duration: Duration::from_secs_f64(9.975),
+            }
+        );
+        // a live stream starts three segments before the end
+        assert_eq!(playlist.start_sequence(), 2681);
+
+        let playlist = parse_media(
+            "#EXTM3U\n#EXT-X-KEY:METHOD=AES-128,URI=\"key\"\n#EXTINF:5,\na.aac\n#EXT-X-ENDLIST\n",
+            "http://example.com/a.m3u8",
+        );
+        assert!(playlist.ended);
+        assert!(playlist.encrypted);
+        assert_eq!(playlist.start_sequence(), 0);
+    }
+}
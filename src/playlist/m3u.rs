@@ -1,20 +1,42 @@
-//! Extract urls from M3U playlist files
+//! Extract urls from M3U playlist files, with the duration and title of `#EXTINF` lines
 
-pub struct PlaylistItem {
-    pub url: String,
-}
+use super::{split_display_title, PlaylistItem};
+use std::time::Duration;
 
 pub fn decode(content: &str) -> Vec<PlaylistItem> {
     let lines = content.lines();
     let mut list = vec![];
+    let mut item = PlaylistItem::default();
     for line in lines {
-        if line.starts_with('#') {
+        let line = line.trim();
+        if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+            let (duration, text) = extinf.split_once(',').unwrap_or((extinf, ""));
+            // there may be attributes like tvg-name="..." after the duration
+            let duration = duration.split_whitespace().next().unwrap_or_default();
+            item.duration = duration.parse::<u64>().ok().map(Duration::from_secs);
+            (item.artist, item.title) = split_display_title(text);
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
             continue;
         }
 
-        list.push(PlaylistItem {
-            url: String::from(line),
-        });
+        item.location = String::from(line);
+        list.push(std::mem::take(&mut item));
     }
     list
 }
+
+/// Extended M3U, with an `#EXTINF` line of duration and "Artist - Title" for each entry
+pub fn encode(items: &[PlaylistItem]) -> String {
+    let mut content = String::from("#EXTM3U\n");
+    for item in items {
+        content.push_str(&format!(
+            "#EXTINF:{},{}\n{}\n",
+            item.duration_secs(),
+            item.display_title(),
+            item.location
+        ));
+    }
+    content
+}
@@ -1,13 +1,147 @@
-//! This is a very simple url extractor for different kinds of playlist formats: M3U, PLS, ASX, XSPF
+//! This is a very simple decoder for different kinds of playlist formats: M3U, PLS, ASX, XSPF.
+//! Besides the location of each entry, it keeps the title, artist and duration the playlist has.
 //!
 //! It is not optimized yet and does create a lot of strings on the way.
+//!
+//! Playlists can be written in the same formats with [`encode`] and [`export`].
 
 mod asx;
+pub mod hls;
 mod m3u;
 mod pls;
 mod xspf;
 
+use crate::track::Track;
+use anyhow::{anyhow, Result};
 use std::error::Error;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+/// An entry of a playlist file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlaylistItem {
+    /// Path or url, relative paths are kept as they are
+    pub location: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl PlaylistItem {
+    /// "Artist - Title" for the formats with a single title field, empty when unknown.
+    pub fn display_title(&self) -> String {
+        match (&self.artist, &self.title) {
+            (Some(artist), Some(title)) => format!("{} - {}", artist, title),
+            (None, Some(title)) => title.clone(),
+            _ => String::new(),
+        }
+    }
+
+    // -1 when unknown, as M3U and PLS have it
+    fn duration_secs(&self) -> i64 {
+        self.duration.map_or(-1, |d| d.as_secs() as i64)
+    }
+}
+
+impl From<&Track> for PlaylistItem {
+    fn from(track: &Track) -> Self {
+        Self {
+            location: track.file().unwrap_or_default().to_string(),
+            title: track.title().map(ToString::to_string),
+            artist: track.artist().map(ToString::to_string),
+            duration: Some(track.duration()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaylistFormat {
+    M3u,
+    Pls,
+    Xspf,
+    Asx,
+}
+
+impl PlaylistFormat {
+    /// The format that goes with the extension of the file.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "m3u" | "m3u8" => Some(Self::M3u),
+            "pls" => Some(Self::Pls),
+            "xspf" => Some(Self::Xspf),
+            "asx" => Some(Self::Asx),
+            _ => None,
+        }
+    }
+}
+
+/// Encode the items as playlist content in the given format. Locations are written as they are.
+pub fn encode(items: &[PlaylistItem], format: PlaylistFormat) -> String {
+    match format {
+        PlaylistFormat::M3u => m3u::encode(items),
+        PlaylistFormat::Pls => pls::encode(items),
+        PlaylistFormat::Xspf => xspf::encode(items),
+        PlaylistFormat::Asx => asx::encode(items),
+    }
+}
+
+/// Write the items to `path`, in the format of its extension. With `relative`, local files
+/// are written relative to the folder of the playlist, otherwise as absolute paths.
+pub fn export(path: &Path, items: &[PlaylistItem], relative: bool) -> Result<()> {
+    let format = PlaylistFormat::from_path(path).ok_or_else(|| {
+        anyhow!(
+            "unknown playlist format for {}, use m3u, m3u8, pls, xspf or asx",
+            path.display()
+        )
+    })?;
+    let base = path
+        .parent()
+        .ok_or_else(|| anyhow!("cannot find path root"))?;
+    let items: Vec<PlaylistItem> = items
+        .iter()
+        .map(|item| {
+            let mut item = item.clone();
+            if relative {
+                if let Some(location) = relative_path(Path::new(&item.location), base) {
+                    item.location = location.to_string_lossy().to_string();
+                }
+            }
+            item
+        })
+        .collect();
+    std::fs::write(path, encode(&items, format))?;
+    Ok(())
+}
+
+// The path of `file` seen from the folder `base`, when both are absolute and share a root.
+fn relative_path(file: &Path, base: &Path) -> Option<PathBuf> {
+    if !file.is_absolute() || !base.is_absolute() {
+        return None;
+    }
+    let file: Vec<Component> = file.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    let common = file.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    if common == 0 {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for _ in common..base.len() {
+        path.push("..");
+    }
+    for component in &file[common..] {
+        path.push(component);
+    }
+    Some(path)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
 
 /// Decode playlist content string. It checks for M3U, PLS, XSPF and ASX content in the string.
 /// # Example
@@ -34,48 +168,35 @@ use std::error::Error;
 /// ```
 /// # Arguments
 /// * `content` - A string slice containing a playlist
-pub fn decode(content: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let mut set = vec![];
+pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
     let content_small = content.to_lowercase();
-    match content_small.find("<playlist") {
-        Some(_) => {
-            let xspf_items = xspf::decode(content)?;
-            for item in xspf_items {
-                if !item.url.is_empty() {
-                    set.push(item.url);
-                }
-                if !item.identifier.is_empty() {
-                    set.push(item.identifier);
-                }
-            }
-        }
-        None => match content_small.find("<asx") {
-            Some(_) => {
-                let pls_items = asx::decode(content)?;
-                for item in pls_items {
-                    set.push(item.url);
-                }
-            }
-            None => match content_small.find("[playlist]") {
-                Some(_) => {
-                    let pls_items = pls::decode(content);
-                    for item in pls_items {
-                        set.push(item.url);
-                    }
-                }
-                None => {
-                    let m3u_items = m3u::decode(content);
-                    for item in m3u_items {
-                        set.push(item.url);
-                    }
-                }
-            },
-        },
+    let items = if content_small.contains("<playlist") {
+        xspf::decode(content)?
+    } else if content_small.contains("<asx") {
+        asx::decode(content)?
+    } else if content_small.contains("[playlist]") {
+        pls::decode(content)
+    } else {
+        m3u::decode(content)
+    };
+    Ok(items
+        .into_iter()
+        .filter(|item| !item.location.is_empty())
+        .collect())
+}
+
+// The other way round of `PlaylistItem::display_title`, for the formats with a single title field.
+fn split_display_title(text: &str) -> (Option<String>, Option<String>) {
+    let text = text.trim();
+    if text.is_empty() {
+        return (None, None);
+    }
+    match text.split_once(" - ") {
+        Some((artist, title)) => (Some(artist.to_string()), Some(title.to_string())),
+        None => (None, Some(text.to_string())),
     }
-    let v: Vec<String> = set.into_iter().collect();
-    Ok(v)
 }
-#[allow(unused)]
+
 pub fn is_content_hls(content: &str) -> bool {
     if content.contains("EXT-X-STREAM-INF") {
         return true;
@@ -88,6 +209,88 @@ pub fn is_content_hls(content: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use super::{encode, relative_path, PlaylistFormat, PlaylistItem};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    fn items() -> Vec<PlaylistItem> {
+        vec![
+            PlaylistItem {
+                location: "/music/Simon & Garfunkel/The Boxer.mp3".to_string(),
+                title: Some("The Boxer".to_string()),
+                artist: Some("Simon & Garfunkel".to_string()),
+                duration: Some(Duration::from_secs(308)),
+            },
+            PlaylistItem {
+                location: "http://this.is.an.example/stream".to_string(),
+                title: Some("Radio".to_string()),
+                artist: None,
+                duration: None,
+            },
+            PlaylistItem {
+                location: "relative/unknown.ogg".to_string(),
+                ..PlaylistItem::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let items = items();
+
+        let content = encode(&items, PlaylistFormat::M3u);
+        assert!(content.starts_with(
+            "#EXTM3U\n#EXTINF:308,Simon & Garfunkel - The Boxer\n/music/Simon & Garfunkel/The Boxer.mp3\n"
+        ));
+        let content = encode(&items, PlaylistFormat::Pls);
+        assert!(content.contains("Length1=308\n"));
+        let content = encode(&items, PlaylistFormat::Xspf);
+        assert!(content.contains("<creator>Simon &amp; Garfunkel</creator>"));
+        assert!(content.contains("<duration>308000</duration>"));
+        assert!(content.contains(
+            "<location>file:///music/Simon%20%26%20Garfunkel/The%20Boxer.mp3</location>"
+        ));
+        let content = encode(&items, PlaylistFormat::Asx);
+        assert!(content.contains("<duration value=\"00:05:08\" />"));
+
+        for format in [
+            PlaylistFormat::M3u,
+            PlaylistFormat::Pls,
+            PlaylistFormat::Xspf,
+            PlaylistFormat::Asx,
+        ] {
+            let content = encode(&items, format);
+            assert_eq!(
+                crate::playlist::decode(&content).unwrap(),
+                items,
+                "{:?}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn relative_paths() {
+        let base = Path::new("/home/me/Music/playlists");
+        assert_eq!(
+            relative_path(Path::new("/home/me/Music/jazz/So What.flac"), base),
+            Some(PathBuf::from("../jazz/So What.flac"))
+        );
+        assert_eq!(
+            relative_path(Path::new("/home/me/Music/playlists/a.mp3"), base),
+            Some(PathBuf::from("a.mp3"))
+        );
+        assert_eq!(
+            relative_path(Path::new("http://example.org/a.mp3"), base),
+            None
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("/tmp/list.M3U8")),
+            Some(PlaylistFormat::M3u)
+        );
+        assert_eq!(PlaylistFormat::from_path(Path::new("/tmp/list.txt")), None);
+    }
+
     #[test]
     fn xspf() {
         let s = r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -103,18 +306,25 @@ mod tests {
         <identifier>Identifier2</identifier>
         <location>http://this.is.an.example2</location>
     </track>
+    <track>
+        <title>Title3</title>
+        <creator>Artist3</creator>
+        <duration>61500</duration>
+        <identifier>http://this.is.an.example3</identifier>
+    </track>
     </trackList>
 </playlist>"#;
         let items = crate::playlist::xspf::decode(s);
         assert!(items.is_ok());
         let items = items.unwrap();
-        assert!(items.len() == 2);
-        assert!(items[0].url == "http://this.is.an.example");
-        assert!(items[0].title == "Title");
-        assert!(items[0].identifier == "Identifier");
-        assert!(items[1].url == "http://this.is.an.example2");
-        assert!(items[1].title == "Title2");
-        assert!(items[1].identifier == "Identifier2");
+        assert!(items.len() == 3);
+        assert!(items[0].location == "http://this.is.an.example");
+        assert!(items[0].title.as_deref() == Some("Title"));
+        assert!(items[1].location == "http://this.is.an.example2");
+        assert!(items[1].title.as_deref() == Some("Title2"));
+        assert!(items[2].location == "http://this.is.an.example3");
+        assert!(items[2].artist.as_deref() == Some("Artist3"));
+        assert!(items[2].duration == Some(Duration::from_millis(61500)));
     }
 
     #[test]
@@ -127,6 +337,8 @@ mod tests {
   </entry>
   <entry>
     <title>title2</title>
+    <author>author2</author>
+    <duration value="01:02.5"/>
     <ref href="ref2"/>
   </entry>
 </asx>"#;
@@ -134,17 +346,29 @@ mod tests {
         assert!(items.is_ok());
         let items = items.unwrap();
         assert!(items.len() == 2);
-        assert!(items[0].url == "ref1");
-        assert!(items[0].title == "title1");
-        assert!(items[1].url == "ref2");
-        assert!(items[1].title == "title2");
+        assert!(items[0].location == "ref1");
+        assert!(items[0].title.as_deref() == Some("title1"));
+        assert!(items[1].location == "ref2");
+        assert!(items[1].title.as_deref() == Some("title2"));
+        assert!(items[1].artist.as_deref() == Some("author2"));
+        assert!(items[1].duration == Some(Duration::from_millis(62500)));
     }
 
     #[test]
     fn m3u() {
         let items = crate::playlist::m3u::decode("http://this.is.an.example");
         assert!(items.len() == 1);
-        assert!(items[0].url == "http://this.is.an.example");
+        assert!(items[0].location == "http://this.is.an.example");
+
+        let items = crate::playlist::m3u::decode(
+            "#EXTM3U\n#EXTINF:-1 tvg-id=\"radio\",Some Radio\nhttp://this.is.an.example\n\n#EXTINF:200,Artist - Title\nsong.mp3\n",
+        );
+        assert!(items.len() == 2);
+        assert!(items[0].title.as_deref() == Some("Some Radio"));
+        assert!(items[0].duration.is_none());
+        assert!(items[1].artist.as_deref() == Some("Artist"));
+        assert!(items[1].title.as_deref() == Some("Title"));
+        assert!(items[1].duration == Some(Duration::from_secs(200)));
     }
 
     #[test]
@@ -156,8 +380,8 @@ Title1=mytitle
         ",
         );
         assert!(items.len() == 1);
-        assert!(items[0].url == "http://this.is.an.example");
-        assert!(items[0].title == "mytitle");
+        assert!(items[0].location == "http://this.is.an.example");
+        assert!(items[0].title.as_deref() == Some("mytitle"));
     }
 
     #[test]
@@ -169,8 +393,8 @@ Title=mytitle
         ",
         );
         assert!(items.len() == 1);
-        assert!(items[0].url == "http://this.is.an.example");
-        assert!(items[0].title == "mytitle");
+        assert!(items[0].location == "http://this.is.an.example");
+        assert!(items[0].title.as_deref() == Some("mytitle"));
     }
 
     #[test]
@@ -182,7 +406,7 @@ Title=mytitle
         ",
         );
         assert!(items.len() == 1);
-        assert!(items[0].url == "http://this.is.an.example");
-        assert!(items[0].title == "mytitle");
+        assert!(items[0].location == "http://this.is.an.example");
+        assert!(items[0].title.as_deref() == Some("mytitle"));
     }
 }
@@ -1,18 +1,16 @@
-//! Decode File and Title parts from simple playlist PLS files
+//! Decode File, Title and Length parts from simple playlist PLS files
 
-use std::collections::HashMap;
-
-pub struct PlaylistItem {
-    pub title: String,
-    pub url: String,
-}
+use super::{split_display_title, PlaylistItem};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 
 pub fn decode(content: &str) -> Vec<PlaylistItem> {
     let lines = content.lines();
     let mut list = vec![];
     let mut found_pls = false;
-    let mut map_urls = HashMap::new();
+    let mut map_urls = BTreeMap::new();
     let mut map_title = HashMap::new();
+    let mut map_length = HashMap::new();
     let mut default_title = "";
     for line in lines {
         if line.starts_with('#') {
@@ -43,17 +41,42 @@ pub fn decode(content: &str) -> Vec<PlaylistItem> {
                         default_title = title;
                     }
                 }
+            } else if line.starts_with("Length") {
+                if let Some((key, value)) = line.split_once('=') {
+                    if let (Ok(id), Ok(secs)) = (key[6..].parse::<u32>(), value.trim().parse()) {
+                        map_length.insert(id, Duration::from_secs(secs));
+                    }
+                }
             }
         }
     }
 
     for (key, value) in map_urls {
         let title = map_title.get(&key).unwrap_or(&default_title);
+        let (artist, title) = split_display_title(title);
         list.push(PlaylistItem {
-            title: String::from(*title),
-            url: String::from(value),
+            location: String::from(value.trim()),
+            title,
+            artist,
+            duration: map_length.get(&key).copied(),
         });
     }
 
     list
 }
+
+/// PLS version 2, where the title holds "Artist - Title" as there is no artist key
+pub fn encode(items: &[PlaylistItem]) -> String {
+    let mut content = String::from("[playlist]\n");
+    for (i, item) in items.iter().enumerate() {
+        let id = i + 1;
+        content.push_str(&format!("File{}={}\n", id, item.location));
+        let title = item.display_title();
+        if !title.is_empty() {
+            content.push_str(&format!("Title{}={}\n", id, title));
+        }
+        content.push_str(&format!("Length{}={}\n", id, item.duration_secs()));
+    }
+    content.push_str(&format!("NumberOfEntries={}\nVersion=2\n", items.len()));
+    content
+}
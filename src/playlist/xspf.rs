@@ -1,21 +1,14 @@
+use super::{escape, PlaylistItem};
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use std::error::Error;
-
-#[derive(Clone)]
-pub struct PlaylistItem {
-    pub title: String,
-    pub url: String,
-    pub identifier: String,
-}
+use std::time::Duration;
 
 pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
     let mut list = vec![];
-    let mut item = PlaylistItem {
-        title: String::from(""),
-        url: String::from(""),
-        identifier: String::from(""),
-    };
+    let mut item = PlaylistItem::default();
+    // used as the location when a track has none
+    let mut identifier = String::new();
 
     let mut reader = Reader::from_str(content);
     reader.trim_text(true);
@@ -25,52 +18,43 @@ pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
         match reader.read_event(&mut buf) {
             Ok(Event::Empty(ref e)) => {
                 xml_stack.push(reader.decode(e.name())?.to_lowercase());
-
-                let path = xml_stack.join("/");
-                for a in e.attributes() {
-                    let a = a?;
-                    let key = reader.decode(a.key)?.to_lowercase();
-                    let value = reader.decode(&a.value)?;
-                    if path == "asx/entry/ref" && key == "href" {
-                        item.url = value.to_string();
-                    }
-                }
-
                 xml_stack.pop();
             }
             Ok(Event::Start(ref e)) => {
                 xml_stack.push(reader.decode(e.name())?.to_lowercase());
-
-                let path = xml_stack.join("/");
-                for a in e.attributes() {
-                    let a = a?;
-                    let key = reader.decode(a.key)?.to_lowercase();
-                    let value = reader.decode(&a.value)?;
-                    if path == "asx/entry/ref" && key == "href" {
-                        item.url = value.to_string();
-                    }
-                }
             }
             Ok(Event::End(_)) => {
                 let path = xml_stack.join("/");
                 if path == "playlist/tracklist/track" {
-                    list.push(item.clone());
-                    item.title = String::from("");
-                    item.url = String::from("");
-                    item.identifier = String::from("");
+                    if item.location.is_empty() {
+                        item.location = std::mem::take(&mut identifier);
+                    }
+                    list.push(std::mem::take(&mut item));
+                    identifier.clear();
                 }
                 xml_stack.pop();
             }
             Ok(Event::Text(e)) => {
                 let path = xml_stack.join("/");
                 if path == "playlist/tracklist/track/title" {
-                    item.title = e.unescape_and_decode(&reader)?.clone();
+                    item.title = Some(e.unescape_and_decode(&reader)?);
+                }
+                if path == "playlist/tracklist/track/creator" {
+                    item.artist = Some(e.unescape_and_decode(&reader)?);
+                }
+                if path == "playlist/tracklist/track/duration" {
+                    item.duration = e
+                        .unescape_and_decode(&reader)?
+                        .trim()
+                        .parse()
+                        .ok()
+                        .map(Duration::from_millis);
                 }
                 if path == "playlist/tracklist/track/location" {
-                    item.url = e.unescape_and_decode(&reader)?.clone();
+                    item.location = location_from_uri(&e.unescape_and_decode(&reader)?);
                 }
                 if path == "playlist/tracklist/track/identifier" {
-                    item.identifier = e.unescape_and_decode(&reader)?.clone();
+                    identifier = e.unescape_and_decode(&reader)?;
                 }
             }
             Ok(Event::Eof) => break,
@@ -85,3 +69,62 @@ pub fn decode(content: &str) -> Result<Vec<PlaylistItem>, Box<dyn Error>> {
 
     Ok(list)
 }
+
+// Local locations are percent-encoded URIs, the path is what the player needs.
+fn location_from_uri(uri: &str) -> String {
+    if uri.contains("://") && !uri.starts_with("file://") {
+        return uri.to_string();
+    }
+    let path = uri
+        .strip_prefix("file://localhost")
+        .or_else(|| uri.strip_prefix("file://"))
+        .unwrap_or(uri);
+    urlencoding::decode(path).map_or_else(|_| path.to_string(), |path| path.into_owned())
+}
+
+pub fn encode(items: &[PlaylistItem]) -> String {
+    let mut content = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n  <trackList>\n",
+    );
+    for item in items {
+        content.push_str("    <track>\n");
+        content.push_str(&format!(
+            "      <location>{}</location>\n",
+            escape(&location_uri(&item.location))
+        ));
+        if let Some(title) = &item.title {
+            content.push_str(&format!("      <title>{}</title>\n", escape(title)));
+        }
+        if let Some(artist) = &item.artist {
+            content.push_str(&format!("      <creator>{}</creator>\n", escape(artist)));
+        }
+        if let Some(duration) = item.duration {
+            content.push_str(&format!(
+                "      <duration>{}</duration>\n",
+                duration.as_millis()
+            ));
+        }
+        content.push_str("    </track>\n");
+    }
+    content.push_str("  </trackList>\n</playlist>\n");
+    content
+}
+
+// Locations are URIs in XSPF, so local paths get percent-encoded, absolute
+// ones with a `file://` scheme.
+fn location_uri(location: &str) -> String {
+    if location.contains("://") {
+        return location.to_string();
+    }
+    let path = location
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/");
+    if location.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        path
+    }
+}
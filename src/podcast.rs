@@ -0,0 +1,492 @@
+//! Podcast feeds: reading the episodes out of RSS and Atom feeds, and downloading them.
+//!
+//! The subscriptions, episodes, downloads and playback positions are kept in the library
+//! database, see [`DataBase`](crate::sqlite::DataBase).
+use anyhow::{bail, Result};
+use quick_xml::events::attributes::Attributes;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use ureq::{Agent, AgentBuilder};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    pub episodes: Vec<FeedEpisode>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeedEpisode {
+    /// Tells the episode apart in the feed, the url of the audio when the feed has no id
+    pub guid: String,
+    pub title: String,
+    /// The audio, from the enclosure of the item
+    pub url: String,
+    /// Unix seconds
+    pub published: Option<u64>,
+    /// In seconds
+    pub duration: Option<u64>,
+}
+
+/// Downloads and reads the feed.
+pub fn fetch(url: &str) -> Result<Feed> {
+    let response = agent().get(url).call()?;
+    if response.status() != 200 {
+        bail!("{} answered {}", url, response.status());
+    }
+    parse_feed(&response.into_string()?)
+}
+
+/// Downloads the audio of an episode into `dir`, named after the episode and its id, and
+/// returns the path of the file. It is written under another name first, so that a download that breaks
+/// off doesn't leave a file that looks whole.
+pub fn download(url: &str, dir: &Path, title: &str, id: u64) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let name = download_name(url, title, id);
+    let path = dir.join(&name);
+    let part = dir.join(format!("{}.part", name));
+
+    let response = AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .user_agent(concat!("termusic/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .get(url)
+        .call()?;
+    if response.status() != 200 {
+        bail!("{} answered {}", url, response.status());
+    }
+    let mut file = File::create(&part)?;
+    if let Err(e) = io::copy(&mut response.into_reader(), &mut file) {
+        fs::remove_file(&part).ok();
+        return Err(e.into());
+    }
+    fs::rename(&part, &path)?;
+    Ok(path)
+}
+
+// Episodes can have the same title, the id of the episode keeps their files apart.
+fn download_name(url: &str, title: &str, id: u64) -> String {
+    // the extension of the url, when it looks like one
+    let extension = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension.to_lowercase())
+        .filter(|extension| {
+            (1..=4).contains(&extension.len())
+                && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .unwrap_or_else(|| "mp3".to_string());
+    format!("{} ({}).{}", file_name(title), id, extension)
+}
+
+/// The name of a podcast or episode, without the characters file systems don't take.
+pub fn file_name(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        "episode".to_string()
+    } else {
+        name.chars().take(120).collect()
+    }
+}
+
+fn agent() -> Agent {
+    AgentBuilder::new()
+        .timeout(Duration::from_secs(20))
+        .user_agent(concat!("termusic/", env!("CARGO_PKG_VERSION")))
+        .build()
+}
+
+/// Reads an RSS 2.0 or Atom feed. Items without audio are left out.
+pub fn parse_feed(content: &str) -> Result<Feed> {
+    let mut feed = Feed::default();
+    let mut episode = FeedEpisode::default();
+
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(true);
+    let mut xml_stack: Vec<String> = vec![];
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Empty(ref e)) => {
+                xml_stack.push(reader.decode(e.name())?.to_lowercase());
+                read_attributes(&xml_stack.join("/"), e.attributes(), &reader, &mut episode)?;
+                xml_stack.pop();
+            }
+            Ok(Event::Start(ref e)) => {
+                xml_stack.push(reader.decode(e.name())?.to_lowercase());
+                read_attributes(&xml_stack.join("/"), e.attributes(), &reader, &mut episode)?;
+            }
+            Ok(Event::End(_)) => {
+                let path = xml_stack.join("/");
+                if path == "rss/channel/item" || path == "feed/entry" {
+                    let mut episode = std::mem::take(&mut episode);
+                    if !episode.url.is_empty() {
+                        if episode.guid.is_empty() {
+                            episode.guid = episode.url.clone();
+                        }
+                        feed.episodes.push(episode);
+                    }
+                }
+                xml_stack.pop();
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape_and_decode(&reader)?;
+                read_text(&xml_stack.join("/"), text, &mut feed, &mut episode);
+            }
+            Ok(Event::CData(e)) => {
+                let text = reader.decode(&e)?.to_string();
+                read_text(&xml_stack.join("/"), text, &mut feed, &mut episode);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => bail!("Error at position {}: {:?}", reader.buffer_position(), e),
+            _ => (), // There are several other `Event`s we do not consider here
+        }
+        buf.clear();
+    }
+
+    if feed.title.is_empty() && feed.episodes.is_empty() {
+        bail!("not an RSS or Atom feed");
+    }
+    Ok(feed)
+}
+
+fn read_text(path: &str, text: String, feed: &mut Feed, episode: &mut FeedEpisode) {
+    match path {
+        "rss/channel/title" | "feed/title" => feed.title = text,
+        "rss/channel/description" | "feed/subtitle" => feed.description = text,
+        "rss/channel/item/title" | "feed/entry/title" => episode.title = text,
+        "rss/channel/item/guid" | "feed/entry/id" => episode.guid = text,
+        "rss/channel/item/pubdate" => episode.published = parse_rfc2822(&text),
+        // the date it came out wins, the last update is all some feeds have
+        "feed/entry/published" => episode.published = parse_rfc3339(&text),
+        "feed/entry/updated" if episode.published.is_none() => {
+            episode.published = parse_rfc3339(&text);
+        }
+        "rss/channel/item/itunes:duration" => episode.duration = parse_duration(&text),
+        _ => {}
+    }
+}
+
+fn read_attributes(
+    path: &str,
+    attributes: Attributes,
+    reader: &Reader<&[u8]>,
+    episode: &mut FeedEpisode,
+) -> Result<()> {
+    let is_enclosure = path == "rss/channel/item/enclosure";
+    let is_link = path == "feed/entry/link";
+    if !is_enclosure && !is_link {
+        return Ok(());
+    }
+    let mut url = String::new();
+    let mut rel = String::new();
+    let mut media_type = String::new();
+    for a in attributes {
+        let a = a?;
+        let key = reader.decode(a.key)?.to_lowercase();
+        let value = a.unescape_and_decode_value(reader)?;
+        match key.as_str() {
+            "url" if is_enclosure => url = value,
+            "href" if is_link => url = value,
+            "rel" => rel = value,
+            "type" => media_type = value,
+            _ => {}
+        }
+    }
+    // an entry links its page as well, only the enclosure is the audio
+    let is_audio = is_enclosure || rel == "enclosure";
+    // the first audio enclosure wins, some feeds add a video or a transcript after it
+    let was_audio = !episode.url.is_empty();
+    if is_audio && !was_audio && (media_type.is_empty() || !media_type.starts_with("video")) {
+        episode.url = url;
+    }
+    Ok(())
+}
+
+/// Like `1:02:03`, `62:03` or `3723` seconds, `None` when it doesn't fit.
+fn parse_duration(text: &str) -> Option<u64> {
+    text.trim()
+        .split(':')
+        .try_fold(0_u64, |total, part| {
+            // some feeds have fractions of a second
+            let part = part.split('.').next()?;
+            total
+                .checked_mul(60)?
+                .checked_add(part.trim().parse::<u64>().ok()?)
+        })
+        .filter(|duration| *duration > 0)
+}
+
+/// Like `Mon, 02 Jan 2006 15:04:05 -0700`, the date format of RSS.
+fn parse_rfc2822(text: &str) -> Option<u64> {
+    // the day of the week is left out by some feeds
+    let text = text.split_once(',').map_or(text, |(_, date)| date);
+    let mut parts = text.split_whitespace();
+    let day: i64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?.get(..3)?.to_lowercase();
+    let month = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ]
+    .iter()
+    .position(|name| *name == month)? as i64
+        + 1;
+    let mut year: i64 = parts.next()?.parse().ok()?;
+    if year < 100 {
+        year += if year < 50 { 2000 } else { 1900 };
+    }
+    let mut time = parts.next()?.split(':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let minute: i64 = time.next()?.parse().ok()?;
+    let second: i64 = time.next().map_or(Some(0), |second| second.parse().ok())?;
+    // 60 for a leap second
+    if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..=60).contains(&second) {
+        return None;
+    }
+    let offset = match parts.next() {
+        Some(zone) if zone.starts_with(['+', '-']) && zone.len() == 5 => {
+            let value: i64 = zone[1..].parse().ok()?;
+            let offset = (value / 100 * 60 + value % 100) * 60;
+            if zone.starts_with('-') {
+                -offset
+            } else {
+                offset
+            }
+        }
+        Some("EDT") => -4 * 3600,
+        Some("EST" | "CDT") => -5 * 3600,
+        Some("CST" | "MDT") => -6 * 3600,
+        Some("MST" | "PDT") => -7 * 3600,
+        Some("PST") => -8 * 3600,
+        _ => 0,
+    };
+    unix_time(
+        year,
+        month,
+        day,
+        hour * 3600 + minute * 60 + second - offset,
+    )
+}
+
+/// Like `2006-01-02T15:04:05Z` or `2006-01-02T15:04:05.123+07:00`, the date format of Atom.
+fn parse_rfc3339(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let year: i64 = text.get(0..4)?.parse().ok()?;
+    let month: i64 = text.get(5..7)?.parse().ok()?;
+    let day: i64 = text.get(8..10)?.parse().ok()?;
+    let hour: i64 = text.get(11..13)?.parse().ok()?;
+    let minute: i64 = text.get(14..16)?.parse().ok()?;
+    let second: i64 = text.get(17..19)?.parse().ok()?;
+    let zone = text[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match zone.get(..1) {
+        Some(sign @ ("+" | "-")) => {
+            let hours: i64 = zone.get(1..3)?.parse().ok()?;
+            let minutes: i64 = zone.get(4..6)?.parse().ok()?;
+            let offset = (hours * 60 + minutes) * 60;
+            if sign == "-" {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => 0,
+    };
+    unix_time(
+        year,
+        month,
+        day,
+        hour * 3600 + minute * 60 + second - offset,
+    )
+}
+
+// Seconds since 1970 of the day, plus the seconds into it. `None` for years that don't fit.
+fn unix_time(year: i64, month: i64, day: i64, seconds: i64) -> Option<u64> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // days from the civil calendar, counting years from March so that the leap day is last
+    let year = if month <= 2 {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era
+        .checked_mul(146_097)?
+        .checked_add(day_of_era - 719_468)?;
+    u64::try_from(days.checked_mul(86400)?.checked_add(seconds)?).ok()
+}
+
+/// Unix seconds as the day, like `2006-01-02`.
+pub fn format_date(time: u64) -> String {
+    // the civil calendar from the days, the other way around than above
+    let days = i64::try_from(time / 86400).unwrap_or_default() + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = (month_index + 2) % 12 + 1;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+  <channel>
+    <title>Test &amp; Talk</title>
+    <description><![CDATA[A show <b>about</b> tests]]></description>
+    <item>
+      <title>Second</title>
+      <guid isPermaLink="false">ep-2</guid>
+      <pubDate>Tue, 10 Jun 2003 04:00:00 +0200</pubDate>
+      <itunes:duration>1:02:03</itunes:duration>
+      <enclosure url="https://example.com/ep2.mp3?source=rss" length="1" type="audio/mpeg"/>
+    </item>
+    <item>
+      <title>First</title>
+      <pubDate>09 Jun 2003 04:00 GMT</pubDate>
+      <itunes:duration>125</itunes:duration>
+      <enclosure url="https://example.com/ep1.m4a" length="1" type="audio/x-m4a"></enclosure>
+    </item>
+    <item>
+      <title>Just text</title>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom Cast</title>
+  <subtitle>Feeds of the other kind</subtitle>
+  <entry>
+    <title>Pilot</title>
+    <id>urn:uuid:1225c695</id>
+    <updated>2003-12-13T18:30:02.25+01:00</updated>
+    <link href="https://example.com/pilot"/>
+    <link rel="enclosure" type="audio/ogg" href="https://example.com/pilot.ogg"/>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn rss_feed() {
+        let feed = parse_feed(RSS).unwrap();
+        assert_eq!(feed.title, "Test & Talk");
+        assert_eq!(feed.description, "A show <b>about</b> tests");
+        assert_eq!(
+            feed.episodes,
+            vec![
+                FeedEpisode {
+                    guid: "ep-2".to_string(),
+                    title: "Second".to_string(),
+                    url: "https://example.com/ep2.mp3?source=rss".to_string(),
+                    published: Some(1_055_210_400),
+                    duration: Some(3723),
+                },
+                FeedEpisode {
+                    guid: "https://example.com/ep1.m4a".to_string(),
+                    title: "First".to_string(),
+                    url: "https://example.com/ep1.m4a".to_string(),
+                    published: Some(1_055_131_200),
+                    duration: Some(125),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn atom_feed() {
+        let feed = parse_feed(ATOM).unwrap();
+        assert_eq!(feed.title, "Atom Cast");
+        assert_eq!(feed.description, "Feeds of the other kind");
+        assert_eq!(feed.episodes.len(), 1);
+        assert_eq!(feed.episodes[0].guid, "urn:uuid:1225c695");
+        assert_eq!(feed.episodes[0].url, "https://example.com/pilot.ogg");
+        assert_eq!(feed.episodes[0].published, Some(1_071_336_602));
+
+        assert!(parse_feed("<html><body>not a feed</body></html>").is_err());
+    }
+
+    #[test]
+    fn dates_and_durations() {
+        assert_eq!(parse_rfc2822("Thu, 01 Jan 1970 00:00:00 +0000"), Some(0));
+        assert_eq!(
+            parse_rfc2822("Sat, 29 Feb 2020 23:59:59 -0100"),
+            Some(1_583_024_399)
+        );
+        assert_eq!(parse_rfc3339("2020-02-29T23:59:59Z"), Some(1_583_020_799));
+        assert_eq!(parse_rfc2822("yesterday"), None);
+        assert_eq!(format_date(1_583_020_799), "2020-02-29");
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(parse_duration("62:03"), Some(3723));
+        assert_eq!(parse_duration("00:10.5"), Some(10));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(file_name("a/b: c?"), "a_b_ c_");
+    }
+
+    #[test]
+    fn overflowing_dates_and_durations() {
+        assert_eq!(parse_duration(&format!("{}:00", u64::MAX)), None);
+        assert_eq!(parse_duration(&format!("1:{}", u64::MAX)), None);
+        assert_eq!(parse_duration(&u64::MAX.to_string()), Some(u64::MAX));
+
+        for year in [i64::MAX, i64::MIN] {
+            assert_eq!(unix_time(year, 1, 1, 0), None);
+            assert_eq!(unix_time(year, 6, 1, 0), None);
+        }
+        assert_eq!(unix_time(1971, 1, 1, i64::MAX), None);
+        assert_eq!(
+            parse_rfc2822(&format!("01 Jan {} 00:00:00 GMT", i64::MAX)),
+            None
+        );
+        assert_eq!(
+            parse_rfc2822(&format!("01 Jan 2000 {}:00:00 GMT", i64::MAX)),
+            None
+        );
+        assert_eq!(parse_rfc2822("01 Jan 2000 24:00:00 GMT"), None);
+    }
+
+    #[test]
+    fn download_names() {
+        assert_eq!(
+            download_name("https://example.com/ep2.MP3?source=rss", "Second", 7),
+            "Second (7).mp3"
+        );
+        assert_eq!(
+            download_name("https://example.com/audio", "Q&A: part 1", 8),
+            "Q&A_ part 1 (8).mp3"
+        );
+        // episodes with the same title, or that only differ after the first 120 characters
+        let long = "x".repeat(130);
+        assert_ne!(
+            download_name("https://example.com/a.m4a", &long, 1),
+            download_name("https://example.com/a.m4a", &format!("{}y", long), 2)
+        );
+        assert_ne!(
+            download_name("https://example.com/a.m4a", "Bonus", 1),
+            download_name("https://example.com/b.m4a", "Bonus", 2)
+        );
+    }
+}
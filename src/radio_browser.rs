@@ -0,0 +1,285 @@
+//! Internet radio stations from a radio-browser compatible api, like the one of
+//! radio-browser.info, and the stations kept as favourites.
+//!
+//! A search is typed as the words of the name, with `tag:`, `country:` and
+//! `codec:` in front of the other criteria, like `jazz tag:smooth country:FR`.
+//! Two letter countries are taken as country codes.
+use crate::config::get_app_config_path;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use ureq::{Agent, AgentBuilder};
+
+const FAVOURITES_FILE: &str = "radio_favourites.toml";
+pub const PAGE_SIZE: u32 = 50;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Station {
+    pub name: String,
+    pub url: String,
+    /// Comma separated, like `jazz,smooth jazz`
+    pub tags: String,
+    pub country: String,
+    pub codec: String,
+    /// In kbit/s, 0 when unknown
+    pub bitrate: u32,
+    /// The id of the station in the api, to tell favourites apart
+    pub uuid: String,
+}
+
+// A station as the api returns it, with more fields than are kept.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ApiStation {
+    stationuuid: String,
+    name: String,
+    url: String,
+    url_resolved: String,
+    tags: String,
+    country: String,
+    codec: String,
+    bitrate: u32,
+}
+
+impl From<ApiStation> for Station {
+    // The resolved url has the playlist of the station looked up already.
+    fn from(station: ApiStation) -> Self {
+        let url = if station.url_resolved.is_empty() {
+            station.url
+        } else {
+            station.url_resolved
+        };
+        Self {
+            name: station.name.trim().to_string(),
+            url,
+            tags: station.tags,
+            country: station.country,
+            codec: station.codec,
+            bitrate: station.bitrate,
+            uuid: station.stationuuid,
+        }
+    }
+}
+
+impl Station {
+    /// Like `MP3 128k`, or just the codec when the bitrate is unknown.
+    pub fn format(&self) -> String {
+        if self.bitrate == 0 {
+            return self.codec.clone();
+        }
+        format!("{} {}k", self.codec, self.bitrate)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub name: String,
+    pub tag: String,
+    pub country: String,
+    pub codec: String,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut name = vec![];
+        for word in input.split_whitespace() {
+            let (key, value) = word.split_once(':').unwrap_or_default();
+            let field = match key {
+                "tag" => &mut query.tag,
+                "country" => &mut query.country,
+                "codec" => &mut query.codec,
+                _ => {
+                    name.push(word);
+                    continue;
+                }
+            };
+            // a value can't have spaces, so `_` stands for them, as in `country:united_kingdom`
+            *field = value.replace('_', " ");
+        }
+        query.name = name.join(" ");
+        query
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    // The parameters of the search endpoint, the most voted stations first.
+    fn params(&self, page: u32) -> Vec<(&'static str, String)> {
+        let mut params = vec![];
+        if !self.name.is_empty() {
+            params.push(("name", self.name.clone()));
+        }
+        if !self.tag.is_empty() {
+            params.push(("tag", self.tag.clone()));
+        }
+        if self.country.len() == 2 {
+            params.push(("countrycode", self.country.to_uppercase()));
+        } else if !self.country.is_empty() {
+            params.push(("country", self.country.clone()));
+        }
+        if !self.codec.is_empty() {
+            params.push(("codec", self.codec.clone()));
+        }
+        params.push(("order", "votes".to_string()));
+        params.push(("reverse", "true".to_string()));
+        params.push(("hidebroken", "true".to_string()));
+        params.push(("limit", PAGE_SIZE.to_string()));
+        params.push(("offset", (page * PAGE_SIZE).to_string()));
+        params
+    }
+}
+
+/// One page of the stations matching the query, counted from 0.
+pub fn search(base_url: &str, query: &Query, page: u32) -> Result<Vec<Station>> {
+    let url = format!("{}/json/stations/search", base_url.trim_end_matches('/'));
+    let mut request = agent().get(&url);
+    for (key, value) in query.params(page) {
+        request = request.query(key, &value);
+    }
+    let response = request.call()?;
+    if response.status() != 200 {
+        bail!("{} answered {}", base_url, response.status());
+    }
+    parse_stations(&response.into_string()?)
+}
+
+fn agent() -> Agent {
+    // the api asks for a user agent that tells the app
+    AgentBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .user_agent(concat!("termusic/", env!("CARGO_PKG_VERSION")))
+        .build()
+}
+
+fn parse_stations(data: &str) -> Result<Vec<Station>> {
+    let stations: Vec<ApiStation> = serde_json::from_str(data)?;
+    Ok(stations
+        .into_iter()
+        .map(Station::from)
+        .filter(|station| !station.url.is_empty())
+        .collect())
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct FavouritesFile {
+    #[serde(default)]
+    stations: Vec<Station>,
+}
+
+/// The favourite stations, in the order they were added.
+pub fn load_favourites() -> Result<Vec<Station>> {
+    let path = favourites_path()?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let file: FavouritesFile = toml::from_str(&fs::read_to_string(path)?)?;
+    Ok(file.stations)
+}
+
+pub fn save_favourites(stations: &[Station]) -> Result<()> {
+    let file = FavouritesFile {
+        stations: stations.to_vec(),
+    };
+    fs::write(favourites_path()?, toml::to_string(&file)?)?;
+    Ok(())
+}
+
+fn favourites_path() -> Result<PathBuf> {
+    let mut path = get_app_config_path()?;
+    path.push(FAVOURITES_FILE);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const RESPONSE: &str = r#"[
+        {"changeuuid":"1","stationuuid":"96062a7b","name":" Jazz Radio ",
+         "url":"http://jazz.example.com/listen.pls","url_resolved":"http://jazz.example.com/stream.mp3",
+         "homepage":"","favicon":"","tags":"jazz,smooth jazz","country":"France",
+         "countrycode":"FR","codec":"MP3","bitrate":128,"votes":1200,"lastcheckok":1},
+        {"stationuuid":"0f7a","name":"No Resolve","url":"http://other.example.com/aac",
+         "url_resolved":"","tags":"","country":"","codec":"AAC+","bitrate":0}
+    ]"#;
+
+    #[test]
+    fn query_parsing() {
+        let query = Query::parse("  smooth  jazz tag:chill country:united_kingdom codec:aac ");
+        assert_eq!(
+            query,
+            Query {
+                name: "smooth jazz".to_string(),
+                tag: "chill".to_string(),
+                country: "united kingdom".to_string(),
+                codec: "aac".to_string(),
+            }
+        );
+        assert!(Query::parse(" ").is_empty());
+
+        let params = Query::parse("country:fr").params(2);
+        assert!(params.contains(&("countrycode", "FR".to_string())));
+        assert!(params.contains(&("offset", "100".to_string())));
+        assert!(!params.iter().any(|(key, _)| *key == "name"));
+    }
+
+    #[test]
+    fn stations_from_api() {
+        let stations = parse_stations(RESPONSE).unwrap();
+        assert_eq!(
+            stations[0],
+            Station {
+                name: "Jazz Radio".to_string(),
+                url: "http://jazz.example.com/stream.mp3".to_string(),
+                tags: "jazz,smooth jazz".to_string(),
+                country: "France".to_string(),
+                codec: "MP3".to_string(),
+                bitrate: 128,
+                uuid: "96062a7b".to_string(),
+            }
+        );
+        assert_eq!(stations[0].format(), "MP3 128k");
+        assert_eq!(stations[1].url, "http://other.example.com/aac");
+        assert_eq!(stations[1].format(), "AAC+");
+
+        // favourites keep all fields
+        let file = FavouritesFile { stations };
+        let read: FavouritesFile = toml::from_str(&toml::to_string(&file).unwrap()).unwrap();
+        assert_eq!(read.stations, file.stations);
+    }
+
+    #[test]
+    fn search_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut request = vec![0; 4096];
+            let len = socket.read(&mut request).unwrap();
+            write!(
+                socket,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                RESPONSE.len(),
+                RESPONSE
+            )
+            .unwrap();
+            String::from_utf8_lossy(&request[..len]).to_string()
+        });
+
+        let stations = search(&base_url, &Query::parse("jazz radio tag:smooth"), 0).unwrap();
+        assert_eq!(stations.len(), 2);
+        let request = server.join().unwrap();
+        let request_line = request.lines().next().unwrap();
+        assert!(request_line.starts_with("GET /json/stations/search?name=jazz"));
+        assert!(request_line.contains("tag=smooth"));
+        assert!(request_line.contains("limit=50&offset=0"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Loudness measurement after ITU-R BS.1770 / EBU R128.
+//!
+//! Samples are K-weighted, cut into 400ms blocks overlapping by 75%, and the blocks are gated
+//! at -70 LUFS and then at 10 LU below the loudness of what's left. The true peak is taken from
+//! a 4x oversampled signal.
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+const ABSOLUTE_GATE: f64 = -70.0;
+const RELATIVE_GATE: f64 = -10.0;
+// a 400ms block is made of 4 steps of 100ms
+const STEPS_PER_BLOCK: usize = 4;
+const OVERSAMPLING: usize = 4;
+const TAPS_PER_PHASE: usize = 12;
+
+#[derive(Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 3],
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    const fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b,
+            a,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    // transposed direct form II
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z1;
+        self.z1 = self.b[1] * x - self.a[1] * y + self.z2;
+        self.z2 = self.b[2] * x - self.a[2] * y;
+        y
+    }
+}
+
+/// The two stages of the K-weighting filter, computed for any sample rate.
+fn k_weighting(sample_rate: u32) -> (Biquad, Biquad) {
+    let rate = f64::from(sample_rate);
+
+    // high shelf, models the acoustic effect of the head
+    let f0 = 1_681.974_450_955_533;
+    let gain = 3.999_843_853_973_347;
+    let q = 0.707_175_236_955_419_6;
+    let k = (PI * f0 / rate).tan();
+    let vh = 10_f64.powf(gain / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        [1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    // high pass
+    let f0 = 38.135_470_876_024_44;
+    let q = 0.500_327_037_323_877_3;
+    let k = (PI * f0 / rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let highpass = Biquad::new(
+        [1.0, -2.0, 1.0],
+        [1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    (shelf, highpass)
+}
+
+/// Surround channels count a bit more and LFE is left out, assuming the usual
+/// L R C LFE Ls Rs order.
+fn channel_weight(channels: usize, index: usize) -> f64 {
+    match (channels, index) {
+        (6, 3) => 0.0,
+        (6, 4 | 5) | (5, 3 | 4) => 1.41,
+        _ => 1.0,
+    }
+}
+
+/// Windowed sinc used for the 4x oversampling of the true peak measurement.
+#[allow(clippy::cast_precision_loss)]
+fn interpolation_filter() -> Vec<f64> {
+    let len = OVERSAMPLING * TAPS_PER_PHASE;
+    let center = (len - 1) as f64 / 2.0;
+    (0..len)
+        .map(|i| {
+            let x = (i as f64 - center) / OVERSAMPLING as f64;
+            let sinc = if x == 0.0 {
+                1.0
+            } else {
+                (PI * x).sin() / (PI * x)
+            };
+            let window = 0.5 - 0.5 * (2.0 * PI * (i as f64 + 0.5) / len as f64).cos();
+            sinc * window
+        })
+        .collect()
+}
+
+pub struct LoudnessMeter {
+    channels: usize,
+    filters: Vec<(Biquad, Biquad)>,
+    weights: Vec<f64>,
+    interpolation: Vec<f64>,
+    history: Vec<VecDeque<f64>>,
+    channel: usize,
+    frames_per_step: usize,
+    step_frames: usize,
+    step_energy: Vec<f64>,
+    steps: VecDeque<f64>,
+    blocks: Vec<f64>,
+    peak: f64,
+}
+
+impl LoudnessMeter {
+    pub fn new(channels: u16, sample_rate: u32) -> Self {
+        let channels = usize::from(channels.max(1));
+        let filter = k_weighting(sample_rate);
+        Self {
+            channels,
+            filters: vec![filter; channels],
+            weights: (0..channels).map(|i| channel_weight(channels, i)).collect(),
+            interpolation: interpolation_filter(),
+            history: vec![VecDeque::from(vec![0.0; TAPS_PER_PHASE]); channels],
+            channel: 0,
+            frames_per_step: (sample_rate as usize / 10).max(1),
+            step_frames: 0,
+            step_energy: vec![0.0; channels],
+            steps: VecDeque::with_capacity(STEPS_PER_BLOCK),
+            blocks: Vec::new(),
+            peak: 0.0,
+        }
+    }
+
+    /// Feeds one interleaved sample, in the range -1.0..1.0.
+    pub fn push(&mut self, sample: f64) {
+        let channel = self.channel;
+
+        let (shelf, highpass) = &mut self.filters[channel];
+        let weighted = highpass.process(shelf.process(sample));
+        self.step_energy[channel] += weighted * weighted;
+
+        self.update_peak(channel, sample);
+
+        self.channel += 1;
+        if self.channel == self.channels {
+            self.channel = 0;
+            self.step_frames += 1;
+            if self.step_frames == self.frames_per_step {
+                self.finish_step();
+            }
+        }
+    }
+
+    fn update_peak(&mut self, channel: usize, sample: f64) {
+        let history = &mut self.history[channel];
+        history.pop_back();
+        history.push_front(sample);
+
+        let mut peak = sample.abs();
+        for phase in 0..OVERSAMPLING {
+            let value: f64 = history
+                .iter()
+                .enumerate()
+                .map(|(k, x)| self.interpolation[phase + OVERSAMPLING * k] * x)
+                .sum();
+            peak = peak.max(value.abs());
+        }
+        self.peak = self.peak.max(peak);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish_step(&mut self) {
+        let frames = self.step_frames as f64;
+        let energy = self
+            .step_energy
+            .iter()
+            .zip(&self.weights)
+            .map(|(e, w)| w * e / frames)
+            .sum();
+        self.step_energy.iter_mut().for_each(|e| *e = 0.0);
+        self.step_frames = 0;
+
+        if self.steps.len() == STEPS_PER_BLOCK {
+            self.steps.pop_front();
+        }
+        self.steps.push_back(energy);
+        if self.steps.len() == STEPS_PER_BLOCK {
+            #[allow(clippy::cast_precision_loss)]
+            let block = self.steps.iter().sum::<f64>() / STEPS_PER_BLOCK as f64;
+            self.blocks.push(block);
+        }
+    }
+
+    /// Mean square of every 400ms block measured so far. Blocks of several tracks can be
+    /// put together and given to `gated_loudness` to get the loudness of an album.
+    pub fn blocks(&self) -> &[f64] {
+        &self.blocks
+    }
+
+    /// Integrated loudness in LUFS, `None` when the input was too short or silent.
+    pub fn loudness(&self) -> Option<f64> {
+        gated_loudness(&self.blocks)
+    }
+
+    /// Linear true peak over all channels.
+    pub const fn true_peak(&self) -> f64 {
+        self.peak
+    }
+}
+
+fn energy_to_loudness(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn gated_loudness(blocks: &[f64]) -> Option<f64> {
+    let mean = |blocks: &[f64]| -> Option<f64> {
+        if blocks.is_empty() {
+            return None;
+        }
+        Some(blocks.iter().sum::<f64>() / blocks.len() as f64)
+    };
+
+    let absolute: Vec<f64> = blocks
+        .iter()
+        .copied()
+        .filter(|b| energy_to_loudness(*b) > ABSOLUTE_GATE)
+        .collect();
+    let relative_gate = energy_to_loudness(mean(&absolute)?) + RELATIVE_GATE;
+
+    let relative: Vec<f64> = absolute
+        .into_iter()
+        .filter(|b| energy_to_loudness(*b) > relative_gate)
+        .collect();
+    mean(&relative).map(energy_to_loudness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoudnessMeter;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn sine(meter: &mut LoudnessMeter, amplitude: f64, secs: usize) {
+        let rate = 48000;
+        for n in 0..rate * secs {
+            let value = amplitude * (2.0 * std::f64::consts::PI * 997.0 * n as f64 / 48000.0).sin();
+            meter.push(value);
+            meter.push(value);
+        }
+    }
+
+    #[test]
+    fn test_sine_loudness() {
+        // EBU Tech 3341: a stereo 1kHz sine at -23 dBFS reads -23 LUFS
+        let mut meter = LoudnessMeter::new(2, 48000);
+        sine(&mut meter, 10_f64.powf(-23.0 / 20.0), 20);
+        let loudness = meter.loudness().unwrap();
+        assert!((loudness + 23.0).abs() < 0.1, "loudness {}", loudness);
+        let peak = meter.true_peak();
+        assert!(
+            (peak - 10_f64.powf(-23.0 / 20.0)).abs() < 0.01,
+            "peak {}",
+            peak
+        );
+    }
+
+    #[test]
+    fn test_silence() {
+        let mut meter = LoudnessMeter::new(2, 44100);
+        for _ in 0..44100 * 2 * 2 {
+            meter.push(0.0);
+        }
+        assert!(meter.loudness().is_none());
+    }
+}
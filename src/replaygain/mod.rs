@@ -0,0 +1,146 @@
+/**
+ * MIT License
+ *
+ * termusic - Copyright (c) 2021 Larry Hao
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+mod ebur128;
+
+use crate::player::{Source, Symphonia};
+use crate::track::{ReplayGainInfo, Track};
+use crate::utils::filetype_supported;
+use anyhow::{anyhow, Result};
+use ebur128::{gated_loudness, LoudnessMeter};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// `ReplayGain` 2.0 reference level in LUFS.
+const REFERENCE_LOUDNESS: f64 = -18.0;
+
+struct Measurement {
+    track: Track,
+    loudness: Option<f64>,
+    blocks: Vec<f64>,
+    peak: f64,
+}
+
+/// Measures every supported file under `path` and writes the track and album gains and peaks
+/// into their tags. Tracks in the same directory with the same album tag are taken as one album.
+/// `progress` is called before each file with its index, the number of files and the file name.
+///
+/// Returns the number of files tagged and the files that failed, with the reason.
+pub fn scan<F>(path: &Path, mut progress: F) -> (usize, Vec<(String, String)>)
+where
+    F: FnMut(usize, usize, &str),
+{
+    let files = collect_files(path);
+    let total = files.len();
+
+    let mut failed = Vec::new();
+    let mut albums: BTreeMap<(PathBuf, String), Vec<Measurement>> = BTreeMap::new();
+    for (index, file) in files.iter().enumerate() {
+        let name = file
+            .file_name()
+            .unwrap_or(file.as_os_str())
+            .to_string_lossy();
+        progress(index + 1, total, &name);
+
+        match measure(file) {
+            Ok(measurement) => {
+                let directory = file.parent().map(Path::to_path_buf).unwrap_or_default();
+                let album = measurement.track.album().unwrap_or_default().to_string();
+                albums
+                    .entry((directory, album))
+                    .or_default()
+                    .push(measurement);
+            }
+            Err(e) => failed.push((file.to_string_lossy().to_string(), e.to_string())),
+        }
+    }
+
+    let mut tagged = 0;
+    for measurements in albums.values_mut() {
+        let blocks: Vec<f64> = measurements
+            .iter()
+            .flat_map(|m| m.blocks.iter().copied())
+            .collect();
+        let album_gain = gated_loudness(&blocks).map(|l| REFERENCE_LOUDNESS - l);
+        let album_peak = measurements.iter().map(|m| m.peak).fold(0.0, f64::max);
+
+        for measurement in measurements {
+            let info = ReplayGainInfo {
+                track_gain: measurement.loudness.map(|l| to_f32(REFERENCE_LOUDNESS - l)),
+                track_peak: Some(to_f32(measurement.peak)),
+                album_gain: album_gain.map(to_f32),
+                album_peak: Some(to_f32(album_peak)),
+            };
+            measurement.track.set_replaygain(info);
+
+            match measurement.track.save_replaygain() {
+                Ok(()) => tagged += 1,
+                Err(e) => failed.push((
+                    measurement.track.file().unwrap_or_default().to_string(),
+                    e.to_string(),
+                )),
+            }
+        }
+    }
+
+    (tagged, failed)
+}
+
+fn collect_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(path)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|e| e.file_type().is_file() && filetype_supported(&e.path().to_string_lossy()))
+        .map(walkdir::DirEntry::into_path)
+        .collect();
+    files.sort();
+    files
+}
+
+fn measure(path: &Path) -> Result<Measurement> {
+    let track = Track::read_from_path(path)?;
+    let decoder = Symphonia::new(File::open(path)?, false).map_err(|e| anyhow!("{}", e))?;
+
+    let mut meter = LoudnessMeter::new(decoder.channels(), decoder.sample_rate());
+    for sample in decoder {
+        meter.push(f64::from(sample) / 32768.0);
+    }
+
+    Ok(Measurement {
+        track,
+        loudness: meter.loudness(),
+        blocks: meter.blocks().to_vec(),
+        peak: meter.true_peak(),
+    })
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_f32(value: f64) -> f32 {
+    value as f32
+}
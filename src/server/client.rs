@@ -0,0 +1,96 @@
+use super::protocol::{Event, Request, Response};
+use super::socket_path;
+use anyhow::{anyhow, bail, Result};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::UnixStream;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+// how long a request waits for the answer of a server that is stuck
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connection to a running server.
+pub struct Client {
+    stream: UnixStream,
+    reader: BufReader<UnixStream>,
+    // answers to requests that timed out, they come before the one to the next request
+    late_answers: usize,
+}
+
+impl Client {
+    pub fn connect() -> Result<Self> {
+        Self::from_stream(UnixStream::connect(socket_path()?)?)
+    }
+
+    /// Uses an already connected socket.
+    pub fn from_stream(stream: UnixStream) -> Result<Self> {
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(Self {
+            stream,
+            reader,
+            late_answers: 0,
+        })
+    }
+
+    /// Sends a request and waits for the answer. An error answer is returned as `Err`.
+    pub fn request(&mut self, request: &Request) -> Result<Response> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        self.stream.write_all(line.as_bytes())?;
+
+        let mut answer = String::new();
+        loop {
+            answer.clear();
+            match self.reader.read_line(&mut answer) {
+                Ok(0) => bail!("the server closed the connection"),
+                Ok(_) if self.late_answers > 0 => self.late_answers -= 1,
+                Ok(_) => break,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    self.late_answers += 1;
+                    bail!(
+                        "the server didn't answer within {} seconds",
+                        REQUEST_TIMEOUT.as_secs()
+                    );
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        match serde_json::from_str(&answer)? {
+            Response::Error(e) => Err(anyhow!(e)),
+            response => Ok(response),
+        }
+    }
+
+    /// Turns the connection into a stream of events, read on a thread of its own. The
+    /// receiver hangs up when the server goes away.
+    pub fn subscribe(mut self) -> Result<Receiver<Event>> {
+        // events come whenever something happens
+        self.stream.set_read_timeout(None)?;
+        let mut line = serde_json::to_string(&Request::Subscribe)?;
+        line.push('\n');
+        self.stream.write_all(line.as_bytes())?;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for line in self.reader.lines() {
+                let event = match line {
+                    Ok(line) => serde_json::from_str(&line),
+                    Err(_) => break,
+                };
+                if let Ok(event) = event {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+            tx.send(Event::Message {
+                title: "Server".to_string(),
+                text: "Lost the connection to the server.".to_string(),
+            })
+            .ok();
+        });
+        Ok(rx)
+    }
+}
@@ -0,0 +1,971 @@
+//! ## Server
+//!
+//! Plays the music in the background, so that it goes on when the terminal is closed. The
+//! TUI and other clients control it through a Unix socket, see `protocol` for the messages.
+mod client;
+#[cfg(feature = "mpd")]
+mod mpd;
+mod protocol;
+
+pub use client::Client;
+pub use protocol::{AbLoop, Event, PlayerState, PlaylistEdit, Request, Response, SleepTimer};
+
+use crate::config::{get_app_config_path, Settings};
+use crate::cue;
+use crate::player::{GeneralPlayer, Loop, PlayerMsg, PlayerTrait, Status};
+use crate::sqlite::{DataBase, PlayEvent};
+use crate::track::Track;
+use crate::utils::is_stream;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command as Process, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// how often the position is checked, for the progress events and the gapless playback
+const TICK_INTERVAL: Duration = Duration::from_millis(500);
+// how long to wait for a server that was just started to take connections
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(3);
+// a track that ends this many seconds before its end, or less, counts as played
+const COMPLETE_MARGIN: i64 = 3;
+// how far a track with a bookmark plays on before its position is saved again
+const BOOKMARK_SAVE_INTERVAL: i64 = 15;
+// how long the music fades out before the sleep timer pauses it
+const SLEEP_FADE: Duration = Duration::from_secs(60);
+
+/// `$XDG_RUNTIME_DIR/termusic.sock`, or the config dir when there is no runtime dir.
+pub fn socket_path() -> Result<PathBuf> {
+    let mut path = match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => get_app_config_path()?,
+    };
+    path.push("termusic.sock");
+    Ok(path)
+}
+
+/// Connects to the server, starting one in the background first if none is running.
+pub fn connect_or_spawn() -> Result<Client> {
+    if let Ok(client) = Client::connect() {
+        return Ok(client);
+    }
+
+    // a process group of its own keeps it alive when the terminal goes away
+    Process::new(std::env::current_exe()?)
+        .arg("--server")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn()?;
+
+    let started = Instant::now();
+    loop {
+        match Client::connect() {
+            Ok(client) => return Ok(client),
+            Err(e) if started.elapsed() > STARTUP_TIMEOUT => {
+                bail!("cannot connect to the server: {}", e)
+            }
+            Err(_) => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+enum Command {
+    Request(Request, Sender<Response>),
+    Subscribe(Sender<Event>),
+    // the current track and the playlist, for the MPD queue
+    #[cfg(feature = "mpd")]
+    Tracks(Sender<(Option<Track>, Vec<Track>)>),
+}
+
+/// Runs the server until a client asks it to quit.
+pub fn run(config: &Settings) -> Result<()> {
+    let path = socket_path()?;
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            bail!("a server is already running on {}", path.display());
+        }
+        // left behind by a server that didn't shut down cleanly
+        fs::remove_file(&path)?;
+    }
+    let mut server = Server::new(config)?;
+    let listener = UnixListener::bind(&path)?;
+
+    let (command_tx, command_rx) = mpsc::channel();
+    #[cfg(feature = "mpd")]
+    if !config.mpd_address.is_empty() {
+        if let Err(e) = mpd::spawn(config, &command_tx) {
+            eprintln!(
+                "cannot listen for MPD clients on {}: {}",
+                config.mpd_address, e
+            );
+        }
+    }
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let command_tx = command_tx.clone();
+            thread::spawn(move || handle_connection(stream, &command_tx));
+        }
+    });
+
+    server.run(&command_rx);
+    server.save_bookmark();
+    server.player.playlist.save()?;
+    server.player.playlist.save_resume_point(server.position)?;
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+fn write_line<T: Serialize>(stream: &mut UnixStream, message: &T) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+fn handle_connection(stream: UnixStream, commands: &Sender<Command>) -> Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = match serde_json::from_str(&line) {
+            Ok(Request::Subscribe) => {
+                let (event_tx, event_rx) = mpsc::channel();
+                commands.send(Command::Subscribe(event_tx))?;
+                for event in event_rx {
+                    write_line(&mut writer, &event)?;
+                }
+                return Ok(());
+            }
+            // answered first, the process is gone right after
+            Ok(Request::Quit) => {
+                write_line(&mut writer, &Response::Ok)?;
+                commands.send(Command::Request(Request::Quit, mpsc::channel().0))?;
+                return Ok(());
+            }
+            Ok(request) => request,
+            Err(e) => {
+                write_line(
+                    &mut writer,
+                    &Response::Error(format!("invalid request: {}", e)),
+                )?;
+                continue;
+            }
+        };
+        let (response_tx, response_rx) = mpsc::channel();
+        commands.send(Command::Request(request, response_tx))?;
+        write_line(&mut writer, &response_rx.recv()?)?;
+    }
+    Ok(())
+}
+
+// What keeps the position of a track, so that it goes on from there next time.
+enum Bookmark {
+    Episode(u64),
+    // a track at least `remember_position_minutes` long
+    File(String),
+}
+
+// The sleep timer, with a point in time rather than the seconds left.
+enum Sleep {
+    At(Instant),
+    Tracks(u32),
+}
+
+struct Server {
+    player: GeneralPlayer,
+    db: DataBase,
+    // the file whose start is in the play history, until it is played or skipped
+    playing: Option<String>,
+    subscribers: Vec<Sender<Event>>,
+    position: i64,
+    duration: i64,
+    // what an internet radio said it plays, until the track changes
+    stream_title: Option<String>,
+    // where the position of the playing track is kept, with the position saved last
+    bookmark: Option<(Bookmark, i64)>,
+    // where the track was left off, to seek there once it plays
+    resume: Option<i64>,
+    sleep: Option<Sleep>,
+    ab_loop: Option<AbLoop>,
+    // the default device couldn't be opened either when the output device went away, which
+    // is told once rather than on every tick
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    output_device_failed: bool,
+    quit: bool,
+}
+
+impl Server {
+    fn new(config: &Settings) -> Result<Self> {
+        Ok(Self {
+            player: GeneralPlayer::new(config)?,
+            db: DataBase::new(config),
+            playing: None,
+            subscribers: Vec::new(),
+            position: 0,
+            duration: 0,
+            stream_title: None,
+            bookmark: None,
+            resume: None,
+            sleep: None,
+            ab_loop: None,
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            output_device_failed: false,
+            quit: false,
+        })
+    }
+
+    fn run(&mut self, commands: &Receiver<Command>) {
+        let mut last_tick: Option<Instant> = None;
+        while !self.quit {
+            if last_tick.is_none_or(|tick| tick.elapsed() >= TICK_INTERVAL) {
+                self.tick();
+                last_tick = Some(Instant::now());
+            }
+
+            match commands.recv_timeout(Duration::from_millis(20)) {
+                Ok(Command::Request(request, response_tx)) => {
+                    let response = self.handle_request(request);
+                    response_tx.send(response).ok();
+                }
+                Ok(Command::Subscribe(event_tx)) => {
+                    // a new client starts from the current state
+                    event_tx.send(self.track_event()).ok();
+                    event_tx.send(Event::State(self.state())).ok();
+                    self.subscribers.push(event_tx);
+                }
+                #[cfg(feature = "mpd")]
+                Ok(Command::Tracks(tracks_tx)) => {
+                    let playlist = &self.player.playlist;
+                    let tracks = playlist.tracks.iter().cloned().collect();
+                    tracks_tx
+                        .send((playlist.current_track.clone(), tracks))
+                        .ok();
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            while let Ok(msg) = self.player.message_rx.try_recv() {
+                self.handle_player_msg(msg);
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.player.is_stopped() {
+            let had_track = self.player.playlist.current_track.is_some();
+            self.player.start_play();
+            if had_track && self.player.playlist.current_track.is_none() {
+                self.track_changed();
+            }
+        }
+
+        #[cfg(not(any(feature = "mpv", feature = "gst")))]
+        {
+            self.player.get_progress().ok();
+            self.check_output_device();
+        }
+
+        if let Some(Sleep::At(at)) = self.sleep {
+            let left = at.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                self.fall_asleep();
+            } else {
+                self.sleep_fade(left);
+            }
+        }
+    }
+
+    // Goes on with the default device when the selected one went away.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    fn check_output_device(&mut self) {
+        if !self.player.output_device_lost() {
+            self.output_device_failed = false;
+            return;
+        }
+        if self.output_device_failed {
+            return;
+        }
+        let text = match self.player.set_output_device("") {
+            Ok(()) => "Output device not available, switched to the default one.".to_string(),
+            Err(e) => {
+                self.output_device_failed = true;
+                e.to_string()
+            }
+        };
+        self.broadcast(&Event::Message {
+            title: "Output device".to_string(),
+            text,
+        });
+    }
+
+    fn handle_player_msg(&mut self, msg: PlayerMsg) {
+        match msg {
+            PlayerMsg::Eos => {
+                self.finish_play();
+                if self.player.playlist.is_empty() {
+                    self.stop();
+                    return;
+                }
+                self.player.start_play();
+            }
+            PlayerMsg::AboutToFinish => {
+                if self.player.config.gapless || !self.player.crossfade().is_zero() {
+                    self.player.enqueue_next();
+                }
+            }
+            PlayerMsg::CurrentTrackUpdated => {
+                if (self.player.config.speed - 10).abs() >= 1 {
+                    self.player.set_speed(self.player.config.speed);
+                }
+                // with gapless playback there is no end of stream in between
+                self.finish_play();
+                self.start_play_history();
+                self.start_bookmark();
+                self.count_sleep_tracks();
+                self.start_ab_loop();
+                self.position = 0;
+                #[allow(clippy::cast_possible_wrap)]
+                if let Some(track) = &self.player.playlist.current_track {
+                    self.duration = track.duration().as_secs() as i64;
+                }
+                self.track_changed();
+            }
+            PlayerMsg::Progress(position, duration) => {
+                #[cfg(any(feature = "mpv", feature = "gst"))]
+                let Some((position, duration)) = self.keep_to_segment(position, duration) else {
+                    return;
+                };
+                self.position = position;
+                self.duration = duration;
+                // seeking only works once the length is known
+                if duration > 0 {
+                    if let Some(resume) = self.resume.take() {
+                        self.seek(resume - position);
+                    }
+                }
+                if self
+                    .bookmark
+                    .as_ref()
+                    .is_some_and(|(_, saved)| (position - saved).abs() >= BOOKMARK_SAVE_INTERVAL)
+                {
+                    self.save_bookmark();
+                }
+                if let Some(Sleep::Tracks(1)) = self.sleep {
+                    #[allow(clippy::cast_sign_loss)]
+                    let left = (duration - position).max(0) as u64;
+                    if duration > 0 {
+                        self.sleep_fade(Duration::from_secs(left));
+                    }
+                }
+                // the default backend loops by itself, more precisely
+                #[cfg(any(feature = "mpv", feature = "gst"))]
+                if let Some(AbLoop { a, b: Some(b) }) = self.ab_loop {
+                    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+                    if position as f64 >= b {
+                        self.seek((a - position as f64).floor() as i64);
+                    }
+                }
+                self.broadcast(&Event::Progress { position, duration });
+                #[cfg(any(not(feature = "gst"), feature = "mpv"))]
+                self.check_about_to_finish(position, duration);
+            }
+            PlayerMsg::StreamTitle(title) => {
+                self.stream_title = Some(title.clone());
+                self.broadcast(&Event::StreamTitle { title });
+            }
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            PlayerMsg::StreamOpened(id, decoder) => self.player.stream_opened(id, decoder),
+        }
+    }
+
+    // The other backends play the whole file for the track of a CUE sheet, so it is kept to its
+    // segment by seeking to the start and skipping at the end. The position and duration are
+    // turned into the segment's, none while it is not there yet.
+    #[cfg(any(feature = "mpv", feature = "gst"))]
+    #[allow(clippy::cast_possible_wrap)]
+    fn keep_to_segment(&mut self, position: i64, duration: i64) -> Option<(i64, i64)> {
+        let Some(segment) = self
+            .player
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::segment)
+        else {
+            return Some((position, duration));
+        };
+        let start = segment.start.as_secs() as i64;
+        let end = segment.end.map_or(duration, |end| end.as_secs() as i64);
+        if duration > 0 && position < start {
+            self.player.seek(start - position).ok();
+            return None;
+        }
+        if segment.end.is_some() && position >= end {
+            self.player.skip();
+            return None;
+        }
+        Some((position - start, end - start))
+    }
+
+    // About to finish signal is a simulation of gstreamer, and used for gapless and crossfade
+    #[cfg(any(not(feature = "gst"), feature = "mpv"))]
+    fn check_about_to_finish(&mut self, position: i64, duration: i64) {
+        // for unsupported file format, there is no progress
+        if duration == 0 {
+            return;
+        }
+        // the next track has to be queued before its crossfade starts
+        let crossfade = self.player.crossfade();
+        #[allow(clippy::cast_possible_wrap)]
+        let lead = crossfade.as_secs() as i64 + 2;
+        // a track that loops doesn't get to its end
+        if !self.player.playlist.is_empty()
+            && !self.player.has_next_track()
+            && self.ab_loop.is_none_or(|ab_loop| ab_loop.b.is_none())
+            && position * 2 >= duration
+            && duration - position < lead
+            && (self.player.config.gapless || !crossfade.is_zero())
+        {
+            self.player.message_tx.send(PlayerMsg::AboutToFinish).ok();
+        }
+    }
+
+    fn handle_request(&mut self, request: Request) -> Response {
+        match request {
+            Request::Play => {
+                if self.player.is_paused() {
+                    self.player.set_status(Status::Running);
+                    self.player.resume();
+                }
+            }
+            Request::Pause => {
+                if self.player.is_running() {
+                    self.player.set_status(Status::Paused);
+                    self.player.pause();
+                }
+            }
+            Request::TogglePause => match self.player.status() {
+                Status::Running => {
+                    self.player.set_status(Status::Paused);
+                    self.player.pause();
+                }
+                Status::Paused => {
+                    self.player.set_status(Status::Running);
+                    self.player.resume();
+                }
+                Status::Stopped => {}
+            },
+            Request::Next => self.player.skip(),
+            Request::Previous => self.previous(),
+            Request::Stop => self.player.stop(),
+            Request::Seek(offset) => self.seek(offset),
+            Request::SetVolume(volume) => {
+                self.player.set_volume(volume);
+                self.player.config.volume = self.player.volume();
+            }
+            Request::VolumeUp => {
+                self.player.volume_up();
+                self.player.config.volume = self.player.volume();
+            }
+            Request::VolumeDown => {
+                self.player.volume_down();
+                self.player.config.volume = self.player.volume();
+            }
+            Request::SetSpeed(speed) => {
+                self.player.set_speed(speed.clamp(1, 30));
+                self.player.config.speed = self.player.speed();
+            }
+            Request::SpeedUp => {
+                self.player.speed_up();
+                self.player.config.speed = self.player.speed();
+            }
+            Request::SpeedDown => {
+                self.player.speed_down();
+                self.player.config.speed = self.player.speed();
+            }
+            Request::ToggleGapless => {
+                self.player.config.gapless = !self.player.config.gapless;
+                self.player.toggle_gapless();
+            }
+            Request::SetLoopMode(mode) => self.set_loop_mode(mode),
+            Request::SetEqualizer(name) => self.player.set_equalizer(&name),
+            Request::SetOutputDevice(name) => {
+                if let Err(e) = self.set_output_device(&name) {
+                    return Response::Error(e.to_string());
+                }
+            }
+            Request::SetSleepTimer(timer) => {
+                if let Err(e) = self.set_sleep_timer(timer) {
+                    return Response::Error(e.to_string());
+                }
+            }
+            Request::MarkAbLoop => self.mark_ab_loop(),
+            Request::Queue(files) => {
+                let mut failed = Vec::new();
+                for file in files {
+                    match cue::read_tracks(Path::new(&file)) {
+                        Ok(tracks) => self.player.playlist.tracks.extend(tracks),
+                        Err(e) => failed.push(format!("{}: {}", file, e)),
+                    }
+                }
+                self.playlist_changed();
+                if !failed.is_empty() {
+                    return Response::Error(format!("cannot add {}", failed.join(", ")));
+                }
+            }
+            Request::SetPlaylist(files) => {
+                self.player.playlist.tracks = self.player.playlist.tracks_from_files(&files);
+                self.playlist_changed();
+            }
+            Request::EditPlaylist { base, edits } => {
+                self.player.playlist.apply_edits(&base, &edits);
+                self.playlist_changed();
+            }
+            Request::Status => return Response::State(self.state()),
+            Request::Subscribe => {
+                return Response::Error("subscribe has to be the first request".to_string());
+            }
+            Request::Quit => {
+                self.quit = true;
+                return Response::Ok;
+            }
+        }
+
+        let state = self.state();
+        self.broadcast(&Event::State(state.clone()));
+        Response::State(state)
+    }
+
+    fn previous(&mut self) {
+        if let Loop::Single | Loop::Queue = self.player.config.loop_mode {
+            return;
+        }
+
+        if self.player.playlist.is_empty() {
+            self.stop();
+            return;
+        }
+
+        // the current track went to the back of the playlist, the one before it is next to it
+        for _ in 0..2 {
+            if let Some(song) = self.player.playlist.tracks.pop_back() {
+                self.player.playlist.tracks.push_front(song);
+            }
+        }
+        self.player.skip();
+    }
+
+    fn seek(&mut self, offset: i64) {
+        // FIXME: dirty fix for seeking when paused with symphonia,basically set it to play
+        // in rusty sink code, and seek, and then set it back to pause.
+        #[cfg(not(any(feature = "mpv", feature = "gst")))]
+        let paused = self.player.is_paused();
+        #[cfg(not(any(feature = "mpv", feature = "gst")))]
+        if paused {
+            self.player.set_volume(0);
+        }
+
+        self.player.seek(offset).ok();
+
+        #[cfg(not(any(feature = "mpv", feature = "gst")))]
+        if paused {
+            thread::sleep(Duration::from_millis(50));
+            self.player.pause();
+            self.player.set_volume(self.player.config.volume);
+        }
+    }
+
+    fn set_loop_mode(&mut self, mode: Loop) {
+        // in single mode the current track is kept at the front of the playlist, in playlist
+        // mode at the back
+        let tracks = &mut self.player.playlist.tracks;
+        match (self.player.config.loop_mode, mode) {
+            (Loop::Playlist, Loop::Single) => {
+                if let Some(song) = tracks.pop_back() {
+                    tracks.push_front(song);
+                }
+            }
+            (Loop::Single, Loop::Playlist | Loop::Queue) => {
+                if let Some(song) = tracks.pop_front() {
+                    tracks.push_back(song);
+                }
+            }
+            _ => {}
+        }
+        self.player.config.loop_mode = mode;
+        self.playlist_changed();
+    }
+
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    fn set_output_device(&mut self, name: &str) -> Result<()> {
+        self.player.set_output_device(name)?;
+        self.player.config.output_device = name.to_string();
+        Ok(())
+    }
+
+    #[cfg(any(feature = "mpv", feature = "gst"))]
+    #[allow(clippy::unused_self)]
+    fn set_output_device(&mut self, _name: &str) -> Result<()> {
+        bail!("output device selection is only available with the default backend")
+    }
+
+    fn start_play_history(&mut self) {
+        let file = self
+            .player
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::file);
+        if let Some(file) = file {
+            self.db.add_play_event(file, PlayEvent::Start).ok();
+            self.playing = Some(file.to_string());
+        }
+    }
+
+    // Skipping shows up as an end of stream as well, so the position tells the two apart.
+    fn finish_play(&mut self) {
+        let complete = play_complete(self.position, self.duration, self.player.config.crossfade);
+        if let Some(file) = self.playing.take() {
+            let event = if complete {
+                PlayEvent::Complete
+            } else {
+                PlayEvent::Skip
+            };
+            self.db.add_play_event(&file, event).ok();
+        }
+        match &self.bookmark {
+            Some((Bookmark::Episode(id), _)) if complete => {
+                self.db.set_episode_played(*id, true).ok();
+            }
+            Some((Bookmark::File(file), _)) if complete => {
+                self.db.set_track_position(file, 0).ok();
+            }
+            Some(_) => self.save_bookmark(),
+            None => {}
+        }
+        self.bookmark = None;
+        self.resume = None;
+    }
+
+    // Podcast episodes and long tracks go on where they were left off, unless they were
+    // played to the end. So does the track that played when the server quit.
+    fn start_bookmark(&mut self) {
+        let Some(track) = self.player.playlist.current_track.as_ref() else {
+            return;
+        };
+        let Some(file) = track.file().map(ToString::to_string) else {
+            return;
+        };
+        let long = u64::from(self.player.config.remember_position_minutes) * 60;
+        let bookmark = if let Some(episode) = self.db.get_episode_by_location(&file) {
+            let position = if episode.played { 0 } else { episode.position };
+            Some((Bookmark::Episode(episode.id), position))
+        } else if long > 0 && !is_stream(&file) && track.duration().as_secs() >= long {
+            let position = self.db.get_track_position(&file);
+            Some((Bookmark::File(file.clone()), position))
+        } else {
+            None
+        };
+        if let Some((bookmark, position)) = bookmark {
+            #[allow(clippy::cast_possible_wrap)]
+            let position = position as i64;
+            if position > 0 {
+                self.resume = Some(position);
+            }
+            self.bookmark = Some((bookmark, position));
+        }
+        if let Some(position) = self.player.take_resume_point(&file) {
+            self.resume = Some(position).filter(|position| *position > 0);
+        }
+    }
+
+    fn save_bookmark(&mut self) {
+        // before the seek to where it was left off, the position is not the track's yet
+        if self.resume.is_some() {
+            return;
+        }
+        #[allow(clippy::cast_sign_loss)]
+        let position = self.position.max(0) as u64;
+        if let Some((bookmark, saved)) = self.bookmark.as_mut() {
+            match bookmark {
+                Bookmark::Episode(id) => self.db.set_episode_position(*id, position).ok(),
+                Bookmark::File(file) => self.db.set_track_position(file, position).ok(),
+            };
+            *saved = self.position;
+        }
+    }
+
+    // The loop kept for the file, if any.
+    fn start_ab_loop(&mut self) {
+        let file = self
+            .player
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::file);
+        self.ab_loop = file
+            .filter(|_| self.player.config.remember_ab_loop)
+            .and_then(|file| self.db.get_ab_loop(file))
+            .map(|(a, b)| AbLoop { a, b: Some(b) });
+        self.apply_ab_loop();
+    }
+
+    fn mark_ab_loop(&mut self) {
+        let Some(file) = self
+            .player
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::file)
+            .map(ToString::to_string)
+        else {
+            return;
+        };
+        self.ab_loop = next_ab_loop(self.ab_loop, self.elapsed());
+        self.apply_ab_loop();
+        if self.player.config.remember_ab_loop {
+            if let Some(ab_loop) = kept_ab_loop(self.ab_loop) {
+                self.db.set_ab_loop(&file, ab_loop).ok();
+            }
+        }
+    }
+
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    fn apply_ab_loop(&mut self) {
+        let ab_loop = self.ab_loop.and_then(|ab_loop| {
+            ab_loop.b.map(|b| {
+                (
+                    Duration::from_secs_f64(ab_loop.a),
+                    Duration::from_secs_f64(b),
+                )
+            })
+        });
+        self.player.set_ab_loop(ab_loop);
+    }
+
+    // the other backends loop on the progress messages
+    #[cfg(any(feature = "mpv", feature = "gst"))]
+    #[allow(clippy::unused_self)]
+    fn apply_ab_loop(&mut self) {}
+
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    fn elapsed(&self) -> f64 {
+        self.player.elapsed().as_secs_f64()
+    }
+
+    #[cfg(any(feature = "mpv", feature = "gst"))]
+    #[allow(clippy::cast_precision_loss)]
+    fn elapsed(&self) -> f64 {
+        self.position as f64
+    }
+
+    fn set_sleep_timer(&mut self, timer: Option<SleepTimer>) -> Result<()> {
+        self.sleep = match timer {
+            Some(SleepTimer::Seconds(seconds)) => {
+                let at = Some(seconds)
+                    .filter(|seconds| *seconds <= SleepTimer::MAX_SECONDS)
+                    .and_then(|seconds| Instant::now().checked_add(Duration::from_secs(seconds)));
+                let Some(at) = at else {
+                    bail!("a sleep timer of {} seconds is longer than a day", seconds);
+                };
+                Some(Sleep::At(at))
+            }
+            Some(SleepTimer::Tracks(tracks)) => Some(Sleep::Tracks(tracks.max(1))),
+            None => None,
+        };
+        // it may have been fading out already
+        self.player.set_volume(self.player.config.volume);
+        Ok(())
+    }
+
+    // A track that ends counts down a sleep timer for a number of tracks. When it was the
+    // last one, the next one is paused right at its start.
+    fn count_sleep_tracks(&mut self) {
+        if let Some(Sleep::Tracks(tracks)) = &mut self.sleep {
+            *tracks -= 1;
+            if *tracks == 0 {
+                self.fall_asleep();
+            } else {
+                self.broadcast(&Event::State(self.state()));
+            }
+        }
+    }
+
+    // The volume goes down over the last minute before the sleep timer pauses.
+    fn sleep_fade(&mut self, left: Duration) {
+        if left >= SLEEP_FADE || !self.player.is_running() {
+            return;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let volume = (f64::from(self.player.config.volume) * left.as_secs_f64()
+            / SLEEP_FADE.as_secs_f64())
+        .round() as i32;
+        if volume != self.player.volume() {
+            self.player.set_volume(volume);
+        }
+    }
+
+    fn fall_asleep(&mut self) {
+        self.sleep = None;
+        if self.player.is_running() {
+            self.player.set_status(Status::Paused);
+            self.player.pause();
+        }
+        self.player.set_volume(self.player.config.volume);
+        self.broadcast(&Event::State(self.state()));
+    }
+
+    fn stop(&mut self) {
+        self.save_bookmark();
+        self.bookmark = None;
+        // a timer for a number of tracks has nothing left to count
+        if let Some(Sleep::Tracks(_)) = self.sleep {
+            self.sleep = None;
+        }
+        self.player.set_status(Status::Stopped);
+        self.player.playlist.current_track = None;
+        self.player.stop();
+        self.position = 0;
+        self.duration = 0;
+        self.track_changed();
+    }
+
+    fn state(&self) -> PlayerState {
+        let current = self.player.playlist.current_track.as_ref();
+        let tag = |f: fn(&Track) -> Option<&str>| current.and_then(f).map(ToString::to_string);
+        PlayerState {
+            status: self.player.status(),
+            // not what the sleep timer faded it to
+            volume: self.player.config.volume,
+            speed: self.player.speed(),
+            gapless: self.player.config.gapless,
+            loop_mode: self.player.config.loop_mode,
+            position: self.position,
+            duration: self.duration,
+            file: tag(Track::file),
+            title: tag(Track::title),
+            artist: tag(Track::artist),
+            album: tag(Track::album),
+            stream_title: self.stream_title.clone(),
+            ab_loop: self.ab_loop,
+            sleep_timer: match self.sleep {
+                Some(Sleep::At(at)) => Some(SleepTimer::Seconds(
+                    at.saturating_duration_since(Instant::now()).as_secs(),
+                )),
+                Some(Sleep::Tracks(tracks)) => Some(SleepTimer::Tracks(tracks)),
+                None => None,
+            },
+        }
+    }
+
+    fn track_event(&self) -> Event {
+        Event::TrackChanged {
+            current: self
+                .player
+                .playlist
+                .current_track
+                .as_ref()
+                .and_then(Track::file)
+                .map(ToString::to_string),
+            tracks: self.player.playlist.files(),
+        }
+    }
+
+    fn track_changed(&mut self) {
+        self.stream_title = None;
+        let event = self.track_event();
+        self.broadcast(&event);
+        // the loop and the sleep timer belong to the track that played
+        self.broadcast(&Event::State(self.state()));
+        self.player.playlist.save().ok();
+    }
+
+    fn playlist_changed(&mut self) {
+        self.broadcast(&Event::PlaylistChanged {
+            tracks: self.player.playlist.files(),
+        });
+        self.player.playlist.save().ok();
+    }
+
+    // subscribers that hung up are dropped
+    fn broadcast(&mut self, event: &Event) {
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+// Whether a track that ended at `position` of `duration` seconds was played to the end.
+fn play_complete(position: i64, duration: i64, crossfade: u32) -> bool {
+    // a crossfade starts the next track before the end
+    duration - position <= COMPLETE_MARGIN + i64::from(crossfade)
+}
+
+// A goes where the track plays at `now`, then B, unless it is before A, which moves A there.
+// The third mark clears the loop.
+fn next_ab_loop(ab_loop: Option<AbLoop>, now: f64) -> Option<AbLoop> {
+    match ab_loop {
+        Some(AbLoop { a, b: None }) if now > a => Some(AbLoop { a, b: Some(now) }),
+        Some(AbLoop { b: Some(_), .. }) => None,
+        _ => Some(AbLoop { a: now, b: None }),
+    }
+}
+
+// What the database keeps of the loop, none to forget it. A loop that is only half done
+// leaves it as it is.
+fn kept_ab_loop(ab_loop: Option<AbLoop>) -> Option<Option<(f64, f64)>> {
+    match ab_loop {
+        Some(AbLoop { a, b: Some(b) }) => Some(Some((a, b))),
+        Some(AbLoop { b: None, .. }) => None,
+        None => Some(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_complete() {
+        assert!(play_complete(180, 180, 0));
+        assert!(play_complete(177, 180, 0));
+        assert!(!play_complete(176, 180, 0));
+        assert!(!play_complete(30, 180, 0));
+        // the crossfade cuts the track short
+        assert!(play_complete(172, 180, 5));
+        assert!(!play_complete(171, 180, 5));
+    }
+
+    #[test]
+    fn test_mark_ab_loop() {
+        let a = next_ab_loop(None, 12.5);
+        assert_eq!(a, Some(AbLoop { a: 12.5, b: None }));
+        assert_eq!(kept_ab_loop(a), None);
+
+        // B before A moves A
+        let a = next_ab_loop(a, 10.0);
+        assert_eq!(a, Some(AbLoop { a: 10.0, b: None }));
+
+        let ab = next_ab_loop(a, 20.0);
+        assert_eq!(
+            ab,
+            Some(AbLoop {
+                a: 10.0,
+                b: Some(20.0)
+            })
+        );
+        assert_eq!(kept_ab_loop(ab), Some(Some((10.0, 20.0))));
+
+        let cleared = next_ab_loop(ab, 15.0);
+        assert_eq!(cleared, None);
+        assert_eq!(kept_ab_loop(cleared), Some(None));
+    }
+}
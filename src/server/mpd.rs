@@ -0,0 +1,1223 @@
+//! A listener that speaks enough of the MPD protocol for the usual MPD clients to control the
+//! server. Each connection is turned into requests on the server loop, just like the ones from
+//! the control socket, and the library comes from the sqlite database.
+//!
+//! MPD's queue is the current track followed by the playlist, so the current track is always
+//! at position 0. Song ids are shared by the connections and stay with a song while it is in
+//! the queue.
+use super::{Command, Event, PlayerState, Request, Response};
+use crate::config::Settings;
+use crate::player::{Loop, Status};
+use crate::sqlite::{DataBase, SearchCriteria, TrackForDB, TrackOrder};
+use crate::track::Track;
+use crate::utils::filetype_supported;
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const GREETING: &str = "OK MPD 0.23.5\n";
+// how often a waiting connection looks at the events
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const ACK_ERROR_ARG: u32 = 2;
+const ACK_ERROR_UNKNOWN: u32 = 5;
+const ACK_ERROR_NO_EXIST: u32 = 50;
+const ACK_ERROR_SYSTEM: u32 = 52;
+
+const COMMANDS: [&str; 50] = [
+    "add",
+    "addid",
+    "clear",
+    "close",
+    "command_list_begin",
+    "command_list_end",
+    "command_list_ok_begin",
+    "commands",
+    "consume",
+    "currentsong",
+    "decoders",
+    "delete",
+    "deleteid",
+    "find",
+    "idle",
+    "list",
+    "listall",
+    "listallinfo",
+    "lsinfo",
+    "next",
+    "noidle",
+    "notcommands",
+    "outputs",
+    "pause",
+    "ping",
+    "play",
+    "playid",
+    "playlistid",
+    "playlistinfo",
+    "plchanges",
+    "plchangesposid",
+    "previous",
+    "random",
+    "repeat",
+    "replay_gain_status",
+    "rescan",
+    "search",
+    "seekcur",
+    "setvol",
+    "single",
+    "stats",
+    "status",
+    "stop",
+    "tagtypes",
+    "update",
+    "urlhandlers",
+    "volume",
+    "xfade",
+    "crossfade",
+    "toggleoutput",
+];
+
+const TAG_TYPES: [&str; 4] = ["Artist", "Album", "Title", "Genre"];
+// what `search`, `find` and `list` filter on, next to `any`
+const SEARCH_TAGS: [&str; 6] = ["artist", "albumartist", "album", "title", "genre", "file"];
+
+/// Starts taking MPD connections on `mpd_address`.
+pub fn spawn(config: &Settings, commands: &Sender<Command>) -> Result<()> {
+    let listener = TcpListener::bind(&config.mpd_address)?;
+    let config = config.clone();
+    let commands = commands.clone();
+    let ids = Arc::new(Mutex::new(SongIds::default()));
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let config = config.clone();
+            let commands = commands.clone();
+            let ids = Arc::clone(&ids);
+            thread::spawn(move || {
+                if let Ok(mut connection) = Connection::new(stream, &config, commands, ids) {
+                    connection.run().ok();
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+// An error answer, `ACK [code@index] {command} message`.
+struct Ack {
+    code: u32,
+    message: String,
+}
+
+impl Ack {
+    fn new(code: u32, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    fn arg(message: &str) -> Self {
+        Self::new(ACK_ERROR_ARG, message)
+    }
+
+    fn gone() -> Self {
+        Self::new(ACK_ERROR_SYSTEM, "the player went away")
+    }
+}
+
+type Answer = std::result::Result<String, Ack>;
+
+// The ids of the songs in the queue. A queue is matched by file against the one the ids were
+// last given for, so that a song keeps its id when others are deleted or it is moved.
+#[derive(Default)]
+struct SongIds {
+    queue: Vec<(String, u32)>,
+    next: u32,
+}
+
+impl SongIds {
+    fn update(&mut self, songs: &[Song]) -> Vec<u32> {
+        // the ids of each file, to be handed out in queue order
+        let mut known: HashMap<String, Vec<u32>> = HashMap::new();
+        for (file, id) in std::mem::take(&mut self.queue).into_iter().rev() {
+            known.entry(file).or_default().push(id);
+        }
+        for song in songs {
+            let id = match known.get_mut(&song.file).and_then(Vec::pop) {
+                Some(id) => id,
+                None => {
+                    self.next = self.next.wrapping_add(1);
+                    self.next
+                }
+            };
+            self.queue.push((song.file.clone(), id));
+        }
+        self.queue.iter().map(|(_, id)| *id).collect()
+    }
+}
+
+// MPD's queue, with the current track first if there is one.
+struct Queue {
+    has_current: bool,
+    songs: Vec<Song>,
+    ids: Vec<u32>,
+}
+
+impl Queue {
+    fn position(&self, id: usize) -> std::result::Result<usize, Ack> {
+        self.ids
+            .iter()
+            .position(|known| usize::try_from(*known) == Ok(id))
+            .ok_or_else(|| Ack::new(ACK_ERROR_NO_EXIST, "No such song"))
+    }
+}
+
+enum Input {
+    Line(String),
+    Timeout,
+    Closed,
+}
+
+// What MPD shows of a song.
+struct Song {
+    file: String,
+    title: Option<String>,
+    artist: Option<String>,
+    album: Option<String>,
+    genre: Option<String>,
+    duration: u64,
+}
+
+impl From<&Track> for Song {
+    fn from(track: &Track) -> Self {
+        Self {
+            file: track.file().unwrap_or_default().to_string(),
+            title: track.title().map(ToString::to_string),
+            artist: track.artist().map(ToString::to_string),
+            album: track.album().map(ToString::to_string),
+            genre: track.genre().map(ToString::to_string),
+            duration: track.duration().as_secs(),
+        }
+    }
+}
+
+impl From<&TrackForDB> for Song {
+    fn from(track: &TrackForDB) -> Self {
+        // the database has placeholders for missing tags
+        let tag = |value: &str, missing: &str| (value != missing).then(|| value.to_string());
+        Self {
+            file: track.file.clone(),
+            title: tag(&track.title, "Unknown Title"),
+            artist: tag(&track.artist, "Unknown Artist"),
+            album: tag(&track.album, "empty"),
+            genre: tag(&track.genre, "no type"),
+            duration: track.duration.as_secs(),
+        }
+    }
+}
+
+impl Song {
+    // The tag a search or list type refers to, `None` for unknown types.
+    fn tag(&self, tag: &str) -> Option<Option<&str>> {
+        let value = match tag.to_lowercase().as_str() {
+            "artist" | "albumartist" => &self.artist,
+            "album" => &self.album,
+            "title" => &self.title,
+            "genre" => &self.genre,
+            "file" => return Some(Some(self.file.as_str())),
+            _ => return None,
+        };
+        Some(value.as_deref())
+    }
+}
+
+struct Connection {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+    // what was read of a line before a timeout
+    line: String,
+    commands: Sender<Command>,
+    events: Receiver<Event>,
+    // subsystems changed since the last idle
+    changed: BTreeSet<&'static str>,
+    state: Option<PlayerState>,
+    db: DataBase,
+    music_dir: PathBuf,
+    crossfade: u32,
+    ids: Arc<Mutex<SongIds>>,
+}
+
+impl Connection {
+    fn new(
+        stream: TcpStream,
+        config: &Settings,
+        commands: Sender<Command>,
+        ids: Arc<Mutex<SongIds>>,
+    ) -> Result<Self> {
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        let (event_tx, events) = mpsc::channel();
+        commands.send(Command::Subscribe(event_tx))?;
+
+        let mut connection = Self {
+            writer: stream.try_clone()?,
+            reader: BufReader::new(stream),
+            line: String::new(),
+            commands,
+            events,
+            changed: BTreeSet::new(),
+            state: None,
+            db: DataBase::new(config),
+            music_dir: PathBuf::from(shellexpand::tilde(&config.music_dir).to_string()),
+            crossfade: config.crossfade,
+            ids,
+        };
+        // the server starts with the current track and state, that is no change
+        connection.events.recv()?;
+        if let Event::State(state) = connection.events.recv()? {
+            connection.state = Some(state);
+        }
+        Ok(connection)
+    }
+
+    fn run(&mut self) -> Result<()> {
+        self.writer.write_all(GREETING.as_bytes())?;
+        while let Some(line) = self.next_line()? {
+            if !self.handle_line(&line)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // Returns false when the connection is to be closed.
+    fn handle_line(&mut self, line: &str) -> Result<bool> {
+        let mut words = line.split_whitespace();
+        match words.next().unwrap_or_default() {
+            "close" => return Ok(false),
+            "idle" => {
+                let filter: Vec<String> = words.map(str::to_lowercase).collect();
+                return self.idle(&filter);
+            }
+            "noidle" => {}
+            "command_list_begin" | "command_list_ok_begin" => {
+                let list_ok = line.trim() == "command_list_ok_begin";
+                let mut list = Vec::new();
+                loop {
+                    match self.next_line()? {
+                        Some(line) if line.trim() == "command_list_end" => break,
+                        Some(line) => list.push(line),
+                        None => return Ok(false),
+                    }
+                }
+                let mut out = String::new();
+                for (index, line) in list.iter().enumerate() {
+                    match self.execute(line) {
+                        Ok(answer) => {
+                            out.push_str(&answer);
+                            if list_ok {
+                                out.push_str("list_OK\n");
+                            }
+                        }
+                        Err(ack) => {
+                            out.push_str(&Self::ack_line(&ack, index, line));
+                            self.writer.write_all(out.as_bytes())?;
+                            return Ok(true);
+                        }
+                    }
+                }
+                out.push_str("OK\n");
+                self.writer.write_all(out.as_bytes())?;
+            }
+            _ => {
+                let out = match self.execute(line) {
+                    Ok(answer) => answer + "OK\n",
+                    Err(ack) => Self::ack_line(&ack, 0, line),
+                };
+                self.writer.write_all(out.as_bytes())?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn ack_line(ack: &Ack, index: usize, line: &str) -> String {
+        let command = line.split_whitespace().next().unwrap_or_default();
+        format!(
+            "ACK [{}@{}] {{{}}} {}\n",
+            ack.code, index, command, ack.message
+        )
+    }
+
+    // Waits for a whole line, `None` when the client hung up.
+    fn next_line(&mut self) -> Result<Option<String>> {
+        loop {
+            match self.poll_line()? {
+                Input::Line(line) => return Ok(Some(line)),
+                Input::Closed => return Ok(None),
+                Input::Timeout => {}
+            }
+        }
+    }
+
+    fn poll_line(&mut self) -> Result<Input> {
+        self.collect_events();
+        match self.reader.read_line(&mut self.line) {
+            Ok(0) => Ok(Input::Closed),
+            Ok(_) => {
+                let line = self.line.trim_end().to_string();
+                self.line.clear();
+                Ok(Input::Line(line))
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                Ok(Input::Timeout)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn collect_events(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                Event::TrackChanged { .. } | Event::StreamTitle { .. } => {
+                    self.changed.insert("player");
+                    self.changed.insert("playlist");
+                }
+                Event::PlaylistChanged { .. } => {
+                    self.changed.insert("playlist");
+                }
+                Event::State(state) => {
+                    if let Some(last) = &self.state {
+                        if last.status != state.status {
+                            self.changed.insert("player");
+                        }
+                        if last.volume != state.volume {
+                            self.changed.insert("mixer");
+                        }
+                        if last.loop_mode != state.loop_mode || last.speed != state.speed {
+                            self.changed.insert("options");
+                        }
+                    }
+                    self.state = Some(state);
+                }
+                Event::Progress { .. } | Event::Message { .. } => {}
+            }
+        }
+    }
+
+    // Answers once one of the subsystems changed, or when the client sends `noidle`.
+    fn idle(&mut self, filter: &[String]) -> Result<bool> {
+        loop {
+            let hits: Vec<&'static str> = self
+                .changed
+                .iter()
+                .copied()
+                .filter(|subsystem| filter.is_empty() || filter.iter().any(|f| f == subsystem))
+                .collect();
+            if !hits.is_empty() {
+                let mut out = String::new();
+                for subsystem in hits {
+                    self.changed.remove(subsystem);
+                    writeln!(out, "changed: {}", subsystem).ok();
+                }
+                out.push_str("OK\n");
+                self.writer.write_all(out.as_bytes())?;
+                return Ok(true);
+            }
+
+            match self.poll_line()? {
+                Input::Line(line) if line.trim() == "noidle" => {
+                    self.writer.write_all(b"OK\n")?;
+                    return Ok(true);
+                }
+                Input::Line(line) => return self.handle_line(&line),
+                Input::Closed => return Ok(false),
+                Input::Timeout => {}
+            }
+        }
+    }
+
+    fn request(&self, request: Request) -> std::result::Result<PlayerState, Ack> {
+        let (response_tx, response_rx) = mpsc::channel();
+        self.commands
+            .send(Command::Request(request, response_tx))
+            .map_err(|_| Ack::gone())?;
+        match response_rx.recv().map_err(|_| Ack::gone())? {
+            Response::State(state) => Ok(state),
+            Response::Error(e) => Err(Ack::new(ACK_ERROR_SYSTEM, &e)),
+            Response::Ok => Err(Ack::new(ACK_ERROR_SYSTEM, "unexpected answer")),
+        }
+    }
+
+    fn state(&self) -> std::result::Result<PlayerState, Ack> {
+        self.request(Request::Status)
+    }
+
+    // The current track, if there is one, and the playlist.
+    fn queue(&self) -> std::result::Result<Queue, Ack> {
+        let (tracks_tx, tracks_rx) = mpsc::channel();
+        self.commands
+            .send(Command::Tracks(tracks_tx))
+            .map_err(|_| Ack::gone())?;
+        let (current, tracks) = tracks_rx.recv().map_err(|_| Ack::gone())?;
+        let has_current = current.is_some();
+        let songs: Vec<Song> = current
+            .iter()
+            .chain(tracks.iter())
+            .map(Song::from)
+            .collect();
+        let ids = self.ids.lock().map_err(|_| Ack::gone())?.update(&songs);
+        Ok(Queue {
+            has_current,
+            songs,
+            ids,
+        })
+    }
+
+    fn playlist_files(queue: Queue) -> Vec<String> {
+        queue
+            .songs
+            .into_iter()
+            .skip(usize::from(queue.has_current))
+            .map(|song| song.file)
+            .collect()
+    }
+
+    fn execute(&mut self, line: &str) -> Answer {
+        let args = split_args(line)?;
+        let (command, args) = args
+            .split_first()
+            .ok_or_else(|| Ack::new(ACK_ERROR_UNKNOWN, "No command given"))?;
+        let arg = args.first().map(String::as_str);
+        let mut out = String::new();
+        match command.as_str() {
+            "ping" | "noidle" => {}
+            "commands" => {
+                for command in COMMANDS {
+                    writeln!(out, "command: {}", command).ok();
+                }
+            }
+            "notcommands" | "decoders" | "urlhandlers" => {}
+            "tagtypes" => {
+                // enabling and disabling tags is accepted, all of them are always sent
+                if arg.is_none() {
+                    for tag in TAG_TYPES {
+                        writeln!(out, "tagtype: {}", tag).ok();
+                    }
+                }
+            }
+            "outputs" => {
+                out.push_str(
+                    "outputid: 0\noutputname: termusic\nplugin: termusic\noutputenabled: 1\n",
+                );
+            }
+            "toggleoutput" => {}
+            "replay_gain_status" => out.push_str("replay_gain_mode: off\n"),
+            "status" => out = self.status()?,
+            "stats" => out = self.stats(),
+            "currentsong" => {
+                let mut queue = self.queue()?;
+                if queue.has_current {
+                    // like MPD, the title of a radio is the song it plays
+                    if let Some(title) = self.state()?.stream_title {
+                        queue.songs[0].title = Some(title);
+                    }
+                    self.write_song(&mut out, &queue.songs[0], Some((0, queue.ids[0])));
+                }
+            }
+            "playlistinfo" | "playlistid" | "plchanges" => {
+                let queue = self.queue()?;
+                let range = match (command.as_str(), arg) {
+                    ("plchanges", _) | (_, None) => 0..queue.songs.len(),
+                    ("playlistid", Some(_)) => {
+                        let pos = queue.position(parse_number(arg)?)?;
+                        pos..pos + 1
+                    }
+                    (_, Some(range)) => parse_range(range, queue.songs.len())?,
+                };
+                for (pos, song) in queue.songs.iter().enumerate() {
+                    if range.contains(&pos) {
+                        self.write_song(&mut out, song, Some((pos, queue.ids[pos])));
+                    }
+                }
+            }
+            "plchangesposid" => {
+                for (pos, id) in self.queue()?.ids.iter().enumerate() {
+                    writeln!(out, "cpos: {}\nId: {}", pos, id).ok();
+                }
+            }
+            "add" | "addid" => {
+                let uri = arg.ok_or_else(|| Ack::arg("missing argument"))?;
+                let files = self.audio_files(uri)?;
+                let count = files.len();
+                self.request(Request::Queue(files))?;
+                if command == "addid" {
+                    let ids = self.queue()?.ids;
+                    if let Some(id) = ids.get(ids.len().saturating_sub(count)) {
+                        writeln!(out, "Id: {}", id).ok();
+                    }
+                }
+            }
+            "clear" => {
+                self.request(Request::SetPlaylist(Vec::new()))?;
+                self.request(Request::Stop)?;
+            }
+            "delete" => {
+                let pos = parse_number(arg)?;
+                self.delete(self.queue()?, pos)?;
+            }
+            "deleteid" => {
+                let queue = self.queue()?;
+                let pos = queue.position(parse_number(arg)?)?;
+                self.delete(queue, pos)?;
+            }
+            "play" | "playid" => match arg {
+                None => {
+                    self.request(Request::Play)?;
+                }
+                Some(_) => {
+                    let queue = self.queue()?;
+                    let pos = match command.as_str() {
+                        "playid" => queue.position(parse_number(arg)?)?,
+                        _ => parse_number(arg)?,
+                    };
+                    self.play(queue, pos)?;
+                }
+            },
+            "pause" => {
+                let request = match arg {
+                    Some("1") => Request::Pause,
+                    Some("0") => Request::Play,
+                    None => Request::TogglePause,
+                    Some(_) => return Err(Ack::arg("Boolean (0/1) expected")),
+                };
+                self.request(request)?;
+            }
+            // the player has no stop that keeps the current track, pausing is the closest
+            "stop" => {
+                self.request(Request::Pause)?;
+            }
+            "next" => {
+                self.request(Request::Next)?;
+            }
+            "previous" => {
+                self.request(Request::Previous)?;
+            }
+            "seekcur" => {
+                let time = arg.ok_or_else(|| Ack::arg("missing argument"))?;
+                let seconds = time
+                    .parse::<f64>()
+                    .map_err(|_| Ack::arg("Number expected"))?;
+                #[allow(clippy::cast_possible_truncation)]
+                let mut offset = seconds.round() as i64;
+                if !time.starts_with('+') && !time.starts_with('-') {
+                    offset -= self.state()?.position;
+                }
+                self.request(Request::Seek(offset))?;
+            }
+            "setvol" => {
+                let volume = parse_number(arg)?;
+                self.request(Request::SetVolume(i32::try_from(volume).unwrap_or(100)))?;
+            }
+            "volume" => {
+                let offset = arg
+                    .and_then(|a| a.parse::<i32>().ok())
+                    .ok_or_else(|| Ack::arg("Integer expected"))?;
+                let volume = self.state()?.volume + offset;
+                self.request(Request::SetVolume(volume))?;
+            }
+            "repeat" | "single" | "consume" => {
+                let on = match arg {
+                    Some("1") => true,
+                    Some("0") => false,
+                    _ => return Err(Ack::arg("Boolean (0/1) expected")),
+                };
+                let current = self.state()?.loop_mode;
+                let mode = loop_mode(command, on, current);
+                if mode != current {
+                    self.request(Request::SetLoopMode(mode))?;
+                }
+            }
+            "random" => {
+                if arg != Some("0") {
+                    return Err(Ack::arg("random playback is not supported"));
+                }
+            }
+            "xfade" | "crossfade" => {
+                return Err(Ack::arg("crossfade is set in config.toml"));
+            }
+            "search" | "find" => {
+                let exact = command == "find";
+                for song in self.search(args, exact)? {
+                    self.write_song(&mut out, &song, None);
+                }
+            }
+            "list" => out = self.list(args)?,
+            "listall" | "listallinfo" => {
+                for song in self.library() {
+                    if command == "listall" {
+                        writeln!(out, "file: {}", self.uri(&song.file)).ok();
+                    } else {
+                        self.write_song(&mut out, &song, None);
+                    }
+                }
+            }
+            "lsinfo" => out = self.lsinfo(arg.unwrap_or_default())?,
+            "update" | "rescan" => {
+                self.db.sync_database();
+                out.push_str("updating_db: 1\n");
+            }
+            _ => {
+                return Err(Ack::new(
+                    ACK_ERROR_UNKNOWN,
+                    &format!("unknown command \"{}\"", command),
+                ))
+            }
+        }
+        Ok(out)
+    }
+
+    fn status(&self) -> Answer {
+        let state = self.state()?;
+        let Queue {
+            has_current,
+            songs,
+            ids,
+        } = self.queue()?;
+        let (repeat, single, consume) = match state.loop_mode {
+            Loop::Playlist => (1, 0, 0),
+            Loop::Single => (1, 1, 0),
+            Loop::Queue => (0, 0, 1),
+        };
+        let status = match state.status {
+            Status::Running => "play",
+            Status::Paused => "pause",
+            Status::Stopped => "stop",
+        };
+
+        let mut out = String::new();
+        writeln!(out, "volume: {}", state.volume).ok();
+        writeln!(out, "repeat: {}", repeat).ok();
+        writeln!(out, "random: 0").ok();
+        writeln!(out, "single: {}", single).ok();
+        writeln!(out, "consume: {}", consume).ok();
+        writeln!(out, "playlist: {}", playlist_version(&songs)).ok();
+        writeln!(out, "playlistlength: {}", songs.len()).ok();
+        writeln!(out, "state: {}", status).ok();
+        writeln!(out, "xfade: {}", self.crossfade).ok();
+        if has_current {
+            writeln!(out, "song: 0\nsongid: {}", ids[0]).ok();
+            writeln!(out, "time: {}:{}", state.position, state.duration).ok();
+            writeln!(out, "elapsed: {}.000", state.position).ok();
+            writeln!(out, "duration: {}.000", state.duration).ok();
+        }
+        if songs.len() > usize::from(has_current) {
+            let next = usize::from(has_current);
+            writeln!(out, "nextsong: {}\nnextsongid: {}", next, ids[next]).ok();
+        }
+        Ok(out)
+    }
+
+    fn stats(&mut self) -> String {
+        let songs = self.library();
+        let artists: BTreeSet<_> = songs.iter().filter_map(|s| s.artist.as_ref()).collect();
+        let albums: BTreeSet<_> = songs.iter().filter_map(|s| s.album.as_ref()).collect();
+        let playtime: u64 = songs.iter().map(|s| s.duration).sum();
+        format!(
+            "artists: {}\nalbums: {}\nsongs: {}\nuptime: 0\nplaytime: 0\ndb_playtime: {}\ndb_update: 0\n",
+            artists.len(),
+            albums.len(),
+            songs.len(),
+            playtime
+        )
+    }
+
+    fn play(&self, queue: Queue, pos: usize) -> std::result::Result<(), Ack> {
+        let has_current = queue.has_current;
+        if has_current && pos == 0 {
+            let state = self.request(Request::Play)?;
+            // playing the current track again starts it over
+            if state.status == Status::Running {
+                self.request(Request::Seek(-state.position))?;
+            }
+            return Ok(());
+        }
+
+        // the track is moved to the front, where the player goes on with after a stop
+        let mut files = Self::playlist_files(queue);
+        let index = pos - usize::from(has_current);
+        if index >= files.len() {
+            return Err(Ack::new(ACK_ERROR_ARG, "Bad song index"));
+        }
+        let file = files.remove(index);
+        files.insert(0, file);
+        self.request(Request::SetPlaylist(files))?;
+        self.request(Request::Stop)?;
+        Ok(())
+    }
+
+    fn delete(&self, queue: Queue, pos: usize) -> std::result::Result<(), Ack> {
+        let has_current = queue.has_current;
+        if has_current && pos == 0 {
+            self.request(Request::Next)?;
+            return Ok(());
+        }
+        let mut files = Self::playlist_files(queue);
+        let index = pos - usize::from(has_current);
+        if index >= files.len() {
+            return Err(Ack::new(ACK_ERROR_ARG, "Bad song index"));
+        }
+        files.remove(index);
+        self.request(Request::SetPlaylist(files))?;
+        Ok(())
+    }
+
+    // All songs of the database, with the files as clients know them so that they can be
+    // searched for.
+    fn library(&mut self) -> Vec<Song> {
+        let records = self.db.get_all_records().unwrap_or_default();
+        records
+            .iter()
+            .map(|record| Song {
+                file: self.uri(&record.file),
+                ..Song::from(record)
+            })
+            .collect()
+    }
+
+    // `TYPE WHAT` pairs that all have to match, ignoring case and partial for search.
+    fn search(&mut self, args: &[String], exact: bool) -> std::result::Result<Vec<Song>, Ack> {
+        if args.is_empty() || !args.len().is_multiple_of(2) {
+            return Err(Ack::arg("incorrect arguments"));
+        }
+        let filters: Vec<(&str, String)> = args
+            .chunks(2)
+            .map(|pair| (pair[0].as_str(), pair[1].to_lowercase()))
+            .collect();
+        for (tag, _) in &filters {
+            if *tag != "any" && !SEARCH_TAGS.contains(&tag.to_lowercase().as_str()) {
+                return Err(Ack::arg(&format!("unknown tag type \"{}\"", tag)));
+            }
+        }
+
+        let matches = |value: Option<&str>, what: &str| {
+            value.is_some_and(|value| {
+                let value = value.to_lowercase();
+                if exact {
+                    value == what
+                } else {
+                    value.contains(what)
+                }
+            })
+        };
+        let songs = self
+            .library()
+            .into_iter()
+            .filter(|song| {
+                filters.iter().all(|(tag, what)| {
+                    if *tag == "any" {
+                        TAG_TYPES
+                            .iter()
+                            .chain(["file"].iter())
+                            .any(|tag| matches(song.tag(tag).flatten(), what))
+                    } else {
+                        matches(song.tag(tag).flatten(), what)
+                    }
+                })
+            })
+            .collect();
+        Ok(songs)
+    }
+
+    // `list TYPE [FILTERTYPE FILTERWHAT...]`, the distinct values of a tag.
+    fn list(&mut self, args: &[String]) -> Answer {
+        let (tag, filters) = args
+            .split_first()
+            .ok_or_else(|| Ack::arg("too few arguments"))?;
+        let tag = tag.to_lowercase();
+        let key = match tag.as_str() {
+            "artist" | "albumartist" => "Artist",
+            "album" => "Album",
+            "title" => "Title",
+            "genre" => "Genre",
+            "file" => "file",
+            _ => return Err(Ack::arg(&format!("unknown tag type \"{}\"", tag))),
+        };
+
+        // grouping is not supported, the values are listed without it
+        let mut filters: Vec<String> = filters
+            .iter()
+            .take_while(|arg| arg.as_str() != "group")
+            .cloned()
+            .collect();
+        // the old form `list album ARTIST`
+        if tag == "album" && filters.len() == 1 {
+            filters.insert(0, "artist".to_string());
+        }
+
+        let values: BTreeSet<String> = if filters.is_empty() {
+            match tag.as_str() {
+                "artist" | "albumartist" => {
+                    self.criterias(&SearchCriteria::Artist, "Unknown Artist")
+                }
+                "album" => self.criterias(&SearchCriteria::Album, "empty"),
+                "genre" => self.criterias(&SearchCriteria::Genre, "no type"),
+                _ => self
+                    .library()
+                    .iter()
+                    .filter_map(|song| song.tag(&tag).flatten().map(ToString::to_string))
+                    .collect(),
+            }
+        } else {
+            self.search(&filters, true)?
+                .iter()
+                .filter_map(|song| song.tag(&tag).flatten().map(ToString::to_string))
+                .collect()
+        };
+
+        let mut out = String::new();
+        for value in values {
+            let value = if key == "file" {
+                self.uri(&value)
+            } else {
+                value
+            };
+            writeln!(out, "{}: {}", key, value).ok();
+        }
+        Ok(out)
+    }
+
+    fn criterias(&mut self, criteria: &SearchCriteria, missing: &str) -> BTreeSet<String> {
+        self.db
+            .get_criterias(criteria)
+            .into_iter()
+            .filter(|value| value != missing)
+            .collect()
+    }
+
+    // The directories and songs right inside a directory of the music dir.
+    fn lsinfo(&mut self, uri: &str) -> Answer {
+        let dir = resolve(&self.music_dir, uri)?;
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|_| Ack::new(ACK_ERROR_NO_EXIST, "No such directory"))?;
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .collect();
+        paths.sort();
+
+        let known: HashMap<String, TrackForDB> = self
+            .db
+            .get_record_by_criteria(
+                &dir.to_string_lossy(),
+                &SearchCriteria::Directory,
+                TrackOrder::Name,
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .map(|record| (record.file.clone(), record))
+            .collect();
+
+        let mut out = String::new();
+        for path in paths {
+            let file = path.to_string_lossy().to_string();
+            if path.is_dir() {
+                writeln!(out, "directory: {}", self.uri(&file)).ok();
+            } else if filetype_supported(&file) {
+                match known.get(&file) {
+                    Some(record) => self.write_song(&mut out, &Song::from(record), None),
+                    None => {
+                        writeln!(out, "file: {}", self.uri(&file)).ok();
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    // With the position and id for songs of the queue.
+    fn write_song(&self, out: &mut String, song: &Song, queued: Option<(usize, u32)>) {
+        writeln!(out, "file: {}", self.uri(&song.file)).ok();
+        for (key, value) in [
+            ("Title", &song.title),
+            ("Artist", &song.artist),
+            ("Album", &song.album),
+            ("Genre", &song.genre),
+        ] {
+            if let Some(value) = value {
+                writeln!(out, "{}: {}", key, value).ok();
+            }
+        }
+        writeln!(out, "Time: {}", song.duration).ok();
+        writeln!(out, "duration: {}.000", song.duration).ok();
+        if let Some((pos, id)) = queued {
+            writeln!(out, "Pos: {}\nId: {}", pos, id).ok();
+        }
+    }
+
+    // MPD clients use paths relative to the music dir.
+    fn uri(&self, file: &str) -> String {
+        Path::new(file)
+            .strip_prefix(&self.music_dir)
+            .map_or_else(|_| file.to_string(), |p| p.to_string_lossy().to_string())
+    }
+
+    // The file itself, or all audio files under a directory.
+    fn audio_files(&self, uri: &str) -> std::result::Result<Vec<String>, Ack> {
+        let path = resolve(&self.music_dir, uri)?;
+        if !path.exists() {
+            return Err(Ack::new(ACK_ERROR_NO_EXIST, "No such directory"));
+        }
+        if !path.is_dir() {
+            let file = path.to_string_lossy().to_string();
+            if !filetype_supported(&file) {
+                return Err(Ack::arg("Unsupported file type"));
+            }
+            return Ok(vec![file]);
+        }
+        let mut files: Vec<String> = walkdir::WalkDir::new(path)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.path().to_string_lossy().to_string())
+            .filter(|file| filetype_supported(file))
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+}
+
+// The path of a uri, which is relative to the music dir and can't leave it.
+fn resolve(music_dir: &Path, uri: &str) -> std::result::Result<PathBuf, Ack> {
+    let uri = uri.strip_prefix("file://").unwrap_or(uri);
+    let uri = uri.trim_end_matches('/');
+    let path = Path::new(uri);
+    if !path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(Ack::arg("Malformed URI"));
+    }
+    let path = music_dir.join(path);
+    if !path.starts_with(music_dir) {
+        return Err(Ack::arg("Malformed URI"));
+    }
+    Ok(path)
+}
+
+// Splits a command line into words, where double quoted ones can contain spaces and
+// backslash escapes.
+fn split_args(line: &str) -> std::result::Result<Vec<String>, Ack> {
+    let mut args = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut arg = String::new();
+        if c == '"' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => arg.extend(chars.next()),
+                    Some(c) => arg.push(c),
+                    None => return Err(Ack::arg("Missing closing '\"'")),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                arg.push(c);
+                chars.next();
+            }
+        }
+        args.push(arg);
+    }
+    Ok(args)
+}
+
+// The loop mode after `repeat`, `single` or `consume` turned on or off. MPD's flags are
+// combined into the closest mode, and turning off one that isn't on changes nothing.
+fn loop_mode(command: &str, on: bool, current: Loop) -> Loop {
+    match (command, on) {
+        ("repeat", true) if current == Loop::Queue => Loop::Playlist,
+        ("repeat", false) => Loop::Queue,
+        ("single", true) => Loop::Single,
+        ("single", false) if current == Loop::Single => Loop::Playlist,
+        ("consume", true) => Loop::Queue,
+        ("consume", false) if current == Loop::Queue => Loop::Playlist,
+        _ => current,
+    }
+}
+
+fn parse_number(arg: Option<&str>) -> std::result::Result<usize, Ack> {
+    arg.and_then(|a| a.parse().ok())
+        .ok_or_else(|| Ack::arg("Integer expected"))
+}
+
+// `POS` or `START:END`, where the end is optional.
+fn parse_range(arg: &str, len: usize) -> std::result::Result<std::ops::Range<usize>, Ack> {
+    let bad = || Ack::arg("Integer or range expected");
+    match arg.split_once(':') {
+        Some((start, "")) => Ok(start.parse().map_err(|_| bad())?..len),
+        Some((start, end)) => {
+            Ok(start.parse().map_err(|_| bad())?..end.parse().map_err(|_| bad())?)
+        }
+        None => {
+            let pos: usize = arg.parse().map_err(|_| bad())?;
+            if pos >= len {
+                return Err(Ack::arg("Bad song index"));
+            }
+            Ok(pos..pos + 1)
+        }
+    }
+}
+
+// Changes whenever the queue does, which is what clients look at to reload it.
+fn playlist_version(songs: &[Song]) -> u32 {
+    let mut hasher = DefaultHasher::new();
+    for song in songs {
+        song.file.hash(&mut hasher);
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let version = hasher.finish() as u32;
+    version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn args(line: &str) -> Vec<String> {
+        split_args(line).map_err(|ack| ack.message).unwrap()
+    }
+
+    fn songs(files: &[&str]) -> Vec<Song> {
+        files
+            .iter()
+            .map(|file| Song {
+                file: (*file).to_string(),
+                title: None,
+                artist: None,
+                album: None,
+                genre: None,
+                duration: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(args("  play  "), vec!["play"]);
+        assert_eq!(args("seekcur +10"), vec!["seekcur", "+10"]);
+        assert_eq!(
+            args(r#"find artist "The Band" album "A \"B\" C\\""#),
+            vec!["find", "artist", "The Band", "album", r#"A "B" C\"#]
+        );
+        assert_eq!(args(r#"add """#), vec!["add", ""]);
+        assert!(args("").is_empty());
+        let unclosed = split_args(r#"add "a b"#).err().unwrap();
+        assert_eq!(unclosed.code, ACK_ERROR_ARG);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let range = |arg| parse_range(arg, 5).map_err(|ack| ack.message);
+        assert_eq!(range("2"), Ok(2..3));
+        assert_eq!(range("1:3"), Ok(1..3));
+        assert_eq!(range("3:"), Ok(3..5));
+        assert_eq!(range("5"), Err("Bad song index".to_string()));
+        for bad in ["", "x", "1:x", ":2", "-1"] {
+            assert_eq!(range(bad), Err("Integer or range expected".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_ack_line() {
+        let ack = Ack::new(ACK_ERROR_UNKNOWN, "unknown command \"foo\"");
+        assert_eq!(
+            Connection::ack_line(&ack, 2, "foo bar"),
+            "ACK [5@2] {foo} unknown command \"foo\"\n"
+        );
+        assert_eq!(
+            Connection::ack_line(&Ack::arg("No command given"), 0, ""),
+            "ACK [2@0] {} No command given\n"
+        );
+    }
+
+    #[test]
+    fn test_loop_mode() {
+        use Loop::{Playlist, Queue, Single};
+        for (command, on, current, mode) in [
+            ("repeat", true, Queue, Playlist),
+            ("repeat", true, Single, Single),
+            ("repeat", false, Playlist, Queue),
+            ("repeat", false, Single, Queue),
+            ("single", true, Playlist, Single),
+            ("single", true, Queue, Single),
+            ("single", false, Single, Playlist),
+            ("single", false, Queue, Queue),
+            ("consume", true, Playlist, Queue),
+            ("consume", false, Queue, Playlist),
+            ("consume", false, Single, Single),
+        ] {
+            assert_eq!(
+                loop_mode(command, on, current),
+                mode,
+                "{} {} in {:?}",
+                command,
+                on,
+                current
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let music_dir = Path::new("/music");
+        let path = |uri| resolve(music_dir, uri).map_err(|ack| ack.message);
+        assert_eq!(path(""), Ok(PathBuf::from("/music")));
+        assert_eq!(path("Jazz/"), Ok(PathBuf::from("/music/Jazz")));
+        assert_eq!(
+            path("Jazz/./So What.flac"),
+            Ok(PathBuf::from("/music/Jazz/So What.flac"))
+        );
+        for bad in [
+            "/etc/passwd",
+            "file:///etc/passwd",
+            "../secret.mp3",
+            "Jazz/../../secret.mp3",
+            "Jazz/..",
+        ] {
+            assert_eq!(path(bad), Err("Malformed URI".to_string()), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_song_ids() {
+        let mut ids = SongIds::default();
+        assert_eq!(ids.update(&songs(&["a", "b", "c", "d"])), vec![1, 2, 3, 4]);
+        // deleted and moved songs keep their ids
+        assert_eq!(ids.update(&songs(&["a", "d", "c"])), vec![1, 4, 3]);
+        // the next track, and new ones get new ids
+        assert_eq!(ids.update(&songs(&["d", "c", "e"])), vec![4, 3, 5]);
+        // the same file twice is told apart by order
+        assert_eq!(ids.update(&songs(&["d", "c", "e", "d"])), vec![4, 3, 5, 6]);
+        assert_eq!(ids.update(&songs(&["c", "d", "e", "d"])), vec![3, 4, 5, 6]);
+        assert!(ids.update(&[]).is_empty());
+        assert_eq!(ids.update(&songs(&["c"])), vec![7]);
+    }
+}
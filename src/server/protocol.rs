@@ -0,0 +1,317 @@
+//! Messages exchanged over the control socket, one JSON object per line.
+//!
+//! A client writes a `Request` and reads back a `Response`. After `Request::Subscribe` the
+//! connection only carries `Event`s from the server, until the client hangs up.
+use crate::player::{Loop, Status};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Request {
+    /// Resumes playback, or starts the first track of the playlist when stopped
+    Play,
+    Pause,
+    TogglePause,
+    Next,
+    Previous,
+    /// Stops the current track, the player goes on with the first one of the playlist
+    Stop,
+    /// Seeks forward with a positive offset and backward with a negative one
+    Seek(i64),
+    SetVolume(i32),
+    VolumeUp,
+    VolumeDown,
+    SetSpeed(i32),
+    SpeedUp,
+    SpeedDown,
+    ToggleGapless,
+    SetLoopMode(Loop),
+    SetEqualizer(String),
+    SetOutputDevice(String),
+    /// Sets the sleep timer, or cancels it with none
+    SetSleepTimer(Option<SleepTimer>),
+    /// Marks point A of a loop in the current track at where it plays, then point B, and
+    /// clears the loop the third time
+    MarkAbLoop,
+    /// Adds files to the end of the playlist
+    Queue(Vec<String>),
+    /// Replaces the tracks after the current one
+    SetPlaylist(Vec<String>),
+    /// Changes the tracks after the current one, which were the `base` files when the edits
+    /// were made. Tracks that started playing since are left alone.
+    EditPlaylist {
+        base: Vec<String>,
+        edits: Vec<PlaylistEdit>,
+    },
+    Status,
+    Subscribe,
+    /// Stops playback and shuts the server down
+    Quit,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    Ok,
+    State(PlayerState),
+    Error(String),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    /// A new track started, or playback stopped when `current` is none
+    TrackChanged {
+        current: Option<String>,
+        tracks: Vec<String>,
+    },
+    /// The tracks after the current one changed
+    PlaylistChanged {
+        tracks: Vec<String>,
+    },
+    Progress {
+        position: i64,
+        duration: i64,
+    },
+    State(PlayerState),
+    /// The song an internet radio is playing now
+    StreamTitle {
+        title: String,
+    },
+    /// Something the user should know about, like a lost output device
+    Message {
+        title: String,
+        text: String,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlayerState {
+    pub status: Status,
+    pub volume: i32,
+    pub speed: i32,
+    pub gapless: bool,
+    pub loop_mode: Loop,
+    /// Position in the current track, in seconds
+    pub position: i64,
+    /// Length of the current track, in seconds
+    pub duration: i64,
+    pub file: Option<String>,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// The song title sent along by an internet radio
+    #[serde(default)]
+    pub stream_title: Option<String>,
+    /// What is left of the sleep timer
+    #[serde(default)]
+    pub sleep_timer: Option<SleepTimer>,
+    #[serde(default)]
+    pub ab_loop: Option<AbLoop>,
+}
+
+/// A part of the current track that plays over and over, in seconds.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AbLoop {
+    pub a: f64,
+    /// None while only point A is marked
+    pub b: Option<f64>,
+}
+
+/// A change to the tracks after the current one. Positions are the ones of the list as it is
+/// after the edits before.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistEdit {
+    /// Inserts the files before the track at `index`
+    Add { index: usize, files: Vec<String> },
+    /// Removes the tracks from `index` on, which are the files
+    Remove { index: usize, files: Vec<String> },
+    /// Moves the track at `from`, which is the file, to `to`
+    Move {
+        from: usize,
+        to: usize,
+        file: String,
+    },
+}
+
+impl PlaylistEdit {
+    /// The edits that turn the `old` files into the `new` ones. Only what changed between the
+    /// tracks both start and end with is sent, as a move when one track changed places.
+    pub fn diff(old: &[String], new: &[String]) -> Vec<Self> {
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let removed = &old[prefix..old.len() - suffix];
+        let added = &new[prefix..new.len() - suffix];
+        let len = removed.len();
+
+        let remove = || Self::Remove {
+            index: prefix,
+            files: removed.to_vec(),
+        };
+        let add = || Self::Add {
+            index: prefix,
+            files: added.to_vec(),
+        };
+        if removed.is_empty() && added.is_empty() {
+            Vec::new()
+        } else if removed.is_empty() {
+            vec![add()]
+        } else if added.is_empty() {
+            vec![remove()]
+        } else if len >= 2 && added.len() == len && added[..len - 1] == removed[1..] {
+            // the first one went to the end, the others moved up
+            vec![Self::Move {
+                from: prefix,
+                to: prefix + len - 1,
+                file: removed[0].clone(),
+            }]
+        } else if len >= 2 && added.len() == len && added[1..] == removed[..len - 1] {
+            vec![Self::Move {
+                from: prefix + len - 1,
+                to: prefix,
+                file: removed[len - 1].clone(),
+            }]
+        } else {
+            vec![remove(), add()]
+        }
+    }
+}
+
+/// When playback pauses by itself, for falling asleep to music.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SleepTimer {
+    /// After this many seconds, with the music fading out over the last minute
+    Seconds(u64),
+    /// At the end of this many tracks, the current one included
+    Tracks(u32),
+}
+
+impl SleepTimer {
+    /// The longest time a sleep timer can be set to, a day.
+    pub const MAX_SECONDS: u64 = 24 * 60 * 60;
+}
+
+impl FromStr for SleepTimer {
+    type Err = String;
+
+    /// Minutes like `30`, `end` for the end of the current track or `end+2` for the end of
+    /// the second track after it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(more) = s.strip_prefix("end") {
+            let more: u32 = match more.strip_prefix('+') {
+                Some(more) => more
+                    .parse()
+                    .map_err(|_| format!("'{}' is not a number of tracks", more))?,
+                None if more.is_empty() => 0,
+                None => return Err(format!("'{}' is not a sleep timer", s)),
+            };
+            return Ok(Self::Tracks(more.saturating_add(1)));
+        }
+        match s.parse::<u64>() {
+            Ok(minutes) if minutes > Self::MAX_SECONDS / 60 => {
+                Err(format!("{} minutes is longer than a day", minutes))
+            }
+            Ok(minutes) if minutes > 0 => Ok(Self::Seconds(minutes * 60)),
+            _ => Err(format!("'{}' is not a number of minutes, end or end+N", s)),
+        }
+    }
+}
+
+impl fmt::Display for SleepTimer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Seconds(seconds) => write!(f, "{}:{:02}", seconds / 60, seconds % 60),
+            Self::Tracks(1) => write!(f, "end of track"),
+            Self::Tracks(tracks) => write!(f, "{} tracks", tracks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(names: &str) -> Vec<String> {
+        names.chars().map(String::from).collect()
+    }
+
+    #[test]
+    fn playlist_diff() {
+        let diff = |old, new| PlaylistEdit::diff(&files(old), &files(new));
+        assert_eq!(diff("abc", "abc"), vec![]);
+        assert_eq!(
+            diff("abc", "abxyc"),
+            vec![PlaylistEdit::Add {
+                index: 2,
+                files: files("xy")
+            }]
+        );
+        assert_eq!(
+            diff("abcd", "ad"),
+            vec![PlaylistEdit::Remove {
+                index: 1,
+                files: files("bc")
+            }]
+        );
+        assert_eq!(
+            diff("abcd", "acbd"),
+            vec![PlaylistEdit::Move {
+                from: 1,
+                to: 2,
+                file: "b".to_string()
+            }]
+        );
+        assert_eq!(
+            diff("abcd", "dabc"),
+            vec![PlaylistEdit::Move {
+                from: 3,
+                to: 0,
+                file: "d".to_string()
+            }]
+        );
+        assert_eq!(
+            diff("abcd", "dcba"),
+            vec![
+                PlaylistEdit::Remove {
+                    index: 0,
+                    files: files("abcd")
+                },
+                PlaylistEdit::Add {
+                    index: 0,
+                    files: files("dcba")
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn sleep_timer() {
+        assert_eq!("30".parse(), Ok(SleepTimer::Seconds(1800)));
+        assert_eq!("end".parse(), Ok(SleepTimer::Tracks(1)));
+        assert_eq!("end+2".parse(), Ok(SleepTimer::Tracks(3)));
+        assert!("0".parse::<SleepTimer>().is_err());
+        assert!("ending".parse::<SleepTimer>().is_err());
+        assert!("end+x".parse::<SleepTimer>().is_err());
+        assert_eq!(
+            "1440".parse(),
+            Ok(SleepTimer::Seconds(SleepTimer::MAX_SECONDS))
+        );
+        assert_eq!(
+            "1441".parse::<SleepTimer>(),
+            Err("1441 minutes is longer than a day".to_string())
+        );
+        assert!("307445734561825861".parse::<SleepTimer>().is_err());
+        assert!("18446744073709551615".parse::<SleepTimer>().is_err());
+        assert_eq!(SleepTimer::Seconds(1781).to_string(), "29:41");
+    }
+}
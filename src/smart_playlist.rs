@@ -0,0 +1,397 @@
+//! Playlists made of the library tracks matching a set of rules, like
+//! `genre = Jazz AND rating >= 4 AND not played in 30 days`.
+//!
+//! Each one is a toml file in the `smart_playlists` folder of the config dir,
+//! named after the playlist. The rules are turned into a `WHERE` clause for
+//! the track query of the database, so the playlist follows the library.
+use crate::config::get_app_config_path;
+use crate::utils::get_pin_yin;
+use anyhow::{anyhow, bail, Context, Result};
+use rusqlite::types::Value;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+const SMART_PLAYLIST_DIR: &str = "smart_playlists";
+
+/// How the tracks are picked when there are more than the limit.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmartOrder {
+    #[default]
+    Name,
+    Artist,
+    Album,
+    Random,
+    Rating,
+    PlayCount,
+    LastPlayed,
+}
+
+impl SmartOrder {
+    const fn sql(self) -> &'static str {
+        match self {
+            Self::Name => " ORDER BY track.name",
+            Self::Artist => " ORDER BY track.artist, track.album, track.name",
+            Self::Album => " ORDER BY track.album, track.name",
+            Self::Random => " ORDER BY RANDOM()",
+            Self::Rating => " ORDER BY IFNULL(rating, 0) DESC",
+            Self::PlayCount => " ORDER BY IFNULL(play_count, 0) DESC",
+            Self::LastPlayed => " ORDER BY last_played DESC",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SmartPlaylist {
+    /// Taken from the file name
+    #[serde(skip)]
+    pub name: String,
+    pub rules: String,
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub order: SmartOrder,
+}
+
+impl SmartPlaylist {
+    /// All the smart playlists of the config dir, sorted by name. A couple of
+    /// examples are written the first time.
+    pub fn load_all() -> Result<Vec<Self>> {
+        let path = Self::dir()?;
+        if !path.exists() {
+            fs::create_dir_all(&path)?;
+            for playlist in Self::examples() {
+                playlist.save()?;
+            }
+        }
+
+        let mut playlists = vec![];
+        for entry in fs::read_dir(&path)?.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "toml") {
+                continue;
+            }
+            let string = fs::read_to_string(&path)?;
+            let mut playlist: Self = toml::from_str(&string)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            playlist.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            playlists.push(playlist);
+        }
+        playlists.sort_by_cached_key(|p| get_pin_yin(&p.name));
+        Ok(playlists)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut path = Self::dir()?;
+        fs::create_dir_all(&path)?;
+        path.push(format!("{}.toml", self.name));
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn dir() -> Result<PathBuf> {
+        let mut path = get_app_config_path()?;
+        path.push(SMART_PLAYLIST_DIR);
+        Ok(path)
+    }
+
+    fn examples() -> Vec<Self> {
+        vec![
+            Self {
+                name: "Favourites".to_string(),
+                rules: "favourite".to_string(),
+                limit: None,
+                order: SmartOrder::Artist,
+            },
+            Self {
+                name: "Not played lately".to_string(),
+                rules: "rating >= 4 AND not played in 30 days".to_string(),
+                limit: Some(50),
+                order: SmartOrder::Random,
+            },
+        ]
+    }
+
+    /// The `WHERE`, `ORDER BY` and `LIMIT` clauses for the track query, with
+    /// the values of its parameters.
+    pub fn compile(&self) -> Result<(String, Vec<Value>)> {
+        let mut values = vec![];
+        let mut sql = String::new();
+        let condition = compile_rules(&self.rules, &mut values)
+            .with_context(|| format!("invalid rules for {}", self.name))?;
+        if !condition.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&condition);
+        }
+        sql.push_str(self.order.sql());
+        if let Some(limit) = self.limit {
+            sql.push_str(" LIMIT ?");
+            values.push(Value::Integer(i64::from(limit)));
+        }
+        Ok((sql, values))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Word {
+    text: String,
+    quoted: bool,
+}
+
+impl Word {
+    fn is(&self, keyword: &str) -> bool {
+        !self.quoted && self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+// Splits the rules into words, where a quoted string is one word and the
+// comparison operators are words of their own.
+fn split_words(rules: &str) -> Result<Vec<Word>> {
+    let mut words = vec![];
+    let mut chars = rules.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => bail!("missing closing quote"),
+                    }
+                }
+                words.push(Word { text, quoted: true });
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                let mut text = c.to_string();
+                if chars.peek() == Some(&'=') {
+                    text.push('=');
+                    chars.next();
+                }
+                words.push(Word {
+                    text,
+                    quoted: false,
+                });
+            }
+            c => {
+                let mut text = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "\"=!<>~".contains(c) {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                words.push(Word {
+                    text,
+                    quoted: false,
+                });
+            }
+        }
+    }
+    Ok(words)
+}
+
+fn compile_rules(rules: &str, values: &mut Vec<Value>) -> Result<String> {
+    let words = split_words(rules)?;
+    if words.is_empty() {
+        return Ok(String::new());
+    }
+    let mut sql = String::new();
+    let mut start = 0;
+    for (i, word) in words.iter().enumerate() {
+        let joiner = if word.is("and") {
+            "AND"
+        } else if word.is("or") {
+            "OR"
+        } else {
+            continue;
+        };
+        let condition = compile_condition(&words[start..i], values)?;
+        sql.push_str(&format!("({}) {} ", condition, joiner));
+        start = i + 1;
+    }
+    let condition = compile_condition(&words[start..], values)?;
+    sql.push_str(&format!("({})", condition));
+    Ok(sql)
+}
+
+// sql for the rules that look at when a track was last started
+const NOW: &str = "CAST(strftime('%s', 'now') AS INTEGER)";
+
+fn compile_condition(words: &[Word], values: &mut Vec<Value>) -> Result<String> {
+    if words.is_empty() {
+        bail!("missing condition next to and/or");
+    }
+    let negated = words[0].is("not");
+    let rest = if negated { &words[1..] } else { words };
+
+    if rest.len() == 1 && (rest[0].is("favourite") || rest[0].is("favorite")) {
+        return Ok(format!("IFNULL(favourite, 0) = {}", u8::from(!negated)));
+    }
+    if rest.len() == 4
+        && rest[0].is("played")
+        && rest[1].is("in")
+        && (rest[3].is("days") || rest[3].is("day"))
+    {
+        let days: i64 = rest[2]
+            .text
+            .parse()
+            .map_err(|_| anyhow!("expected a number of days instead of {}", rest[2].text))?;
+        let seconds = days
+            .checked_mul(24 * 60 * 60)
+            .ok_or_else(|| anyhow!("{} days is too long ago", days))?;
+        values.push(Value::Integer(seconds));
+        let op = if negated { "<" } else { ">=" };
+        return Ok(format!("IFNULL(last_played, 0) {} {} - ?", op, NOW));
+    }
+    if negated {
+        bail!("not only goes with favourite or played in");
+    }
+
+    let (field, op) = match words {
+        [field, op, _, ..] => (field, op.text.as_str()),
+        _ => bail!(
+            "don't know what to do with {}",
+            words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+    };
+    let value = words[2..]
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (column, numeric) = match field.text.to_lowercase().as_str() {
+        "artist" => ("track.artist", false),
+        "title" => ("track.title", false),
+        "album" => ("track.album", false),
+        "genre" => ("track.genre", false),
+        "directory" => ("track.directory", false),
+        "name" => ("track.name", false),
+        "ext" => ("track.ext", false),
+        "duration" => ("track.duration", true),
+        "rating" => ("IFNULL(rating, 0)", true),
+        "plays" => ("IFNULL(play_count, 0)", true),
+        "skips" => ("IFNULL(skip_count, 0)", true),
+        f => bail!("unknown field {}", f),
+    };
+
+    if numeric {
+        let number: i64 = value
+            .parse()
+            .map_err(|_| anyhow!("expected a number for {} instead of {}", field.text, value))?;
+        let op = match op {
+            "=" | "<" | "<=" | ">" | ">=" => op,
+            "!=" => "<>",
+            op => bail!("{} can't be used with {}", op, field.text),
+        };
+        values.push(Value::Integer(number));
+        return Ok(format!("{} {} ?", column, op));
+    }
+
+    let sql = match op {
+        "=" => format!("{} = ? COLLATE NOCASE", column),
+        "!=" => format!("{} <> ? COLLATE NOCASE", column),
+        "~" => {
+            values.push(Value::Text(format!("%{}%", value)));
+            return Ok(format!("{} LIKE ?", column));
+        }
+        op => bail!("{} can't be used with {}", op, field.text),
+    };
+    values.push(Value::Text(value));
+    Ok(sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(rules: &str, limit: Option<u32>, order: SmartOrder) -> Result<(String, Vec<Value>)> {
+        SmartPlaylist {
+            name: "test".to_string(),
+            rules: rules.to_string(),
+            limit,
+            order,
+        }
+        .compile()
+    }
+
+    #[test]
+    fn test_compile_rules() {
+        let (sql, values) = compile(
+            "genre = Jazz AND rating >= 4 AND not played in 30 days",
+            Some(50),
+            SmartOrder::Random,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            format!(
+                " WHERE (track.genre = ? COLLATE NOCASE) AND (IFNULL(rating, 0) >= ?) \
+                 AND (IFNULL(last_played, 0) < {} - ?) ORDER BY RANDOM() LIMIT ?",
+                NOW
+            )
+        );
+        assert_eq!(
+            values,
+            vec![
+                Value::Text("Jazz".to_string()),
+                Value::Integer(4),
+                Value::Integer(30 * 24 * 60 * 60),
+                Value::Integer(50),
+            ]
+        );
+
+        let (sql, values) = compile(
+            "artist=\"Simon and Garfunkel\" or title ~ love or favourite",
+            None,
+            SmartOrder::Name,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            " WHERE (track.artist = ? COLLATE NOCASE) OR (track.title LIKE ?) \
+             OR (IFNULL(favourite, 0) = 1) ORDER BY track.name"
+        );
+        assert_eq!(
+            values,
+            vec![
+                Value::Text("Simon and Garfunkel".to_string()),
+                Value::Text("%love%".to_string()),
+            ]
+        );
+
+        let (sql, values) = compile("", Some(10), SmartOrder::PlayCount).unwrap();
+        assert_eq!(sql, " ORDER BY IFNULL(play_count, 0) DESC LIMIT ?");
+        assert_eq!(values, vec![Value::Integer(10)]);
+    }
+
+    #[test]
+    fn test_compile_invalid_rules() {
+        for rules in [
+            "rating >= four",
+            "genre > Jazz",
+            "mood = happy",
+            "genre = Jazz AND",
+            "AND rating = 5",
+            "not rating = 5",
+            "artist = \"Miles",
+            "played in a while",
+            "played in 9223372036854775807 days",
+        ] {
+            assert!(
+                compile(rules, None, SmartOrder::Name).is_err(),
+                "{} should not compile",
+                rules
+            );
+        }
+    }
+}
@@ -1,13 +1,25 @@
 // database
 use crate::config::{get_app_config_path, Settings};
+use crate::cue;
+use crate::named_playlist::NamedPlaylist;
+use crate::podcast::Feed;
+use crate::smart_playlist::{SmartOrder, SmartPlaylist};
 use crate::track::Track;
 use crate::ui::model::Model;
 use crate::utils::get_pin_yin;
-use rusqlite::{params, Connection, Error, Result, Row};
-use std::path::{Path, PathBuf};
-use std::time::{Duration, UNIX_EPOCH};
+use rusqlite::{params, params_from_iter, Connection, Error, Result, Row};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 const DB_VERSION: u32 = 1;
 
+// the tracks with their play counts from the history and their ratings
+const SELECT_TRACKS: &str = "SELECT track.*, IFNULL(play_count, 0), IFNULL(skip_count, 0),
+    last_played, IFNULL(rating, 0), IFNULL(favourite, 0)
+    FROM track LEFT JOIN track_stats ON track.file = track_stats.file
+    LEFT JOIN track_rating ON track.file = track_rating.file";
+
 #[allow(unused)]
 pub struct DataBase {
     conn: Connection,
@@ -27,13 +39,176 @@ pub struct TrackForDB {
     pub ext: String,
     pub directory: String,
     pub last_modified: String,
+    pub play_count: u32,
+    pub skip_count: u32,
+    /// Unix time of the last start, `None` when never played
+    pub last_played: Option<u64>,
+    /// Stars from 1 to 5, 0 when not rated
+    pub rating: u8,
+    pub favourite: bool,
+}
+
+/// A podcast subscription, with the episodes not played yet.
+#[derive(Clone, Debug)]
+pub struct PodcastForDB {
+    pub id: u64,
+    /// Of the feed
+    pub url: String,
+    pub title: String,
+    pub unplayed: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct EpisodeForDB {
+    pub id: u64,
+    /// The title of the podcast
+    pub podcast: String,
+    pub title: String,
+    /// Of the audio
+    pub url: String,
+    /// Unix seconds
+    pub published: Option<u64>,
+    /// In seconds, from the feed
+    pub duration: Option<u64>,
+    /// The download, `None` when it is streamed
+    pub file: Option<String>,
+    /// Seconds into the episode where it was left off
+    pub position: u64,
+    pub played: bool,
+}
+
+impl EpisodeForDB {
+    /// What goes into the playlist, the download when there is one.
+    pub fn location(&self) -> &str {
+        self.file.as_deref().unwrap_or(&self.url)
+    }
 }
 
+const SELECT_EPISODES: &str = "SELECT episode.id, podcast.title, episode.title,
+    episode.url, published, duration, file, position, played
+    FROM episode JOIN podcast ON podcast.id = episode.podcast_id";
+
 pub enum SearchCriteria {
     Artist,
     Album,
     Genre,
     Directory,
+    /// Not a column, the tracks come from the rules of a smart playlist
+    SmartPlaylist,
+}
+
+/// What happened to a track, as kept in the play history.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlayEvent {
+    Start,
+    /// Played until the end, or close enough to it
+    Complete,
+    Skip,
+}
+
+impl std::fmt::Display for PlayEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Start => write!(f, "start"),
+            Self::Complete => write!(f, "complete"),
+            Self::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+/// How the tracks of a search result are sorted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackOrder {
+    Name,
+    PlayCount,
+    LastPlayed,
+    SkipCount,
+}
+
+impl TrackOrder {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Name => Self::PlayCount,
+            Self::PlayCount => Self::LastPlayed,
+            Self::LastPlayed => Self::SkipCount,
+            Self::SkipCount => Self::Name,
+        }
+    }
+
+    const fn sql(self) -> &'static str {
+        match self {
+            Self::Name => "",
+            Self::PlayCount => " ORDER BY play_count DESC",
+            Self::LastPlayed => " ORDER BY last_played DESC",
+            Self::SkipCount => " ORDER BY skip_count DESC",
+        }
+    }
+}
+
+impl std::fmt::Display for TrackOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "name"),
+            Self::PlayCount => write!(f, "play count"),
+            Self::LastPlayed => write!(f, "last played"),
+            Self::SkipCount => write!(f, "skip count"),
+        }
+    }
+}
+
+/// What the statistics are counted by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsCriteria {
+    Track,
+    Artist,
+    Album,
+}
+
+impl std::fmt::Display for StatsCriteria {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Track => write!(f, "tracks"),
+            Self::Artist => write!(f, "artists"),
+            Self::Album => write!(f, "albums"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsPeriod {
+    Week,
+    Month,
+    AllTime,
+}
+
+impl StatsPeriod {
+    // unix time the period starts at
+    fn since(self) -> u64 {
+        let days = match self {
+            Self::Week => 7,
+            Self::Month => 30,
+            Self::AllTime => return 0,
+        };
+        now().saturating_sub(days * 24 * 60 * 60)
+    }
+}
+
+impl std::fmt::Display for StatsPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Week => write!(f, "last 7 days"),
+            Self::Month => write!(f, "last 30 days"),
+            Self::AllTime => write!(f, "all time"),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
 
 impl From<usize> for SearchCriteria {
@@ -42,6 +217,7 @@ impl From<usize> for SearchCriteria {
             1 => Self::Album,
             2 => Self::Genre,
             3 => Self::Directory,
+            4 => Self::SmartPlaylist,
             _ => Self::Artist,
             // 0 | _ => Self::Artist,
         }
@@ -55,6 +231,7 @@ impl std::fmt::Display for SearchCriteria {
             Self::Album => write!(f, "album"),
             Self::Genre => write!(f, "genre"),
             Self::Directory => write!(f, "directory"),
+            Self::SmartPlaylist => write!(f, "smart playlist"),
         }
     }
 }
@@ -66,7 +243,11 @@ impl DataBase {
         let mut db_path = get_app_config_path().expect("failed to get app configuration path");
         db_path.push("library.db");
         let conn = Connection::open(db_path).expect("open db failed");
+        Self::with_connection(conn, path)
+    }
 
+    // Brings the tables of the connection up to date.
+    fn with_connection(conn: Connection, path: PathBuf) -> Self {
         let user_version: u32 = conn
             .query_row("SELECT user_version FROM pragma_user_version", [], |r| {
                 r.get(0)
@@ -96,6 +277,81 @@ impl DataBase {
         )
         .expect("create table track failed");
 
+        // kept apart from the tracks, so that it survives a rebuild of the library
+        conn.execute(
+            "create table if not exists play_history(
+             id integer primary key,
+             file TEXT NOT NULL,
+             event TEXT NOT NULL,
+             timestamp INTEGER NOT NULL
+            )",
+            [],
+        )
+        .expect("create table play_history failed");
+        conn.execute(
+            "create view if not exists track_stats as
+             SELECT file,
+             SUM(event = 'complete') AS play_count,
+             SUM(event = 'skip') AS skip_count,
+             MAX(CASE WHEN event = 'start' THEN timestamp END) AS last_played
+             FROM play_history GROUP BY file",
+            [],
+        )
+        .expect("create view track_stats failed");
+        conn.execute(
+            "create table if not exists track_rating(
+             file TEXT PRIMARY KEY,
+             rating INTEGER NOT NULL DEFAULT 0,
+             favourite INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )
+        .expect("create table track_rating failed");
+        conn.execute(
+            "create table if not exists track_position(
+             file TEXT PRIMARY KEY,
+             position INTEGER NOT NULL
+            )",
+            [],
+        )
+        .expect("create table track_position failed");
+        conn.execute(
+            "create table if not exists ab_loop(
+             file TEXT PRIMARY KEY,
+             a REAL NOT NULL,
+             b REAL NOT NULL
+            )",
+            [],
+        )
+        .expect("create table ab_loop failed");
+        conn.execute(
+            "create table if not exists podcast(
+             id integer primary key,
+             url TEXT NOT NULL UNIQUE,
+             title TEXT NOT NULL,
+             description TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("create table podcast failed");
+        conn.execute(
+            "create table if not exists episode(
+             id integer primary key,
+             podcast_id INTEGER NOT NULL,
+             guid TEXT NOT NULL,
+             title TEXT NOT NULL,
+             url TEXT NOT NULL,
+             published INTEGER,
+             duration INTEGER,
+             file TEXT,
+             position INTEGER NOT NULL DEFAULT 0,
+             played INTEGER NOT NULL DEFAULT 0,
+             UNIQUE(podcast_id, guid)
+            )",
+            [],
+        )
+        .expect("create table episode failed");
+
         Self { conn, path }
     }
 
@@ -103,6 +359,15 @@ impl DataBase {
         let tx = self.conn.transaction()?;
 
         for track in tracks {
+            // a changed file replaces its old record
+            tx.execute("DELETE FROM track WHERE file = ?", params![track.file()])?;
+            // a rating from the tags is taken over, unless there is one already
+            if track.rating() > 0 {
+                tx.execute(
+                    "INSERT OR IGNORE INTO track_rating (file, rating) values (?1, ?2)",
+                    params![track.file(), track.rating()],
+                )?;
+            }
             tx.execute(
             "INSERT INTO track (artist, title, album, genre,  file, duration, name, ext, directory, last_modified) 
             values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
@@ -146,10 +411,11 @@ impl DataBase {
 
         for r in rows.flatten() {
             let r_u64: u64 = r.parse().unwrap();
-            let file = track.file().unwrap();
-            let path = Path::new(file);
-            let timestamp = path.metadata().unwrap().modified().unwrap();
-            let timestamp_u64 = timestamp.duration_since(UNIX_EPOCH).unwrap().as_secs();
+            let timestamp_u64 = track
+                .last_modified
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
             if timestamp_u64 <= r_u64 {
                 return Ok(false);
             }
@@ -171,35 +437,56 @@ impl DataBase {
 
     pub fn sync_database(&mut self) {
         let mut track_vec: Vec<Track> = vec![];
-        let all_items = walkdir::WalkDir::new(self.path.as_path()).follow_links(true);
-        for record in all_items
+        let all_items: Vec<PathBuf> = walkdir::WalkDir::new(self.path.as_path())
+            .follow_links(true)
             .into_iter()
             .filter_map(std::result::Result::ok)
             .filter(|f| f.file_type().is_file())
-        {
-            let track = Track::read_from_path(record.path()).unwrap();
-            match self.need_update(&track) {
-                Ok(true) => {
-                    track_vec.push(track);
-                }
-                Ok(false) => {}
-                Err(e) => {
-                    eprintln!("Error in need_update: {}", e);
+            .map(walkdir::DirEntry::into_path)
+            .collect();
+        // the files split by a cue sheet are in as the tracks of the sheet
+        let split = cue::split_files(all_items.iter().map(PathBuf::as_path));
+        let mut seen: HashSet<String> = HashSet::new();
+        for record in all_items.iter().filter(|path| !split.contains(*path)) {
+            for track in cue::read_tracks(record).unwrap_or_default() {
+                seen.extend(track.file().map(ToString::to_string));
+                match self.need_update(&track) {
+                    Ok(true) => {
+                        track_vec.push(track);
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        eprintln!("Error in need_update: {}", e);
+                    }
                 }
             }
         }
+        // new files, by name and duration, to find out where the missing ones went
+        let mut new_files: HashMap<(String, u64), String> = HashMap::new();
+        for track in &track_vec {
+            if let (Some(name), Some(file)) = (track.name(), track.file()) {
+                new_files.insert(
+                    (name.to_string(), track.duration().as_secs()),
+                    file.to_string(),
+                );
+            }
+        }
         if !track_vec.is_empty() {
             self.add_records(track_vec).expect("add record error");
         }
 
         let mut track_vec2: Vec<String> = vec![];
+        let mut moved: HashMap<String, String> = HashMap::new();
 
         if let Ok(vec) = self.get_all_records() {
             for record in vec {
-                let path = Path::new(&record.file);
-                if path.exists() {
+                if seen.contains(&record.file) {
                     continue;
                 }
+                if let Some(file) = new_files.get(&(record.name.clone(), record.duration.as_secs()))
+                {
+                    moved.insert(record.file.clone(), file.clone());
+                }
                 track_vec2.push(record.file.clone());
             }
 
@@ -208,10 +495,33 @@ impl DataBase {
                     .expect("delete record error");
             }
         }
+
+        if let Err(e) = self.relocate_records(&moved) {
+            eprintln!("Error in relocate_records: {}", e);
+        }
+        if let Err(e) = NamedPlaylist::relocate(&moved) {
+            eprintln!("Error in relocating playlists: {}", e);
+        }
+    }
+
+    // ratings and play history follow a file that was moved
+    fn relocate_records(&mut self, moved: &HashMap<String, String>) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for (old, new) in moved {
+            tx.execute(
+                "UPDATE OR IGNORE track_rating SET file = ?2 WHERE file = ?1",
+                params![old, new],
+            )?;
+            tx.execute(
+                "UPDATE play_history SET file = ?2 WHERE file = ?1",
+                params![old, new],
+            )?;
+        }
+        tx.commit()
     }
 
     pub fn get_all_records(&mut self) -> Result<Vec<TrackForDB>> {
-        let mut stmt = self.conn.prepare("SELECT * FROM track")?;
+        let mut stmt = self.conn.prepare(SELECT_TRACKS)?;
         let vec: Vec<TrackForDB> = stmt
             .query_map([], |row| Ok(Self::track_db(row)))?
             .flatten()
@@ -223,8 +533,9 @@ impl DataBase {
         &mut self,
         str: &str,
         cri: &SearchCriteria,
+        order: TrackOrder,
     ) -> Result<Vec<TrackForDB>> {
-        let search_str = format!("SELECT * FROM track WHERE {} = ?", cri);
+        let search_str = format!("{} WHERE track.{} = ?{}", SELECT_TRACKS, cri, order.sql());
         let mut stmt = self.conn.prepare(&search_str)?;
 
         let mut vec_records: Vec<TrackForDB> = stmt
@@ -237,7 +548,38 @@ impl DataBase {
         // eprintln!("cri: {}", cri);
         // eprintln!("vec: {:?}", vec_records);
 
-        vec_records.sort_by_cached_key(|k| get_pin_yin(&k.name));
+        if order == TrackOrder::Name {
+            vec_records.sort_by_cached_key(|k| get_pin_yin(&k.name));
+        }
+        Ok(vec_records)
+    }
+
+    /// The tracks matching the rules of a smart playlist. Its own order picks
+    /// them, `order` sorts them afterwards unless it is by name.
+    pub fn get_smart_playlist(
+        &self,
+        playlist: &SmartPlaylist,
+        order: TrackOrder,
+    ) -> anyhow::Result<Vec<TrackForDB>> {
+        let (clause, values) = playlist.compile()?;
+        let mut stmt = self.conn.prepare(&format!("{}{}", SELECT_TRACKS, clause))?;
+        let mut vec_records: Vec<TrackForDB> = stmt
+            .query_map(params_from_iter(values.iter()), |row| {
+                Ok(Self::track_db(row))
+            })?
+            .flatten()
+            .collect();
+
+        match order {
+            TrackOrder::Name => {
+                if playlist.order == SmartOrder::Name {
+                    vec_records.sort_by_cached_key(|k| get_pin_yin(&k.name));
+                }
+            }
+            TrackOrder::PlayCount => vec_records.sort_by_key(|k| Reverse(k.play_count)),
+            TrackOrder::LastPlayed => vec_records.sort_by_key(|k| Reverse(k.last_played)),
+            TrackOrder::SkipCount => vec_records.sort_by_key(|k| Reverse(k.skip_count)),
+        }
         Ok(vec_records)
     }
 
@@ -255,7 +597,131 @@ impl DataBase {
             ext: row.get(8).unwrap(),
             directory: row.get(9).unwrap(),
             last_modified: row.get(10).unwrap(),
+            play_count: row.get(11).unwrap(),
+            skip_count: row.get(12).unwrap(),
+            last_played: row.get(13).unwrap(),
+            rating: row.get(14).unwrap(),
+            favourite: row.get(15).unwrap(),
+        }
+    }
+
+    /// The rating and favourite flag of a file, which doesn't have to be in the library.
+    pub fn get_rating(&self, file: &str) -> (u8, bool) {
+        self.conn
+            .query_row(
+                "SELECT rating, favourite FROM track_rating WHERE file = ?",
+                [file],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap_or((0, false))
+    }
+
+    pub fn set_rating(&self, file: &str, rating: u8) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO track_rating (file, rating) values (?1, ?2)
+             ON CONFLICT(file) DO UPDATE SET rating = excluded.rating",
+            params![file, rating.min(5)],
+        )?;
+        Ok(())
+    }
+
+    /// Seconds into the file where it was left off, 0 when it starts from the beginning.
+    pub fn get_track_position(&self, file: &str) -> u64 {
+        self.conn
+            .query_row(
+                "SELECT position FROM track_position WHERE file = ?",
+                [file],
+                |row| row.get(0),
+            )
+            .unwrap_or(0)
+    }
+
+    /// Remembers where the file was left off, 0 forgets it.
+    pub fn set_track_position(&self, file: &str, position: u64) -> Result<()> {
+        if position == 0 {
+            self.conn
+                .execute("DELETE FROM track_position WHERE file = ?", [file])?;
+        } else {
+            self.conn.execute(
+                "INSERT INTO track_position (file, position) values (?1, ?2)
+                 ON CONFLICT(file) DO UPDATE SET position = excluded.position",
+                params![file, position],
+            )?;
         }
+        Ok(())
+    }
+
+    /// The A-B loop of the file in seconds, if it has one.
+    pub fn get_ab_loop(&self, file: &str) -> Option<(f64, f64)> {
+        self.conn
+            .query_row("SELECT a, b FROM ab_loop WHERE file = ?", [file], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .ok()
+    }
+
+    /// Keeps the A-B loop of the file, none forgets it.
+    pub fn set_ab_loop(&self, file: &str, ab_loop: Option<(f64, f64)>) -> Result<()> {
+        match ab_loop {
+            Some((a, b)) => self.conn.execute(
+                "INSERT INTO ab_loop (file, a, b) values (?1, ?2, ?3)
+                 ON CONFLICT(file) DO UPDATE SET a = excluded.a, b = excluded.b",
+                params![file, a, b],
+            )?,
+            None => self
+                .conn
+                .execute("DELETE FROM ab_loop WHERE file = ?", [file])?,
+        };
+        Ok(())
+    }
+
+    pub fn set_favourite(&self, file: &str, favourite: bool) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO track_rating (file, favourite) values (?1, ?2)
+             ON CONFLICT(file) DO UPDATE SET favourite = excluded.favourite",
+            params![file, favourite],
+        )?;
+        Ok(())
+    }
+
+    pub fn add_play_event(&self, file: &str, event: PlayEvent) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO play_history (file, event, timestamp) values (?1, ?2, ?3)",
+            params![file, event.to_string(), now()],
+        )?;
+        Ok(())
+    }
+
+    /// The most played tracks, artists or albums of the period with their play counts.
+    pub fn get_top_played(
+        &self,
+        criteria: StatsCriteria,
+        period: StatsPeriod,
+        limit: u32,
+    ) -> Result<Vec<(String, u32)>> {
+        let group = match criteria {
+            // a missing tag would make the whole name NULL
+            StatsCriteria::Track => {
+                "IFNULL(track.artist, 'Unknown Artist') || ' - ' || IFNULL(track.title, 'Unknown Title')"
+            }
+            StatsCriteria::Artist => "IFNULL(track.artist, 'Unknown Artist')",
+            StatsCriteria::Album => "IFNULL(track.album, 'empty')",
+        };
+        let search_str = format!(
+            "SELECT {group}, COUNT(*) AS plays FROM play_history
+             JOIN track ON track.file = play_history.file
+             WHERE play_history.event = 'complete' AND play_history.timestamp >= ?1
+             GROUP BY {group} ORDER BY plays DESC LIMIT ?2",
+            group = group
+        );
+        let mut stmt = self.conn.prepare(&search_str)?;
+        let vec = stmt
+            .query_map(params![period.since(), limit], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .flatten()
+            .collect();
+        Ok(vec)
     }
 
     pub fn get_criterias(&mut self, cri: &SearchCriteria) -> Vec<String> {
@@ -278,4 +744,256 @@ impl DataBase {
         vec.sort_by_cached_key(|k| get_pin_yin(k));
         vec
     }
+
+    /// Subscribes to the feed, or updates it when it was subscribed already. Returns the id
+    /// of the podcast.
+    pub fn add_podcast(&mut self, url: &str, feed: &Feed) -> Result<u64> {
+        self.conn.execute(
+            "INSERT INTO podcast (url, title, description) values (?1, ?2, ?3)
+             ON CONFLICT(url) DO NOTHING",
+            params![url, feed.title, feed.description],
+        )?;
+        let id = self
+            .conn
+            .query_row("SELECT id FROM podcast WHERE url = ?", [url], |row| {
+                row.get(0)
+            })?;
+        self.update_podcast(id, feed)?;
+        Ok(id)
+    }
+
+    /// Takes the new episodes of the feed. Episodes it doesn't list anymore are kept, along
+    /// with the downloads and positions of all of them.
+    pub fn update_podcast(&mut self, id: u64, feed: &Feed) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "UPDATE podcast SET title = ?2, description = ?3 WHERE id = ?1",
+            params![id, feed.title, feed.description],
+        )?;
+        for episode in &feed.episodes {
+            tx.execute(
+                "INSERT INTO episode (podcast_id, guid, title, url, published, duration)
+                 values (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(podcast_id, guid) DO UPDATE SET title = excluded.title,
+                 url = excluded.url, published = excluded.published, duration = excluded.duration",
+                params![
+                    id,
+                    episode.guid,
+                    episode.title,
+                    episode.url,
+                    episode.published,
+                    episode.duration
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_podcasts(&self) -> Result<Vec<PodcastForDB>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT podcast.id, podcast.url, podcast.title,
+             COUNT(episode.id) - IFNULL(SUM(played), 0)
+             FROM podcast LEFT JOIN episode ON podcast.id = episode.podcast_id
+             GROUP BY podcast.id ORDER BY podcast.title COLLATE NOCASE",
+        )?;
+        let vec = stmt
+            .query_map([], |row| {
+                Ok(PodcastForDB {
+                    id: row.get(0)?,
+                    url: row.get(1)?,
+                    title: row.get(2)?,
+                    unplayed: row.get(3)?,
+                })
+            })?
+            .flatten()
+            .collect();
+        Ok(vec)
+    }
+
+    /// Unsubscribes, the downloads are left to the caller.
+    pub fn delete_podcast(&mut self, id: u64) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM episode WHERE podcast_id = ?", [id])?;
+        tx.execute("DELETE FROM podcast WHERE id = ?", [id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The episodes of the podcast, the newest first.
+    pub fn get_episodes(&self, podcast_id: u64) -> Result<Vec<EpisodeForDB>> {
+        let search_str = format!(
+            "{} WHERE podcast_id = ? ORDER BY published DESC, episode.id",
+            SELECT_EPISODES
+        );
+        let mut stmt = self.conn.prepare(&search_str)?;
+        let vec = stmt
+            .query_map([podcast_id], |row| Ok(Self::episode_db(row)))?
+            .flatten()
+            .collect();
+        Ok(vec)
+    }
+
+    /// The episode that is played from the url or downloaded file.
+    pub fn get_episode_by_location(&self, location: &str) -> Option<EpisodeForDB> {
+        let search_str = format!(
+            "{} WHERE episode.url = ?1 OR episode.file = ?1",
+            SELECT_EPISODES
+        );
+        self.conn
+            .query_row(&search_str, [location], |row| Ok(Self::episode_db(row)))
+            .ok()
+    }
+
+    fn episode_db(row: &Row) -> EpisodeForDB {
+        EpisodeForDB {
+            id: row.get(0).unwrap(),
+            podcast: row.get(1).unwrap(),
+            title: row.get(2).unwrap(),
+            url: row.get(3).unwrap(),
+            published: row.get(4).unwrap(),
+            duration: row.get(5).unwrap(),
+            file: row.get(6).unwrap(),
+            position: row.get(7).unwrap(),
+            played: row.get(8).unwrap(),
+        }
+    }
+
+    pub fn set_episode_file(&self, id: u64, file: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE episode SET file = ?2 WHERE id = ?1",
+            params![id, file],
+        )?;
+        Ok(())
+    }
+
+    /// Marks the episode played or not, it starts over from the beginning either way.
+    pub fn set_episode_played(&self, id: u64, played: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE episode SET played = ?2, position = 0 WHERE id = ?1",
+            params![id, played],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_episode_position(&self, id: u64, position: u64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE episode SET position = ?2 WHERE id = ?1",
+            params![id, position],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn db() -> DataBase {
+        let conn = Connection::open_in_memory().unwrap();
+        DataBase::with_connection(conn, PathBuf::new())
+    }
+
+    fn add_track(db: &DataBase, file: &str, artist: Option<&str>, title: Option<&str>) {
+        db.conn
+            .execute(
+                "INSERT INTO track (artist, title, album, file) values (?1, ?2, 'Album', ?3)",
+                params![artist, title, file],
+            )
+            .unwrap();
+    }
+
+    // an event of `days` ago
+    fn add_event(db: &DataBase, file: &str, event: PlayEvent, days: u64) {
+        db.conn
+            .execute(
+                "INSERT INTO play_history (file, event, timestamp) values (?1, ?2, ?3)",
+                params![file, event.to_string(), now() - days * 24 * 60 * 60],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_track_stats() {
+        let db = db();
+        add_event(&db, "a.mp3", PlayEvent::Start, 3);
+        add_event(&db, "a.mp3", PlayEvent::Complete, 3);
+        add_event(&db, "a.mp3", PlayEvent::Start, 1);
+        add_event(&db, "a.mp3", PlayEvent::Skip, 1);
+        add_event(&db, "a.mp3", PlayEvent::Start, 0);
+        add_event(&db, "a.mp3", PlayEvent::Complete, 0);
+        add_event(&db, "b.mp3", PlayEvent::Skip, 0);
+
+        let stats = |file: &str| -> (u32, u32, Option<u64>) {
+            db.conn
+                .query_row(
+                    "SELECT play_count, skip_count, last_played FROM track_stats WHERE file = ?",
+                    [file],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .unwrap()
+        };
+        let (plays, skips, last_played) = stats("a.mp3");
+        assert_eq!((plays, skips), (2, 1));
+        assert!(last_played.unwrap() + 60 > now());
+        assert_eq!(stats("b.mp3"), (0, 1, None));
+    }
+
+    #[test]
+    fn test_top_played() {
+        let db = db();
+        add_track(&db, "a.mp3", Some("Artist"), Some("A"));
+        add_track(&db, "b.mp3", Some("Artist"), Some("B"));
+        add_track(&db, "c.mp3", None, None);
+        for days in [0, 10, 100, 200] {
+            add_event(&db, "a.mp3", PlayEvent::Complete, days);
+        }
+        for days in [1, 2, 5] {
+            add_event(&db, "b.mp3", PlayEvent::Complete, days);
+        }
+        add_event(&db, "b.mp3", PlayEvent::Skip, 2);
+        add_event(&db, "c.mp3", PlayEvent::Complete, 20);
+
+        let top = |criteria, period| db.get_top_played(criteria, period, 10).unwrap();
+        let top_tracks = |period| top(StatsCriteria::Track, period);
+        let plays = |name: &str, count| (name.to_string(), count);
+        assert_eq!(
+            top_tracks(StatsPeriod::Week),
+            vec![plays("Artist - B", 3), plays("Artist - A", 1)]
+        );
+        assert_eq!(
+            top_tracks(StatsPeriod::Month),
+            vec![
+                plays("Artist - B", 3),
+                plays("Artist - A", 2),
+                plays("Unknown Artist - Unknown Title", 1),
+            ]
+        );
+        assert_eq!(top_tracks(StatsPeriod::AllTime)[0], plays("Artist - A", 4));
+        assert_eq!(
+            top(StatsCriteria::Artist, StatsPeriod::AllTime),
+            vec![plays("Artist", 7), plays("Unknown Artist", 1)]
+        );
+        assert_eq!(
+            top(StatsCriteria::Album, StatsPeriod::Week),
+            vec![plays("Album", 4)]
+        );
+    }
+
+    #[test]
+    fn test_ab_loop() {
+        let db = db();
+        assert_eq!(db.get_ab_loop("a.mp3"), None);
+        db.set_ab_loop("a.mp3", Some((10.0, 20.5))).unwrap();
+        db.set_ab_loop("b.mp3", Some((1.0, 2.0))).unwrap();
+        assert_eq!(db.get_ab_loop("a.mp3"), Some((10.0, 20.5)));
+
+        db.set_ab_loop("a.mp3", Some((30.0, 40.0))).unwrap();
+        assert_eq!(db.get_ab_loop("a.mp3"), Some((30.0, 40.0)));
+
+        db.set_ab_loop("a.mp3", None).unwrap();
+        assert_eq!(db.get_ab_loop("a.mp3"), None);
+        assert_eq!(db.get_ab_loop("b.mp3"), Some((1.0, 2.0)));
+    }
 }
@@ -21,10 +21,16 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
+use crate::chapter::{self, Chapter};
+use crate::cue;
+use crate::player::ReplayGain;
+use crate::playlist::PlaylistItem;
 use crate::songtag::lrc::Lyric;
 use anyhow::{bail, Result};
 use id3::frame::Lyrics;
-use lofty::id3::v2::{Frame, FrameFlags, FrameValue, ID3v2Tag, LanguageFrame, TextEncoding};
+use lofty::id3::v2::{
+    EncodedTextFrame, Frame, FrameFlags, FrameValue, ID3v2Tag, LanguageFrame, TextEncoding,
+};
 use lofty::{
     mp3::Mp3File, Accessor, AudioFile, FileType, ItemKey, ItemValue, Picture, PictureType, TagExt,
     TagItem,
@@ -75,11 +81,243 @@ pub struct Track {
     // Performer
     // Disc
     // Comment
+    replaygain: ReplayGainInfo,
+    /// Stars from 1 to 5, 0 when not rated
+    rating: u8,
+    /// The part of `audio_file` played, for the tracks of a CUE sheet
+    segment: Option<Segment>,
+    audio_file: Option<String>,
+    /// CUE sheet embedded in the tags
+    cuesheet: Option<String>,
+    chapters: Vec<Chapter>,
+}
+
+/// Where a track of a CUE sheet starts in its audio file, and where it ends. It goes on to the
+/// end of the file when there is no end.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Segment {
+    pub start: Duration,
+    pub end: Option<Duration>,
+}
+
+/// `ReplayGain` values read from the tags. Gains are in dB, peaks are linear.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ReplayGainInfo {
+    pub track_gain: Option<f32>,
+    pub track_peak: Option<f32>,
+    pub album_gain: Option<f32>,
+    pub album_peak: Option<f32>,
+}
+
+impl ReplayGainInfo {
+    /// Linear factor to multiply samples with. If the requested gain is missing, the other one
+    /// is used instead, and the result is lowered when needed so that the peak doesn't clip.
+    pub fn factor(&self, mode: ReplayGain, preamp: f32) -> f32 {
+        let (gain, peak) = match mode {
+            ReplayGain::Off => return 1.0,
+            ReplayGain::Track => (
+                self.track_gain.or(self.album_gain),
+                self.track_peak.or(self.album_peak),
+            ),
+            ReplayGain::Album => (
+                self.album_gain.or(self.track_gain),
+                self.album_peak.or(self.track_peak),
+            ),
+        };
+
+        let gain = match gain {
+            Some(g) => g,
+            None => return 1.0,
+        };
+
+        let mut factor = 10_f32.powf((gain + preamp) / 20.0);
+        if let Some(peak) = peak.filter(|p| *p > 0.0) {
+            factor = factor.min(1.0 / peak);
+        }
+        factor
+    }
+
+    fn parse_item(&mut self, key: &str, value: &str) {
+        // mp4 freeform atoms look like "----:com.apple.iTunes:replaygain_track_gain"
+        let key = key.rsplit(':').next().unwrap_or(key).to_uppercase();
+        match key.as_str() {
+            "REPLAYGAIN_TRACK_GAIN" => self.track_gain = parse_gain(value),
+            "REPLAYGAIN_TRACK_PEAK" => self.track_peak = value.trim().parse().ok(),
+            "REPLAYGAIN_ALBUM_GAIN" => self.album_gain = parse_gain(value),
+            "REPLAYGAIN_ALBUM_PEAK" => self.album_peak = value.trim().parse().ok(),
+            // Opus stores Q7.8 gains relative to -23 LUFS, ReplayGain uses -18 LUFS
+            "R128_TRACK_GAIN" => self.track_gain = parse_r128(value),
+            "R128_ALBUM_GAIN" => self.album_gain = parse_r128(value),
+            _ => {}
+        }
+    }
+
+    /// Tag items for the values that are set. Opus files only get the `R128_*` gains, as the
+    /// Opus spec asks for.
+    #[allow(clippy::cast_possible_truncation)]
+    fn items(&self, file_type: FileType) -> Vec<(String, String)> {
+        let mut items = Vec::new();
+        if file_type == FileType::Opus {
+            let r128 = |gain: f32| ((gain - 5.0) * 256.0).round().clamp(-32768.0, 32767.0) as i16;
+            if let Some(gain) = self.track_gain {
+                items.push(("R128_TRACK_GAIN", r128(gain).to_string()));
+            }
+            if let Some(gain) = self.album_gain {
+                items.push(("R128_ALBUM_GAIN", r128(gain).to_string()));
+            }
+        } else {
+            let values = [
+                (
+                    "REPLAYGAIN_TRACK_GAIN",
+                    self.track_gain.map(|g| format!("{:.2} dB", g)),
+                ),
+                (
+                    "REPLAYGAIN_TRACK_PEAK",
+                    self.track_peak.map(|p| format!("{:.6}", p)),
+                ),
+                (
+                    "REPLAYGAIN_ALBUM_GAIN",
+                    self.album_gain.map(|g| format!("{:.2} dB", g)),
+                ),
+                (
+                    "REPLAYGAIN_ALBUM_PEAK",
+                    self.album_peak.map(|p| format!("{:.6}", p)),
+                ),
+            ];
+            for (key, value) in values {
+                if let Some(value) = value {
+                    items.push((key, value));
+                }
+            }
+        }
+
+        items
+            .into_iter()
+            .map(|(key, value)| match file_type {
+                FileType::MP4 => (format!("----:com.apple.iTunes:{}", key), value),
+                _ => (key.to_string(), value),
+            })
+            .collect()
+    }
+}
+
+fn is_replaygain_key(key: &str) -> bool {
+    let key = key.rsplit(':').next().unwrap_or(key).to_uppercase();
+    key.starts_with("REPLAYGAIN_") || key.starts_with("R128_")
+}
+
+fn is_replaygain_frame(frame: &Frame) -> bool {
+    matches!(
+        frame.content(),
+        FrameValue::UserText(EncodedTextFrame { description, .. }) if is_replaygain_key(description)
+    )
+}
+
+// The email of the POPM frame termusic writes, the frames of other players are left as they are.
+const RATING_EMAIL: &str = "termusic";
+
+// ID3 has no standard for the stars, this is the mapping most players use.
+const fn popm_to_stars(popm: u8) -> u8 {
+    match popm {
+        0 => 0,
+        1..=31 => 1,
+        32..=95 => 2,
+        96..=159 => 3,
+        160..=223 => 4,
+        _ => 5,
+    }
+}
+
+const fn stars_to_popm(stars: u8) -> u8 {
+    match stars {
+        0 => 0,
+        1 => 1,
+        2 => 64,
+        3 => 128,
+        4 => 196,
+        _ => 255,
+    }
+}
+
+// FMPS_RATING goes from 0.0 to 1.0, RATING is 0 to 100 or, with some taggers, 1 to 5.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_rating(key: &str, value: &str) -> Option<u8> {
+    let value: f32 = value.trim().parse().ok()?;
+    let stars = match key.to_uppercase().as_str() {
+        "FMPS_RATING" => value * 5.0,
+        "RATING" if value <= 5.0 => value,
+        "RATING" => value / 20.0,
+        _ => return None,
+    };
+    Some(stars.round().clamp(0.0, 5.0) as u8)
+}
+
+fn is_rating_key(key: &str) -> bool {
+    matches!(key.to_uppercase().as_str(), "FMPS_RATING" | "RATING")
+}
+
+// Vorbis comments are the only other tags the rating is written to.
+const fn has_vorbis_comments(file_type: FileType) -> bool {
+    matches!(
+        file_type,
+        FileType::FLAC | FileType::Vorbis | FileType::Opus | FileType::Speex
+    )
+}
+
+fn is_own_popm(frame: &Frame) -> bool {
+    matches!(frame.content(), FrameValue::Popularimeter(popm) if popm.email == RATING_EMAIL)
+}
+
+// The stars of the POPM frame of termusic, or else of the first one of another player.
+fn popm_stars(tag: &ID3v2Tag) -> Option<u8> {
+    let ratings = tag.iter().filter_map(|frame| match frame.content() {
+        FrameValue::Popularimeter(popm) => Some((popm.email.as_str(), popm.rating)),
+        _ => None,
+    });
+    let mut rating = None;
+    for (email, popm) in ratings {
+        if email == RATING_EMAIL {
+            return Some(popm_to_stars(popm));
+        }
+        rating = rating.or(Some(popm_to_stars(popm)));
+    }
+    rating
+}
+
+// lofty reads POPM frames but refuses to write them back, so they are passed on as raw bytes.
+fn writable_frame(frame: &Frame) -> Frame {
+    if let FrameValue::Popularimeter(popm) = frame.content() {
+        if let Ok(raw) = Frame::new("POPM", FrameValue::Binary(popm.as_bytes()), *frame.flags()) {
+            return raw;
+        }
+    }
+    frame.clone()
+}
+
+fn parse_gain(value: &str) -> Option<f32> {
+    // "-6.54 dB"
+    value
+        .trim()
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn parse_r128(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .parse::<i16>()
+        .ok()
+        .map(|q| f32::from(q) / 256.0 + 5.0)
 }
 
 impl Track {
     pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
+        if let Some(location) = path.to_str().filter(|p| cue::is_cue_track(p)) {
+            return cue::read_track(location);
+        }
 
         let probe = lofty::Probe::open(path)?;
         let file_type = probe.file_type();
@@ -102,6 +340,29 @@ impl Track {
                 song.title = tag.title().map(str::to_string);
                 song.genre = tag.get_string(&ItemKey::Genre).map(str::to_string);
 
+                let mut comments = Vec::new();
+                for item in tag.items() {
+                    match (item.key(), item.value()) {
+                        (ItemKey::Unknown(key), ItemValue::Text(value)) => {
+                            comments.push((key.as_str(), value.as_str()));
+                            if key.eq_ignore_ascii_case("CUESHEET") {
+                                song.cuesheet = Some(value.clone());
+                            }
+                            song.replaygain.parse_item(key, value);
+                            if let Some(rating) = parse_rating(key, value) {
+                                song.rating = rating;
+                            }
+                        }
+                        (ItemKey::Popularimeter, ItemValue::Text(value)) => {
+                            if let Some(rating) = parse_rating("RATING", value) {
+                                song.rating = rating;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                song.chapters = chapter::from_comments(comments);
+
                 // Get all of the lyrics tags
                 let mut lyric_frames: Vec<Lyrics> = Vec::new();
                 match file_type {
@@ -110,6 +371,21 @@ impl Track {
                         let file = Mp3File::read_from(&mut reader, false)?;
 
                         if let Some(id3v2_tag) = file.id3v2_tag() {
+                            // ReplayGain is stored in TXXX frames, the description is the key
+                            for frame in id3v2_tag.iter() {
+                                if let FrameValue::UserText(EncodedTextFrame {
+                                    description,
+                                    content,
+                                    ..
+                                }) = frame.content()
+                                {
+                                    song.replaygain.parse_item(description, content);
+                                }
+                            }
+                            if let Some(stars) = popm_stars(id3v2_tag) {
+                                song.rating = stars;
+                            }
+
                             for lyrics_frame in id3v2_tag.unsync_text() {
                                 lyric_frames.push(Lyrics {
                                     lang: lyrics_frame.language.clone(),
@@ -142,6 +418,9 @@ impl Track {
                 song.picture = picture;
             }
         }
+        if file_type == Some(FileType::MP4) {
+            song.chapters = chapter::read_mp4(path).unwrap_or_default();
+        }
 
         let mut parent_folder: PathBuf = PathBuf::new();
 
@@ -167,7 +446,7 @@ impl Track {
 
     fn new<P: AsRef<Path>>(path: P) -> Self {
         let p = path.as_ref();
-        let directory = Some(p.parent().unwrap().to_string_lossy().into_owned());
+        let directory = p.parent().map(|d| d.to_string_lossy().into_owned());
         let ext = p.extension().and_then(OsStr::to_str).map(String::from);
         let artist = Some(String::from("Unsupported?"));
         let album = Some(String::from("Unsupported?"));
@@ -183,7 +462,10 @@ impl Track {
         let picture: Option<Picture> = None;
         let album_photo: Option<String> = None;
         let genre = Some(String::from("Unknown"));
-        let last_modified = p.metadata().unwrap().modified().unwrap();
+        let last_modified = p
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(std::time::UNIX_EPOCH);
         Self {
             ext,
             file_type: None,
@@ -201,9 +483,49 @@ impl Track {
             album_photo,
             last_modified,
             genre,
+            replaygain: ReplayGainInfo::default(),
+            rating: 0,
+            segment: None,
+            audio_file: None,
+            cuesheet: None,
+            chapters: Vec::new(),
         }
     }
 
+    /// Turns the track of a whole audio file into the track of a CUE sheet at `location`, which
+    /// plays the `segment` of the file.
+    pub fn into_cue_track(mut self, location: &str, name: &str, segment: Segment) -> Self {
+        self.audio_file = self.file.take();
+        self.file = Some(location.to_string());
+        self.name = Some(name.to_string());
+        self.duration = segment
+            .end
+            .unwrap_or(self.duration)
+            .saturating_sub(segment.start);
+        self.segment = Some(segment);
+        self.cuesheet = None;
+        self.chapters.clear();
+        // the lyrics of the whole file are out of time
+        self.lyric_frames.clear();
+        self.lyric_selected_index = 0;
+        self.parsed_lyric = None;
+        self
+    }
+
+    /// A track with what the playlist file knows about the entry, for streams or to spare
+    /// reading the tags of every file of a long playlist.
+    pub fn from_playlist_item(item: &PlaylistItem) -> Self {
+        let mut track = Self::new(&item.location);
+        track.artist = item.artist.clone();
+        track.album = None;
+        track.genre = None;
+        if item.title.is_some() {
+            track.title = item.title.clone();
+        }
+        track.duration = item.duration.unwrap_or_default();
+        track
+    }
+
     pub fn adjust_lyric_delay(&mut self, time_pos: i64, offset: i64) -> Result<()> {
         if let Some(lyric) = self.parsed_lyric.as_mut() {
             lyric.adjust_offset(time_pos, offset);
@@ -364,6 +686,24 @@ impl Track {
         // }
     }
 
+    /// The file to play, which is not the file of the track for the tracks of a CUE sheet.
+    pub fn audio_file(&self) -> Option<&str> {
+        self.audio_file.as_deref().or_else(|| self.file())
+    }
+
+    pub const fn segment(&self) -> Option<Segment> {
+        self.segment
+    }
+
+    /// Chapters of an audiobook or a podcast episode, in the order they play.
+    pub fn chapters(&self) -> &[Chapter] {
+        &self.chapters
+    }
+
+    pub fn cuesheet(&self) -> Option<&str> {
+        self.cuesheet.as_deref()
+    }
+
     pub fn directory(&self) -> Option<&str> {
         self.directory.as_deref()
         // match self.directory.as_ref() {
@@ -380,6 +720,22 @@ impl Track {
         // }
     }
 
+    pub const fn replaygain(&self) -> ReplayGainInfo {
+        self.replaygain
+    }
+
+    pub const fn rating(&self) -> u8 {
+        self.rating
+    }
+
+    pub fn set_rating(&mut self, rating: u8) {
+        self.rating = rating.min(5);
+    }
+
+    pub fn set_replaygain(&mut self, replaygain: ReplayGainInfo) {
+        self.replaygain = replaygain;
+    }
+
     pub const fn duration(&self) -> Duration {
         self.duration
     }
@@ -462,6 +818,9 @@ impl Track {
                         tag.insert_picture(any_picture);
                     }
 
+                    self.insert_replaygain_frames(&mut tag);
+                    self.insert_rating_frame(&mut tag);
+
                     tag.save_to_path(file_path)?;
                 }
             }
@@ -491,6 +850,11 @@ impl Track {
                         tag.push_picture(any_picture);
                     }
 
+                    if let Some(file_type) = self.file_type {
+                        self.push_replaygain_items(&mut tag, file_type);
+                        self.push_rating_items(&mut tag, file_type);
+                    }
+
                     tag.save_to_path(file_path)?;
                 }
             }
@@ -500,6 +864,160 @@ impl Track {
         Ok(())
     }
 
+    /// Writes the `ReplayGain` values into the file, without touching the other tags or the
+    /// file name.
+    pub fn save_replaygain(&self) -> Result<()> {
+        let file_path = match self.file() {
+            Some(f) => f,
+            None => return Ok(()),
+        };
+
+        match self.file_type {
+            Some(FileType::MP3) => {
+                let mut reader = BufReader::new(File::open(file_path)?);
+                let file = Mp3File::read_from(&mut reader, false)?;
+
+                let mut tag = ID3v2Tag::default();
+                if let Some(id3v2_tag) = file.id3v2_tag() {
+                    for frame in id3v2_tag.iter().filter(|f| !is_replaygain_frame(f)) {
+                        tag.insert(writable_frame(frame));
+                    }
+                }
+                self.insert_replaygain_frames(&mut tag);
+
+                tag.save_to_path(file_path)?;
+            }
+            // RIFF INFO and AIFF text chunks only have a fixed set of fields
+            Some(FileType::WAV | FileType::AIFF) => {
+                bail!("ReplayGain tags are not supported for this file type");
+            }
+            Some(file_type) => {
+                let mut tagged_file = lofty::read_from_path(file_path, false)?;
+                let tag_type = file_type.primary_tag_type();
+                if tagged_file.tag(&tag_type).is_none() {
+                    tagged_file.insert_tag(lofty::Tag::new(tag_type));
+                }
+
+                if let Some(tag) = tagged_file.tag_mut(&tag_type) {
+                    tag.retain_items(|item| match item.key() {
+                        ItemKey::Unknown(key) => !is_replaygain_key(key),
+                        _ => true,
+                    });
+                    self.push_replaygain_items(tag, file_type);
+                }
+
+                tagged_file.save_to_path(file_path)?;
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Writes the rating into the file, without touching the other tags or the file name.
+    /// The tracks of a CUE sheet share the audio file, their ratings are only kept in the
+    /// database.
+    pub fn save_rating(&self) -> Result<()> {
+        if self.audio_file.is_some() {
+            return Ok(());
+        }
+        let file_path = match self.file() {
+            Some(f) => f,
+            None => return Ok(()),
+        };
+
+        match self.file_type {
+            Some(FileType::MP3) => {
+                let mut reader = BufReader::new(File::open(file_path)?);
+                let file = Mp3File::read_from(&mut reader, false)?;
+
+                let mut tag = ID3v2Tag::default();
+                if let Some(id3v2_tag) = file.id3v2_tag() {
+                    for frame in id3v2_tag.iter().filter(|f| !is_own_popm(f)) {
+                        tag.insert(writable_frame(frame));
+                    }
+                }
+                self.insert_rating_frame(&mut tag);
+
+                tag.save_to_path(file_path)?;
+            }
+            Some(file_type) if has_vorbis_comments(file_type) => {
+                let mut tagged_file = lofty::read_from_path(file_path, false)?;
+                let tag_type = file_type.primary_tag_type();
+                if tagged_file.tag(&tag_type).is_none() {
+                    tagged_file.insert_tag(lofty::Tag::new(tag_type));
+                }
+
+                if let Some(tag) = tagged_file.tag_mut(&tag_type) {
+                    tag.retain_items(|item| match item.key() {
+                        ItemKey::Unknown(key) => !is_rating_key(key),
+                        ItemKey::Popularimeter => false,
+                        _ => true,
+                    });
+                    self.push_rating_items(tag, file_type);
+                }
+
+                tagged_file.save_to_path(file_path)?;
+            }
+            Some(_) => bail!("ratings can only be written to mp3, flac, ogg and opus files"),
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn insert_rating_frame(&self, tag: &mut ID3v2Tag) {
+        if self.rating == 0 {
+            return;
+        }
+        let mut content = format!("{}\0", RATING_EMAIL).into_bytes();
+        content.push(stars_to_popm(self.rating));
+        // the play counter, which is not kept in the file
+        content.extend([0; 4]);
+        if let Ok(frame) = Frame::new("POPM", FrameValue::Binary(content), FrameFlags::default()) {
+            tag.insert(frame);
+        }
+    }
+
+    fn push_rating_items(&self, tag: &mut lofty::Tag, file_type: FileType) {
+        if self.rating == 0 || !has_vorbis_comments(file_type) {
+            return;
+        }
+        let rating = f32::from(self.rating);
+        for (key, value) in [
+            ("FMPS_RATING", format!("{:.1}", rating / 5.0)),
+            ("RATING", format!("{}", self.rating * 20)),
+        ] {
+            tag.push_item_unchecked(TagItem::new(
+                ItemKey::Unknown(key.to_string()),
+                ItemValue::Text(value),
+            ));
+        }
+    }
+
+    fn insert_replaygain_frames(&self, tag: &mut ID3v2Tag) {
+        for (key, value) in self.replaygain.items(FileType::MP3) {
+            if let Ok(frame) = Frame::new(
+                "TXXX",
+                FrameValue::UserText(EncodedTextFrame {
+                    encoding: TextEncoding::UTF8,
+                    description: key,
+                    content: value,
+                }),
+                FrameFlags::default(),
+            ) {
+                tag.insert(frame);
+            }
+        }
+    }
+
+    fn push_replaygain_items(&self, tag: &mut lofty::Tag, file_type: FileType) {
+        for (key, value) in self.replaygain.items(file_type) {
+            // lofty refuses keys it doesn't know with the checked variants
+            tag.push_item_unchecked(TagItem::new(ItemKey::Unknown(key), ItemValue::Text(value)));
+        }
+    }
+
     fn rename_by_tag(&mut self) -> Result<()> {
         if let Some(ext) = self.ext() {
             let new_name = format!(
@@ -581,3 +1099,289 @@ fn create_lyrics(tag: &mut lofty::Tag, lyric_frames: &mut Vec<Lyrics>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_replaygain_parse_item() {
+        let mut info = ReplayGainInfo::default();
+        info.parse_item("REPLAYGAIN_TRACK_GAIN", "-6.54 dB");
+        info.parse_item("replaygain_track_peak", " 0.988 ");
+        info.parse_item("----:com.apple.iTunes:replaygain_album_gain", "+1.50 dB");
+        info.parse_item("COMMENT", "-3 dB");
+        assert_eq!(
+            info,
+            ReplayGainInfo {
+                track_gain: Some(-6.54),
+                track_peak: Some(0.988),
+                album_gain: Some(1.5),
+                album_peak: None,
+            }
+        );
+
+        // Q7.8 relative to -23 LUFS, so 0 is +5 dB in ReplayGain terms
+        let mut info = ReplayGainInfo::default();
+        info.parse_item("R128_TRACK_GAIN", "0");
+        info.parse_item("R128_ALBUM_GAIN", "-2816");
+        assert_eq!(info.track_gain, Some(5.0));
+        assert_eq!(info.album_gain, Some(-6.0));
+        info.parse_item("R128_TRACK_GAIN", "-6.54 dB");
+        assert_eq!(info.track_gain, None);
+    }
+
+    #[test]
+    fn test_replaygain_items() {
+        let info = ReplayGainInfo {
+            track_gain: Some(-6.54),
+            track_peak: Some(0.988),
+            album_gain: Some(1.5),
+            album_peak: Some(1.0),
+        };
+        let round_trip = |items: &[(String, String)]| {
+            let mut parsed = ReplayGainInfo::default();
+            for (key, value) in items {
+                parsed.parse_item(key, value);
+            }
+            parsed
+        };
+
+        let items = info.items(FileType::FLAC);
+        assert_eq!(
+            items,
+            vec![
+                ("REPLAYGAIN_TRACK_GAIN".to_string(), "-6.54 dB".to_string()),
+                ("REPLAYGAIN_TRACK_PEAK".to_string(), "0.988000".to_string()),
+                ("REPLAYGAIN_ALBUM_GAIN".to_string(), "1.50 dB".to_string()),
+                ("REPLAYGAIN_ALBUM_PEAK".to_string(), "1.000000".to_string()),
+            ]
+        );
+        assert_eq!(round_trip(&items), info);
+
+        // only the gains, in Q7.8 relative to -23 LUFS
+        let items = info.items(FileType::Opus);
+        assert_eq!(
+            items,
+            vec![
+                ("R128_TRACK_GAIN".to_string(), "-2954".to_string()),
+                ("R128_ALBUM_GAIN".to_string(), "-896".to_string()),
+            ]
+        );
+        let parsed = round_trip(&items);
+        // a step of Q7.8 is 1/256 dB
+        assert!((parsed.track_gain.unwrap() + 6.54).abs() < 1.0 / 256.0);
+        assert_close(parsed.album_gain.unwrap(), 1.5);
+        assert_eq!((parsed.track_peak, parsed.album_peak), (None, None));
+
+        let items = info.items(FileType::MP4);
+        assert!(items
+            .iter()
+            .all(|(key, _)| key.starts_with("----:com.apple.iTunes:REPLAYGAIN_")));
+        assert_eq!(items.len(), 4);
+        assert_eq!(round_trip(&items), info);
+
+        // TXXX frames with the key as their description
+        let mut track = Track::new("/music/a.mp3");
+        track.set_replaygain(info);
+        let mut tag = ID3v2Tag::default();
+        track.insert_replaygain_frames(&mut tag);
+        let mut parsed = ReplayGainInfo::default();
+        for frame in tag.iter() {
+            assert_eq!(frame.id_str(), "TXXX");
+            assert!(is_replaygain_frame(frame));
+            if let FrameValue::UserText(EncodedTextFrame {
+                description,
+                content,
+                ..
+            }) = frame.content()
+            {
+                parsed.parse_item(description, content);
+            }
+        }
+        assert_eq!(tag.iter().count(), 4);
+        assert_eq!(parsed, info);
+
+        // RIFF INFO and AIFF text chunks have no place for them, the file is left alone
+        for (file, file_type) in [
+            ("/nonexistent/a.wav", FileType::WAV),
+            ("/nonexistent/a.aiff", FileType::AIFF),
+        ] {
+            let mut track = Track::new(file);
+            track.file_type = Some(file_type);
+            track.set_replaygain(info);
+            assert_eq!(
+                track.save_replaygain().unwrap_err().to_string(),
+                "ReplayGain tags are not supported for this file type"
+            );
+        }
+    }
+
+    #[test]
+    fn test_replaygain_factor() {
+        let info = ReplayGainInfo {
+            track_gain: Some(-6.0),
+            track_peak: Some(0.5),
+            album_gain: Some(-12.0),
+            album_peak: None,
+        };
+        assert_close(info.factor(ReplayGain::Off, 3.0), 1.0);
+        assert_close(
+            info.factor(ReplayGain::Track, 0.0),
+            10_f32.powf(-6.0 / 20.0),
+        );
+        assert_close(
+            info.factor(ReplayGain::Album, 0.0),
+            10_f32.powf(-12.0 / 20.0),
+        );
+        assert_close(
+            info.factor(ReplayGain::Album, 6.0),
+            10_f32.powf(-6.0 / 20.0),
+        );
+        // +12 dB would take the peak of 0.5 to about 2, so it is held at 1
+        assert_close(info.factor(ReplayGain::Track, 18.0), 2.0);
+
+        // the other gain stands in for a missing one, and no gain leaves the track as it is
+        let track_only = ReplayGainInfo {
+            track_gain: Some(-3.0),
+            ..ReplayGainInfo::default()
+        };
+        assert_close(
+            track_only.factor(ReplayGain::Album, 0.0),
+            10_f32.powf(-3.0 / 20.0),
+        );
+        assert_close(
+            ReplayGainInfo::default().factor(ReplayGain::Track, 6.0),
+            1.0,
+        );
+    }
+
+    #[test]
+    fn test_popm_round_trip() {
+        for stars in 0..=5 {
+            assert_eq!(popm_to_stars(stars_to_popm(stars)), stars);
+        }
+        // the ranges other players write
+        for (popm, stars) in [(0, 0), (1, 1), (31, 1), (32, 2), (95, 2), (96, 3), (159, 3)] {
+            assert_eq!(popm_to_stars(popm), stars, "POPM {}", popm);
+        }
+        for (popm, stars) in [(160, 4), (223, 4), (224, 5), (255, 5)] {
+            assert_eq!(popm_to_stars(popm), stars, "POPM {}", popm);
+        }
+    }
+
+    #[test]
+    fn test_parse_rating() {
+        assert_eq!(parse_rating("FMPS_RATING", "0.6"), Some(3));
+        assert_eq!(parse_rating("fmps_rating", "1.0"), Some(5));
+        assert_eq!(parse_rating("FMPS_RATING", "0"), Some(0));
+        // 0 to 100
+        assert_eq!(parse_rating("RATING", "80"), Some(4));
+        assert_eq!(parse_rating("RATING", "100"), Some(5));
+        assert_eq!(parse_rating("RATING", "30"), Some(2));
+        // 1 to 5
+        assert_eq!(parse_rating("RATING", "3"), Some(3));
+        assert_eq!(parse_rating("RATING", " 5 "), Some(5));
+        assert_eq!(parse_rating("RATING", "255"), Some(5));
+        assert_eq!(parse_rating("RATING", "-1"), Some(0));
+        assert_eq!(parse_rating("RATING", "good"), None);
+        assert_eq!(parse_rating("COMMENT", "5"), None);
+    }
+
+    #[test]
+    fn test_popm_of_other_players() {
+        // a few silent MPEG-1 Layer III frames, 128 kbit/s at 44.1 kHz
+        let mut mp3 = Vec::new();
+        for _ in 0..4 {
+            mp3.extend([0xFF, 0xFB, 0x90, 0x64]);
+            mp3.extend([0; 413]);
+        }
+        let path = std::env::temp_dir().join(format!("termusic-popm-{}.mp3", std::process::id()));
+        std::fs::write(&path, mp3).unwrap();
+        let popm_frames = || {
+            let mut reader = BufReader::new(File::open(&path).unwrap());
+            let file = Mp3File::read_from(&mut reader, false).unwrap();
+            file.id3v2_tag()
+                .unwrap()
+                .iter()
+                .filter_map(|frame| match frame.content() {
+                    FrameValue::Popularimeter(popm) => Some((popm.email.clone(), popm.rating)),
+                    _ => None,
+                })
+                .collect::<Vec<(String, u8)>>()
+        };
+
+        // the rating of another player, with its play counter
+        let mut content = b"player@example.com\0".to_vec();
+        content.extend([64, 0, 0, 0, 9]);
+        let mut tag = ID3v2Tag::default();
+        tag.insert(Frame::new("POPM", FrameValue::Binary(content), FrameFlags::default()).unwrap());
+        tag.save_to_path(&path).unwrap();
+        let mut track = Track::read_from_path(&path).unwrap();
+        assert_eq!(track.rating(), 2);
+
+        // the rating of termusic goes before it, and replaces only its own frame
+        for stars in [5, 3] {
+            track.set_rating(stars);
+            track.save_rating().unwrap();
+            assert_eq!(Track::read_from_path(&path).unwrap().rating(), stars);
+            assert_eq!(
+                popm_frames(),
+                vec![
+                    ("player@example.com".to_string(), 64),
+                    (RATING_EMAIL.to_string(), stars_to_popm(stars)),
+                ]
+            );
+        }
+
+        // without a rating of its own, the one of the other player is read again
+        track.set_rating(0);
+        track.save_rating().unwrap();
+        assert_eq!(Track::read_from_path(&path).unwrap().rating(), 2);
+        assert_eq!(popm_frames(), vec![("player@example.com".to_string(), 64)]);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_rating_items_round_trip() {
+        for stars in 1..=5 {
+            let mut track = Track::new("/music/a.flac");
+            track.set_rating(stars);
+            let mut tag = lofty::Tag::new(lofty::TagType::VorbisComments);
+            track.push_rating_items(&mut tag, FileType::FLAC);
+            let ratings: Vec<Option<u8>> = tag
+                .items()
+                .iter()
+                .map(|item| match (item.key(), item.value()) {
+                    (ItemKey::Unknown(key), ItemValue::Text(value)) => parse_rating(key, value),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(ratings, vec![Some(stars); 2]);
+        }
+
+        // not written to the shared audio file, which doesn't exist here
+        let segment = Segment {
+            start: Duration::from_secs(60),
+            end: None,
+        };
+        let mut track = Track::new("/nonexistent/album.flac").into_cue_track(
+            "cue:///nonexistent/album.cue/2",
+            "2",
+            segment,
+        );
+        track.file_type = Some(FileType::FLAC);
+        track.set_rating(4);
+        assert!(track.save_rating().is_ok());
+    }
+}
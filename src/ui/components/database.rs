@@ -1,5 +1,9 @@
 use crate::config::{Keys, Settings};
+use crate::smart_playlist::SmartPlaylist;
+use crate::sqlite::{SearchCriteria, TrackForDB, TrackOrder};
+use crate::ui::components::rating_stars;
 use crate::ui::{DBMsg, Id, Model, Msg};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tui_realm_stdlib::List;
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::props::{Alignment, BorderType, TableBuilder, TextSpan};
@@ -62,6 +66,8 @@ impl DBListCriteria {
                         .add_col(TextSpan::from("Genre"))
                         .add_row()
                         .add_col(TextSpan::from("Directory"))
+                        .add_row()
+                        .add_col(TextSpan::from("Smart Playlists"))
                         .build(),
                 ),
             on_key_tab,
@@ -408,6 +414,9 @@ impl Component<Msg, NoUserEvent> for DBListSearchTracks {
             Event::Keyboard(keyevent) if keyevent == self.keys.database_add_all.key_event() => {
                 return Some(Msg::DataBase(DBMsg::AddAllToPlaylist))
             }
+            Event::Keyboard(keyevent) if keyevent == self.keys.database_sort.key_event() => {
+                return Some(Msg::DataBase(DBMsg::CycleOrder))
+            }
 
             Event::Keyboard(keyevent) if keyevent == self.keys.library_search.key_event() => {
                 return Some(Msg::GeneralSearch(crate::ui::GSMsg::PopupShowDatabase))
@@ -430,8 +439,20 @@ impl Model {
 
             table
                 .add_col(TextSpan::from(format!("{}", idx + 1)))
-                .add_col(TextSpan::from(" "))
-                .add_col(TextSpan::from(record.name.to_string()));
+                .add_col(TextSpan::from(" "));
+            if let Some(stat) = Self::database_track_stat(record, self.db_track_order) {
+                table.add_col(TextSpan::new(format!("[{}] ", stat)).fg(Color::LightYellow));
+            }
+            table.add_col(TextSpan::from(record.name.to_string()));
+            if record.rating > 0 || record.favourite {
+                table.add_col(
+                    TextSpan::new(format!(
+                        " {}",
+                        rating_stars(record.rating, record.favourite)
+                    ))
+                    .fg(Color::LightRed),
+                );
+            }
         }
         if self.db_search_results.is_empty() {
             table.add_col(TextSpan::from("empty results"));
@@ -478,21 +499,107 @@ impl Model {
     }
 
     pub fn database_update_search_results(&mut self) {
-        self.db_search_results = self.db.get_criterias(&self.db_criteria);
+        self.database_load_search_results();
+        self.app.active(&Id::DBListSearchResult).ok();
+    }
+
+    fn database_load_search_results(&mut self) {
+        if let SearchCriteria::SmartPlaylist = self.db_criteria {
+            // read again every time, so that edited definitions show up
+            self.db_smart_playlists = match SmartPlaylist::load_all() {
+                Ok(playlists) => playlists,
+                Err(e) => {
+                    self.mount_error_popup(format!("smart playlist error: {:#}", e).as_str());
+                    Vec::new()
+                }
+            };
+            self.db_search_results = self
+                .db_smart_playlists
+                .iter()
+                .map(|p| p.name.clone())
+                .collect();
+        } else {
+            self.db_search_results = self.db.get_criterias(&self.db_criteria);
+        }
         // eprintln!("{:?}", self.db_search_results);
         self.database_sync_results();
-        self.app.active(&Id::DBListSearchResult).ok();
+    }
+
+    // The value the tracks are sorted by, next to their names.
+    fn database_track_stat(record: &TrackForDB, order: TrackOrder) -> Option<String> {
+        match order {
+            TrackOrder::Name => None,
+            TrackOrder::PlayCount => Some(format!("{} plays", record.play_count)),
+            TrackOrder::SkipCount => Some(format!("{} skips", record.skip_count)),
+            TrackOrder::LastPlayed => {
+                let stat = match record.last_played {
+                    Some(time) => {
+                        let now = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs();
+                        match now.saturating_sub(time) / (24 * 60 * 60) {
+                            0 => "today".to_string(),
+                            1 => "yesterday".to_string(),
+                            days => format!("{} days ago", days),
+                        }
+                    }
+                    None => "never".to_string(),
+                };
+                Some(stat)
+            }
+        }
+    }
+
+    pub fn database_update_tracks_title(&mut self) {
+        let title = match self.db_track_order {
+            TrackOrder::Name => " Tracks ".to_string(),
+            order => format!(" Tracks by {} ", order),
+        };
+        self.app
+            .attr(
+                &Id::DBListSearchTracks,
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
+            )
+            .ok();
     }
 
     pub fn database_update_search_tracks(&mut self, index: usize) {
-        if let Ok(vec) = self
-            .db
-            .get_record_by_criteria(&self.db_search_results[index], &self.db_criteria)
-        {
+        self.database_load_search_tracks(index);
+        self.app.active(&Id::DBListSearchTracks).ok();
+    }
+
+    fn database_load_search_tracks(&mut self, index: usize) {
+        if let SearchCriteria::SmartPlaylist = self.db_criteria {
+            if let Some(playlist) = self.db_smart_playlists.get(index) {
+                match self.db.get_smart_playlist(playlist, self.db_track_order) {
+                    Ok(vec) => self.db_search_tracks = vec,
+                    Err(e) => {
+                        self.db_search_tracks = Vec::new();
+                        self.mount_error_popup(format!("smart playlist error: {:#}", e).as_str());
+                    }
+                }
+            }
+        } else if let Ok(vec) = self.db.get_record_by_criteria(
+            &self.db_search_results[index],
+            &self.db_criteria,
+            self.db_track_order,
+        ) {
             self.db_search_tracks = vec;
         };
+        self.db_search_index = Some(index);
         self.database_sync_tracks();
-        self.app.active(&Id::DBListSearchTracks).ok();
+    }
+
+    /// Picks the tracks of the shown smart playlist again, once the library changed.
+    pub fn database_refresh_smart_playlist(&mut self, index: Option<usize>) {
+        if let SearchCriteria::SmartPlaylist = self.db_criteria {
+            self.database_load_search_results();
+            if let Some(index) = index {
+                self.database_load_search_tracks(index);
+            }
+        }
     }
 
     #[allow(unused)]
@@ -561,8 +668,10 @@ impl Model {
 
         self.db_search_results = vec![];
         self.db_search_tracks = vec![];
+        self.db_search_index = None;
         self.database_sync_tracks();
         self.database_sync_results();
+        self.database_update_tracks_title();
 
         // if focus_database {
         //     assert!(self.app.active(&Id::DBListCriteria).is_ok());
@@ -112,6 +112,38 @@ impl KEInput {
             IdKeyEditor::GlobalLayoutDatabaseInput => keys.global_layout_database.key(),
             IdKeyEditor::DatabaseAddAllInput => keys.database_add_all.key(),
             IdKeyEditor::GlobalPlayerToggleGaplessInput => keys.global_player_toggle_gapless.key(),
+            IdKeyEditor::LibraryReplaygainScanInput => keys.library_replaygain_scan.key(),
+            IdKeyEditor::GlobalEqualizerOpenInput => keys.global_equalizer_open.key(),
+            IdKeyEditor::GlobalOutputDeviceOpenInput => keys.global_output_device_open.key(),
+            IdKeyEditor::GlobalStatsOpenInput => keys.global_stats_open.key(),
+            IdKeyEditor::DatabaseSortInput => keys.database_sort.key(),
+            IdKeyEditor::GlobalRatingUpInput => keys.global_rating_up.key(),
+            IdKeyEditor::GlobalRatingDownInput => keys.global_rating_down.key(),
+            IdKeyEditor::GlobalFavouriteToggleInput => keys.global_favourite_toggle.key(),
+            IdKeyEditor::GlobalLayoutPlaylistsInput => keys.global_layout_playlists.key(),
+            IdKeyEditor::GlobalNamedPlaylistAppendInput => keys.global_named_playlist_append.key(),
+            IdKeyEditor::NamedPlaylistNewInput => keys.named_playlist_new.key(),
+            IdKeyEditor::NamedPlaylistRenameInput => keys.named_playlist_rename.key(),
+            IdKeyEditor::NamedPlaylistDuplicateInput => keys.named_playlist_duplicate.key(),
+            IdKeyEditor::NamedPlaylistDeleteInput => keys.named_playlist_delete.key(),
+            IdKeyEditor::NamedPlaylistLoadInput => keys.named_playlist_load.key(),
+            IdKeyEditor::PlaylistExportInput => keys.playlist_export.key(),
+            IdKeyEditor::NamedPlaylistExportInput => keys.named_playlist_export.key(),
+            IdKeyEditor::GlobalRadioBrowserOpenInput => keys.global_radio_browser_open.key(),
+            IdKeyEditor::GlobalLayoutPodcastsInput => keys.global_layout_podcasts.key(),
+            IdKeyEditor::PodcastAddInput => keys.podcast_add.key(),
+            IdKeyEditor::PodcastDeleteInput => keys.podcast_delete.key(),
+            IdKeyEditor::PodcastRefreshInput => keys.podcast_refresh.key(),
+            IdKeyEditor::PodcastRefreshAllInput => keys.podcast_refresh_all.key(),
+            IdKeyEditor::PodcastDownloadInput => keys.podcast_download.key(),
+            IdKeyEditor::PodcastMarkPlayedInput => keys.podcast_mark_played.key(),
+            IdKeyEditor::GlobalSleepTimerOpenInput => keys.global_sleep_timer_open.key(),
+            IdKeyEditor::GlobalPlayerAbLoopInput => keys.global_player_ab_loop.key(),
+            IdKeyEditor::GlobalPlayerChapterNextInput => keys.global_player_chapter_next.key(),
+            IdKeyEditor::GlobalPlayerChapterPreviousInput => {
+                keys.global_player_chapter_previous.key()
+            }
+            IdKeyEditor::GlobalChapterListOpenInput => keys.global_chapter_list_open.key(),
             _ => "".to_string(),
         }
     }
@@ -1260,3 +1292,753 @@ impl Component<Msg, NoUserEvent> for KEGlobalPlayerToggleGaplessInput {
         self.component.on(ev)
     }
 }
+
+#[derive(MockComponent)]
+pub struct KELibraryReplaygainScanInput {
+    component: KEInput,
+}
+
+impl KELibraryReplaygainScanInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::LibraryReplaygainScanInput,
+                keys,
+                Msg::KeyEditor(KEMsg::LibraryReplaygainScanInputBlurDown),
+                Msg::KeyEditor(KEMsg::LibraryReplaygainScanInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KELibraryReplaygainScanInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalEqualizerOpenInput {
+    component: KEInput,
+}
+
+impl KEGlobalEqualizerOpenInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalEqualizerOpenInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalEqualizerOpenInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalEqualizerOpenInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalEqualizerOpenInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalOutputDeviceOpenInput {
+    component: KEInput,
+}
+
+impl KEGlobalOutputDeviceOpenInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalOutputDeviceOpenInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalOutputDeviceOpenInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalOutputDeviceOpenInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalOutputDeviceOpenInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalStatsOpenInput {
+    component: KEInput,
+}
+
+impl KEGlobalStatsOpenInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalStatsOpenInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalStatsOpenInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalStatsOpenInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalStatsOpenInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEDatabaseSortInput {
+    component: KEInput,
+}
+
+impl KEDatabaseSortInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::DatabaseSortInput,
+                keys,
+                Msg::KeyEditor(KEMsg::DatabaseSortInputBlurDown),
+                Msg::KeyEditor(KEMsg::DatabaseSortInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEDatabaseSortInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalRatingUpInput {
+    component: KEInput,
+}
+
+impl KEGlobalRatingUpInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalRatingUpInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalRatingUpInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalRatingUpInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalRatingUpInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalRatingDownInput {
+    component: KEInput,
+}
+
+impl KEGlobalRatingDownInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalRatingDownInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalRatingDownInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalRatingDownInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalRatingDownInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalFavouriteToggleInput {
+    component: KEInput,
+}
+
+impl KEGlobalFavouriteToggleInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalFavouriteToggleInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalFavouriteToggleInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalFavouriteToggleInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalFavouriteToggleInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalLayoutPlaylistsInput {
+    component: KEInput,
+}
+
+impl KEGlobalLayoutPlaylistsInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalLayoutPlaylistsInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalLayoutPlaylistsInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalLayoutPlaylistsInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalLayoutPlaylistsInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalNamedPlaylistAppendInput {
+    component: KEInput,
+}
+
+impl KEGlobalNamedPlaylistAppendInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalNamedPlaylistAppendInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalNamedPlaylistAppendInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalNamedPlaylistAppendInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalNamedPlaylistAppendInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistNewInput {
+    component: KEInput,
+}
+
+impl KENamedPlaylistNewInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::NamedPlaylistNewInput,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistNewInputBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistNewInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistNewInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistRenameInput {
+    component: KEInput,
+}
+
+impl KENamedPlaylistRenameInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::NamedPlaylistRenameInput,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistRenameInputBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistRenameInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistRenameInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistDuplicateInput {
+    component: KEInput,
+}
+
+impl KENamedPlaylistDuplicateInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::NamedPlaylistDuplicateInput,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistDuplicateInputBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistDuplicateInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistDuplicateInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistDeleteInput {
+    component: KEInput,
+}
+
+impl KENamedPlaylistDeleteInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::NamedPlaylistDeleteInput,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistDeleteInputBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistDeleteInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistDeleteInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistLoadInput {
+    component: KEInput,
+}
+
+impl KENamedPlaylistLoadInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::NamedPlaylistLoadInput,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistLoadInputBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistLoadInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistLoadInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPlaylistExportInput {
+    component: KEInput,
+}
+
+impl KEPlaylistExportInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PlaylistExportInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PlaylistExportInputBlurDown),
+                Msg::KeyEditor(KEMsg::PlaylistExportInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPlaylistExportInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistExportInput {
+    component: KEInput,
+}
+
+impl KENamedPlaylistExportInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::NamedPlaylistExportInput,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistExportInputBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistExportInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistExportInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalRadioBrowserOpenInput {
+    component: KEInput,
+}
+
+impl KEGlobalRadioBrowserOpenInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalRadioBrowserOpenInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalRadioBrowserOpenInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalRadioBrowserOpenInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalRadioBrowserOpenInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalLayoutPodcastsInput {
+    component: KEInput,
+}
+
+impl KEGlobalLayoutPodcastsInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalLayoutPodcastsInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalLayoutPodcastsInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalLayoutPodcastsInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalLayoutPodcastsInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastAddInput {
+    component: KEInput,
+}
+
+impl KEPodcastAddInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PodcastAddInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastAddInputBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastAddInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastAddInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastDeleteInput {
+    component: KEInput,
+}
+
+impl KEPodcastDeleteInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PodcastDeleteInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastDeleteInputBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastDeleteInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastDeleteInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastRefreshInput {
+    component: KEInput,
+}
+
+impl KEPodcastRefreshInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PodcastRefreshInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastRefreshInputBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastRefreshInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastRefreshInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastRefreshAllInput {
+    component: KEInput,
+}
+
+impl KEPodcastRefreshAllInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PodcastRefreshAllInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastRefreshAllInputBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastRefreshAllInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastRefreshAllInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastDownloadInput {
+    component: KEInput,
+}
+
+impl KEPodcastDownloadInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PodcastDownloadInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastDownloadInputBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastDownloadInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastDownloadInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastMarkPlayedInput {
+    component: KEInput,
+}
+
+impl KEPodcastMarkPlayedInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::PodcastMarkPlayedInput,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastMarkPlayedInputBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastMarkPlayedInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastMarkPlayedInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalSleepTimerOpenInput {
+    component: KEInput,
+}
+
+impl KEGlobalSleepTimerOpenInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalSleepTimerOpenInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalSleepTimerOpenInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalSleepTimerOpenInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalSleepTimerOpenInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalPlayerAbLoopInput {
+    component: KEInput,
+}
+
+impl KEGlobalPlayerAbLoopInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalPlayerAbLoopInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalPlayerAbLoopInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalPlayerAbLoopInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalPlayerAbLoopInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalPlayerChapterNextInput {
+    component: KEInput,
+}
+
+impl KEGlobalPlayerChapterNextInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalPlayerChapterNextInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterNextInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterNextInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalPlayerChapterNextInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalPlayerChapterPreviousInput {
+    component: KEInput,
+}
+
+impl KEGlobalPlayerChapterPreviousInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalPlayerChapterPreviousInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterPreviousInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterPreviousInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalPlayerChapterPreviousInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalChapterListOpenInput {
+    component: KEInput,
+}
+
+impl KEGlobalChapterListOpenInput {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KEInput::new(
+                "",
+                IdKeyEditor::GlobalChapterListOpenInput,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalChapterListOpenInputBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalChapterListOpenInputBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalChapterListOpenInput {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
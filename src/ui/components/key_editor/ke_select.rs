@@ -174,6 +174,38 @@ impl KESelectModifier {
             IdKeyEditor::GlobalLayoutDatabase => keys.global_layout_database.modifier(),
             IdKeyEditor::DatabaseAddAll => keys.database_add_all.modifier(),
             IdKeyEditor::GlobalPlayerToggleGapless => keys.global_player_toggle_gapless.modifier(),
+            IdKeyEditor::LibraryReplaygainScan => keys.library_replaygain_scan.modifier(),
+            IdKeyEditor::GlobalEqualizerOpen => keys.global_equalizer_open.modifier(),
+            IdKeyEditor::GlobalOutputDeviceOpen => keys.global_output_device_open.modifier(),
+            IdKeyEditor::GlobalStatsOpen => keys.global_stats_open.modifier(),
+            IdKeyEditor::DatabaseSort => keys.database_sort.modifier(),
+            IdKeyEditor::GlobalRatingUp => keys.global_rating_up.modifier(),
+            IdKeyEditor::GlobalRatingDown => keys.global_rating_down.modifier(),
+            IdKeyEditor::GlobalFavouriteToggle => keys.global_favourite_toggle.modifier(),
+            IdKeyEditor::GlobalLayoutPlaylists => keys.global_layout_playlists.modifier(),
+            IdKeyEditor::GlobalNamedPlaylistAppend => keys.global_named_playlist_append.modifier(),
+            IdKeyEditor::NamedPlaylistNew => keys.named_playlist_new.modifier(),
+            IdKeyEditor::NamedPlaylistRename => keys.named_playlist_rename.modifier(),
+            IdKeyEditor::NamedPlaylistDuplicate => keys.named_playlist_duplicate.modifier(),
+            IdKeyEditor::NamedPlaylistDelete => keys.named_playlist_delete.modifier(),
+            IdKeyEditor::NamedPlaylistLoad => keys.named_playlist_load.modifier(),
+            IdKeyEditor::PlaylistExport => keys.playlist_export.modifier(),
+            IdKeyEditor::NamedPlaylistExport => keys.named_playlist_export.modifier(),
+            IdKeyEditor::GlobalRadioBrowserOpen => keys.global_radio_browser_open.modifier(),
+            IdKeyEditor::GlobalLayoutPodcasts => keys.global_layout_podcasts.modifier(),
+            IdKeyEditor::PodcastAdd => keys.podcast_add.modifier(),
+            IdKeyEditor::PodcastDelete => keys.podcast_delete.modifier(),
+            IdKeyEditor::PodcastRefresh => keys.podcast_refresh.modifier(),
+            IdKeyEditor::PodcastRefreshAll => keys.podcast_refresh_all.modifier(),
+            IdKeyEditor::PodcastDownload => keys.podcast_download.modifier(),
+            IdKeyEditor::PodcastMarkPlayed => keys.podcast_mark_played.modifier(),
+            IdKeyEditor::GlobalSleepTimerOpen => keys.global_sleep_timer_open.modifier(),
+            IdKeyEditor::GlobalPlayerAbLoop => keys.global_player_ab_loop.modifier(),
+            IdKeyEditor::GlobalPlayerChapterNext => keys.global_player_chapter_next.modifier(),
+            IdKeyEditor::GlobalPlayerChapterPrevious => {
+                keys.global_player_chapter_previous.modifier()
+            }
+            IdKeyEditor::GlobalChapterListOpen => keys.global_chapter_list_open.modifier(),
             _ => 0,
         }
     }
@@ -1273,3 +1305,753 @@ impl Component<Msg, NoUserEvent> for KEGlobalPlayerToggleGapless {
         self.component.on(ev)
     }
 }
+
+#[derive(MockComponent)]
+pub struct KELibraryReplaygainScan {
+    component: KESelectModifier,
+}
+
+impl KELibraryReplaygainScan {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "L ReplayGain Scan",
+                IdKeyEditor::LibraryReplaygainScan,
+                keys,
+                Msg::KeyEditor(KEMsg::LibraryReplaygainScanBlurDown),
+                Msg::KeyEditor(KEMsg::LibraryReplaygainScanBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KELibraryReplaygainScan {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalEqualizerOpen {
+    component: KESelectModifier,
+}
+
+impl KEGlobalEqualizerOpen {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Equalizer",
+                IdKeyEditor::GlobalEqualizerOpen,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalEqualizerOpenBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalEqualizerOpenBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalEqualizerOpen {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalOutputDeviceOpen {
+    component: KESelectModifier,
+}
+
+impl KEGlobalOutputDeviceOpen {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Output Device",
+                IdKeyEditor::GlobalOutputDeviceOpen,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalOutputDeviceOpenBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalOutputDeviceOpenBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalOutputDeviceOpen {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalStatsOpen {
+    component: KESelectModifier,
+}
+
+impl KEGlobalStatsOpen {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Stats",
+                IdKeyEditor::GlobalStatsOpen,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalStatsOpenBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalStatsOpenBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalStatsOpen {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEDatabaseSort {
+    component: KESelectModifier,
+}
+
+impl KEDatabaseSort {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "DB Sort",
+                IdKeyEditor::DatabaseSort,
+                keys,
+                Msg::KeyEditor(KEMsg::DatabaseSortBlurDown),
+                Msg::KeyEditor(KEMsg::DatabaseSortBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEDatabaseSort {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalRatingUp {
+    component: KESelectModifier,
+}
+
+impl KEGlobalRatingUp {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Rating Up",
+                IdKeyEditor::GlobalRatingUp,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalRatingUpBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalRatingUpBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalRatingUp {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalRatingDown {
+    component: KESelectModifier,
+}
+
+impl KEGlobalRatingDown {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Rating Down",
+                IdKeyEditor::GlobalRatingDown,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalRatingDownBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalRatingDownBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalRatingDown {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalFavouriteToggle {
+    component: KESelectModifier,
+}
+
+impl KEGlobalFavouriteToggle {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Favourite",
+                IdKeyEditor::GlobalFavouriteToggle,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalFavouriteToggleBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalFavouriteToggleBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalFavouriteToggle {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalLayoutPlaylists {
+    component: KESelectModifier,
+}
+
+impl KEGlobalLayoutPlaylists {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Layout Playlists",
+                IdKeyEditor::GlobalLayoutPlaylists,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalLayoutPlaylistsBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalLayoutPlaylistsBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalLayoutPlaylists {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalNamedPlaylistAppend {
+    component: KESelectModifier,
+}
+
+impl KEGlobalNamedPlaylistAppend {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Add To Playlist",
+                IdKeyEditor::GlobalNamedPlaylistAppend,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalNamedPlaylistAppendBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalNamedPlaylistAppendBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalNamedPlaylistAppend {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistNew {
+    component: KESelectModifier,
+}
+
+impl KENamedPlaylistNew {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "NP New",
+                IdKeyEditor::NamedPlaylistNew,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistNewBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistNewBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistNew {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistRename {
+    component: KESelectModifier,
+}
+
+impl KENamedPlaylistRename {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "NP Rename",
+                IdKeyEditor::NamedPlaylistRename,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistRenameBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistRenameBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistRename {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistDuplicate {
+    component: KESelectModifier,
+}
+
+impl KENamedPlaylistDuplicate {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "NP Duplicate",
+                IdKeyEditor::NamedPlaylistDuplicate,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistDuplicateBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistDuplicateBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistDuplicate {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistDelete {
+    component: KESelectModifier,
+}
+
+impl KENamedPlaylistDelete {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "NP Delete",
+                IdKeyEditor::NamedPlaylistDelete,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistDeleteBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistDeleteBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistDelete {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistLoad {
+    component: KESelectModifier,
+}
+
+impl KENamedPlaylistLoad {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "NP Load",
+                IdKeyEditor::NamedPlaylistLoad,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistLoadBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistLoadBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistLoad {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPlaylistExport {
+    component: KESelectModifier,
+}
+
+impl KEPlaylistExport {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "P Export",
+                IdKeyEditor::PlaylistExport,
+                keys,
+                Msg::KeyEditor(KEMsg::PlaylistExportBlurDown),
+                Msg::KeyEditor(KEMsg::PlaylistExportBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPlaylistExport {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KENamedPlaylistExport {
+    component: KESelectModifier,
+}
+
+impl KENamedPlaylistExport {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "NP Export",
+                IdKeyEditor::NamedPlaylistExport,
+                keys,
+                Msg::KeyEditor(KEMsg::NamedPlaylistExportBlurDown),
+                Msg::KeyEditor(KEMsg::NamedPlaylistExportBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KENamedPlaylistExport {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalRadioBrowserOpen {
+    component: KESelectModifier,
+}
+
+impl KEGlobalRadioBrowserOpen {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Radio",
+                IdKeyEditor::GlobalRadioBrowserOpen,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalRadioBrowserOpenBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalRadioBrowserOpenBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalRadioBrowserOpen {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalLayoutPodcasts {
+    component: KESelectModifier,
+}
+
+impl KEGlobalLayoutPodcasts {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Layout Podcasts",
+                IdKeyEditor::GlobalLayoutPodcasts,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalLayoutPodcastsBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalLayoutPodcastsBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalLayoutPodcasts {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastAdd {
+    component: KESelectModifier,
+}
+
+impl KEPodcastAdd {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "Podcast Add",
+                IdKeyEditor::PodcastAdd,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastAddBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastAddBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastAdd {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastDelete {
+    component: KESelectModifier,
+}
+
+impl KEPodcastDelete {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "Podcast Delete",
+                IdKeyEditor::PodcastDelete,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastDeleteBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastDeleteBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastDelete {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastRefresh {
+    component: KESelectModifier,
+}
+
+impl KEPodcastRefresh {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "Podcast Refresh",
+                IdKeyEditor::PodcastRefresh,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastRefreshBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastRefreshBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastRefresh {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastRefreshAll {
+    component: KESelectModifier,
+}
+
+impl KEPodcastRefreshAll {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "Podcast Refresh All",
+                IdKeyEditor::PodcastRefreshAll,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastRefreshAllBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastRefreshAllBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastRefreshAll {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastDownload {
+    component: KESelectModifier,
+}
+
+impl KEPodcastDownload {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "Podcast Download",
+                IdKeyEditor::PodcastDownload,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastDownloadBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastDownloadBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastDownload {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEPodcastMarkPlayed {
+    component: KESelectModifier,
+}
+
+impl KEPodcastMarkPlayed {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "Podcast Played",
+                IdKeyEditor::PodcastMarkPlayed,
+                keys,
+                Msg::KeyEditor(KEMsg::PodcastMarkPlayedBlurDown),
+                Msg::KeyEditor(KEMsg::PodcastMarkPlayedBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEPodcastMarkPlayed {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalSleepTimerOpen {
+    component: KESelectModifier,
+}
+
+impl KEGlobalSleepTimerOpen {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Sleep Timer",
+                IdKeyEditor::GlobalSleepTimerOpen,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalSleepTimerOpenBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalSleepTimerOpenBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalSleepTimerOpen {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalPlayerAbLoop {
+    component: KESelectModifier,
+}
+
+impl KEGlobalPlayerAbLoop {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G P A-B Loop",
+                IdKeyEditor::GlobalPlayerAbLoop,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalPlayerAbLoopBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalPlayerAbLoopBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalPlayerAbLoop {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalPlayerChapterNext {
+    component: KESelectModifier,
+}
+
+impl KEGlobalPlayerChapterNext {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G P Chapter Next",
+                IdKeyEditor::GlobalPlayerChapterNext,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterNextBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterNextBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalPlayerChapterNext {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalPlayerChapterPrevious {
+    component: KESelectModifier,
+}
+
+impl KEGlobalPlayerChapterPrevious {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G P Chapter Prev",
+                IdKeyEditor::GlobalPlayerChapterPrevious,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterPreviousBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalPlayerChapterPreviousBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalPlayerChapterPrevious {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct KEGlobalChapterListOpen {
+    component: KESelectModifier,
+}
+
+impl KEGlobalChapterListOpen {
+    pub fn new(keys: &Keys) -> Self {
+        Self {
+            component: KESelectModifier::new(
+                "G Chapter List",
+                IdKeyEditor::GlobalChapterListOpen,
+                keys,
+                Msg::KeyEditor(KEMsg::GlobalChapterListOpenBlurDown),
+                Msg::KeyEditor(KEMsg::GlobalChapterListOpenBlurUp),
+            ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for KEGlobalChapterListOpen {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        self.component.on(ev)
+    }
+}
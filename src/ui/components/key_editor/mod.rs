@@ -25,6 +25,121 @@ pub const CONTROL_ALT_SHIFT: KeyModifiers = KeyModifiers::from_bits_truncate(
     KeyModifiers::ALT.bits() | KeyModifiers::CONTROL.bits() | KeyModifiers::SHIFT.bits(),
 );
 
+// the select and input of the keys that don't fit on the first page of the editor
+pub const KE_SECOND_PAGE: [(IdKeyEditor, IdKeyEditor); 30] = [
+    (
+        IdKeyEditor::LibraryReplaygainScan,
+        IdKeyEditor::LibraryReplaygainScanInput,
+    ),
+    (
+        IdKeyEditor::GlobalEqualizerOpen,
+        IdKeyEditor::GlobalEqualizerOpenInput,
+    ),
+    (
+        IdKeyEditor::GlobalOutputDeviceOpen,
+        IdKeyEditor::GlobalOutputDeviceOpenInput,
+    ),
+    (
+        IdKeyEditor::GlobalStatsOpen,
+        IdKeyEditor::GlobalStatsOpenInput,
+    ),
+    (IdKeyEditor::DatabaseSort, IdKeyEditor::DatabaseSortInput),
+    (
+        IdKeyEditor::GlobalRatingUp,
+        IdKeyEditor::GlobalRatingUpInput,
+    ),
+    (
+        IdKeyEditor::GlobalRatingDown,
+        IdKeyEditor::GlobalRatingDownInput,
+    ),
+    (
+        IdKeyEditor::GlobalFavouriteToggle,
+        IdKeyEditor::GlobalFavouriteToggleInput,
+    ),
+    (
+        IdKeyEditor::GlobalLayoutPlaylists,
+        IdKeyEditor::GlobalLayoutPlaylistsInput,
+    ),
+    (
+        IdKeyEditor::GlobalNamedPlaylistAppend,
+        IdKeyEditor::GlobalNamedPlaylistAppendInput,
+    ),
+    (
+        IdKeyEditor::NamedPlaylistNew,
+        IdKeyEditor::NamedPlaylistNewInput,
+    ),
+    (
+        IdKeyEditor::NamedPlaylistRename,
+        IdKeyEditor::NamedPlaylistRenameInput,
+    ),
+    (
+        IdKeyEditor::NamedPlaylistDuplicate,
+        IdKeyEditor::NamedPlaylistDuplicateInput,
+    ),
+    (
+        IdKeyEditor::NamedPlaylistDelete,
+        IdKeyEditor::NamedPlaylistDeleteInput,
+    ),
+    (
+        IdKeyEditor::NamedPlaylistLoad,
+        IdKeyEditor::NamedPlaylistLoadInput,
+    ),
+    (
+        IdKeyEditor::PlaylistExport,
+        IdKeyEditor::PlaylistExportInput,
+    ),
+    (
+        IdKeyEditor::NamedPlaylistExport,
+        IdKeyEditor::NamedPlaylistExportInput,
+    ),
+    (
+        IdKeyEditor::GlobalRadioBrowserOpen,
+        IdKeyEditor::GlobalRadioBrowserOpenInput,
+    ),
+    (
+        IdKeyEditor::GlobalLayoutPodcasts,
+        IdKeyEditor::GlobalLayoutPodcastsInput,
+    ),
+    (IdKeyEditor::PodcastAdd, IdKeyEditor::PodcastAddInput),
+    (IdKeyEditor::PodcastDelete, IdKeyEditor::PodcastDeleteInput),
+    (
+        IdKeyEditor::PodcastRefresh,
+        IdKeyEditor::PodcastRefreshInput,
+    ),
+    (
+        IdKeyEditor::PodcastRefreshAll,
+        IdKeyEditor::PodcastRefreshAllInput,
+    ),
+    (
+        IdKeyEditor::PodcastDownload,
+        IdKeyEditor::PodcastDownloadInput,
+    ),
+    (
+        IdKeyEditor::PodcastMarkPlayed,
+        IdKeyEditor::PodcastMarkPlayedInput,
+    ),
+    (
+        IdKeyEditor::GlobalSleepTimerOpen,
+        IdKeyEditor::GlobalSleepTimerOpenInput,
+    ),
+    (
+        IdKeyEditor::GlobalPlayerAbLoop,
+        IdKeyEditor::GlobalPlayerAbLoopInput,
+    ),
+    (
+        IdKeyEditor::GlobalPlayerChapterNext,
+        IdKeyEditor::GlobalPlayerChapterNextInput,
+    ),
+    (
+        IdKeyEditor::GlobalPlayerChapterPrevious,
+        IdKeyEditor::GlobalPlayerChapterPreviousInput,
+    ),
+    (
+        IdKeyEditor::GlobalChapterListOpen,
+        IdKeyEditor::GlobalChapterListOpenInput,
+    ),
+];
+
 #[derive(MockComponent)]
 pub struct KERadioOk {
     component: Radio,
@@ -485,6 +600,250 @@ impl Model {
                 self.ke_key_config.global_player_toggle_gapless =
                     BindingForEvent { code, modifiers }
             }
+
+            IdKeyEditor::LibraryReplaygainScan | IdKeyEditor::LibraryReplaygainScanInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::LibraryReplaygainScan,
+                    IdKeyEditor::LibraryReplaygainScanInput,
+                );
+                self.ke_key_config.library_replaygain_scan = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalEqualizerOpen | IdKeyEditor::GlobalEqualizerOpenInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalEqualizerOpen,
+                    IdKeyEditor::GlobalEqualizerOpenInput,
+                );
+                self.ke_key_config.global_equalizer_open = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalOutputDeviceOpen | IdKeyEditor::GlobalOutputDeviceOpenInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalOutputDeviceOpen,
+                    IdKeyEditor::GlobalOutputDeviceOpenInput,
+                );
+                self.ke_key_config.global_output_device_open = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalStatsOpen | IdKeyEditor::GlobalStatsOpenInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalStatsOpen,
+                    IdKeyEditor::GlobalStatsOpenInput,
+                );
+                self.ke_key_config.global_stats_open = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::DatabaseSort | IdKeyEditor::DatabaseSortInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::DatabaseSort,
+                    IdKeyEditor::DatabaseSortInput,
+                );
+                self.ke_key_config.database_sort = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalRatingUp | IdKeyEditor::GlobalRatingUpInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalRatingUp,
+                    IdKeyEditor::GlobalRatingUpInput,
+                );
+                self.ke_key_config.global_rating_up = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalRatingDown | IdKeyEditor::GlobalRatingDownInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalRatingDown,
+                    IdKeyEditor::GlobalRatingDownInput,
+                );
+                self.ke_key_config.global_rating_down = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalFavouriteToggle | IdKeyEditor::GlobalFavouriteToggleInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalFavouriteToggle,
+                    IdKeyEditor::GlobalFavouriteToggleInput,
+                );
+                self.ke_key_config.global_favourite_toggle = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalLayoutPlaylists | IdKeyEditor::GlobalLayoutPlaylistsInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalLayoutPlaylists,
+                    IdKeyEditor::GlobalLayoutPlaylistsInput,
+                );
+                self.ke_key_config.global_layout_playlists = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalNamedPlaylistAppend
+            | IdKeyEditor::GlobalNamedPlaylistAppendInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalNamedPlaylistAppend,
+                    IdKeyEditor::GlobalNamedPlaylistAppendInput,
+                );
+                self.ke_key_config.global_named_playlist_append =
+                    BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::NamedPlaylistNew | IdKeyEditor::NamedPlaylistNewInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::NamedPlaylistNew,
+                    IdKeyEditor::NamedPlaylistNewInput,
+                );
+                self.ke_key_config.named_playlist_new = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::NamedPlaylistRename | IdKeyEditor::NamedPlaylistRenameInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::NamedPlaylistRename,
+                    IdKeyEditor::NamedPlaylistRenameInput,
+                );
+                self.ke_key_config.named_playlist_rename = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::NamedPlaylistDuplicate | IdKeyEditor::NamedPlaylistDuplicateInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::NamedPlaylistDuplicate,
+                    IdKeyEditor::NamedPlaylistDuplicateInput,
+                );
+                self.ke_key_config.named_playlist_duplicate = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::NamedPlaylistDelete | IdKeyEditor::NamedPlaylistDeleteInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::NamedPlaylistDelete,
+                    IdKeyEditor::NamedPlaylistDeleteInput,
+                );
+                self.ke_key_config.named_playlist_delete = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::NamedPlaylistLoad | IdKeyEditor::NamedPlaylistLoadInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::NamedPlaylistLoad,
+                    IdKeyEditor::NamedPlaylistLoadInput,
+                );
+                self.ke_key_config.named_playlist_load = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PlaylistExport | IdKeyEditor::PlaylistExportInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PlaylistExport,
+                    IdKeyEditor::PlaylistExportInput,
+                );
+                self.ke_key_config.playlist_export = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::NamedPlaylistExport | IdKeyEditor::NamedPlaylistExportInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::NamedPlaylistExport,
+                    IdKeyEditor::NamedPlaylistExportInput,
+                );
+                self.ke_key_config.named_playlist_export = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalRadioBrowserOpen | IdKeyEditor::GlobalRadioBrowserOpenInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalRadioBrowserOpen,
+                    IdKeyEditor::GlobalRadioBrowserOpenInput,
+                );
+                self.ke_key_config.global_radio_browser_open = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalLayoutPodcasts | IdKeyEditor::GlobalLayoutPodcastsInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalLayoutPodcasts,
+                    IdKeyEditor::GlobalLayoutPodcastsInput,
+                );
+                self.ke_key_config.global_layout_podcasts = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PodcastAdd | IdKeyEditor::PodcastAddInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PodcastAdd,
+                    IdKeyEditor::PodcastAddInput,
+                );
+                self.ke_key_config.podcast_add = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PodcastDelete | IdKeyEditor::PodcastDeleteInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PodcastDelete,
+                    IdKeyEditor::PodcastDeleteInput,
+                );
+                self.ke_key_config.podcast_delete = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PodcastRefresh | IdKeyEditor::PodcastRefreshInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PodcastRefresh,
+                    IdKeyEditor::PodcastRefreshInput,
+                );
+                self.ke_key_config.podcast_refresh = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PodcastRefreshAll | IdKeyEditor::PodcastRefreshAllInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PodcastRefreshAll,
+                    IdKeyEditor::PodcastRefreshAllInput,
+                );
+                self.ke_key_config.podcast_refresh_all = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PodcastDownload | IdKeyEditor::PodcastDownloadInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PodcastDownload,
+                    IdKeyEditor::PodcastDownloadInput,
+                );
+                self.ke_key_config.podcast_download = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::PodcastMarkPlayed | IdKeyEditor::PodcastMarkPlayedInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::PodcastMarkPlayed,
+                    IdKeyEditor::PodcastMarkPlayedInput,
+                );
+                self.ke_key_config.podcast_mark_played = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalSleepTimerOpen | IdKeyEditor::GlobalSleepTimerOpenInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalSleepTimerOpen,
+                    IdKeyEditor::GlobalSleepTimerOpenInput,
+                );
+                self.ke_key_config.global_sleep_timer_open = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalPlayerAbLoop | IdKeyEditor::GlobalPlayerAbLoopInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalPlayerAbLoop,
+                    IdKeyEditor::GlobalPlayerAbLoopInput,
+                );
+                self.ke_key_config.global_player_ab_loop = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalPlayerChapterNext | IdKeyEditor::GlobalPlayerChapterNextInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalPlayerChapterNext,
+                    IdKeyEditor::GlobalPlayerChapterNextInput,
+                );
+                self.ke_key_config.global_player_chapter_next = BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalPlayerChapterPrevious
+            | IdKeyEditor::GlobalPlayerChapterPreviousInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalPlayerChapterPrevious,
+                    IdKeyEditor::GlobalPlayerChapterPreviousInput,
+                );
+                self.ke_key_config.global_player_chapter_previous =
+                    BindingForEvent { code, modifiers }
+            }
+
+            IdKeyEditor::GlobalChapterListOpen | IdKeyEditor::GlobalChapterListOpenInput => {
+                let (code, modifiers) = self.extract_key_mod_and_code(
+                    IdKeyEditor::GlobalChapterListOpen,
+                    IdKeyEditor::GlobalChapterListOpenInput,
+                );
+                self.ke_key_config.global_chapter_list_open = BindingForEvent { code, modifiers }
+            }
             _ => {}
         }
     }
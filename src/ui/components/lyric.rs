@@ -89,6 +89,20 @@ impl Model {
             }
         };
 
+        // radios tell what they play instead
+        if let Some(title) = &self.player.stream_title {
+            self.app
+                .attr(
+                    &Id::Lyric,
+                    Attribute::Text,
+                    AttrValue::Payload(PropPayload::Vec(vec![PropValue::TextSpan(
+                        TextSpan::from(format!("On air: {}", title)),
+                    )])),
+                )
+                .ok();
+            return;
+        }
+
         if song.lyric_frames_is_empty() {
             self.app
                 .attr(
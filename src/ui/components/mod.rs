@@ -29,9 +29,12 @@ mod key_editor;
 mod label;
 mod lyric;
 mod music_library;
+mod named_playlist;
 mod playlist;
+mod podcast;
 mod popups;
 mod progress;
+mod radio_browser;
 mod tag_editor;
 mod xywh;
 mod youtube_search;
@@ -41,12 +44,16 @@ pub use general_search::{GSInputPopup, GSTablePopup, Source};
 pub use label::Label;
 pub use lyric::Lyric;
 pub use music_library::MusicLibrary;
+pub use named_playlist::{NPDeleteConfirmPopup, NPList, NPNameInputPopup, NPPickPopup, NPTracks};
 pub use playlist::Playlist;
+pub use podcast::{PCAddPopup, PCDeletePopup, PCEpisodes, PCFeeds};
 pub use popups::{
-    DeleteConfirmInputPopup, DeleteConfirmRadioPopup, ErrorPopup, HelpPopup, MessagePopup,
-    QuitPopup,
+    ChapterPopup, DeleteConfirmInputPopup, DeleteConfirmRadioPopup, EqualizerPopup, ErrorPopup,
+    HelpPopup, MessagePopup, OutputDevicePopup, PlaylistExportPopup, QuitPopup, SleepTimerPopup,
+    StatsPopup, STATS_CRITERIAS, STATS_PERIODS,
 };
 pub use progress::Progress;
+pub use radio_browser::{RBInputPopup, RBTablePopup};
 pub use youtube_search::{YSInputPopup, YSTablePopup};
 //Tag Editor Controls
 pub use color_editor::{
@@ -66,11 +73,14 @@ pub use tag_editor::{
 pub use xywh::Xywh;
 
 use crate::config::Keys;
-use crate::player::{Loop, PlayerTrait, Status};
+use crate::player::{PlayerTrait, Status};
 // #[cfg(any(feature = "mpris", feature = "discord"))]
 // use crate::track::Track;
 use crate::ui::model::TermusicLayout;
-use crate::ui::{CEMsg, GSMsg, Id, KEMsg, Model, Msg, PLMsg, YSMsg};
+use crate::ui::{
+    CEMsg, CHMsg, EQMsg, GSMsg, Id, KEMsg, Model, Msg, NPMsg, ODMsg, PLMsg, RBMsg, RTMsg, STMsg,
+    YSMsg,
+};
 use tui_realm_stdlib::Phantom;
 use tuirealm::event::NoUserEvent;
 use tuirealm::props::{Alignment, AttrValue, Attribute, Borders, Color, Style};
@@ -197,12 +207,96 @@ impl Component<Msg, NoUserEvent> for GlobalListener {
                 Some(Msg::LayoutDataBase)
             }
 
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_layout_playlists.key_event() =>
+            {
+                Some(Msg::LayoutPlaylists)
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_layout_podcasts.key_event() =>
+            {
+                Some(Msg::LayoutPodcasts)
+            }
+
             Event::Keyboard(keyevent)
                 if keyevent == self.keys.global_player_toggle_gapless.key_event() =>
             {
                 Some(Msg::PlayerToggleGapless)
             }
 
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_equalizer_open.key_event() =>
+            {
+                Some(Msg::Equalizer(EQMsg::PopupShow))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_output_device_open.key_event() =>
+            {
+                Some(Msg::OutputDevice(ODMsg::PopupShow))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_stats_open.key_event() => {
+                Some(Msg::Stats(STMsg::PopupShow))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_rating_up.key_event() => {
+                Some(Msg::Rating(RTMsg::Up))
+            }
+
+            Event::Keyboard(keyevent) if keyevent == self.keys.global_rating_down.key_event() => {
+                Some(Msg::Rating(RTMsg::Down))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_favourite_toggle.key_event() =>
+            {
+                Some(Msg::Rating(RTMsg::ToggleFavourite))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_named_playlist_append.key_event() =>
+            {
+                Some(Msg::NamedPlaylist(NPMsg::AppendShow))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_radio_browser_open.key_event() =>
+            {
+                Some(Msg::RadioBrowser(RBMsg::InputPopupShow))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_sleep_timer_open.key_event() =>
+            {
+                Some(Msg::SleepTimerPopupShow)
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_ab_loop.key_event() =>
+            {
+                Some(Msg::PlayerAbLoop)
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_chapter_next.key_event() =>
+            {
+                Some(Msg::Chapter(CHMsg::Next))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_player_chapter_previous.key_event() =>
+            {
+                Some(Msg::Chapter(CHMsg::Previous))
+            }
+
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.global_chapter_list_open.key_event() =>
+            {
+                Some(Msg::Chapter(CHMsg::PopupShow))
+            }
+
             _ => None,
         }
     }
@@ -296,10 +390,70 @@ impl Model {
                 SubEventClause::Keyboard(keys.global_layout_database.key_event()),
                 SubClause::Always,
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_layout_playlists.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_layout_podcasts.key_event()),
+                SubClause::Always,
+            ),
             Sub::new(
                 SubEventClause::Keyboard(keys.global_player_toggle_gapless.key_event()),
                 SubClause::Always,
             ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_equalizer_open.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_output_device_open.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_stats_open.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_rating_up.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_rating_down.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_favourite_toggle.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_named_playlist_append.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_radio_browser_open.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_sleep_timer_open.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_ab_loop.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_chapter_next.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_player_chapter_previous.key_event()),
+                SubClause::Always,
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(keys.global_chapter_list_open.key_event()),
+                SubClause::Always,
+            ),
             Sub::new(SubEventClause::WindowResize, SubClause::Always),
         ]
     }
@@ -308,11 +462,7 @@ impl Model {
         self.time_pos = 0;
         self.player.set_status(Status::Stopped);
         self.player.playlist.current_track = None;
-        self.player.stop();
-        self.player
-            .message_tx
-            .send(crate::player::PlayerMsg::Progress(0, 60))
-            .ok();
+        self.progress_update(0, 60);
         if let Err(e) = self.update_photo() {
             self.mount_error_popup(format!("update photo error: {}", e).as_str());
         };
@@ -343,22 +493,7 @@ impl Model {
     }
 
     pub fn player_previous(&mut self) {
-        if let Loop::Single | Loop::Queue = self.config.loop_mode {
-            return;
-        }
-
-        if self.player.playlist.is_empty() {
-            self.player_stop();
-            return;
-        }
-
-        if let Some(song) = self.player.playlist.tracks.pop_back() {
-            self.player.playlist.tracks.push_front(song);
-        }
-        if let Some(song) = self.player.playlist.tracks.pop_back() {
-            self.player.playlist.tracks.push_front(song);
-        }
-        self.player.skip();
+        self.player.previous();
     }
 
     pub fn player_toggle_pause(&mut self) {
@@ -385,24 +520,7 @@ impl Model {
     }
 
     pub fn player_seek(&mut self, offset: i64) {
-        // FIXME: dirty fix for seeking when paused with symphonia,basically set it to play
-        // in rusty sink code, and seek, and then set it back to pause.
-        #[cfg(not(any(feature = "mpv", feature = "gst")))]
-        let paused = self.player.is_paused();
-        #[cfg(not(any(feature = "mpv", feature = "gst")))]
-        if paused {
-            self.player.set_volume(0);
-        }
-
         self.player.seek(offset).ok();
-
-        #[cfg(not(any(feature = "mpv", feature = "gst")))]
-        if paused {
-            self.force_redraw();
-            std::thread::sleep(std::time::Duration::from_millis(50));
-            self.player.pause();
-            self.player.set_volume(self.config.volume);
-        }
     }
 
     pub fn global_fix_focus(&mut self) {
@@ -437,10 +555,36 @@ impl Model {
             }
         }
 
+        if let Ok(f) = self.app.query(&Id::NamedPlaylistList, Attribute::Focus) {
+            if Some(AttrValue::Flag(true)) == f {
+                focus = true;
+            }
+        }
+
+        if let Ok(f) = self.app.query(&Id::NamedPlaylistTracks, Attribute::Focus) {
+            if Some(AttrValue::Flag(true)) == f {
+                focus = true;
+            }
+        }
+
+        if let Ok(f) = self.app.query(&Id::PodcastFeeds, Attribute::Focus) {
+            if Some(AttrValue::Flag(true)) == f {
+                focus = true;
+            }
+        }
+
+        if let Ok(f) = self.app.query(&Id::PodcastEpisodes, Attribute::Focus) {
+            if Some(AttrValue::Flag(true)) == f {
+                focus = true;
+            }
+        }
+
         if !focus {
             match self.layout {
                 TermusicLayout::TreeView => self.app.active(&Id::Library).ok(),
                 TermusicLayout::DataBase => self.app.active(&Id::DBListCriteria).ok(),
+                TermusicLayout::Playlists => self.app.active(&Id::NamedPlaylistList).ok(),
+                TermusicLayout::Podcasts => self.app.active(&Id::PodcastFeeds).ok(),
             };
         }
     }
@@ -461,6 +605,12 @@ pub fn get_block<'a>(props: &Borders, title: (String, Alignment), focus: bool) -
 }
 
 // Draw an area (WxH / 3) in the middle of the parent area
+/// Filled stars for the rating, with a heart in front for favourites.
+pub fn rating_stars(rating: u8, favourite: bool) -> String {
+    let heart = if favourite { "♥ " } else { "" };
+    format!("{}{}", heart, "★".repeat(rating.into()))
+}
+
 pub fn draw_area_in_relative(parent: Rect, width: u16, height: u16) -> Rect {
     let new_area = Layout::default()
         .direction(Direction::Vertical)
@@ -1,4 +1,7 @@
 use crate::config::{Keys, Settings};
+use crate::cue::{self, CueSheet};
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+use crate::ui::model::UpdateComponents;
 use crate::ui::model::MAX_DEPTH;
 use crate::ui::{Id, LIMsg, Model, Msg, TEMsg, YSMsg};
 use crate::utils::get_pin_yin;
@@ -88,6 +91,18 @@ impl MusicLibrary {
         }
         CmdResult::None
     }
+
+    // Directories open, and so do cue sheets until they are open. An open cue sheet is added
+    // to the playlist like the files are.
+    fn opens(&self, node_id: &str) -> bool {
+        Path::new(node_id).is_dir()
+            || self
+                .component
+                .tree()
+                .root()
+                .query(&node_id.to_string())
+                .is_some_and(|node| !node.is_leaf() && self.component.tree_state().is_closed(node))
+    }
 }
 
 impl Component<Msg, NoUserEvent> for MusicLibrary {
@@ -106,8 +121,7 @@ impl Component<Msg, NoUserEvent> for MusicLibrary {
                 modifiers: KeyModifiers::NONE,
             }) => {
                 let current_node = self.component.tree_state().selected().unwrap();
-                let p: &Path = Path::new(current_node);
-                if p.is_dir() {
+                if self.opens(current_node) {
                     self.perform(Cmd::Custom(TREE_CMD_OPEN))
                 } else {
                     return Some(Msg::Playlist(crate::ui::PLMsg::Add(
@@ -117,8 +131,7 @@ impl Component<Msg, NoUserEvent> for MusicLibrary {
             }
             Event::Keyboard(keyevent) if keyevent == self.keys.global_right.key_event() => {
                 let current_node = self.component.tree_state().selected().unwrap();
-                let p: &Path = Path::new(current_node);
-                if p.is_dir() {
+                if self.opens(current_node) {
                     self.perform(Cmd::Custom(TREE_CMD_OPEN))
                 } else {
                     return Some(Msg::Playlist(crate::ui::PLMsg::Add(
@@ -202,6 +215,14 @@ impl Component<Msg, NoUserEvent> for MusicLibrary {
                     current_node.to_string(),
                 )));
             }
+            Event::Keyboard(keyevent)
+                if keyevent == self.keys.library_replaygain_scan.key_event() =>
+            {
+                let current_node = self.component.tree_state().selected().unwrap();
+                return Some(Msg::Library(LIMsg::ReplayGainScan(
+                    current_node.to_string(),
+                )));
+            }
 
             _ => CmdResult::None,
         };
@@ -245,6 +266,18 @@ impl Model {
                 }
             }
         }
+        // the tracks of a cue sheet are under it
+        if depth > 0 && cue::is_cue_sheet(p) {
+            if let Ok(sheet) = CueSheet::read(p) {
+                for track in sheet.tracks {
+                    let title = track.title.unwrap_or_default();
+                    node.add_child(Node::new(
+                        cue::location(p, track.number),
+                        format!("{:02} {}", track.number, title),
+                    ));
+                }
+            }
+        }
         node
     }
     pub fn library_dir_children(p: &Path) -> Vec<String> {
@@ -268,7 +301,9 @@ impl Model {
 
     pub fn library_reload_with_node_focus(&mut self, node: Option<&str>) {
         self.db.sync_database();
+        let db_search_index = self.db_search_index;
         self.database_reload();
+        self.database_refresh_smart_playlist(db_search_index);
         self.library_reload_tree();
         if let Some(n) = node {
             assert!(self
@@ -385,6 +420,38 @@ impl Model {
         }
     }
 
+    // A file is scanned along with the rest of its folder, so that the album gain is right.
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn library_replaygain_scan(&mut self, node_id: &str) {
+        let path = Path::new(node_id);
+        let dir = if path.is_dir() {
+            path.to_path_buf()
+        } else {
+            match path.parent() {
+                Some(p) => p.to_path_buf(),
+                None => return,
+            }
+        };
+
+        let tx = self.sender.clone();
+        std::thread::spawn(move || {
+            let (tagged, failed) = crate::replaygain::scan(&dir, |index, total, name| {
+                tx.send(UpdateComponents::ReplayGainScanRunning(format!(
+                    " Scanning ReplayGain: {}/{} {}",
+                    index, total, name
+                )))
+                .ok();
+            });
+            tx.send(UpdateComponents::ReplayGainScanCompleted(tagged, failed))
+                .ok();
+        });
+    }
+
+    #[cfg(any(feature = "mpv", feature = "gst"))]
+    pub fn library_replaygain_scan(&mut self, _node_id: &str) {
+        self.mount_error_popup("ReplayGain scan is only available with the default backend.");
+    }
+
     pub fn library_paste(&mut self) -> Result<()> {
         if let Ok(State::One(StateValue::String(new_id))) = self.app.state(&Id::Library) {
             let old_id = self.yanked_node_id.as_ref().context("no id yanked")?;
@@ -0,0 +1,693 @@
+use crate::config::{Keys, Settings};
+use crate::named_playlist::{NamedPlaylist, PlaylistEntry};
+use crate::playlist::PlaylistItem;
+use crate::track::Track;
+use crate::ui::{Id, Model, Msg, NPMsg};
+use crate::utils::filetype_supported;
+use std::path::Path;
+use tui_realm_stdlib::{Input, List, Radio, Table};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, InputType, TableBuilder, TextSpan};
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers, NoUserEvent},
+    AttrValue, Attribute, Component, Event, MockComponent, State, StateValue,
+};
+
+fn np_list(config: &Settings, title: &str) -> List {
+    List::default()
+        .borders(
+            Borders::default().modifiers(BorderType::Rounded).color(
+                config
+                    .style_color_symbol
+                    .library_border()
+                    .unwrap_or(Color::Blue),
+            ),
+        )
+        .background(
+            config
+                .style_color_symbol
+                .library_background()
+                .unwrap_or(Color::Reset),
+        )
+        .foreground(
+            config
+                .style_color_symbol
+                .library_foreground()
+                .unwrap_or(Color::Yellow),
+        )
+        .title(title, Alignment::Left)
+        .scroll(true)
+        .highlighted_color(
+            config
+                .style_color_symbol
+                .library_highlight()
+                .unwrap_or(Color::LightBlue),
+        )
+        .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+        .rewind(false)
+        .step(4)
+        .rows(
+            TableBuilder::default()
+                .add_col(TextSpan::from("empty"))
+                .build(),
+        )
+}
+
+#[derive(MockComponent)]
+pub struct NPList {
+    component: List,
+    keys: Keys,
+}
+
+impl NPList {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: np_list(config, " Playlists "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for NPList {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let index = match self.state() {
+            State::One(StateValue::Usize(index)) => Some(index),
+            _ => None,
+        };
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                if let Some(AttrValue::Table(t)) = self.query(Attribute::Content) {
+                    if index.is_some_and(|index| index >= t.len() - 1) {
+                        return Some(Msg::NamedPlaylist(NPMsg::ListBlurDown));
+                    }
+                }
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                if let Some(AttrValue::Table(t)) = self.query(Attribute::Content) {
+                    if index.is_some_and(|index| index >= t.len() - 1) {
+                        return Some(Msg::NamedPlaylist(NPMsg::ListBlurDown));
+                    }
+                }
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(key) if key == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                return Some(Msg::NamedPlaylist(NPMsg::ListBlurDown))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::NamedPlaylist(NPMsg::ListBlurUp)),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::Show(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::Show(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_new.key_event() => {
+                return Some(Msg::NamedPlaylist(NPMsg::NewShow))
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_rename.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::RenameShow(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_duplicate.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::Duplicate(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_delete.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::DeleteShow(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_load.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::Load(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_export.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::ExportShow(index)));
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct NPTracks {
+    component: List,
+    keys: Keys,
+}
+
+impl NPTracks {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: np_list(config, " Tracks "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for NPTracks {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let index = match self.state() {
+            State::One(StateValue::Usize(index)) => Some(index),
+            _ => None,
+        };
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                if index == Some(0) {
+                    return Some(Msg::NamedPlaylist(NPMsg::TracksBlurUp));
+                }
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                if index == Some(0) {
+                    return Some(Msg::NamedPlaylist(NPMsg::TracksBlurUp));
+                }
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(key) if key == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                return Some(Msg::NamedPlaylist(NPMsg::TracksBlurDown))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::NamedPlaylist(NPMsg::TracksBlurUp)),
+
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::TrackAdd(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_delete.key_event() => {
+                return index.map(|index| Msg::NamedPlaylist(NPMsg::TrackRemove(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.named_playlist_load.key_event() => {
+                return Some(Msg::NamedPlaylist(NPMsg::LoadShown));
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct NPNameInputPopup {
+    component: Input,
+}
+
+impl NPNameInputPopup {
+    pub fn new(config: &Settings, name: &str) -> Self {
+        let title = if name.is_empty() {
+            "Name of the new playlist:".to_string()
+        } else {
+            format!("Rename {} to:", name)
+        };
+        Self {
+            component: Input::default()
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::Green),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(name)
+                .title(title, Alignment::Left),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for NPNameInputPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::NamedPlaylist(NPMsg::NameInputCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::Submit(State::One(StateValue::String(input_string))) => Some(
+                Msg::NamedPlaylist(NPMsg::NameInputCloseOk(input_string.trim().to_string())),
+            ),
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct NPDeleteConfirmPopup {
+    component: Radio,
+    keys: Keys,
+}
+
+impl NPDeleteConfirmPopup {
+    pub fn new(config: &Settings, name: &str) -> Self {
+        Self {
+            component: Radio::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::LightRed),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightRed),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(format!("Delete playlist {}?", name), Alignment::Left)
+                .rewind(true)
+                .choices(&["No", "Yes"])
+                .value(0),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for NPDeleteConfirmPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(key) if key == self.keys.global_left.key_event() => {
+                self.perform(Cmd::Move(Direction::Left))
+            }
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                self.perform(Cmd::Move(Direction::Right))
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::NamedPlaylist(NPMsg::DeleteCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::NamedPlaylist(NPMsg::DeleteCloseCancel))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => return None,
+        };
+        match cmd_result {
+            CmdResult::Submit(State::One(StateValue::Usize(1))) => {
+                Some(Msg::NamedPlaylist(NPMsg::DeleteCloseOk))
+            }
+            CmdResult::Submit(_) => Some(Msg::NamedPlaylist(NPMsg::DeleteCloseCancel)),
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct NPPickPopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl NPPickPopup {
+    pub fn new(config: &Settings, names: &[String], count: usize) -> Self {
+        let mut table = TableBuilder::default();
+        for (index, name) in names.iter().enumerate() {
+            if index > 0 {
+                table.add_row();
+            }
+            table.add_col(TextSpan::from(name));
+        }
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .title(
+                    format!("Add {} track(s) to: Enter to select, Esc to cancel.", count),
+                    Alignment::Center,
+                )
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&["Playlist"])
+                .column_spacing(1)
+                .widths(&[100])
+                .table(table.build()),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for NPPickPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::NamedPlaylist(NPMsg::AppendCloseOk(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::NamedPlaylist(NPMsg::AppendCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::NamedPlaylist(NPMsg::AppendCloseCancel))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+impl Model {
+    /// Reads the playlist names again and shows them.
+    pub fn named_playlist_reload(&mut self) {
+        match NamedPlaylist::names() {
+            Ok(names) => self.np_names = names,
+            Err(e) => self.mount_error_popup(format!("playlists error: {}", e).as_str()),
+        }
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, name) in self.np_names.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::from(format!("{}", idx + 1)))
+                .add_col(TextSpan::from(" "))
+                .add_col(TextSpan::from(name));
+        }
+        if self.np_names.is_empty() {
+            table.add_col(TextSpan::from(format!(
+                "no playlists, <{}> to create one",
+                self.config.keys.named_playlist_new
+            )));
+        }
+        self.app
+            .attr(
+                &Id::NamedPlaylistList,
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+
+        // the shown one may be gone or renamed
+        let shown = self
+            .np_shown
+            .as_ref()
+            .filter(|p| self.np_names.contains(&p.name))
+            .map(|p| p.name.clone());
+        self.np_shown = shown.and_then(|name| NamedPlaylist::load(&name).ok());
+        self.named_playlist_sync_tracks();
+    }
+
+    pub fn named_playlist_sync_tracks(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        let entries = self.np_shown.as_ref().map_or(&[][..], |p| &p.entries[..]);
+        for (idx, entry) in entries.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::from(format!("{}", idx + 1)))
+                .add_col(TextSpan::from(" "));
+            if Path::new(&entry.file).exists() {
+                table.add_col(TextSpan::from(entry.display()));
+            } else {
+                table.add_col(
+                    TextSpan::new(format!("{} (missing)", entry.display())).fg(Color::Red),
+                );
+            }
+        }
+        if entries.is_empty() {
+            table.add_col(TextSpan::from("empty playlist"));
+        }
+        let title = match &self.np_shown {
+            Some(p) => format!(" Tracks of {} ", p.name),
+            None => " Tracks ".to_string(),
+        };
+        self.app
+            .attr(
+                &Id::NamedPlaylistTracks,
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::NamedPlaylistTracks,
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
+            )
+            .ok();
+    }
+
+    pub fn named_playlist_show(&mut self, index: usize) {
+        if let Some(name) = self.np_names.get(index) {
+            match NamedPlaylist::load(name) {
+                Ok(playlist) => {
+                    self.np_shown = Some(playlist);
+                    self.named_playlist_sync_tracks();
+                    self.app.active(&Id::NamedPlaylistTracks).ok();
+                }
+                Err(e) => self.mount_error_popup(format!("playlist error: {}", e).as_str()),
+            }
+        }
+    }
+
+    pub fn named_playlist_export_show(&mut self, index: usize) {
+        if let Some(name) = self.np_names.get(index).cloned() {
+            match NamedPlaylist::load(&name) {
+                Ok(playlist) => {
+                    let items = playlist.entries.iter().map(PlaylistItem::from).collect();
+                    self.playlist_export_show(items, &name);
+                }
+                Err(e) => self.mount_error_popup(format!("playlist error: {}", e).as_str()),
+            }
+        }
+    }
+
+    /// Replaces the play queue with the tracks of the playlist.
+    pub fn named_playlist_load(&mut self, name: &str) {
+        match NamedPlaylist::load(name) {
+            Ok(playlist) => {
+                let files: Vec<String> = playlist.entries.into_iter().map(|e| e.file).collect();
+                self.player.playlist.tracks.clear();
+                self.playlist_add_items_common(&files);
+                self.show_message_timeout("Playlist", &format!("{} loaded", name), None);
+            }
+            Err(e) => self.mount_error_popup(format!("playlist error: {}", e).as_str()),
+        }
+    }
+
+    /// The files the append key works on: the selected track of the play queue or
+    /// database, the selected file or folder of the library, or else the current track.
+    pub fn named_playlist_append_target(&self) -> Vec<String> {
+        if let (Ok(Some(AttrValue::Flag(true))), Ok(State::One(StateValue::String(node)))) = (
+            self.app.query(&Id::Library, Attribute::Focus),
+            self.app.state(&Id::Library),
+        ) {
+            let path = Path::new(&node);
+            if path.is_dir() {
+                return Self::library_dir_children(path);
+            }
+            return vec![node];
+        }
+        self.selected_track().into_iter().collect()
+    }
+
+    pub fn named_playlist_append(&mut self, index: usize) {
+        let files = std::mem::take(&mut self.np_append);
+        let name = match self.np_names.get(index) {
+            Some(name) => name.clone(),
+            None => return,
+        };
+        let entries: Vec<PlaylistEntry> = files
+            .iter()
+            .filter(|file| filetype_supported(file))
+            .map(|file| PlaylistEntry::from_file(file))
+            .collect();
+        let count = entries.len();
+        match NamedPlaylist::append(&name, entries) {
+            Ok(()) => {
+                self.show_message_timeout(
+                    "Playlist",
+                    &format!("{} track(s) added to {}", count, name),
+                    None,
+                );
+                if self.np_shown.as_ref().is_some_and(|p| p.name == name) {
+                    self.np_shown = NamedPlaylist::load(&name).ok();
+                    self.named_playlist_sync_tracks();
+                }
+            }
+            Err(e) => self.mount_error_popup(format!("playlist error: {}", e).as_str()),
+        }
+    }
+
+    pub fn named_playlist_remove_track(&mut self, index: usize) {
+        if let Some(playlist) = &mut self.np_shown {
+            if index >= playlist.entries.len() {
+                return;
+            }
+            playlist.entries.remove(index);
+            if let Err(e) = playlist.save() {
+                self.mount_error_popup(format!("playlist error: {}", e).as_str());
+            }
+            self.named_playlist_sync_tracks();
+        }
+    }
+
+    pub fn named_playlist_add_track(&mut self, index: usize) {
+        let file = self
+            .np_shown
+            .as_ref()
+            .and_then(|p| p.entries.get(index))
+            .map(|e| e.file.clone());
+        if let Some(file) = file {
+            if Track::read_from_path(&file).is_ok() {
+                self.playlist_add(&file);
+            } else {
+                self.mount_error_popup(format!("{} is missing", file).as_str());
+            }
+        }
+    }
+}
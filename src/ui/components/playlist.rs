@@ -2,13 +2,15 @@ use crate::{
     config::{Keys, Settings},
     player::Loop,
     track::Track,
-    ui::{GSMsg, Id, Model, Msg, PLMsg},
+    ui::{components::rating_stars, GSMsg, Id, Model, Msg, PLMsg},
 };
 
+use crate::cue;
 use crate::player::PlayerTrait;
+use crate::playlist::PlaylistItem;
 use crate::sqlite::TrackForDB;
-use crate::utils::{filetype_supported, is_playlist};
-use anyhow::{anyhow, bail, Result};
+use crate::utils::{filetype_supported, is_playlist, is_stream};
+use anyhow::{anyhow, Result};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use std::path::{Path, PathBuf};
@@ -65,9 +67,9 @@ impl Playlist {
                 .rewind(false)
                 .step(4)
                 .row_height(1)
-                .headers(&["Duration", "Artist", "Title", "Album"])
+                .headers(&["Duration", "Artist", "Title", "Album", "Rating"])
                 .column_spacing(2)
-                .widths(&[12, 20, 25, 43])
+                .widths(&[12, 20, 25, 33, 10])
                 .table(
                     TableBuilder::default()
                         .add_col(TextSpan::from("Empty"))
@@ -128,6 +130,9 @@ impl Component<Msg, NoUserEvent> for Playlist {
             Event::Keyboard(key) if key == self.keys.playlist_delete_all.key_event() => {
                 return Some(Msg::Playlist(PLMsg::DeleteAll))
             }
+            Event::Keyboard(key) if key == self.keys.playlist_export.key_event() => {
+                return Some(Msg::Playlist(PLMsg::ExportShow))
+            }
             Event::Keyboard(key) if key == self.keys.playlist_shuffle.key_event() => {
                 return Some(Msg::Playlist(PLMsg::Shuffle))
             }
@@ -183,35 +188,71 @@ impl Model {
         self.playlist_sync();
     }
 
+    /// Asks where to write the tracks, suggesting `<name>.m3u` in the music folder.
+    pub fn playlist_export_show(&mut self, items: Vec<PlaylistItem>, name: &str) {
+        if items.is_empty() {
+            self.mount_error_popup("Nothing to export, the playlist is empty.");
+            return;
+        }
+        self.export_items = items;
+        let mut path = Self::get_full_path_from_config(&self.config);
+        path.push(format!("{}.m3u", name));
+        self.mount_playlist_export_popup(&path.to_string_lossy());
+    }
+
+    pub fn playlist_export(&mut self, path: &str, relative: bool) {
+        let items = std::mem::take(&mut self.export_items);
+        let path = PathBuf::from(shellexpand::tilde(path).to_string());
+        match crate::playlist::export(&path, &items, relative) {
+            Ok(()) => self.show_message_timeout(
+                "Export",
+                &format!("{} tracks written to {}", items.len(), path.display()),
+                None,
+            ),
+            Err(e) => self.mount_error_popup(format!("export error: {}", e).as_str()),
+        }
+    }
+
     fn playlist_add_playlist(&mut self, current_node: &str) -> Result<()> {
         let p = Path::new(current_node);
         let p_base = p.parent().ok_or_else(|| anyhow!("cannot find path root"))?;
         let str = std::fs::read_to_string(p)?;
         let items =
             crate::playlist::decode(&str).map_err(|e| anyhow!("playlist decode error: {}", e))?;
-        let mut vec = vec![];
-        for item in items {
-            if !filetype_supported(&item) {
-                continue;
+        let mut index = 0;
+        for mut item in items {
+            if !is_stream(&item.location) {
+                let pathbuf = Self::playlist_get_absolute_pathbuf(&item.location, p_base)?;
+                item.location = pathbuf.to_string_lossy().to_string();
+                if !filetype_supported(&item.location) || !pathbuf.exists() {
+                    continue;
+                }
             }
-            if let Ok(pathbuf) = Self::playlist_get_absolute_pathbuf(&item, p_base) {
-                vec.push(pathbuf.to_string_lossy().to_string());
+            // the tags are only read for entries the playlist tells nothing about
+            let track = if item.title.is_none() && !is_stream(&item.location) {
+                match Track::read_from_path(&item.location) {
+                    Ok(track) => track,
+                    Err(_) => continue,
+                }
+            } else {
+                Track::from_playlist_item(&item)
+            };
+            if self.config.add_playlist_front {
+                self.player.playlist.tracks.insert(index, track);
+                index += 1;
+            } else {
+                self.player.playlist.tracks.push_back(track);
             }
         }
-        self.playlist_add_items_common(&vec);
         self.playlist_sync();
         Ok(())
     }
 
     fn playlist_get_absolute_pathbuf(item: &str, p_base: &Path) -> Result<PathBuf> {
-        let url_decoded = urlencoding::decode(item)?.into_owned();
-        let mut url = url_decoded.clone();
+        let mut url = item.to_string();
         let mut pathbuf = PathBuf::from(p_base);
-        if url_decoded.starts_with("http") {
-            bail!("http not supported");
-        }
-        if url_decoded.starts_with("file") {
-            url = url_decoded.replace("file://", "");
+        if let Some(path) = item.strip_prefix("file://") {
+            url = urlencoding::decode(path)?.into_owned();
         }
         if Path::new(&url).is_relative() {
             pathbuf.push(url);
@@ -226,14 +267,16 @@ impl Model {
             self.playlist_add_playlist(current_node)?;
             return Ok(());
         }
-        if !filetype_supported(current_node) {
+        if !filetype_supported(current_node) && !cue::is_cue(current_node) {
             return Ok(());
         }
-        let item = Track::read_from_path(current_node)?;
+        let items = cue::read_tracks(Path::new(current_node))?;
         if add_playlist_front {
-            self.player.playlist.tracks.push_front(item);
+            for item in items.into_iter().rev() {
+                self.player.playlist.tracks.push_front(item);
+            }
         } else {
-            self.player.playlist.tracks.push_back(item);
+            self.player.playlist.tracks.extend(items);
         }
         self.playlist_sync();
         Ok(())
@@ -241,7 +284,7 @@ impl Model {
 
     pub fn playlist_add(&mut self, current_node: &str) {
         let p: &Path = Path::new(&current_node);
-        if !p.exists() {
+        if !p.exists() && !cue::is_cue_track(current_node) {
             return;
         }
 
@@ -253,14 +296,15 @@ impl Model {
         }
     }
 
-    fn playlist_add_items_common(&mut self, vec: &[String]) {
+    pub fn playlist_add_items_common(&mut self, vec: &[String]) {
         let mut index = 0;
+        let split = cue::split_files(vec.iter().map(Path::new));
         for s in vec {
-            if !filetype_supported(s) {
+            if (!filetype_supported(s) && !cue::is_cue(s)) || split.contains(Path::new(s)) {
                 continue;
             }
             if self.config.add_playlist_front {
-                if let Ok(item) = Track::read_from_path(s) {
+                for item in cue::read_tracks(Path::new(s)).unwrap_or_default() {
                     self.player.playlist.tracks.insert(index, item);
                     index += 1;
                 }
@@ -283,12 +327,16 @@ impl Model {
     }
 
     pub fn playlist_sync(&mut self) {
+        self.player.sync_playlist();
         let mut table: TableBuilder = TableBuilder::default();
 
         for (idx, record) in self.player.playlist.tracks.iter().enumerate() {
             if idx > 0 {
                 table.add_row();
             }
+            let (rating, favourite) = record
+                .file()
+                .map_or((0, false), |file| self.db.get_rating(file));
 
             let duration = record.duration_formatted().to_string();
             let duration_string = format!("[{:^7.7}]", duration);
@@ -302,13 +350,18 @@ impl Model {
                 .add_col(TextSpan::new(duration_string.as_str()))
                 .add_col(TextSpan::new(artist).fg(tuirealm::tui::style::Color::LightYellow))
                 .add_col(TextSpan::new(title).bold())
-                .add_col(TextSpan::new(record.album().unwrap_or("Unknown Album")));
+                .add_col(TextSpan::new(record.album().unwrap_or("Unknown Album")))
+                .add_col(
+                    TextSpan::new(rating_stars(rating, favourite))
+                        .fg(tuirealm::tui::style::Color::LightRed),
+                );
         }
         if self.player.playlist.tracks.is_empty() {
             table.add_col(TextSpan::from("0"));
             table.add_col(TextSpan::from("empty playlist"));
             table.add_col(TextSpan::from(""));
             table.add_col(TextSpan::from(""));
+            table.add_col(TextSpan::from(""));
         }
 
         let table = table.build();
@@ -395,19 +448,13 @@ impl Model {
             }
             Loop::Playlist => {
                 self.config.loop_mode = Loop::Single;
-                if let Some(song) = self.player.playlist.tracks.pop_back() {
-                    self.player.playlist.tracks.push_front(song);
-                }
             }
             Loop::Single => {
                 self.config.loop_mode = Loop::Queue;
-                if let Some(song) = self.player.playlist.tracks.pop_front() {
-                    self.player.playlist.tracks.push_back(song);
-                }
             }
         };
-        self.player.config.loop_mode = self.config.loop_mode;
-        self.playlist_sync();
+        // the server moves the current track in the playlist to match the mode
+        self.player.set_loop_mode(self.config.loop_mode);
         self.playlist_update_title();
     }
     pub fn playlist_play_selected(&mut self, index: usize) {
@@ -0,0 +1,682 @@
+use crate::config::{Keys, Settings};
+use crate::playlist::PlaylistItem;
+use crate::podcast::{self, format_date};
+use crate::track::Track;
+use crate::ui::model::{PodcastDelete, UpdateComponents};
+use crate::ui::{Id, Model, Msg, PCMsg};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use tui_realm_stdlib::{Input, List, Radio};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, InputType, TableBuilder, TextSpan};
+use tuirealm::{
+    event::{Key, KeyEvent, KeyModifiers, NoUserEvent},
+    AttrValue, Attribute, Component, Event, MockComponent, State, StateValue,
+};
+
+fn pc_list(config: &Settings, title: &str) -> List {
+    List::default()
+        .borders(
+            Borders::default().modifiers(BorderType::Rounded).color(
+                config
+                    .style_color_symbol
+                    .library_border()
+                    .unwrap_or(Color::Blue),
+            ),
+        )
+        .background(
+            config
+                .style_color_symbol
+                .library_background()
+                .unwrap_or(Color::Reset),
+        )
+        .foreground(
+            config
+                .style_color_symbol
+                .library_foreground()
+                .unwrap_or(Color::Yellow),
+        )
+        .title(title, Alignment::Left)
+        .scroll(true)
+        .highlighted_color(
+            config
+                .style_color_symbol
+                .library_highlight()
+                .unwrap_or(Color::LightBlue),
+        )
+        .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+        .rewind(false)
+        .step(4)
+        .rows(
+            TableBuilder::default()
+                .add_col(TextSpan::from("empty"))
+                .build(),
+        )
+}
+
+#[derive(MockComponent)]
+pub struct PCFeeds {
+    component: List,
+    keys: Keys,
+}
+
+impl PCFeeds {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: pc_list(config, " Podcasts "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PCFeeds {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let index = match self.state() {
+            State::One(StateValue::Usize(index)) => Some(index),
+            _ => None,
+        };
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                if let Some(AttrValue::Table(t)) = self.query(Attribute::Content) {
+                    if index.is_some_and(|index| index >= t.len() - 1) {
+                        return Some(Msg::Podcast(PCMsg::FeedsBlurDown));
+                    }
+                }
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                if let Some(AttrValue::Table(t)) = self.query(Attribute::Content) {
+                    if index.is_some_and(|index| index >= t.len() - 1) {
+                        return Some(Msg::Podcast(PCMsg::FeedsBlurDown));
+                    }
+                }
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(key) if key == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                return Some(Msg::Podcast(PCMsg::FeedsBlurDown))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Podcast(PCMsg::FeedsBlurUp)),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                return index.map(|index| Msg::Podcast(PCMsg::Show(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::Show(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_add.key_event() => {
+                return Some(Msg::Podcast(PCMsg::AddShow))
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_delete.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::DeleteShow(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_refresh.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::Refresh(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_refresh_all.key_event() => {
+                return Some(Msg::Podcast(PCMsg::RefreshAll))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PCEpisodes {
+    component: List,
+    keys: Keys,
+}
+
+impl PCEpisodes {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: pc_list(config, " Episodes "),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PCEpisodes {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let index = match self.state() {
+            State::One(StateValue::Usize(index)) => Some(index),
+            _ => None,
+        };
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                if index == Some(0) {
+                    return Some(Msg::Podcast(PCMsg::EpisodesBlurUp));
+                }
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                if index == Some(0) {
+                    return Some(Msg::Podcast(PCMsg::EpisodesBlurUp));
+                }
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => self.perform(Cmd::Scroll(Direction::Down)),
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => self.perform(Cmd::Scroll(Direction::Up)),
+            Event::Keyboard(key) if key == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                return Some(Msg::Podcast(PCMsg::EpisodesBlurDown))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab,
+                modifiers: KeyModifiers::SHIFT,
+            }) => return Some(Msg::Podcast(PCMsg::EpisodesBlurUp)),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                return index.map(|index| Msg::Podcast(PCMsg::EpisodeAdd(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::EpisodeAdd(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_download.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::EpisodeDownload(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_delete.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::EpisodeDeleteShow(index)));
+            }
+            Event::Keyboard(key) if key == self.keys.podcast_mark_played.key_event() => {
+                return index.map(|index| Msg::Podcast(PCMsg::EpisodeTogglePlayed(index)));
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PCAddPopup {
+    component: Input,
+}
+
+impl PCAddPopup {
+    pub fn new(config: &Settings) -> Self {
+        Self {
+            component: Input::default()
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::Green),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title("Url of the podcast feed (RSS or Atom):", Alignment::Left),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PCAddPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::Podcast(PCMsg::AddCloseCancel));
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::Submit(State::One(StateValue::String(input_string))) => Some(Msg::Podcast(
+                PCMsg::AddCloseOk(input_string.trim().to_string()),
+            )),
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct PCDeletePopup {
+    component: Radio,
+    keys: Keys,
+}
+
+impl PCDeletePopup {
+    pub fn new(config: &Settings, title: &str) -> Self {
+        Self {
+            component: Radio::default()
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::LightRed),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(
+                            config
+                                .style_color_symbol
+                                .library_border()
+                                .unwrap_or(Color::LightRed),
+                        )
+                        .modifiers(BorderType::Rounded),
+                )
+                .title(title, Alignment::Left)
+                .rewind(true)
+                .choices(&["No", "Yes"])
+                .value(0),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PCDeletePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(key) if key == self.keys.global_left.key_event() => {
+                self.perform(Cmd::Move(Direction::Left))
+            }
+            Event::Keyboard(key) if key == self.keys.global_right.key_event() => {
+                self.perform(Cmd::Move(Direction::Right))
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::Podcast(PCMsg::DeleteCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::Podcast(PCMsg::DeleteCloseCancel))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => return None,
+        };
+        match cmd_result {
+            CmdResult::Submit(State::One(StateValue::Usize(1))) => {
+                Some(Msg::Podcast(PCMsg::DeleteCloseOk))
+            }
+            CmdResult::Submit(_) => Some(Msg::Podcast(PCMsg::DeleteCloseCancel)),
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+impl Model {
+    /// Reads the podcasts and the shown episodes again and shows them.
+    pub fn podcast_reload(&mut self) {
+        match self.db.get_podcasts() {
+            Ok(podcasts) => self.pc_podcasts = podcasts,
+            Err(e) => self.mount_error_popup(format!("podcasts error: {}", e).as_str()),
+        }
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, podcast) in self.pc_podcasts.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            table.add_col(TextSpan::from(&podcast.title));
+            if podcast.unplayed > 0 {
+                table.add_col(TextSpan::new(format!(" ({})", podcast.unplayed)).bold());
+            }
+        }
+        if self.pc_podcasts.is_empty() {
+            table.add_col(TextSpan::from(format!(
+                "no podcasts, <{}> to subscribe to one",
+                self.config.keys.podcast_add
+            )));
+        }
+        self.app
+            .attr(
+                &Id::PodcastFeeds,
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+
+        // the shown one may be gone
+        let shown = self
+            .pc_shown
+            .filter(|id| self.pc_podcasts.iter().any(|podcast| podcast.id == *id));
+        self.pc_shown = shown;
+        self.pc_episodes = match shown {
+            Some(id) => self.db.get_episodes(id).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        self.podcast_sync_episodes();
+    }
+
+    pub fn podcast_sync_episodes(&mut self) {
+        let mut table: TableBuilder = TableBuilder::default();
+        for (idx, episode) in self.pc_episodes.iter().enumerate() {
+            if idx > 0 {
+                table.add_row();
+            }
+            let mark = if episode.played {
+                "\u{2714}"
+            } else if episode.file.is_some() {
+                "\u{2193}"
+            } else {
+                " "
+            };
+            let date = episode.published.map(format_date).unwrap_or_default();
+            let duration = episode
+                .duration
+                .map(|duration| Track::duration_formatted_short(&Duration::from_secs(duration)));
+            // how far it got, when it was started
+            let length = match (episode.position, duration) {
+                (0, duration) => duration.unwrap_or_default(),
+                (position, duration) => format!(
+                    "{}/{}",
+                    Track::duration_formatted_short(&Duration::from_secs(position)),
+                    duration.unwrap_or_else(|| "?".to_string())
+                ),
+            };
+            let title = TextSpan::new(&episode.title);
+            table
+                .add_col(TextSpan::new(mark).fg(Color::LightGreen))
+                .add_col(TextSpan::from(format!(" {} ", date)))
+                .add_col(if episode.played { title } else { title.bold() })
+                .add_col(TextSpan::from(format!(" {}", length)));
+        }
+        if self.pc_episodes.is_empty() {
+            table.add_col(TextSpan::from("no episodes"));
+        }
+        let title = self
+            .pc_podcasts
+            .iter()
+            .find(|podcast| Some(podcast.id) == self.pc_shown)
+            .map_or_else(
+                || " Episodes ".to_string(),
+                |podcast| format!(" Episodes of {} ", podcast.title),
+            );
+        self.app
+            .attr(
+                &Id::PodcastEpisodes,
+                Attribute::Content,
+                AttrValue::Table(table.build()),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::PodcastEpisodes,
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
+            )
+            .ok();
+    }
+
+    pub fn podcast_show(&mut self, index: usize) {
+        if let Some(podcast) = self.pc_podcasts.get(index) {
+            self.pc_shown = Some(podcast.id);
+            self.podcast_reload();
+            self.app.active(&Id::PodcastEpisodes).ok();
+        }
+    }
+
+    /// Reads the feed in the background, it is stored once it arrives.
+    pub fn podcast_fetch(&self, url: &str) {
+        let tx = self.sender.clone();
+        let url = url.to_string();
+        thread::spawn(move || match podcast::fetch(&url) {
+            Ok(feed) => {
+                tx.send(UpdateComponents::PodcastFetched(url, feed)).ok();
+            }
+            Err(e) => {
+                tx.send(UpdateComponents::PodcastFetchFail(url, e.to_string()))
+                    .ok();
+            }
+        });
+    }
+
+    pub fn podcast_refresh_all(&self) {
+        for podcast in &self.pc_podcasts {
+            self.podcast_fetch(&podcast.url);
+        }
+        self.show_message_timeout(
+            "Podcasts",
+            &format!("Refreshing {} podcast(s)", self.pc_podcasts.len()),
+            None,
+        );
+    }
+
+    /// Subscribes to the feed, or updates the subscription.
+    pub fn podcast_fetched(&mut self, url: &str, feed: &podcast::Feed) {
+        let known = self.pc_podcasts.iter().any(|podcast| podcast.url == url);
+        match self.db.add_podcast(url, feed) {
+            Ok(id) => {
+                if !known {
+                    self.pc_shown = Some(id);
+                    self.show_message_timeout(
+                        "Podcasts",
+                        &format!("Subscribed to {}", feed.title),
+                        None,
+                    );
+                }
+                self.podcast_reload();
+            }
+            Err(e) => self.mount_error_popup(format!("podcasts error: {}", e).as_str()),
+        }
+    }
+
+    pub fn podcast_delete_show(&mut self, index: usize) {
+        if let Some(podcast) = self.pc_podcasts.get(index) {
+            let title = format!(
+                "Unsubscribe from {} and delete its downloads?",
+                podcast.title
+            );
+            self.pc_delete = Some(PodcastDelete::Podcast(podcast.id));
+            self.mount_podcast_delete_popup(&title);
+        }
+    }
+
+    pub fn podcast_episode_delete_show(&mut self, index: usize) {
+        if let Some(episode) = self.pc_episodes.get(index) {
+            if episode.file.is_none() {
+                self.show_message_timeout("Podcasts", "The episode is not downloaded", None);
+                return;
+            }
+            let title = format!("Delete the download of {}?", episode.title);
+            self.pc_delete = Some(PodcastDelete::Download(episode.id));
+            self.mount_podcast_delete_popup(&title);
+        }
+    }
+
+    pub fn podcast_delete(&mut self, target: PodcastDelete) {
+        let result = match target {
+            PodcastDelete::Podcast(id) => {
+                for episode in self.db.get_episodes(id).unwrap_or_default() {
+                    if let Some(file) = episode.file {
+                        fs::remove_file(file).ok();
+                    }
+                }
+                self.db.delete_podcast(id)
+            }
+            PodcastDelete::Download(id) => {
+                if let Some(file) = self
+                    .pc_episodes
+                    .iter()
+                    .find(|episode| episode.id == id)
+                    .and_then(|episode| episode.file.as_ref())
+                {
+                    fs::remove_file(file).ok();
+                }
+                self.db.set_episode_file(id, None)
+            }
+        };
+        if let Err(e) = result {
+            self.mount_error_popup(format!("podcasts error: {}", e).as_str());
+        }
+        self.podcast_reload();
+    }
+
+    /// Adds the episode to the playlist, the download when there is one or else the stream.
+    pub fn podcast_episode_add(&mut self, index: usize) {
+        let Some(episode) = self.pc_episodes.get(index) else {
+            return;
+        };
+        let track = Track::from_playlist_item(&PlaylistItem {
+            location: episode.location().to_string(),
+            title: Some(episode.title.clone()),
+            artist: Some(episode.podcast.clone()),
+            duration: episode.duration.map(Duration::from_secs),
+        });
+        if self.config.add_playlist_front {
+            self.player.playlist.tracks.push_front(track);
+        } else {
+            self.player.playlist.tracks.push_back(track);
+        }
+        self.playlist_sync();
+    }
+
+    pub fn podcast_episode_download(&mut self, index: usize) {
+        let Some(episode) = self.pc_episodes.get(index) else {
+            return;
+        };
+        if episode.file.is_some() {
+            self.show_message_timeout("Podcasts", "The episode is downloaded already", None);
+            return;
+        }
+        if !self.pc_downloading.insert(episode.id) {
+            self.show_message_timeout("Podcasts", "The episode is downloading already", None);
+            return;
+        }
+        let dir = PathBuf::from(shellexpand::tilde(&self.config.podcast_dir).to_string())
+            .join(podcast::file_name(&episode.podcast));
+        let (id, url, title) = (episode.id, episode.url.clone(), episode.title.clone());
+        let tx = self.sender.clone();
+        thread::spawn(move || match podcast::download(&url, &dir, &title, id) {
+            Ok(path) => {
+                let path = path.to_string_lossy().to_string();
+                tx.send(UpdateComponents::PodcastDownloaded(id, path)).ok();
+            }
+            Err(e) => {
+                tx.send(UpdateComponents::PodcastDownloadFail(
+                    id,
+                    title,
+                    e.to_string(),
+                ))
+                .ok();
+            }
+        });
+        self.show_message_timeout("Podcasts", &format!("Downloading {}", episode.title), None);
+    }
+
+    pub fn podcast_downloaded(&mut self, id: u64, file: &str) {
+        self.pc_downloading.remove(&id);
+        if let Err(e) = self.db.set_episode_file(id, Some(file)) {
+            self.mount_error_popup(format!("podcasts error: {}", e).as_str());
+        }
+        self.show_message_timeout("Podcasts", "Download finished", None);
+        self.podcast_reload();
+    }
+
+    /// Marks the episode played, or unplayed when it was already.
+    pub fn podcast_episode_toggle_played(&mut self, index: usize) {
+        if let Some(episode) = self.pc_episodes.get(index) {
+            if let Err(e) = self.db.set_episode_played(episode.id, !episode.played) {
+                self.mount_error_popup(format!("podcasts error: {}", e).as_str());
+            }
+            self.podcast_reload();
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::chapter;
 use crate::config::{Keys, Settings, StyleColorSymbol};
 /**
  * MIT License
@@ -22,14 +23,18 @@ use crate::config::{Keys, Settings, StyleColorSymbol};
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::ui::Msg;
+use crate::player::EQUALIZER_BANDS;
+use crate::sqlite::{StatsCriteria, StatsPeriod};
+use crate::track::Track;
+use crate::ui::{CHMsg, EQMsg, Msg, ODMsg, STMsg};
+use std::time::Duration;
 use tui_realm_stdlib::{Input, Paragraph, Radio, Table};
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers, NoUserEvent};
 use tuirealm::props::{
     Alignment, BorderType, Borders, Color, InputType, TableBuilder, TextModifiers, TextSpan,
 };
-use tuirealm::{Component, Event, MockComponent, State, StateValue};
+use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent, State, StateValue};
 
 #[derive(MockComponent)]
 pub struct QuitPopup {
@@ -189,6 +194,10 @@ impl HelpPopup {
             "<{}/{}>",
             keys.global_lyric_adjust_forward, keys.global_lyric_adjust_backward
         );
+        let key_player_chapter = format!(
+            "<{}/{}>",
+            keys.global_player_chapter_next, keys.global_player_chapter_previous
+        );
         let key_player = format!(
             "<{}/{}/{}>",
             keys.global_player_next, keys.global_player_previous, keys.global_player_toggle_pause,
@@ -274,6 +283,85 @@ impl HelpPopup {
                         )
                         .add_col(TextSpan::from("Toggle gapless playback"))
                         .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_player_ab_loop))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Mark point A of a loop, then point B, then clear it",
+                        ))
+                        .add_row()
+                        .add_col(TextSpan::new(key_player_chapter).bold().fg(Color::Cyan))
+                        .add_col(TextSpan::from("Next/Previous chapter"))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_chapter_list_open))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Show the chapters of the current track"))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_equalizer_open))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Select equalizer preset"))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_output_device_open))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Select audio output device"))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_stats_open))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Show play statistics"))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!(
+                                "<{}/{}/{}>",
+                                keys.global_rating_up,
+                                keys.global_rating_down,
+                                keys.global_favourite_toggle
+                            ))
+                            .bold()
+                            .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Rate selected or current track up/down, toggle favourite",
+                        ))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_named_playlist_append))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Add selected or current track(s) to a named playlist",
+                        ))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_radio_browser_open))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Search radio stations, empty search for favourites",
+                        ))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.global_sleep_timer_open))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Set or cancel the sleep timer"))
+                        .add_row()
                         .add_col(TextSpan::new(key_lyric_adjust).bold().fg(Color::Cyan))
                         .add_col(TextSpan::from("Before 10 seconds,adjust offset of lyrics"))
                         .add_row()
@@ -337,6 +425,15 @@ impl HelpPopup {
                         )
                         .add_col(TextSpan::from("Open tag editor for tag and lyric download"))
                         .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.library_replaygain_scan))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Scan folder loudness and write ReplayGain tags",
+                        ))
+                        .add_row()
                         .add_col(
                             TextSpan::new(format!(
                                 "<{}/{}>",
@@ -416,6 +513,13 @@ impl HelpPopup {
                         )
                         .add_col(TextSpan::from("Swap track down/up in playlist"))
                         .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.playlist_export))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Export playlist to m3u/pls/xspf/asx file"))
+                        .add_row()
                         .add_col(TextSpan::new("Database").bold().fg(Color::LightYellow))
                         .add_row()
                         .add_col(
@@ -434,6 +538,80 @@ impl HelpPopup {
                                 .fg(Color::Cyan),
                         )
                         .add_col(TextSpan::from("Search in database"))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.database_sort))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Sort tracks by name/play count/last played/skip count",
+                        ))
+                        .add_row()
+                        .add_col(TextSpan::new("Playlists").bold().fg(Color::LightYellow))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!(
+                                "<{}/{}/{}/{}>",
+                                keys.named_playlist_new,
+                                keys.named_playlist_rename,
+                                keys.named_playlist_duplicate,
+                                keys.named_playlist_delete
+                            ))
+                            .bold()
+                            .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "New/rename/duplicate/delete playlist, delete removes a track",
+                        ))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!(
+                                "<{}/{}>",
+                                keys.global_right, keys.named_playlist_load
+                            ))
+                            .bold()
+                            .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Show playlist or add track to queue/replace queue with playlist",
+                        ))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!("<{}>", keys.named_playlist_export))
+                                .bold()
+                                .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from("Export playlist to m3u/pls/xspf/asx file"))
+                        .add_row()
+                        .add_col(TextSpan::new("Podcasts").bold().fg(Color::LightYellow))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!(
+                                "<{}/{}/{}/{}>",
+                                keys.podcast_add,
+                                keys.podcast_delete,
+                                keys.podcast_refresh,
+                                keys.podcast_refresh_all
+                            ))
+                            .bold()
+                            .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Subscribe/unsubscribe/refresh podcast/refresh all, delete removes a download",
+                        ))
+                        .add_row()
+                        .add_col(
+                            TextSpan::new(format!(
+                                "<{}/{}/{}>",
+                                keys.global_right, keys.podcast_download, keys.podcast_mark_played
+                            ))
+                            .bold()
+                            .fg(Color::Cyan),
+                        )
+                        .add_col(TextSpan::from(
+                            "Show episodes or add episode to queue/download it/mark it played",
+                        ))
                         .build(),
                 ),
             keys: keys.clone(),
@@ -643,6 +821,173 @@ impl Component<Msg, NoUserEvent> for DeleteConfirmInputPopup {
     }
 }
 
+#[derive(MockComponent)]
+pub struct PlaylistExportPopup {
+    component: Input,
+    relative: bool,
+}
+
+impl PlaylistExportPopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol, path: &str) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .value(path)
+                .title(Self::title(false), Alignment::Left),
+            relative: false,
+        }
+    }
+
+    fn title(relative: bool) -> String {
+        format!(
+            "Export to m3u/pls/xspf/asx, <TAB> paths: {}",
+            if relative { "relative" } else { "absolute" }
+        )
+    }
+}
+
+impl Component<Msg, NoUserEvent> for PlaylistExportPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                self.relative = !self.relative;
+                self.attr(
+                    Attribute::Title,
+                    AttrValue::Title((Self::title(self.relative), Alignment::Left)),
+                );
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::PlaylistExportCloseCancel);
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::Submit(State::One(StateValue::String(path))) => {
+                Some(Msg::PlaylistExportCloseOk(path, self.relative))
+            }
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct SleepTimerPopup {
+    component: Input,
+}
+
+impl SleepTimerPopup {
+    pub fn new(style_color_symbol: &StyleColorSymbol) -> Self {
+        Self {
+            component: Input::default()
+                .foreground(
+                    style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Reset),
+                )
+                .borders(
+                    Borders::default()
+                        .color(style_color_symbol.library_border().unwrap_or(Color::Green))
+                        .modifiers(BorderType::Rounded),
+                )
+                .input_type(InputType::Text)
+                .title(
+                    "Sleep after minutes, end of track or end+N tracks, empty to cancel:",
+                    Alignment::Left,
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for SleepTimerPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => self.perform(Cmd::Move(Direction::Left)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => self.perform(Cmd::Move(Direction::Right)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => self.perform(Cmd::GoTo(Position::Begin)),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => self.perform(Cmd::Cancel),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => self.perform(Cmd::Delete),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::NONE,
+            }) => self.perform(Cmd::Type(ch)),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                return Some(Msg::SleepTimerPopupCloseCancel);
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.perform(Cmd::Submit),
+            _ => CmdResult::None,
+        };
+        match cmd_result {
+            CmdResult::Submit(State::One(StateValue::String(input))) => {
+                Some(Msg::SleepTimerPopupCloseOk(input))
+            }
+            _ => Some(Msg::None),
+        }
+    }
+}
+
 #[derive(MockComponent)]
 pub struct MessagePopup {
     component: Paragraph,
@@ -675,3 +1020,511 @@ impl Component<Msg, NoUserEvent> for MessagePopup {
         None
     }
 }
+
+#[derive(MockComponent)]
+pub struct EqualizerPopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl EqualizerPopup {
+    pub fn new(config: &Settings) -> Self {
+        let mut headers = vec!["Preset".to_string()];
+        headers.extend(EQUALIZER_BANDS.iter().map(|f| {
+            if *f >= 1000.0 {
+                format!("{}k", f / 1000.0)
+            } else {
+                format!("{}", f)
+            }
+        }));
+        let mut widths = vec![20];
+        widths.extend([8; EQUALIZER_BANDS.len()]);
+
+        let mut table = TableBuilder::default();
+        for (index, preset) in config.equalizer_presets.iter().enumerate() {
+            if index > 0 {
+                table.add_row();
+            }
+            table.add_col(TextSpan::new(&preset.name).bold());
+            for band in 0..EQUALIZER_BANDS.len() {
+                let gain = preset.gains.get(band).copied().unwrap_or(0.0);
+                table.add_col(TextSpan::from(format!("{:+}", gain)));
+            }
+        }
+        if config.equalizer_presets.is_empty() {
+            table.add_col(TextSpan::from("No presets in config.toml"));
+        }
+
+        let selected = config
+            .equalizer_presets
+            .iter()
+            .position(|p| p.name == config.equalizer)
+            .unwrap_or(0);
+
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .title(
+                    "Equalizer in dB: Enter to select, Esc to cancel.",
+                    Alignment::Center,
+                )
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&headers)
+                .column_spacing(1)
+                .widths(&widths)
+                .table(table.build())
+                .selected_line(selected),
+            keys: config.keys.clone(),
+        }
+    }
+
+    fn preview(&self) -> Option<Msg> {
+        match self.state() {
+            State::One(StateValue::Usize(index)) => Some(Msg::Equalizer(EQMsg::Preview(index))),
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for EqualizerPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Equalizer(EQMsg::PopupCloseOk(index)));
+                }
+                Some(Msg::None)
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                Some(Msg::Equalizer(EQMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                Some(Msg::Equalizer(EQMsg::PopupCloseCancel))
+            }
+            // moving through the presets lets you hear them right away
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                self.preview()
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                self.preview()
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down));
+                self.preview()
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up));
+                self.preview()
+            }
+            _ => Some(Msg::None),
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct OutputDevicePopup {
+    component: Table,
+    keys: Keys,
+    // device names in the order of the table, the default device first as an empty name
+    devices: Vec<String>,
+}
+
+impl OutputDevicePopup {
+    pub fn new(config: &Settings, names: Vec<String>) -> Self {
+        let mut devices = vec![String::new()];
+        devices.extend(names);
+
+        let mut table = TableBuilder::default();
+        for (index, device) in devices.iter().enumerate() {
+            if index > 0 {
+                table.add_row();
+            }
+            if device.is_empty() {
+                table.add_col(TextSpan::new("Default").bold());
+            } else {
+                table.add_col(TextSpan::from(device));
+            }
+        }
+
+        let selected = devices
+            .iter()
+            .position(|d| *d == config.output_device)
+            .unwrap_or(0);
+
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .title(
+                    "Output device: Enter to select, Esc to cancel.",
+                    Alignment::Center,
+                )
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&["Device"])
+                .column_spacing(1)
+                .widths(&[100])
+                .table(table.build())
+                .selected_line(selected),
+            keys: config.keys.clone(),
+            devices,
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for OutputDevicePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    if let Some(device) = self.devices.get(index) {
+                        return Some(Msg::OutputDevice(ODMsg::PopupCloseOk(device.clone())));
+                    }
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::OutputDevice(ODMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::OutputDevice(ODMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+#[derive(MockComponent)]
+pub struct ChapterPopup {
+    component: Table,
+    keys: Keys,
+}
+
+impl ChapterPopup {
+    /// The chapters of `track`, with the one playing at `position` selected.
+    pub fn new(config: &Settings, track: &Track, position: Duration) -> Self {
+        let mut table = TableBuilder::default();
+        for (index, chapter) in track.chapters().iter().enumerate() {
+            if index > 0 {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::new(format!("{}", index + 1)))
+                .add_col(TextSpan::new(Track::duration_formatted_short(&chapter.start)).bold())
+                .add_col(TextSpan::from(&chapter.title));
+        }
+
+        Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .title("Chapters: Enter to play, Esc to cancel.", Alignment::Center)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&["#", "Start", "Title"])
+                .column_spacing(2)
+                .widths(&[6, 12, 82])
+                .table(table.build())
+                .selected_line(chapter::index_at(track.chapters(), position).unwrap_or(0)),
+            keys: config.keys.clone(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ChapterPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => {
+                if let State::One(StateValue::Usize(index)) = self.state() {
+                    return Some(Msg::Chapter(CHMsg::PopupCloseOk(index)));
+                }
+                CmdResult::None
+            }
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::Chapter(CHMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::Chapter(CHMsg::PopupCloseCancel))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_top.key_event() => {
+                self.perform(Cmd::GoTo(Position::Begin))
+            }
+            Event::Keyboard(key) if key == self.keys.global_goto_bottom.key_event() => {
+                self.perform(Cmd::GoTo(Position::End))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
+
+/// What the statistics popup cycles through.
+pub const STATS_CRITERIAS: [StatsCriteria; 3] = [
+    StatsCriteria::Track,
+    StatsCriteria::Artist,
+    StatsCriteria::Album,
+];
+pub const STATS_PERIODS: [StatsPeriod; 3] =
+    [StatsPeriod::Week, StatsPeriod::Month, StatsPeriod::AllTime];
+
+#[derive(MockComponent)]
+pub struct StatsPopup {
+    component: Table,
+    keys: Keys,
+    // the most played entries with their play counts, by criteria and then by period
+    tops: Vec<Vec<Vec<(String, u32)>>>,
+    criteria: usize,
+    period: usize,
+}
+
+impl StatsPopup {
+    pub fn new(config: &Settings, tops: Vec<Vec<Vec<(String, u32)>>>) -> Self {
+        let mut popup = Self {
+            component: Table::default()
+                .borders(
+                    Borders::default().modifiers(BorderType::Rounded).color(
+                        config
+                            .style_color_symbol
+                            .library_border()
+                            .unwrap_or(Color::Green),
+                    ),
+                )
+                .foreground(
+                    config
+                        .style_color_symbol
+                        .library_foreground()
+                        .unwrap_or(Color::Yellow),
+                )
+                .background(
+                    config
+                        .style_color_symbol
+                        .library_background()
+                        .unwrap_or(Color::Black),
+                )
+                .highlighted_color(
+                    config
+                        .style_color_symbol
+                        .library_highlight()
+                        .unwrap_or(Color::LightBlue),
+                )
+                .highlighted_str(&config.style_color_symbol.library_highlight_symbol)
+                .scroll(true)
+                .rewind(false)
+                .step(4)
+                .row_height(1)
+                .headers(&["#", "Plays", "Name"])
+                .column_spacing(2)
+                .widths(&[6, 8, 86]),
+            keys: config.keys.clone(),
+            tops,
+            criteria: 0,
+            period: 0,
+        };
+        popup.show_top();
+        popup
+    }
+
+    fn show_top(&mut self) {
+        let mut table = TableBuilder::default();
+        let top = &self.tops[self.criteria][self.period];
+        for (index, (name, plays)) in top.iter().enumerate() {
+            if index > 0 {
+                table.add_row();
+            }
+            table
+                .add_col(TextSpan::from(format!("{}", index + 1)))
+                .add_col(TextSpan::new(format!("{}", plays)).bold())
+                .add_col(TextSpan::from(name));
+        }
+        if top.is_empty() {
+            table.add_col(TextSpan::from(""));
+            table.add_col(TextSpan::from(""));
+            table.add_col(TextSpan::from("Nothing played yet"));
+        }
+
+        let title = format!(
+            " Top {}, {}: Tab to switch between tracks/artists/albums, {}/{} for the period ",
+            STATS_CRITERIAS[self.criteria],
+            STATS_PERIODS[self.period],
+            self.keys.global_left,
+            self.keys.global_right,
+        );
+        self.attr(
+            Attribute::Title,
+            AttrValue::Title((title, Alignment::Center)),
+        );
+        self.attr(Attribute::Content, AttrValue::Table(table.build()));
+        self.perform(Cmd::GoTo(Position::Begin));
+    }
+}
+
+impl Component<Msg, NoUserEvent> for StatsPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        let _cmd_result = match ev {
+            Event::Keyboard(key) if key == self.keys.global_quit.key_event() => {
+                return Some(Msg::Stats(STMsg::PopupClose))
+            }
+            Event::Keyboard(key) if key == self.keys.global_esc.key_event() => {
+                return Some(Msg::Stats(STMsg::PopupClose))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                self.criteria = (self.criteria + 1) % STATS_CRITERIAS.len();
+                self.show_top();
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab, ..
+            }) => {
+                self.criteria = (self.criteria + STATS_CRITERIAS.len() - 1) % STATS_CRITERIAS.len();
+                self.show_top();
+                CmdResult::None
+            }
+            Event::Keyboard(key)
+                if key.code == Key::Right || key == self.keys.global_right.key_event() =>
+            {
+                self.period = (self.period + 1) % STATS_PERIODS.len();
+                self.show_top();
+                CmdResult::None
+            }
+            Event::Keyboard(key)
+                if key.code == Key::Left || key == self.keys.global_left.key_event() =>
+            {
+                self.period = (self.period + STATS_PERIODS.len() - 1) % STATS_PERIODS.len();
+                self.show_top();
+                CmdResult::None
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => self.perform(Cmd::Move(Direction::Down)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            Event::Keyboard(key) if key == self.keys.global_down.key_event() => {
+                self.perform(Cmd::Move(Direction::Down))
+            }
+            Event::Keyboard(key) if key == self.keys.global_up.key_event() => {
+                self.perform(Cmd::Move(Direction::Up))
+            }
+            _ => CmdResult::None,
+        };
+        Some(Msg::None)
+    }
+}
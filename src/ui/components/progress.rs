@@ -1,16 +1,29 @@
+use crate::chapter::{self, Chapter};
 use crate::config::Settings;
+use crate::server::SleepTimer;
 use crate::track::Track;
 use crate::ui::{Id, Model, Msg};
+use crate::utils::is_stream;
 
 use std::time::Duration;
 use tui_realm_stdlib::ProgressBar;
+use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::event::NoUserEvent;
-use tuirealm::props::{Alignment, BorderType, Borders, Color, PropPayload, PropValue};
-use tuirealm::{AttrValue, Attribute, Component, Event, MockComponent};
+use tuirealm::props::{
+    Alignment, BorderType, Borders, Color, PropPayload, PropValue, Style, TextModifiers,
+};
+use tuirealm::tui::{layout::Rect, widgets::Paragraph};
+use tuirealm::{AttrValue, Attribute, Component, Event, Frame, MockComponent, State};
+
+/// Fractions of the track where the marks of the A-B loop are drawn on the bar.
+const AB_MARKS: Attribute = Attribute::Custom("ab-marks");
+/// Fractions of the track where its chapters start, ticked on the bottom border.
+const CHAPTER_TICKS: Attribute = Attribute::Custom("chapter-ticks");
 
-#[derive(MockComponent)]
 pub struct Progress {
     component: ProgressBar,
+    marks: Vec<f64>,
+    ticks: Vec<f64>,
 }
 
 impl Progress {
@@ -49,10 +62,72 @@ impl Progress {
                     Alignment::Center,
                 )
                 .progress(0.0),
+            marks: Vec::new(),
+            ticks: Vec::new(),
         }
     }
 }
 
+impl MockComponent for Progress {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area);
+        // the marks go on the middle row of the gauge, inside the borders
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+        let width = area.width - 2;
+        let column =
+            |fraction: f64| area.x + 1 + ((fraction * f64::from(width)) as u16).min(width - 1);
+        // the first chapter mostly starts with the track, which needs no tick
+        let bottom = area.y + area.height - 1;
+        for tick in self.ticks.iter().filter(|tick| **tick > 0.0) {
+            frame.render_widget(Paragraph::new("┴"), Rect::new(column(*tick), bottom, 1, 1));
+        }
+        let y = area.y + area.height / 2;
+        for (mark, symbol) in self.marks.iter().zip(["A", "B"]) {
+            frame.render_widget(
+                Paragraph::new(symbol).style(Style::default().add_modifier(TextModifiers::BOLD)),
+                Rect::new(column(*mark), y, 1, 1),
+            );
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if attr == AB_MARKS {
+            self.marks = fractions(value);
+        } else if attr == CHAPTER_TICKS {
+            self.ticks = fractions(value);
+        } else {
+            self.component.attr(attr, value);
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
+
+fn fractions(value: AttrValue) -> Vec<f64> {
+    match value {
+        AttrValue::Payload(PropPayload::Vec(fractions)) => fractions
+            .into_iter()
+            .map(|fraction| match fraction {
+                PropValue::F64(fraction) => fraction,
+                _ => 0.0,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 impl Component<Msg, NoUserEvent> for Progress {
     fn on(&mut self, _ev: Event<NoUserEvent>) -> Option<Msg> {
         Some(Msg::None)
@@ -74,13 +149,20 @@ impl Model {
 
     pub fn progress_update_title(&mut self) {
         let gapless = if self.config.gapless { "True" } else { "False" };
-        let progress_title = format!(
+        let mut progress_title = format!(
             " Status: {} | Volume: {} | Speed: {:^.1} | Gapless: {} ",
             self.player.status(),
             self.config.volume,
             self.config.speed as f32 / 10.0,
             gapless,
         );
+        if let Some(title) = &self.player.stream_title {
+            progress_title.push_str(&format!("| On air: {:^.40} ", title));
+        }
+        self.sleep_timer_shown = self.player.sleep_timer();
+        if let Some(timer) = &self.sleep_timer_shown {
+            progress_title.push_str(&format!("| Sleep: {} ", timer));
+        }
         self.app
             .attr(
                 &Id::Progress,
@@ -90,7 +172,130 @@ impl Model {
             .ok();
     }
 
+    /// Sets the sleep timer from the input of its popup, empty cancels it.
+    pub fn sleep_timer_set(&mut self, input: &str) {
+        if input.trim().is_empty() {
+            self.player.set_sleep_timer(None);
+        } else {
+            match input.parse::<SleepTimer>() {
+                Ok(timer) => self.player.set_sleep_timer(Some(timer)),
+                Err(e) => self.mount_error_popup(&e),
+            }
+        }
+        self.progress_update_title();
+    }
+
+    /// Counts the sleep timer down in the title, whenever what it shows changes.
+    pub fn progress_update_sleep_timer(&mut self) {
+        if self.player.sleep_timer() != self.sleep_timer_shown {
+            self.progress_update_title();
+            self.redraw = true;
+        }
+    }
+
+    /// Shows the points of the A-B loop and the chapters of the current track on the bar.
+    pub fn progress_update_marks(&mut self) {
+        let track = self.player.playlist.current_track.as_ref();
+        let duration = track.map_or(0.0, |track| track.duration().as_secs_f64());
+        let mut marks = Vec::new();
+        let mut ticks = Vec::new();
+        if duration > 0.0 {
+            if let Some(ab_loop) = self.player.ab_loop {
+                marks.push(PropValue::F64(ab_loop.a / duration));
+                if let Some(b) = ab_loop.b {
+                    marks.push(PropValue::F64(b / duration));
+                }
+            }
+            ticks = track
+                .map(Track::chapters)
+                .unwrap_or_default()
+                .iter()
+                .map(|chapter| PropValue::F64(chapter.start.as_secs_f64() / duration))
+                .collect();
+        }
+        self.app
+            .attr(
+                &Id::Progress,
+                AB_MARKS,
+                AttrValue::Payload(PropPayload::Vec(marks)),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::Progress,
+                CHAPTER_TICKS,
+                AttrValue::Payload(PropPayload::Vec(ticks)),
+            )
+            .ok();
+    }
+
+    // The chapters of the current track, and the one playing.
+    fn chapters(&self) -> (&[Chapter], Option<usize>) {
+        let chapters = self
+            .player
+            .playlist
+            .current_track
+            .as_ref()
+            .map(Track::chapters)
+            .unwrap_or_default();
+        let position = Duration::from_secs(self.time_pos.try_into().unwrap_or(0));
+        (chapters, chapter::index_at(chapters, position))
+    }
+
+    /// Plays the chapter with the index from its start.
+    pub fn chapter_play(&mut self, index: usize) {
+        if let Some(chapter) = self.chapters().0.get(index) {
+            let start = chapter.start.as_secs() as i64;
+            self.player_seek(start - self.time_pos);
+        }
+    }
+
+    /// Goes on with the next chapter, or the next track after the last one.
+    pub fn chapter_next(&mut self) {
+        let (chapters, index) = self.chapters();
+        if chapters.is_empty() {
+            return;
+        }
+        let next = index.map_or(0, |index| index + 1);
+        if next < chapters.len() {
+            self.chapter_play(next);
+        } else {
+            self.player.skip();
+        }
+    }
+
+    /// Goes back to the start of the chapter, or to the chapter before when it just started.
+    pub fn chapter_previous(&mut self) {
+        let (chapters, index) = self.chapters();
+        let Some(index) = index else {
+            return;
+        };
+        let start = chapters[index].start.as_secs() as i64;
+        if self.time_pos - start < 3 && index > 0 {
+            self.chapter_play(index - 1);
+        } else {
+            self.chapter_play(index);
+        }
+    }
+
     pub fn progress_update(&mut self, time_pos: i64, duration: i64) {
+        // streams have no duration, only the time listened is shown
+        if duration == 0 && self.current_is_stream() {
+            self.time_pos = time_pos;
+            self.app
+                .attr(
+                    &Id::Progress,
+                    Attribute::Text,
+                    AttrValue::String(format!(
+                        "{}    -    Live",
+                        Track::duration_formatted_short(&Duration::from_secs(
+                            time_pos.try_into().unwrap_or(0)
+                        ))
+                    )),
+                )
+                .ok();
+            return;
+        }
         // for unsupported file format, don't update progress
         if duration == 0 {
             return;
@@ -102,23 +307,18 @@ impl Model {
 
         let new_prog = Self::progress_safeguard(progress);
 
-        // About to finish signal is a simulation of gstreamer, and used for gapless
-        #[cfg(any(not(feature = "gst"), feature = "mpv"))]
-        if !self.player.playlist.is_empty()
-            && !self.player.has_next_track()
-            && new_prog >= 0.5
-            && duration - time_pos < 2
-            && self.config.gapless
-        {
-            self.player
-                .message_tx
-                .send(crate::player::PlayerMsg::AboutToFinish)
-                .ok();
-        }
-
         self.progress_set(new_prog, duration);
     }
 
+    fn current_is_stream(&self) -> bool {
+        self.player
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::file)
+            .is_some_and(is_stream)
+    }
+
     fn progress_safeguard(progress: f64) -> f64 {
         let mut new_prog = progress / 100.0;
         if new_prog > 1.0 {
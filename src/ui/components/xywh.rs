@@ -229,6 +229,24 @@ impl Model {
         if self.app.mounted(&Id::YoutubeSearchTablePopup) {
             return true;
         }
+        if self.app.mounted(&Id::RadioBrowserTablePopup) {
+            return true;
+        }
+        if self.app.mounted(&Id::EqualizerPopup) {
+            return true;
+        }
+        if self.app.mounted(&Id::OutputDevicePopup) {
+            return true;
+        }
+        if self.app.mounted(&Id::ChapterPopup) {
+            return true;
+        }
+        if self.app.mounted(&Id::StatsPopup) {
+            return true;
+        }
+        if self.app.mounted(&Id::NamedPlaylistPickPopup) {
+            return true;
+        }
 
         if self.app.mounted(&Id::GeneralSearchInput) {
             return true;
@@ -32,9 +32,8 @@ pub mod model;
 
 use crate::config::ColorTermusic;
 use crate::config::Settings;
-#[cfg(not(any(feature = "mpv", feature = "gst")))]
-use crate::player::PlayerTrait;
 use crate::songtag::SongTag;
+use anyhow::Result;
 use model::Model;
 use std::time::Duration;
 use tuirealm::application::PollStrategy;
@@ -48,11 +47,13 @@ const FORCED_REDRAW_INTERVAL: Duration = Duration::from_millis(1000);
 #[derive(Clone, Debug, PartialEq)]
 pub enum Msg {
     // AppClose,
+    Chapter(CHMsg),
     ColorEditor(CEMsg),
     DataBase(DBMsg),
     DeleteConfirmCloseCancel,
     DeleteConfirmCloseOk,
     DeleteConfirmShow,
+    Equalizer(EQMsg),
     ErrorPopupClose,
     GeneralSearch(GSMsg),
     HelpPopupShow,
@@ -60,10 +61,15 @@ pub enum Msg {
     KeyEditor(KEMsg),
     LayoutTreeView,
     LayoutDataBase,
+    LayoutPlaylists,
+    LayoutPodcasts,
     Library(LIMsg),
     LyricCycle,
     LyricAdjustDelay(i64),
+    NamedPlaylist(NPMsg),
+    OutputDevice(ODMsg),
     PlayerToggleGapless,
+    PlayerAbLoop,
     PlayerTogglePause,
     PlayerVolumeUp,
     PlayerVolumeDown,
@@ -71,9 +77,18 @@ pub enum Msg {
     PlayerSpeedDown,
     PlayerSeek(isize),
     Playlist(PLMsg),
+    Podcast(PCMsg),
+    PlaylistExportCloseCancel,
+    PlaylistExportCloseOk(String, bool),
     QuitPopupCloseCancel,
     QuitPopupCloseOk,
     QuitPopupShow,
+    RadioBrowser(RBMsg),
+    Rating(RTMsg),
+    SleepTimerPopupCloseCancel,
+    SleepTimerPopupCloseOk(String),
+    SleepTimerPopupShow,
+    Stats(STMsg),
     TagEditor(TEMsg),
     UpdatePhoto,
     YoutubeSearch(YSMsg),
@@ -87,6 +102,97 @@ pub enum LIMsg {
     TreeBlur,
     Yank,
     Paste,
+    ReplayGainScan(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EQMsg {
+    PopupShow,
+    PopupCloseCancel,
+    PopupCloseOk(usize),
+    Preview(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub enum ODMsg {
+    PopupShow,
+    PopupCloseCancel,
+    /// Name of the device, empty for the default one
+    PopupCloseOk(String),
+}
+
+/// The chapters of the current track.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CHMsg {
+    Next,
+    Previous,
+    PopupShow,
+    PopupCloseCancel,
+    /// Index of the chapter to play
+    PopupCloseOk(usize),
+}
+
+/// Rates the selected track of the playlist or database, or else the current one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RTMsg {
+    Up,
+    Down,
+    ToggleFavourite,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum STMsg {
+    PopupShow,
+    PopupClose,
+}
+
+/// The named playlists, their list and tracks in the playlists layout and their popups.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NPMsg {
+    AppendCloseCancel,
+    AppendCloseOk(usize),
+    AppendShow,
+    DeleteCloseCancel,
+    DeleteCloseOk,
+    DeleteShow(usize),
+    Duplicate(usize),
+    ExportShow(usize),
+    ListBlurDown,
+    ListBlurUp,
+    Load(usize),
+    LoadShown,
+    NameInputCloseCancel,
+    NameInputCloseOk(String),
+    NewShow,
+    RenameShow(usize),
+    Show(usize),
+    TrackAdd(usize),
+    TrackRemove(usize),
+    TracksBlurDown,
+    TracksBlurUp,
+}
+
+/// The podcasts layout, its feeds and episodes lists and their popups.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PCMsg {
+    AddCloseCancel,
+    AddCloseOk(String),
+    AddShow,
+    DeleteCloseCancel,
+    DeleteCloseOk,
+    DeleteShow(usize),
+    EpisodeAdd(usize),
+    EpisodeDeleteShow(usize),
+    EpisodeDownload(usize),
+    EpisodeTogglePlayed(usize),
+    EpisodesBlurDown,
+    EpisodesBlurUp,
+    FeedsBlurDown,
+    FeedsBlurUp,
+    Refresh(usize),
+    RefreshAll,
+    Show(usize),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -95,6 +201,7 @@ pub enum DBMsg {
     AddPlaylist(usize),
     CriteriaBlurDown,
     CriteriaBlurUp,
+    CycleOrder,
     SearchResult(usize),
     SearchResultBlurDown,
     SearchResultBlurUp,
@@ -112,6 +219,7 @@ pub enum PLMsg {
     Add(String),
     Delete(usize),
     DeleteAll,
+    ExportShow,
     LoopModeCycle,
     PlaySelected(usize),
     Shuffle,
@@ -146,6 +254,19 @@ pub enum YSMsg {
     TablePopupCloseCancel,
     TablePopupCloseOk(usize),
 }
+/// The station browser, its search input and the table of stations found or favourites.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RBMsg {
+    InputPopupShow,
+    InputPopupCloseCancel,
+    InputPopupCloseOk(String),
+    TablePopupNext,
+    TablePopupPrevious,
+    TablePopupCloseCancel,
+    TablePopupCloseOk(usize),
+    TablePopupFavourite(usize),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum TEMsg {
     TagEditorRun(String),
@@ -398,18 +519,140 @@ pub enum KEMsg {
     PlaylistSwapUpBlurUp,
     PlaylistSwapUpInputBlurDown,
     PlaylistSwapUpInputBlurUp,
+    LibraryReplaygainScanBlurDown,
+    LibraryReplaygainScanBlurUp,
+    LibraryReplaygainScanInputBlurDown,
+    LibraryReplaygainScanInputBlurUp,
+    GlobalEqualizerOpenBlurDown,
+    GlobalEqualizerOpenBlurUp,
+    GlobalEqualizerOpenInputBlurDown,
+    GlobalEqualizerOpenInputBlurUp,
+    GlobalOutputDeviceOpenBlurDown,
+    GlobalOutputDeviceOpenBlurUp,
+    GlobalOutputDeviceOpenInputBlurDown,
+    GlobalOutputDeviceOpenInputBlurUp,
+    GlobalStatsOpenBlurDown,
+    GlobalStatsOpenBlurUp,
+    GlobalStatsOpenInputBlurDown,
+    GlobalStatsOpenInputBlurUp,
+    DatabaseSortBlurDown,
+    DatabaseSortBlurUp,
+    DatabaseSortInputBlurDown,
+    DatabaseSortInputBlurUp,
+    GlobalRatingUpBlurDown,
+    GlobalRatingUpBlurUp,
+    GlobalRatingUpInputBlurDown,
+    GlobalRatingUpInputBlurUp,
+    GlobalRatingDownBlurDown,
+    GlobalRatingDownBlurUp,
+    GlobalRatingDownInputBlurDown,
+    GlobalRatingDownInputBlurUp,
+    GlobalFavouriteToggleBlurDown,
+    GlobalFavouriteToggleBlurUp,
+    GlobalFavouriteToggleInputBlurDown,
+    GlobalFavouriteToggleInputBlurUp,
+    GlobalLayoutPlaylistsBlurDown,
+    GlobalLayoutPlaylistsBlurUp,
+    GlobalLayoutPlaylistsInputBlurDown,
+    GlobalLayoutPlaylistsInputBlurUp,
+    GlobalNamedPlaylistAppendBlurDown,
+    GlobalNamedPlaylistAppendBlurUp,
+    GlobalNamedPlaylistAppendInputBlurDown,
+    GlobalNamedPlaylistAppendInputBlurUp,
+    NamedPlaylistNewBlurDown,
+    NamedPlaylistNewBlurUp,
+    NamedPlaylistNewInputBlurDown,
+    NamedPlaylistNewInputBlurUp,
+    NamedPlaylistRenameBlurDown,
+    NamedPlaylistRenameBlurUp,
+    NamedPlaylistRenameInputBlurDown,
+    NamedPlaylistRenameInputBlurUp,
+    NamedPlaylistDuplicateBlurDown,
+    NamedPlaylistDuplicateBlurUp,
+    NamedPlaylistDuplicateInputBlurDown,
+    NamedPlaylistDuplicateInputBlurUp,
+    NamedPlaylistDeleteBlurDown,
+    NamedPlaylistDeleteBlurUp,
+    NamedPlaylistDeleteInputBlurDown,
+    NamedPlaylistDeleteInputBlurUp,
+    NamedPlaylistLoadBlurDown,
+    NamedPlaylistLoadBlurUp,
+    NamedPlaylistLoadInputBlurDown,
+    NamedPlaylistLoadInputBlurUp,
+    PlaylistExportBlurDown,
+    PlaylistExportBlurUp,
+    PlaylistExportInputBlurDown,
+    PlaylistExportInputBlurUp,
+    NamedPlaylistExportBlurDown,
+    NamedPlaylistExportBlurUp,
+    NamedPlaylistExportInputBlurDown,
+    NamedPlaylistExportInputBlurUp,
+    GlobalRadioBrowserOpenBlurDown,
+    GlobalRadioBrowserOpenBlurUp,
+    GlobalRadioBrowserOpenInputBlurDown,
+    GlobalRadioBrowserOpenInputBlurUp,
+    GlobalLayoutPodcastsBlurDown,
+    GlobalLayoutPodcastsBlurUp,
+    GlobalLayoutPodcastsInputBlurDown,
+    GlobalLayoutPodcastsInputBlurUp,
+    PodcastAddBlurDown,
+    PodcastAddBlurUp,
+    PodcastAddInputBlurDown,
+    PodcastAddInputBlurUp,
+    PodcastDeleteBlurDown,
+    PodcastDeleteBlurUp,
+    PodcastDeleteInputBlurDown,
+    PodcastDeleteInputBlurUp,
+    PodcastRefreshBlurDown,
+    PodcastRefreshBlurUp,
+    PodcastRefreshInputBlurDown,
+    PodcastRefreshInputBlurUp,
+    PodcastRefreshAllBlurDown,
+    PodcastRefreshAllBlurUp,
+    PodcastRefreshAllInputBlurDown,
+    PodcastRefreshAllInputBlurUp,
+    PodcastDownloadBlurDown,
+    PodcastDownloadBlurUp,
+    PodcastDownloadInputBlurDown,
+    PodcastDownloadInputBlurUp,
+    PodcastMarkPlayedBlurDown,
+    PodcastMarkPlayedBlurUp,
+    PodcastMarkPlayedInputBlurDown,
+    PodcastMarkPlayedInputBlurUp,
+    GlobalSleepTimerOpenBlurDown,
+    GlobalSleepTimerOpenBlurUp,
+    GlobalSleepTimerOpenInputBlurDown,
+    GlobalSleepTimerOpenInputBlurUp,
+    GlobalPlayerAbLoopBlurDown,
+    GlobalPlayerAbLoopBlurUp,
+    GlobalPlayerAbLoopInputBlurDown,
+    GlobalPlayerAbLoopInputBlurUp,
+    GlobalPlayerChapterNextBlurDown,
+    GlobalPlayerChapterNextBlurUp,
+    GlobalPlayerChapterNextInputBlurDown,
+    GlobalPlayerChapterNextInputBlurUp,
+    GlobalPlayerChapterPreviousBlurDown,
+    GlobalPlayerChapterPreviousBlurUp,
+    GlobalPlayerChapterPreviousInputBlurDown,
+    GlobalPlayerChapterPreviousInputBlurUp,
+    GlobalChapterListOpenBlurDown,
+    GlobalChapterListOpenBlurUp,
+    GlobalChapterListOpenInputBlurDown,
+    GlobalChapterListOpenInputBlurUp,
     RadioOkBlurUp,
     RadioOkBlurDown,
 }
 // Let's define the component ids for our application
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum Id {
+    ChapterPopup,
     ColorEditor(IdColorEditor),
     DBListCriteria,
     DBListSearchResult,
     DBListSearchTracks,
     DeleteConfirmRadioPopup,
     DeleteConfirmInputPopup,
+    EqualizerPopup,
     ErrorPopup,
     GeneralSearchInput,
     GeneralSearchTable,
@@ -420,9 +663,24 @@ pub enum Id {
     Library,
     Lyric,
     MessagePopup,
+    NamedPlaylistDeletePopup,
+    NamedPlaylistList,
+    NamedPlaylistNamePopup,
+    NamedPlaylistPickPopup,
+    NamedPlaylistTracks,
+    OutputDevicePopup,
     Playlist,
+    PlaylistExportPopup,
+    PodcastAddPopup,
+    PodcastDeletePopup,
+    PodcastEpisodes,
+    PodcastFeeds,
     Progress,
     QuitPopup,
+    RadioBrowserInputPopup,
+    RadioBrowserTablePopup,
+    SleepTimerPopup,
+    StatsPopup,
     TagEditor(IdTagEditor),
     YoutubeSearchInputPopup,
     YoutubeSearchTablePopup,
@@ -556,16 +814,78 @@ pub enum IdKeyEditor {
     PlaylistSwapDownInput,
     PlaylistSwapUp,
     PlaylistSwapUpInput,
+    LibraryReplaygainScan,
+    LibraryReplaygainScanInput,
+    GlobalEqualizerOpen,
+    GlobalEqualizerOpenInput,
+    GlobalOutputDeviceOpen,
+    GlobalOutputDeviceOpenInput,
+    GlobalStatsOpen,
+    GlobalStatsOpenInput,
+    DatabaseSort,
+    DatabaseSortInput,
+    GlobalRatingUp,
+    GlobalRatingUpInput,
+    GlobalRatingDown,
+    GlobalRatingDownInput,
+    GlobalFavouriteToggle,
+    GlobalFavouriteToggleInput,
+    GlobalLayoutPlaylists,
+    GlobalLayoutPlaylistsInput,
+    GlobalNamedPlaylistAppend,
+    GlobalNamedPlaylistAppendInput,
+    NamedPlaylistNew,
+    NamedPlaylistNewInput,
+    NamedPlaylistRename,
+    NamedPlaylistRenameInput,
+    NamedPlaylistDuplicate,
+    NamedPlaylistDuplicateInput,
+    NamedPlaylistDelete,
+    NamedPlaylistDeleteInput,
+    NamedPlaylistLoad,
+    NamedPlaylistLoadInput,
+    PlaylistExport,
+    PlaylistExportInput,
+    NamedPlaylistExport,
+    NamedPlaylistExportInput,
+    GlobalRadioBrowserOpen,
+    GlobalRadioBrowserOpenInput,
+    GlobalLayoutPodcasts,
+    GlobalLayoutPodcastsInput,
+    PodcastAdd,
+    PodcastAddInput,
+    PodcastDelete,
+    PodcastDeleteInput,
+    PodcastRefresh,
+    PodcastRefreshInput,
+    PodcastRefreshAll,
+    PodcastRefreshAllInput,
+    PodcastDownload,
+    PodcastDownloadInput,
+    PodcastMarkPlayed,
+    PodcastMarkPlayedInput,
+    GlobalSleepTimerOpen,
+    GlobalSleepTimerOpenInput,
+    GlobalPlayerAbLoop,
+    GlobalPlayerAbLoopInput,
+    GlobalPlayerChapterNext,
+    GlobalPlayerChapterNextInput,
+    GlobalPlayerChapterPrevious,
+    GlobalPlayerChapterPreviousInput,
+    GlobalChapterListOpen,
+    GlobalChapterListOpenInput,
     RadioOk,
 }
 
 // StatusLine shows the status of download
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub enum StatusLine {
     Default,
     Success,
     Running,
     Error,
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    Scanning(String),
 }
 
 pub enum SearchLyricState {
@@ -578,10 +898,10 @@ pub struct UI {
 
 impl UI {
     /// Instantiates a new Ui
-    pub fn new(config: &Settings) -> Self {
-        let mut model = Model::new(config);
+    pub fn new(config: &Settings) -> Result<Self> {
+        let mut model = Model::new(config)?;
         model.init_config();
-        Self { model }
+        Ok(Self { model })
     }
     /// ### run
     ///
@@ -590,7 +910,6 @@ impl UI {
         self.model.init_terminal();
         // self.model.player.playlist.playlist_load().ok();
         // Main loop
-        let mut progress_interval = 0;
         while !self.model.quit {
             #[cfg(feature = "mpris")]
             self.model.update_mpris();
@@ -602,17 +921,7 @@ impl UI {
             // #[cfg(not(any(feature = "mpv", feature = "gst")))]
             // self.model.progress_update();
             self.model.update_player_msg();
-
-            if progress_interval == 0 {
-                self.model.run();
-
-                #[cfg(not(any(feature = "mpv", feature = "gst")))]
-                self.model.player.get_progress().ok();
-            }
-            progress_interval += 1;
-            if progress_interval >= 80 {
-                progress_interval = 0;
-            }
+            self.model.progress_update_sleep_timer();
 
             match self.model.app.tick(PollStrategy::Once) {
                 Err(err) => {
@@ -636,7 +945,10 @@ impl UI {
             self.model.view();
             // sleep(Duration::from_millis(20));
         }
-        assert!(self.model.player.playlist.save().is_ok());
+        // the server keeps playing and saves the playlist by itself
+        if self.model.config.quit_server_on_exit {
+            self.model.player.quit();
+        }
         if let Err(e) = self.model.config.save() {
             eprintln!("{}", e);
         };
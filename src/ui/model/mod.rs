@@ -26,10 +26,18 @@
 use crate::discord::Rpc;
 #[cfg(feature = "mpris")]
 mod mpris;
+mod radio_options;
+mod remote;
 mod update;
 mod view;
 mod youtube_options;
-use crate::sqlite::{DataBase, SearchCriteria};
+use crate::named_playlist::NamedPlaylist;
+use crate::playlist::PlaylistItem;
+use crate::podcast::Feed;
+use crate::radio_browser::Station;
+use crate::server::SleepTimer;
+use crate::smart_playlist::SmartPlaylist;
+use crate::sqlite::{DataBase, SearchCriteria, TrackOrder};
 #[cfg(feature = "cover")]
 use crate::ueberzug::UeInstance;
 use crate::{
@@ -39,11 +47,13 @@ use crate::{
 };
 
 use crate::config::{Keys, StyleColorSymbol};
-// use crate::player::{GeneralP, GeneralPl};
-use crate::player::GeneralPlayer;
 use crate::songtag::SongTag;
-use crate::sqlite::TrackForDB;
+use crate::sqlite::{EpisodeForDB, PodcastForDB, TrackForDB};
 use crate::ui::SearchLyricState;
+use anyhow::Result;
+use radio_options::RadioOptions;
+use remote::RemotePlayer;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::{Duration, Instant};
@@ -58,6 +68,17 @@ pub const MAX_DEPTH: usize = 4;
 pub enum TermusicLayout {
     TreeView,
     DataBase,
+    Playlists,
+    Podcasts,
+}
+
+/// What the delete popup of the podcasts layout removes, by id.
+#[derive(Clone, Copy)]
+pub enum PodcastDelete {
+    /// The subscription along with its downloads
+    Podcast(u64),
+    /// The download of an episode
+    Download(u64),
 }
 
 // TransferState is used to describe the status of download
@@ -71,6 +92,19 @@ pub enum UpdateComponents {
     MessageHide((String, String)),
     YoutubeSearchSuccess(YoutubeOptions),
     YoutubeSearchFail(String),
+    RadioSearchSuccess(Vec<Station>),
+    RadioSearchFail(String),
+    // the url with the feed read from it
+    PodcastFetched(String, Feed),
+    PodcastFetchFail(String, String),
+    // the episode with the file it was downloaded to
+    PodcastDownloaded(u64, String),
+    // the episode with its title and the error
+    PodcastDownloadFail(u64, String, String),
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    ReplayGainScanRunning(String),
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    ReplayGainScanCompleted(usize, Vec<(String, String)>),
 }
 
 pub struct Model {
@@ -85,13 +119,14 @@ pub struct Model {
     pub path: PathBuf,
     pub tree: Tree,
     pub config: Settings,
-    pub player: GeneralPlayer,
+    pub player: RemotePlayer,
     pub yanked_node_id: Option<String>,
     // pub current_song: Option<Track>,
     pub tageditor_song: Option<Track>,
     pub time_pos: i64,
     pub lyric_line: String,
     youtube_options: YoutubeOptions,
+    radio_options: RadioOptions,
     pub sender: Sender<UpdateComponents>,
     receiver: Receiver<UpdateComponents>,
     #[cfg(feature = "cover")]
@@ -103,6 +138,8 @@ pub struct Model {
     pub ce_themes: Vec<String>,
     pub ce_style_color_symbol: StyleColorSymbol,
     pub ke_key_config: Keys,
+    // whether the key editor shows the keys of `KE_SECOND_PAGE`
+    pub ke_second_page: bool,
     #[cfg(feature = "mpris")]
     pub mpris: mpris::Mpris,
     #[cfg(feature = "discord")]
@@ -112,6 +149,30 @@ pub struct Model {
     pub db_criteria: SearchCriteria,
     pub db_search_results: Vec<String>,
     pub db_search_tracks: Vec<TrackForDB>,
+    // the search result the tracks are from, to query them again in another order
+    pub db_search_index: Option<usize>,
+    pub db_track_order: TrackOrder,
+    // the definitions behind the results, when they are smart playlists
+    pub db_smart_playlists: Vec<SmartPlaylist>,
+    pub np_names: Vec<String>,
+    // the playlist in the tracks list of the playlists layout
+    pub np_shown: Option<NamedPlaylist>,
+    // the playlist the name input or delete popup is about, none for a new one
+    pub np_target: Option<String>,
+    // the files waiting for a playlist to be picked
+    pub np_append: Vec<String>,
+    // the tracks waiting for the export popup to give a file
+    pub export_items: Vec<PlaylistItem>,
+    pub pc_podcasts: Vec<PodcastForDB>,
+    // the podcast in the episodes list of the podcasts layout
+    pub pc_shown: Option<u64>,
+    pub pc_episodes: Vec<EpisodeForDB>,
+    // what the delete popup is about
+    pub pc_delete: Option<PodcastDelete>,
+    // the episodes that are being downloaded
+    pub pc_downloading: HashSet<u64>,
+    // the sleep timer as the progress title shows it
+    pub sleep_timer_shown: Option<SleepTimer>,
 }
 
 pub enum ViuerSupported {
@@ -121,7 +182,8 @@ pub enum ViuerSupported {
 }
 
 impl Model {
-    pub fn new(config: &Settings) -> Self {
+    pub fn new(config: &Settings) -> Result<Self> {
+        let player = RemotePlayer::new(config)?;
         let path = Self::get_full_path_from_config(config);
         let tree = Tree::new(Self::library_dir_tree(&path, MAX_DEPTH));
 
@@ -139,7 +201,7 @@ impl Model {
         let db_criteria = SearchCriteria::Artist;
         // let viuer_supported =
         //     viuer::KittySupport::None != viuer::get_kitty_support() || viuer::is_iterm_supported();
-        Self {
+        Ok(Self {
             app: Self::init_app(&tree, config),
             quit: false,
             redraw: true,
@@ -148,13 +210,14 @@ impl Model {
             path,
             terminal: TerminalBridge::new().expect("Could not initialize terminal"),
             config: config.clone(),
-            player: GeneralPlayer::new(config),
+            player,
             yanked_node_id: None,
             // current_song: None,
             tageditor_song: None,
             time_pos: 0,
             lyric_line: String::new(),
             youtube_options: YoutubeOptions::new(),
+            radio_options: RadioOptions::new(),
             sender: tx,
             receiver: rx,
             #[cfg(feature = "cover")]
@@ -166,6 +229,7 @@ impl Model {
             ce_themes: vec![],
             ce_style_color_symbol: StyleColorSymbol::default(),
             ke_key_config: Keys::default(),
+            ke_second_page: false,
             #[cfg(feature = "mpris")]
             mpris: mpris::Mpris::default(),
             #[cfg(feature = "discord")]
@@ -175,7 +239,21 @@ impl Model {
             db_criteria,
             db_search_results: Vec::new(),
             db_search_tracks: Vec::new(),
-        }
+            db_search_index: None,
+            db_track_order: TrackOrder::Name,
+            db_smart_playlists: Vec::new(),
+            np_names: Vec::new(),
+            np_shown: None,
+            np_target: None,
+            np_append: Vec::new(),
+            pc_podcasts: Vec::new(),
+            pc_shown: None,
+            pc_episodes: Vec::new(),
+            pc_delete: None,
+            pc_downloading: HashSet::new(),
+            sleep_timer_shown: None,
+            export_items: Vec::new(),
+        })
     }
 
     pub fn get_full_path_from_config(config: &Settings) -> PathBuf {
@@ -212,10 +290,4 @@ impl Model {
     pub fn force_redraw(&mut self) {
         self.redraw = true;
     }
-
-    pub fn run(&mut self) {
-        if self.player.is_stopped() {
-            self.player.start_play();
-        }
-    }
 }
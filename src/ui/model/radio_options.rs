@@ -0,0 +1,212 @@
+use super::{
+    Model,
+    UpdateComponents::{RadioSearchFail, RadioSearchSuccess},
+};
+use crate::playlist::PlaylistItem;
+use crate::radio_browser::{self, Query, Station, PAGE_SIZE};
+use crate::track::Track;
+use crate::ui::Id;
+use std::thread;
+use tuirealm::props::{Alignment, AttrValue, Attribute, TableBuilder, TextSpan};
+
+pub struct RadioOptions {
+    items: Vec<Station>,
+    favourites: Vec<Station>,
+    query: Query,
+    page: u32,
+    // the table lists the favourites rather than search results
+    show_favourites: bool,
+    loading: bool,
+}
+
+impl RadioOptions {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            favourites: Vec::new(),
+            query: Query::default(),
+            page: 0,
+            show_favourites: false,
+            loading: false,
+        }
+    }
+
+    fn shown(&self) -> &[Station] {
+        if self.show_favourites {
+            &self.favourites
+        } else {
+            &self.items
+        }
+    }
+
+    fn is_favourite(&self, station: &Station) -> bool {
+        self.favourites
+            .iter()
+            .any(|favourite| favourite.uuid == station.uuid && favourite.url == station.url)
+    }
+}
+
+impl Model {
+    /// Searches the stations of the input, or shows the favourites when it is empty.
+    pub fn radio_options_search(&mut self, input: &str) {
+        match radio_browser::load_favourites() {
+            Ok(favourites) => self.radio_options.favourites = favourites,
+            Err(e) => self.mount_error_popup(format!("load favourites error: {}", e).as_str()),
+        }
+        let query = Query::parse(input);
+        self.radio_options.show_favourites = query.is_empty();
+        self.radio_options.query = query;
+        self.radio_options.page = 0;
+        if self.radio_options.show_favourites {
+            self.sync_radio_options();
+        } else {
+            self.radio_options_fetch();
+        }
+    }
+
+    fn radio_options_fetch(&mut self) {
+        self.radio_options.loading = true;
+        self.sync_radio_options();
+        let base_url = self.config.radio_browser_url.clone();
+        let query = self.radio_options.query.clone();
+        let page = self.radio_options.page;
+        let tx = self.sender.clone();
+        thread::spawn(
+            move || match radio_browser::search(&base_url, &query, page) {
+                Ok(stations) => {
+                    tx.send(RadioSearchSuccess(stations)).ok();
+                }
+                Err(e) => {
+                    tx.send(RadioSearchFail(e.to_string())).ok();
+                }
+            },
+        );
+    }
+
+    pub fn radio_options_search_done(&mut self, stations: Vec<Station>) {
+        self.radio_options.items = stations;
+        self.radio_options.loading = false;
+        self.sync_radio_options();
+    }
+
+    pub fn radio_options_prev_page(&mut self) {
+        let options = &mut self.radio_options;
+        if options.show_favourites || options.loading || options.page == 0 {
+            return;
+        }
+        options.page -= 1;
+        self.radio_options_fetch();
+    }
+
+    pub fn radio_options_next_page(&mut self) {
+        let options = &mut self.radio_options;
+        // a page that isn't full is the last one
+        if options.show_favourites || options.loading || options.items.len() < PAGE_SIZE as usize {
+            return;
+        }
+        options.page += 1;
+        self.radio_options_fetch();
+    }
+
+    /// Adds the station to the playlist, as a stream with the station name as title.
+    pub fn radio_options_add(&mut self, index: usize) {
+        let Some(station) = self.radio_options.shown().get(index) else {
+            return;
+        };
+        let track = Track::from_playlist_item(&PlaylistItem {
+            location: station.url.clone(),
+            title: Some(station.name.clone()),
+            artist: None,
+            duration: None,
+        });
+        if self.config.add_playlist_front {
+            self.player.playlist.tracks.push_front(track);
+        } else {
+            self.player.playlist.tracks.push_back(track);
+        }
+        self.playlist_sync();
+    }
+
+    pub fn radio_options_toggle_favourite(&mut self, index: usize) {
+        let options = &mut self.radio_options;
+        let Some(station) = options.shown().get(index).cloned() else {
+            return;
+        };
+        if options.is_favourite(&station) {
+            options
+                .favourites
+                .retain(|favourite| favourite.uuid != station.uuid || favourite.url != station.url);
+        } else {
+            options.favourites.push(station);
+        }
+        if let Err(e) = radio_browser::save_favourites(&self.radio_options.favourites) {
+            self.mount_error_popup(format!("save favourites error: {}", e).as_str());
+        }
+        self.sync_radio_options();
+    }
+
+    pub fn sync_radio_options(&mut self) {
+        let options = &self.radio_options;
+        let mut table: TableBuilder = TableBuilder::default();
+        if options.loading {
+            table
+                .add_col(TextSpan::from(""))
+                .add_col(TextSpan::from("Loading..."));
+        } else if options.shown().is_empty() {
+            let text = if options.show_favourites {
+                "No favourites yet, search for stations to add some."
+            } else {
+                "No stations found."
+            };
+            table
+                .add_col(TextSpan::from(""))
+                .add_col(TextSpan::from(text));
+        } else {
+            for (idx, station) in options.shown().iter().enumerate() {
+                if idx > 0 {
+                    table.add_row();
+                }
+                let mark = if options.is_favourite(station) {
+                    "*"
+                } else {
+                    ""
+                };
+                table
+                    .add_col(TextSpan::new(mark))
+                    .add_col(TextSpan::new(&station.name).bold())
+                    .add_col(TextSpan::new(&station.country))
+                    .add_col(TextSpan::new(station.tags.replace(',', ", ")))
+                    .add_col(TextSpan::new(station.format()));
+            }
+        }
+        let table = table.build();
+
+        let title = if options.show_favourites {
+            format!(
+                "\u{2500}\u{2500}\u{2500} Favourites \u{2500}\u{2500}\u{2500}\u{2524} Enter add to playlist, {} remove favourite \u{251c}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
+                self.config.keys.global_favourite_toggle,
+            )
+        } else {
+            format!(
+                "\u{2500}\u{2500}\u{2500} Page {} \u{2500}\u{2500}\u{2500}\u{2524} Tab/Shift+Tab switch pages, Enter add to playlist, {} favourite \u{251c}\u{2500}\u{2500} {} \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
+                options.page + 1,
+                self.config.keys.global_favourite_toggle,
+                self.config.radio_browser_url,
+            )
+        };
+        self.app
+            .attr(
+                &Id::RadioBrowserTablePopup,
+                Attribute::Content,
+                AttrValue::Table(table),
+            )
+            .ok();
+        self.app
+            .attr(
+                &Id::RadioBrowserTablePopup,
+                Attribute::Title,
+                AttrValue::Title((title, Alignment::Left)),
+            )
+            .ok();
+    }
+}
@@ -0,0 +1,205 @@
+use crate::config::Settings;
+use crate::player::{Loop, PlayerTrait, Playlist, Status};
+use crate::server::{
+    self, AbLoop, Client, Event, PlayerState, PlaylistEdit, Request, Response, SleepTimer,
+};
+use crate::track::Track;
+use anyhow::Result;
+use std::sync::mpsc::Receiver;
+use std::time::Instant;
+
+/// The player as seen from the interface. Playback happens in the server, this keeps a copy
+/// of its playlist and state that is updated from its events.
+pub struct RemotePlayer {
+    client: Client,
+    pub events: Receiver<Event>,
+    pub playlist: Playlist,
+    // the tracks as the server last knew them, so that unchanged ones aren't sent back
+    synced: Vec<String>,
+    status: Status,
+    volume: i32,
+    speed: i32,
+    /// The song an internet radio is playing now
+    pub stream_title: Option<String>,
+    // the sleep timer as the server last told, and when it did
+    sleep_timer: Option<(SleepTimer, Instant)>,
+    pub ab_loop: Option<AbLoop>,
+}
+
+impl RemotePlayer {
+    pub fn new(config: &Settings) -> Result<Self> {
+        let client = server::connect_or_spawn()?;
+        let events = Client::connect()?.subscribe()?;
+        Ok(Self {
+            client,
+            events,
+            playlist: Playlist {
+                index: Some(0),
+                ..Playlist::default()
+            },
+            synced: Vec::new(),
+            status: Status::Stopped,
+            volume: config.volume,
+            speed: config.speed,
+            stream_title: None,
+            sleep_timer: None,
+            ab_loop: None,
+        })
+    }
+
+    fn request(&mut self, request: &Request) -> Result<()> {
+        if let Response::State(state) = self.client.request(request)? {
+            self.update_state(&state);
+        }
+        Ok(())
+    }
+
+    pub fn update_state(&mut self, state: &PlayerState) {
+        self.status = state.status;
+        self.volume = state.volume;
+        self.speed = state.speed;
+        self.stream_title.clone_from(&state.stream_title);
+        self.sleep_timer = state.sleep_timer.map(|timer| (timer, Instant::now()));
+        self.ab_loop = state.ab_loop;
+    }
+
+    /// What is left of the sleep timer, counting down the seconds since the server told.
+    pub fn sleep_timer(&self) -> Option<SleepTimer> {
+        self.sleep_timer.map(|(timer, at)| match timer {
+            SleepTimer::Seconds(seconds) => {
+                SleepTimer::Seconds(seconds.saturating_sub(at.elapsed().as_secs()))
+            }
+            SleepTimer::Tracks(_) => timer,
+        })
+    }
+
+    pub fn mark_ab_loop(&mut self) {
+        self.request(&Request::MarkAbLoop).ok();
+    }
+
+    pub fn set_sleep_timer(&mut self, timer: Option<SleepTimer>) {
+        self.request(&Request::SetSleepTimer(timer)).ok();
+    }
+
+    /// Takes the tracks the server has after the current one.
+    pub fn set_tracks(&mut self, files: &[String]) {
+        self.playlist.tracks = self.playlist.tracks_from_files(files);
+        self.synced = files.to_vec();
+    }
+
+    pub fn set_current_track(&mut self, file: Option<&str>) {
+        self.stream_title = None;
+        self.playlist.current_track = file.and_then(|file| {
+            self.playlist
+                .tracks_from_files(&[file.to_string()])
+                .pop_front()
+        });
+    }
+
+    /// Sends what was changed here in the playlist to the server. The edits rather than the
+    /// whole list are sent, as the server may have gone on to the next track in the meantime.
+    pub fn sync_playlist(&mut self) {
+        let files = self.playlist.files();
+        let edits = PlaylistEdit::diff(&self.synced, &files);
+        if !edits.is_empty() {
+            let base = std::mem::replace(&mut self.synced, files);
+            self.request(&Request::EditPlaylist { base, edits }).ok();
+        }
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.status == Status::Stopped
+    }
+
+    pub fn skip(&mut self) {
+        self.request(&Request::Next).ok();
+    }
+
+    pub fn previous(&mut self) {
+        self.request(&Request::Previous).ok();
+    }
+
+    pub fn toggle_gapless(&mut self) {
+        self.request(&Request::ToggleGapless).ok();
+    }
+
+    pub fn set_loop_mode(&mut self, mode: Loop) {
+        self.request(&Request::SetLoopMode(mode)).ok();
+    }
+
+    pub fn set_equalizer(&mut self, name: &str) {
+        self.request(&Request::SetEqualizer(name.to_string())).ok();
+    }
+
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn set_output_device(&mut self, name: &str) -> Result<()> {
+        self.request(&Request::SetOutputDevice(name.to_string()))
+    }
+
+    /// Shuts the server down, which stops the music.
+    pub fn quit(&mut self) {
+        self.client.request(&Request::Quit).ok();
+    }
+}
+
+impl PlayerTrait for RemotePlayer {
+    fn add_and_play(&mut self, current_track: &str) {
+        if let Ok(track) = Track::read_from_path(current_track) {
+            self.playlist.tracks.push_front(track);
+            self.sync_playlist();
+            // the server goes on with the first track of the playlist
+            self.stop();
+        }
+    }
+    fn volume(&self) -> i32 {
+        self.volume
+    }
+    fn volume_up(&mut self) {
+        self.request(&Request::VolumeUp).ok();
+    }
+    fn volume_down(&mut self) {
+        self.request(&Request::VolumeDown).ok();
+    }
+    fn set_volume(&mut self, volume: i32) {
+        self.request(&Request::SetVolume(volume)).ok();
+    }
+    fn pause(&mut self) {
+        self.request(&Request::Pause).ok();
+    }
+    fn resume(&mut self) {
+        self.request(&Request::Play).ok();
+    }
+    fn is_paused(&self) -> bool {
+        self.status == Status::Paused
+    }
+    fn seek(&mut self, secs: i64) -> Result<()> {
+        self.request(&Request::Seek(secs))
+    }
+    // the server sends the progress by itself
+    fn get_progress(&self) -> Result<()> {
+        Ok(())
+    }
+    fn set_speed(&mut self, speed: i32) {
+        self.request(&Request::SetSpeed(speed)).ok();
+    }
+    fn speed_up(&mut self) {
+        self.request(&Request::SpeedUp).ok();
+    }
+    fn speed_down(&mut self) {
+        self.request(&Request::SpeedDown).ok();
+    }
+    fn speed(&self) -> i32 {
+        self.speed
+    }
+    fn stop(&mut self) {
+        self.request(&Request::Stop).ok();
+    }
+}
@@ -27,18 +27,22 @@ use crate::config::ColorTermusic;
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE.
  */
-use crate::player::{PlayerMsg, PlayerTrait};
+use crate::named_playlist::NamedPlaylist;
+use crate::player::PlayerTrait;
+use crate::playlist::PlaylistItem;
+use crate::server::Event;
 use crate::sqlite::SearchCriteria;
+use crate::track::Track;
 use crate::ui::{
     model::{TermusicLayout, UpdateComponents},
-    CEMsg, DBMsg, GSMsg, Id, IdColorEditor, IdKeyEditor, IdTagEditor, KEMsg, LIMsg, Model, Msg,
-    PLMsg, StatusLine, TEMsg, YSMsg,
+    CEMsg, CHMsg, DBMsg, EQMsg, GSMsg, Id, IdColorEditor, IdKeyEditor, IdTagEditor, KEMsg, LIMsg,
+    Model, Msg, NPMsg, ODMsg, PCMsg, PLMsg, RBMsg, RTMsg, STMsg, StatusLine, TEMsg, YSMsg,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread::{self, sleep};
 use std::time::Duration;
 use tuirealm::props::{AttrValue, Attribute, Color};
-use tuirealm::Update;
+use tuirealm::{State, StateValue, Update};
 
 // #[allow(clippy::too_many_lines)]
 impl Update<Msg> for Model {
@@ -54,12 +58,72 @@ impl Update<Msg> for Model {
                 | Msg::DeleteConfirmCloseCancel
                 | Msg::DeleteConfirmCloseOk => self.update_delete_confirmation(&msg),
 
+                Msg::Equalizer(m) => {
+                    self.update_equalizer(&m);
+                    None
+                }
+                Msg::OutputDevice(m) => {
+                    self.update_output_device(m);
+                    None
+                }
+                Msg::Chapter(m) => {
+                    self.update_chapter(&m);
+                    None
+                }
+                Msg::Rating(m) => {
+                    self.update_rating(&m);
+                    None
+                }
+                Msg::NamedPlaylist(m) => {
+                    self.update_named_playlist(m);
+                    None
+                }
+                Msg::Podcast(m) => {
+                    self.update_podcast(m);
+                    None
+                }
+                Msg::Stats(STMsg::PopupShow) => {
+                    self.mount_stats_popup();
+                    None
+                }
+                Msg::Stats(STMsg::PopupClose) => {
+                    self.app.umount(&Id::StatsPopup).ok();
+                    self.app.unlock_subs();
+                    None
+                }
                 Msg::ErrorPopupClose => {
                     if self.app.mounted(&Id::ErrorPopup) {
                         self.app.umount(&Id::ErrorPopup).ok();
                     }
                     None
                 }
+                Msg::PlaylistExportCloseCancel => {
+                    self.export_items.clear();
+                    self.app.umount(&Id::PlaylistExportPopup).ok();
+                    self.app.unlock_subs();
+                    None
+                }
+                Msg::PlaylistExportCloseOk(path, relative) => {
+                    self.app.umount(&Id::PlaylistExportPopup).ok();
+                    self.app.unlock_subs();
+                    self.playlist_export(&path, relative);
+                    None
+                }
+                Msg::SleepTimerPopupShow => {
+                    self.mount_sleep_timer_popup();
+                    None
+                }
+                Msg::SleepTimerPopupCloseCancel => {
+                    self.app.umount(&Id::SleepTimerPopup).ok();
+                    self.app.unlock_subs();
+                    None
+                }
+                Msg::SleepTimerPopupCloseOk(input) => {
+                    self.app.umount(&Id::SleepTimerPopup).ok();
+                    self.app.unlock_subs();
+                    self.sleep_timer_set(&input);
+                    None
+                }
                 Msg::QuitPopupShow => {
                     if self.config.disable_exit_confirmation {
                         self.quit = true;
@@ -97,6 +161,7 @@ impl Update<Msg> for Model {
 
                 Msg::PlayerTogglePause
                 | Msg::PlayerToggleGapless
+                | Msg::PlayerAbLoop
                 | Msg::PlayerSpeedUp
                 | Msg::PlayerSpeedDown
                 | Msg::PlayerVolumeUp
@@ -115,6 +180,10 @@ impl Update<Msg> for Model {
                     self.update_youtube_search(&m);
                     None
                 }
+                Msg::RadioBrowser(m) => {
+                    self.update_radio_browser(&m);
+                    None
+                }
                 Msg::LyricCycle => {
                     self.lyric_cycle();
                     None
@@ -142,7 +211,10 @@ impl Update<Msg> for Model {
                     }
                     None
                 }
-                Msg::LayoutDataBase | Msg::LayoutTreeView => self.update_layout(&msg),
+                Msg::LayoutDataBase
+                | Msg::LayoutTreeView
+                | Msg::LayoutPlaylists
+                | Msg::LayoutPodcasts => self.update_layout(&msg),
 
                 Msg::None => None,
             }
@@ -194,45 +266,51 @@ impl Model {
                 self.progress_update_title();
                 None
             }
+            Msg::PlayerAbLoop => {
+                self.player.mark_ab_loop();
+                self.progress_update_marks();
+                None
+            }
             _ => None,
         }
     }
     fn update_layout(&mut self, msg: &Msg) -> Option<Msg> {
-        match msg {
-            Msg::LayoutDataBase => {
-                if let Ok(f) = self.app.query(&Id::Library, Attribute::Focus) {
-                    if Some(AttrValue::Flag(true)) == f {
-                        self.app.active(&Id::DBListCriteria).ok();
-                    }
-                }
+        // the left side follows the layout when it has the focus
+        let left_focused = [
+            Id::Library,
+            Id::DBListCriteria,
+            Id::DBListSearchResult,
+            Id::DBListSearchTracks,
+            Id::NamedPlaylistList,
+            Id::NamedPlaylistTracks,
+            Id::PodcastFeeds,
+            Id::PodcastEpisodes,
+        ]
+        .iter()
+        .any(|id| {
+            matches!(
+                self.app.query(id, Attribute::Focus),
+                Ok(Some(AttrValue::Flag(true)))
+            )
+        });
 
-                self.layout = TermusicLayout::DataBase;
-                None
+        let (layout, left) = match msg {
+            Msg::LayoutDataBase => (TermusicLayout::DataBase, Id::DBListCriteria),
+            Msg::LayoutPlaylists => {
+                self.named_playlist_reload();
+                (TermusicLayout::Playlists, Id::NamedPlaylistList)
             }
-            Msg::LayoutTreeView => {
-                if let Ok(f) = self.app.query(&Id::DBListCriteria, Attribute::Focus) {
-                    if Some(AttrValue::Flag(true)) == f {
-                        self.app.active(&Id::Library).ok();
-                    }
-                }
-
-                if let Ok(f) = self.app.query(&Id::DBListSearchResult, Attribute::Focus) {
-                    if Some(AttrValue::Flag(true)) == f {
-                        self.app.active(&Id::Library).ok();
-                    }
-                }
-
-                if let Ok(f) = self.app.query(&Id::DBListSearchTracks, Attribute::Focus) {
-                    if Some(AttrValue::Flag(true)) == f {
-                        self.app.active(&Id::Library).ok();
-                    }
-                }
-
-                self.layout = TermusicLayout::TreeView;
-                None
+            Msg::LayoutPodcasts => {
+                self.podcast_reload();
+                (TermusicLayout::Podcasts, Id::PodcastFeeds)
             }
-            _ => None,
+            _ => (TermusicLayout::TreeView, Id::Library),
+        };
+        if left_focused {
+            self.app.active(&left).ok();
         }
+        self.layout = layout;
+        None
     }
     fn update_database_list(&mut self, msg: &DBMsg) -> Option<Msg> {
         match msg {
@@ -255,6 +333,13 @@ impl Model {
             DBMsg::SearchTrack(index) => {
                 self.database_update_search_tracks(*index);
             }
+            DBMsg::CycleOrder => {
+                self.db_track_order = self.db_track_order.next();
+                if let Some(index) = self.db_search_index {
+                    self.database_update_search_tracks(index);
+                }
+                self.database_update_tracks_title();
+            }
             DBMsg::AddPlaylist(index) => {
                 if !self.db_search_tracks.is_empty() {
                     if let Some(track) = self.db_search_tracks.get(*index) {
@@ -280,6 +365,10 @@ impl Model {
                 self.mount_key_editor();
             }
             KEMsg::KeyEditorCloseOk => {
+                if let Err(e) = self.ke_key_config.check_duplicates() {
+                    self.mount_error_popup(format!("Keys not saved: {}", e).as_str());
+                    return;
+                }
                 self.config.keys = self.ke_key_config.clone();
                 if self.app.mounted(&Id::KeyEditor(IdKeyEditor::GlobalQuit)) {
                     self.umount_key_editor();
@@ -472,7 +561,127 @@ impl Model {
             | KEMsg::DatabaseAddAllBlurUp
             | KEMsg::DatabaseAddAllInputBlurDown
             | KEMsg::DatabaseAddAllInputBlurUp
-            | KEMsg::PlaylistSwapUpInputBlurUp => {
+            | KEMsg::PlaylistSwapUpInputBlurUp
+            | KEMsg::LibraryReplaygainScanBlurDown
+            | KEMsg::LibraryReplaygainScanBlurUp
+            | KEMsg::LibraryReplaygainScanInputBlurDown
+            | KEMsg::LibraryReplaygainScanInputBlurUp
+            | KEMsg::GlobalEqualizerOpenBlurDown
+            | KEMsg::GlobalEqualizerOpenBlurUp
+            | KEMsg::GlobalEqualizerOpenInputBlurDown
+            | KEMsg::GlobalEqualizerOpenInputBlurUp
+            | KEMsg::GlobalOutputDeviceOpenBlurDown
+            | KEMsg::GlobalOutputDeviceOpenBlurUp
+            | KEMsg::GlobalOutputDeviceOpenInputBlurDown
+            | KEMsg::GlobalOutputDeviceOpenInputBlurUp
+            | KEMsg::GlobalStatsOpenBlurDown
+            | KEMsg::GlobalStatsOpenBlurUp
+            | KEMsg::GlobalStatsOpenInputBlurDown
+            | KEMsg::GlobalStatsOpenInputBlurUp
+            | KEMsg::DatabaseSortBlurDown
+            | KEMsg::DatabaseSortBlurUp
+            | KEMsg::DatabaseSortInputBlurDown
+            | KEMsg::DatabaseSortInputBlurUp
+            | KEMsg::GlobalRatingUpBlurDown
+            | KEMsg::GlobalRatingUpBlurUp
+            | KEMsg::GlobalRatingUpInputBlurDown
+            | KEMsg::GlobalRatingUpInputBlurUp
+            | KEMsg::GlobalRatingDownBlurDown
+            | KEMsg::GlobalRatingDownBlurUp
+            | KEMsg::GlobalRatingDownInputBlurDown
+            | KEMsg::GlobalRatingDownInputBlurUp
+            | KEMsg::GlobalFavouriteToggleBlurDown
+            | KEMsg::GlobalFavouriteToggleBlurUp
+            | KEMsg::GlobalFavouriteToggleInputBlurDown
+            | KEMsg::GlobalFavouriteToggleInputBlurUp
+            | KEMsg::GlobalLayoutPlaylistsBlurDown
+            | KEMsg::GlobalLayoutPlaylistsBlurUp
+            | KEMsg::GlobalLayoutPlaylistsInputBlurDown
+            | KEMsg::GlobalLayoutPlaylistsInputBlurUp
+            | KEMsg::GlobalNamedPlaylistAppendBlurDown
+            | KEMsg::GlobalNamedPlaylistAppendBlurUp
+            | KEMsg::GlobalNamedPlaylistAppendInputBlurDown
+            | KEMsg::GlobalNamedPlaylistAppendInputBlurUp
+            | KEMsg::NamedPlaylistNewBlurDown
+            | KEMsg::NamedPlaylistNewBlurUp
+            | KEMsg::NamedPlaylistNewInputBlurDown
+            | KEMsg::NamedPlaylistNewInputBlurUp
+            | KEMsg::NamedPlaylistRenameBlurDown
+            | KEMsg::NamedPlaylistRenameBlurUp
+            | KEMsg::NamedPlaylistRenameInputBlurDown
+            | KEMsg::NamedPlaylistRenameInputBlurUp
+            | KEMsg::NamedPlaylistDuplicateBlurDown
+            | KEMsg::NamedPlaylistDuplicateBlurUp
+            | KEMsg::NamedPlaylistDuplicateInputBlurDown
+            | KEMsg::NamedPlaylistDuplicateInputBlurUp
+            | KEMsg::NamedPlaylistDeleteBlurDown
+            | KEMsg::NamedPlaylistDeleteBlurUp
+            | KEMsg::NamedPlaylistDeleteInputBlurDown
+            | KEMsg::NamedPlaylistDeleteInputBlurUp
+            | KEMsg::NamedPlaylistLoadBlurDown
+            | KEMsg::NamedPlaylistLoadBlurUp
+            | KEMsg::NamedPlaylistLoadInputBlurDown
+            | KEMsg::NamedPlaylistLoadInputBlurUp
+            | KEMsg::PlaylistExportBlurDown
+            | KEMsg::PlaylistExportBlurUp
+            | KEMsg::PlaylistExportInputBlurDown
+            | KEMsg::PlaylistExportInputBlurUp
+            | KEMsg::NamedPlaylistExportBlurDown
+            | KEMsg::NamedPlaylistExportBlurUp
+            | KEMsg::NamedPlaylistExportInputBlurDown
+            | KEMsg::NamedPlaylistExportInputBlurUp
+            | KEMsg::GlobalRadioBrowserOpenBlurDown
+            | KEMsg::GlobalRadioBrowserOpenBlurUp
+            | KEMsg::GlobalRadioBrowserOpenInputBlurDown
+            | KEMsg::GlobalRadioBrowserOpenInputBlurUp
+            | KEMsg::GlobalLayoutPodcastsBlurDown
+            | KEMsg::GlobalLayoutPodcastsBlurUp
+            | KEMsg::GlobalLayoutPodcastsInputBlurDown
+            | KEMsg::GlobalLayoutPodcastsInputBlurUp
+            | KEMsg::PodcastAddBlurDown
+            | KEMsg::PodcastAddBlurUp
+            | KEMsg::PodcastAddInputBlurDown
+            | KEMsg::PodcastAddInputBlurUp
+            | KEMsg::PodcastDeleteBlurDown
+            | KEMsg::PodcastDeleteBlurUp
+            | KEMsg::PodcastDeleteInputBlurDown
+            | KEMsg::PodcastDeleteInputBlurUp
+            | KEMsg::PodcastRefreshBlurDown
+            | KEMsg::PodcastRefreshBlurUp
+            | KEMsg::PodcastRefreshInputBlurDown
+            | KEMsg::PodcastRefreshInputBlurUp
+            | KEMsg::PodcastRefreshAllBlurDown
+            | KEMsg::PodcastRefreshAllBlurUp
+            | KEMsg::PodcastRefreshAllInputBlurDown
+            | KEMsg::PodcastRefreshAllInputBlurUp
+            | KEMsg::PodcastDownloadBlurDown
+            | KEMsg::PodcastDownloadBlurUp
+            | KEMsg::PodcastDownloadInputBlurDown
+            | KEMsg::PodcastDownloadInputBlurUp
+            | KEMsg::PodcastMarkPlayedBlurDown
+            | KEMsg::PodcastMarkPlayedBlurUp
+            | KEMsg::PodcastMarkPlayedInputBlurDown
+            | KEMsg::PodcastMarkPlayedInputBlurUp
+            | KEMsg::GlobalSleepTimerOpenBlurDown
+            | KEMsg::GlobalSleepTimerOpenBlurUp
+            | KEMsg::GlobalSleepTimerOpenInputBlurDown
+            | KEMsg::GlobalSleepTimerOpenInputBlurUp
+            | KEMsg::GlobalPlayerAbLoopBlurDown
+            | KEMsg::GlobalPlayerAbLoopBlurUp
+            | KEMsg::GlobalPlayerAbLoopInputBlurDown
+            | KEMsg::GlobalPlayerAbLoopInputBlurUp
+            | KEMsg::GlobalPlayerChapterNextBlurDown
+            | KEMsg::GlobalPlayerChapterNextBlurUp
+            | KEMsg::GlobalPlayerChapterNextInputBlurDown
+            | KEMsg::GlobalPlayerChapterNextInputBlurUp
+            | KEMsg::GlobalPlayerChapterPreviousBlurDown
+            | KEMsg::GlobalPlayerChapterPreviousBlurUp
+            | KEMsg::GlobalPlayerChapterPreviousInputBlurDown
+            | KEMsg::GlobalPlayerChapterPreviousInputBlurUp
+            | KEMsg::GlobalChapterListOpenBlurDown
+            | KEMsg::GlobalChapterListOpenBlurUp
+            | KEMsg::GlobalChapterListOpenInputBlurDown
+            | KEMsg::GlobalChapterListOpenInputBlurUp => {
                 self.update_key_editor_focus(msg);
             }
         }
@@ -956,18 +1165,708 @@ impl Model {
                     .ok();
             }
 
-            KEMsg::GlobalPlayerToggleGaplessBlurDown | KEMsg::RadioOkBlurUp => {
+            KEMsg::GlobalPlayerToggleGaplessBlurDown | KEMsg::LibraryReplaygainScanBlurUp => {
                 self.app
                     .active(&Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGaplessInput))
                     .ok();
             }
-            KEMsg::GlobalPlayerToggleGaplessInputBlurDown | KEMsg::GlobalQuitBlurUp => {
+
+            KEMsg::GlobalPlayerToggleGaplessInputBlurDown
+            | KEMsg::LibraryReplaygainScanInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::LibraryReplaygainScan))
+                    .ok();
+            }
+
+            KEMsg::LibraryReplaygainScanBlurDown | KEMsg::GlobalEqualizerOpenBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::LibraryReplaygainScanInput))
+                    .ok();
+            }
+
+            KEMsg::LibraryReplaygainScanInputBlurDown | KEMsg::GlobalEqualizerOpenInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalEqualizerOpen))
+                    .ok();
+            }
+
+            KEMsg::GlobalEqualizerOpenBlurDown | KEMsg::GlobalOutputDeviceOpenBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalEqualizerOpenInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalEqualizerOpenInputBlurDown | KEMsg::GlobalOutputDeviceOpenInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalOutputDeviceOpen))
+                    .ok();
+            }
+
+            KEMsg::GlobalOutputDeviceOpenBlurDown | KEMsg::GlobalStatsOpenBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalOutputDeviceOpenInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalOutputDeviceOpenInputBlurDown | KEMsg::GlobalStatsOpenInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalStatsOpen))
+                    .ok();
+            }
+
+            KEMsg::GlobalStatsOpenBlurDown | KEMsg::DatabaseSortBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalStatsOpenInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalStatsOpenInputBlurDown | KEMsg::DatabaseSortInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::DatabaseSort))
+                    .ok();
+            }
+
+            KEMsg::DatabaseSortBlurDown | KEMsg::GlobalRatingUpBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::DatabaseSortInput))
+                    .ok();
+            }
+
+            KEMsg::DatabaseSortInputBlurDown | KEMsg::GlobalRatingUpInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalRatingUp))
+                    .ok();
+            }
+
+            KEMsg::GlobalRatingUpBlurDown | KEMsg::GlobalRatingDownBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalRatingUpInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalRatingUpInputBlurDown | KEMsg::GlobalRatingDownInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalRatingDown))
+                    .ok();
+            }
+
+            KEMsg::GlobalRatingDownBlurDown | KEMsg::GlobalFavouriteToggleBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalRatingDownInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalRatingDownInputBlurDown | KEMsg::GlobalFavouriteToggleInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalFavouriteToggle))
+                    .ok();
+            }
+
+            KEMsg::GlobalFavouriteToggleBlurDown | KEMsg::GlobalLayoutPlaylistsBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalFavouriteToggleInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalFavouriteToggleInputBlurDown | KEMsg::GlobalLayoutPlaylistsInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPlaylists))
+                    .ok();
+            }
+
+            KEMsg::GlobalLayoutPlaylistsBlurDown | KEMsg::GlobalNamedPlaylistAppendBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPlaylistsInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalLayoutPlaylistsInputBlurDown
+            | KEMsg::GlobalNamedPlaylistAppendInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalNamedPlaylistAppend))
+                    .ok();
+            }
+
+            KEMsg::GlobalNamedPlaylistAppendBlurDown | KEMsg::NamedPlaylistNewBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalNamedPlaylistAppendInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalNamedPlaylistAppendInputBlurDown | KEMsg::NamedPlaylistNewInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistNew))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistNewBlurDown | KEMsg::NamedPlaylistRenameBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistNewInput))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistNewInputBlurDown | KEMsg::NamedPlaylistRenameInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistRename))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistRenameBlurDown | KEMsg::NamedPlaylistDuplicateBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistRenameInput))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistRenameInputBlurDown | KEMsg::NamedPlaylistDuplicateInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDuplicate))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistDuplicateBlurDown | KEMsg::NamedPlaylistDeleteBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDuplicateInput))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistDuplicateInputBlurDown | KEMsg::NamedPlaylistDeleteInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDelete))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistDeleteBlurDown | KEMsg::NamedPlaylistLoadBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDeleteInput))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistDeleteInputBlurDown | KEMsg::NamedPlaylistLoadInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistLoad))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistLoadBlurDown | KEMsg::PlaylistExportBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistLoadInput))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistLoadInputBlurDown | KEMsg::PlaylistExportInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PlaylistExport))
+                    .ok();
+            }
+
+            KEMsg::PlaylistExportBlurDown | KEMsg::NamedPlaylistExportBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PlaylistExportInput))
+                    .ok();
+            }
+
+            KEMsg::PlaylistExportInputBlurDown | KEMsg::NamedPlaylistExportInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistExport))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistExportBlurDown | KEMsg::GlobalRadioBrowserOpenBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::NamedPlaylistExportInput))
+                    .ok();
+            }
+
+            KEMsg::NamedPlaylistExportInputBlurDown | KEMsg::GlobalRadioBrowserOpenInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalRadioBrowserOpen))
+                    .ok();
+            }
+
+            KEMsg::GlobalRadioBrowserOpenBlurDown | KEMsg::GlobalLayoutPodcastsBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalRadioBrowserOpenInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalRadioBrowserOpenInputBlurDown | KEMsg::GlobalLayoutPodcastsInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPodcasts))
+                    .ok();
+            }
+
+            KEMsg::GlobalLayoutPodcastsBlurDown | KEMsg::PodcastAddBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPodcastsInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalLayoutPodcastsInputBlurDown | KEMsg::PodcastAddInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastAdd))
+                    .ok();
+            }
+
+            KEMsg::PodcastAddBlurDown | KEMsg::PodcastDeleteBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastAddInput))
+                    .ok();
+            }
+
+            KEMsg::PodcastAddInputBlurDown | KEMsg::PodcastDeleteInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastDelete))
+                    .ok();
+            }
+
+            KEMsg::PodcastDeleteBlurDown | KEMsg::PodcastRefreshBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastDeleteInput))
+                    .ok();
+            }
+
+            KEMsg::PodcastDeleteInputBlurDown | KEMsg::PodcastRefreshInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastRefresh))
+                    .ok();
+            }
+
+            KEMsg::PodcastRefreshBlurDown | KEMsg::PodcastRefreshAllBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastRefreshInput))
+                    .ok();
+            }
+
+            KEMsg::PodcastRefreshInputBlurDown | KEMsg::PodcastRefreshAllInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastRefreshAll))
+                    .ok();
+            }
+
+            KEMsg::PodcastRefreshAllBlurDown | KEMsg::PodcastDownloadBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastRefreshAllInput))
+                    .ok();
+            }
+
+            KEMsg::PodcastRefreshAllInputBlurDown | KEMsg::PodcastDownloadInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastDownload))
+                    .ok();
+            }
+
+            KEMsg::PodcastDownloadBlurDown | KEMsg::PodcastMarkPlayedBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastDownloadInput))
+                    .ok();
+            }
+
+            KEMsg::PodcastDownloadInputBlurDown | KEMsg::PodcastMarkPlayedInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastMarkPlayed))
+                    .ok();
+            }
+
+            KEMsg::PodcastMarkPlayedBlurDown | KEMsg::GlobalSleepTimerOpenBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::PodcastMarkPlayedInput))
+                    .ok();
+            }
+
+            KEMsg::PodcastMarkPlayedInputBlurDown | KEMsg::GlobalSleepTimerOpenInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalSleepTimerOpen))
+                    .ok();
+            }
+
+            KEMsg::GlobalSleepTimerOpenBlurDown | KEMsg::GlobalPlayerAbLoopBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalSleepTimerOpenInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalSleepTimerOpenInputBlurDown | KEMsg::GlobalPlayerAbLoopInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalPlayerAbLoop))
+                    .ok();
+            }
+
+            KEMsg::GlobalPlayerAbLoopBlurDown | KEMsg::GlobalPlayerChapterNextBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalPlayerAbLoopInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalPlayerAbLoopInputBlurDown | KEMsg::GlobalPlayerChapterNextInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterNext))
+                    .ok();
+            }
+
+            KEMsg::GlobalPlayerChapterNextBlurDown | KEMsg::GlobalPlayerChapterPreviousBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterNextInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalPlayerChapterNextInputBlurDown
+            | KEMsg::GlobalPlayerChapterPreviousInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterPrevious))
+                    .ok();
+            }
+
+            KEMsg::GlobalPlayerChapterPreviousBlurDown | KEMsg::GlobalChapterListOpenBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(
+                        IdKeyEditor::GlobalPlayerChapterPreviousInput,
+                    ))
+                    .ok();
+            }
+
+            KEMsg::GlobalPlayerChapterPreviousInputBlurDown
+            | KEMsg::GlobalChapterListOpenInputBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalChapterListOpen))
+                    .ok();
+            }
+
+            KEMsg::GlobalChapterListOpenBlurDown | KEMsg::RadioOkBlurUp => {
+                self.app
+                    .active(&Id::KeyEditor(IdKeyEditor::GlobalChapterListOpenInput))
+                    .ok();
+            }
+
+            KEMsg::GlobalChapterListOpenInputBlurDown | KEMsg::GlobalQuitBlurUp => {
                 self.app.active(&Id::KeyEditor(IdKeyEditor::RadioOk)).ok();
             }
             _ => {}
         }
     }
 
+    fn update_equalizer(&mut self, msg: &EQMsg) {
+        match msg {
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            EQMsg::PopupShow => self.mount_equalizer_popup(),
+            #[cfg(any(feature = "mpv", feature = "gst"))]
+            EQMsg::PopupShow => {
+                self.mount_error_popup("Equalizer is only available with the default backend.");
+            }
+            EQMsg::Preview(index) => {
+                if let Some(preset) = self.config.equalizer_presets.get(*index) {
+                    self.player.set_equalizer(&preset.name);
+                }
+            }
+            EQMsg::PopupCloseCancel => {
+                self.app.umount(&Id::EqualizerPopup).ok();
+                self.app.unlock_subs();
+                self.player.set_equalizer(&self.config.equalizer);
+            }
+            EQMsg::PopupCloseOk(index) => {
+                self.app.umount(&Id::EqualizerPopup).ok();
+                self.app.unlock_subs();
+                if let Some(preset) = self.config.equalizer_presets.get(*index) {
+                    self.config.equalizer = preset.name.clone();
+                    self.player.set_equalizer(&preset.name);
+                }
+            }
+        }
+    }
+
+    fn update_rating(&mut self, msg: &RTMsg) {
+        let file = match self.selected_track() {
+            Some(file) => file,
+            None => return,
+        };
+        let (rating, favourite) = self.db.get_rating(&file);
+        let result = match msg {
+            RTMsg::Up => self.db.set_rating(&file, (rating + 1).min(5)),
+            RTMsg::Down => self.db.set_rating(&file, rating.saturating_sub(1)),
+            RTMsg::ToggleFavourite => self.db.set_favourite(&file, !favourite),
+        };
+        if let Err(e) = result {
+            self.mount_error_popup(format!("Rating failed: {}", e).as_str());
+            return;
+        }
+
+        let (rating, favourite) = self.db.get_rating(&file);
+        if self.config.rating_write_to_file && *msg != RTMsg::ToggleFavourite {
+            let saved = Track::read_from_path(&file).and_then(|mut track| {
+                track.set_rating(rating);
+                track.save_rating()
+            });
+            if let Err(e) = saved {
+                self.mount_error_popup(format!("Writing the rating failed: {}", e).as_str());
+            }
+        }
+
+        self.playlist_sync();
+        if let Some(index) = self.db_search_index {
+            self.database_update_search_tracks(index);
+        }
+        self.show_message_timeout(
+            "Rating",
+            &format!(
+                "{} {}",
+                crate::ui::components::rating_stars(rating, favourite),
+                Path::new(&file)
+                    .file_name()
+                    .map_or(file.clone(), |name| name.to_string_lossy().to_string())
+            ),
+            None,
+        );
+    }
+
+    fn update_named_playlist(&mut self, msg: NPMsg) {
+        match msg {
+            NPMsg::ListBlurDown => {
+                self.app.active(&Id::NamedPlaylistTracks).ok();
+            }
+            NPMsg::ListBlurUp | NPMsg::TracksBlurDown => {
+                self.app.active(&Id::Playlist).ok();
+            }
+            NPMsg::TracksBlurUp => {
+                self.app.active(&Id::NamedPlaylistList).ok();
+            }
+            NPMsg::Show(index) => self.named_playlist_show(index),
+            NPMsg::NewShow => {
+                self.np_target = None;
+                self.mount_named_playlist_name_popup("");
+            }
+            NPMsg::RenameShow(index) => {
+                if let Some(name) = self.np_names.get(index).cloned() {
+                    self.mount_named_playlist_name_popup(&name);
+                    self.np_target = Some(name);
+                }
+            }
+            NPMsg::NameInputCloseCancel => {
+                self.app.umount(&Id::NamedPlaylistNamePopup).ok();
+                self.app.unlock_subs();
+            }
+            NPMsg::NameInputCloseOk(name) => {
+                self.app.umount(&Id::NamedPlaylistNamePopup).ok();
+                self.app.unlock_subs();
+                let result = match self.np_target.take() {
+                    Some(old) => NamedPlaylist::rename(&old, &name).map(|_| {
+                        if let Some(shown) = &mut self.np_shown {
+                            if shown.name == old {
+                                shown.name.clone_from(&name);
+                            }
+                        }
+                    }),
+                    None => NamedPlaylist::create(&name),
+                };
+                if let Err(e) = result {
+                    self.mount_error_popup(format!("playlist error: {}", e).as_str());
+                }
+                self.named_playlist_reload();
+            }
+            NPMsg::ExportShow(index) => self.named_playlist_export_show(index),
+            NPMsg::Duplicate(index) => {
+                if let Some(name) = self.np_names.get(index) {
+                    if let Err(e) = NamedPlaylist::duplicate(name) {
+                        self.mount_error_popup(format!("playlist error: {}", e).as_str());
+                    }
+                    self.named_playlist_reload();
+                }
+            }
+            NPMsg::DeleteShow(index) => {
+                if let Some(name) = self.np_names.get(index).cloned() {
+                    self.mount_named_playlist_delete_popup(&name);
+                    self.np_target = Some(name);
+                }
+            }
+            NPMsg::DeleteCloseCancel => {
+                self.np_target = None;
+                self.app.umount(&Id::NamedPlaylistDeletePopup).ok();
+                self.app.unlock_subs();
+            }
+            NPMsg::DeleteCloseOk => {
+                self.app.umount(&Id::NamedPlaylistDeletePopup).ok();
+                self.app.unlock_subs();
+                if let Some(name) = self.np_target.take() {
+                    if let Err(e) = NamedPlaylist::delete(&name) {
+                        self.mount_error_popup(format!("playlist error: {}", e).as_str());
+                    }
+                    self.named_playlist_reload();
+                }
+            }
+            NPMsg::Load(index) => {
+                if let Some(name) = self.np_names.get(index).cloned() {
+                    self.named_playlist_load(&name);
+                }
+            }
+            NPMsg::LoadShown => {
+                if let Some(name) = self.np_shown.as_ref().map(|p| p.name.clone()) {
+                    self.named_playlist_load(&name);
+                }
+            }
+            NPMsg::TrackAdd(index) => self.named_playlist_add_track(index),
+            NPMsg::TrackRemove(index) => self.named_playlist_remove_track(index),
+            NPMsg::AppendShow => {
+                self.np_append = self.named_playlist_append_target();
+                if self.np_append.is_empty() {
+                    return;
+                }
+                match NamedPlaylist::names() {
+                    Ok(names) if names.is_empty() => self.mount_error_popup(
+                        format!(
+                            "No playlists yet, create one with <{}> in the playlists layout <{}>.",
+                            self.config.keys.named_playlist_new,
+                            self.config.keys.global_layout_playlists
+                        )
+                        .as_str(),
+                    ),
+                    Ok(names) => {
+                        self.np_names = names;
+                        self.mount_named_playlist_pick_popup();
+                    }
+                    Err(e) => self.mount_error_popup(format!("playlists error: {}", e).as_str()),
+                }
+            }
+            NPMsg::AppendCloseCancel => {
+                self.np_append.clear();
+                self.app.umount(&Id::NamedPlaylistPickPopup).ok();
+                self.app.unlock_subs();
+            }
+            NPMsg::AppendCloseOk(index) => {
+                self.app.umount(&Id::NamedPlaylistPickPopup).ok();
+                self.app.unlock_subs();
+                self.named_playlist_append(index);
+            }
+        }
+    }
+
+    fn update_podcast(&mut self, msg: PCMsg) {
+        match msg {
+            PCMsg::FeedsBlurDown => {
+                self.app.active(&Id::PodcastEpisodes).ok();
+            }
+            PCMsg::FeedsBlurUp | PCMsg::EpisodesBlurDown => {
+                self.app.active(&Id::Playlist).ok();
+            }
+            PCMsg::EpisodesBlurUp => {
+                self.app.active(&Id::PodcastFeeds).ok();
+            }
+            PCMsg::Show(index) => self.podcast_show(index),
+            PCMsg::AddShow => self.mount_podcast_add_popup(),
+            PCMsg::AddCloseCancel => {
+                self.app.umount(&Id::PodcastAddPopup).ok();
+                self.app.unlock_subs();
+            }
+            PCMsg::AddCloseOk(url) => {
+                self.app.umount(&Id::PodcastAddPopup).ok();
+                self.app.unlock_subs();
+                if !url.is_empty() {
+                    self.podcast_fetch(&url);
+                    self.show_message_timeout("Podcasts", "Reading the feed", None);
+                }
+            }
+            PCMsg::Refresh(index) => {
+                if let Some(podcast) = self.pc_podcasts.get(index) {
+                    self.podcast_fetch(&podcast.url);
+                    self.show_message_timeout(
+                        "Podcasts",
+                        &format!("Refreshing {}", podcast.title),
+                        None,
+                    );
+                }
+            }
+            PCMsg::RefreshAll => self.podcast_refresh_all(),
+            PCMsg::DeleteShow(index) => self.podcast_delete_show(index),
+            PCMsg::EpisodeDeleteShow(index) => self.podcast_episode_delete_show(index),
+            PCMsg::DeleteCloseCancel => {
+                self.pc_delete = None;
+                self.app.umount(&Id::PodcastDeletePopup).ok();
+                self.app.unlock_subs();
+            }
+            PCMsg::DeleteCloseOk => {
+                self.app.umount(&Id::PodcastDeletePopup).ok();
+                self.app.unlock_subs();
+                if let Some(target) = self.pc_delete.take() {
+                    self.podcast_delete(target);
+                }
+            }
+            PCMsg::EpisodeAdd(index) => self.podcast_episode_add(index),
+            PCMsg::EpisodeDownload(index) => self.podcast_episode_download(index),
+            PCMsg::EpisodeTogglePlayed(index) => self.podcast_episode_toggle_played(index),
+        }
+    }
+
+    // The selected track when the playlist or the database tracks have the focus, otherwise
+    // the one playing.
+    pub fn selected_track(&self) -> Option<String> {
+        let selected = |id: &Id| match (self.app.query(id, Attribute::Focus), self.app.state(id)) {
+            (Ok(Some(AttrValue::Flag(true))), Ok(State::One(StateValue::Usize(index)))) => {
+                Some(index)
+            }
+            _ => None,
+        };
+        if let Some(index) = selected(&Id::Playlist) {
+            return self
+                .player
+                .playlist
+                .tracks
+                .get(index)
+                .and_then(Track::file)
+                .map(ToString::to_string);
+        }
+        if let Some(index) = selected(&Id::DBListSearchTracks) {
+            return self.db_search_tracks.get(index).map(|t| t.file.clone());
+        }
+        self.player
+            .playlist
+            .current_track
+            .as_ref()
+            .and_then(Track::file)
+            .map(ToString::to_string)
+    }
+
+    fn update_output_device(&mut self, msg: ODMsg) {
+        match msg {
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            ODMsg::PopupShow => self.mount_output_device_popup(),
+            #[cfg(any(feature = "mpv", feature = "gst"))]
+            ODMsg::PopupShow => {
+                self.mount_error_popup(
+                    "Output device selection is only available with the default backend.",
+                );
+            }
+            ODMsg::PopupCloseCancel => {
+                self.app.umount(&Id::OutputDevicePopup).ok();
+                self.app.unlock_subs();
+            }
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            ODMsg::PopupCloseOk(name) => {
+                self.app.umount(&Id::OutputDevicePopup).ok();
+                self.app.unlock_subs();
+                match self.player.set_output_device(&name) {
+                    Ok(()) => self.config.output_device = name,
+                    Err(e) => self.mount_error_popup(format!("{}", e).as_str()),
+                }
+            }
+            #[cfg(any(feature = "mpv", feature = "gst"))]
+            ODMsg::PopupCloseOk(_) => {}
+        }
+    }
+
+    fn update_chapter(&mut self, msg: &CHMsg) {
+        match msg {
+            CHMsg::Next => self.chapter_next(),
+            CHMsg::Previous => self.chapter_previous(),
+            CHMsg::PopupShow => self.mount_chapter_popup(),
+            CHMsg::PopupCloseCancel => {
+                self.app.umount(&Id::ChapterPopup).ok();
+                self.app.unlock_subs();
+            }
+            CHMsg::PopupCloseOk(index) => {
+                self.app.umount(&Id::ChapterPopup).ok();
+                self.app.unlock_subs();
+                self.chapter_play(*index);
+            }
+        }
+    }
+
     fn update_library(&mut self, msg: &LIMsg) {
         match msg {
             LIMsg::TreeBlur => {
@@ -987,6 +1886,9 @@ impl Model {
                     self.mount_error_popup(format!("Paste error: {}", e).as_str());
                 }
             }
+            LIMsg::ReplayGainScan(node) => {
+                self.library_replaygain_scan(node);
+            }
         }
     }
     fn update_color_editor(&mut self, msg: &CEMsg) {
@@ -1294,6 +2196,48 @@ impl Model {
             }
         }
     }
+    fn update_radio_browser(&mut self, msg: &RBMsg) {
+        match msg {
+            RBMsg::InputPopupShow => {
+                self.mount_radio_browser_input();
+            }
+            RBMsg::InputPopupCloseCancel => {
+                self.app.umount(&Id::RadioBrowserInputPopup).ok();
+                self.app.unlock_subs();
+            }
+            RBMsg::InputPopupCloseOk(input) => {
+                self.app.umount(&Id::RadioBrowserInputPopup).ok();
+                self.app.unlock_subs();
+                self.mount_radio_browser_table();
+                self.radio_options_search(input);
+            }
+            RBMsg::TablePopupNext => {
+                self.radio_options_next_page();
+            }
+            RBMsg::TablePopupPrevious => {
+                self.radio_options_prev_page();
+            }
+            RBMsg::TablePopupFavourite(index) => {
+                self.radio_options_toggle_favourite(*index);
+            }
+            RBMsg::TablePopupCloseOk(index) => {
+                self.radio_options_add(*index);
+                self.app.umount(&Id::RadioBrowserTablePopup).ok();
+                self.app.unlock_subs();
+                if let Err(e) = self.update_photo() {
+                    self.mount_error_popup(format!("update photo error: {}", e).as_ref());
+                }
+            }
+            RBMsg::TablePopupCloseCancel => {
+                self.app.umount(&Id::RadioBrowserTablePopup).ok();
+                self.app.unlock_subs();
+                if let Err(e) = self.update_photo() {
+                    self.mount_error_popup(format!("update photo error: {}", e).as_ref());
+                }
+            }
+        }
+    }
+
     fn update_general_search(&mut self, msg: &GSMsg) {
         match msg {
             GSMsg::PopupShowDatabase => {
@@ -1416,6 +2360,16 @@ impl Model {
             PLMsg::DeleteAll => {
                 self.playlist_empty();
             }
+            PLMsg::ExportShow => {
+                let items = self
+                    .player
+                    .playlist
+                    .tracks
+                    .iter()
+                    .map(PlaylistItem::from)
+                    .collect();
+                self.playlist_export_show(items, "playlist");
+            }
             PLMsg::Shuffle => {
                 self.playlist_shuffle();
             }
@@ -1433,6 +2387,10 @@ impl Model {
             PLMsg::TableBlur => match self.layout {
                 TermusicLayout::TreeView => assert!(self.app.active(&Id::Library).is_ok()),
                 TermusicLayout::DataBase => assert!(self.app.active(&Id::DBListCriteria).is_ok()),
+                TermusicLayout::Playlists => {
+                    assert!(self.app.active(&Id::NamedPlaylistList).is_ok());
+                }
+                TermusicLayout::Podcasts => assert!(self.app.active(&Id::PodcastFeeds).is_ok()),
             },
             PLMsg::NextSong => {
                 self.player.skip();
@@ -1571,6 +2529,50 @@ impl Model {
                 UpdateComponents::YoutubeSearchFail(e) => {
                     self.mount_error_popup(format!("Youtube search fail: {}", e).as_str());
                 }
+                UpdateComponents::RadioSearchSuccess(stations) => {
+                    self.radio_options_search_done(stations);
+                }
+                UpdateComponents::RadioSearchFail(e) => {
+                    self.radio_options_search_done(vec![]);
+                    self.mount_error_popup(format!("radio station search fail: {}", e).as_str());
+                }
+                UpdateComponents::PodcastFetched(url, feed) => {
+                    self.podcast_fetched(&url, &feed);
+                }
+                UpdateComponents::PodcastFetchFail(url, e) => {
+                    self.mount_error_popup(format!("podcast feed {} fail: {}", url, e).as_str());
+                }
+                UpdateComponents::PodcastDownloaded(id, file) => {
+                    self.podcast_downloaded(id, &file);
+                }
+                UpdateComponents::PodcastDownloadFail(id, title, e) => {
+                    self.pc_downloading.remove(&id);
+                    self.mount_error_popup(format!("download of {} fail: {}", title, e).as_str());
+                }
+                #[cfg(not(any(feature = "mpv", feature = "gst")))]
+                UpdateComponents::ReplayGainScanRunning(text) => {
+                    self.update_status_line(StatusLine::Scanning(text));
+                }
+                #[cfg(not(any(feature = "mpv", feature = "gst")))]
+                UpdateComponents::ReplayGainScanCompleted(tagged, failed) => {
+                    self.update_status_line(StatusLine::Default);
+                    if let Some((file, error)) = failed.first() {
+                        self.mount_error_popup(
+                            format!(
+                                "ReplayGain scan failed for {} files, first one is {}: {}",
+                                failed.len(),
+                                file,
+                                error
+                            )
+                            .as_str(),
+                        );
+                    }
+                    self.show_message_timeout(
+                        "ReplayGain",
+                        format!("Tags written to {} files.", tagged).as_str(),
+                        None,
+                    );
+                }
                 UpdateComponents::MessageShow((title, text)) => {
                     self.mount_message(&title, &text);
                 }
@@ -1642,6 +2644,25 @@ impl Model {
                     )
                     .is_ok());
             }
+            #[cfg(not(any(feature = "mpv", feature = "gst")))]
+            StatusLine::Scanning(text) => {
+                assert!(self
+                    .app
+                    .attr(&Id::Label, Attribute::Text, AttrValue::String(text))
+                    .is_ok());
+                assert!(self
+                    .app
+                    .attr(&Id::Label, Attribute::Color, AttrValue::Color(Color::Black))
+                    .is_ok());
+                assert!(self
+                    .app
+                    .attr(
+                        &Id::Label,
+                        Attribute::Background,
+                        AttrValue::Color(Color::Yellow)
+                    )
+                    .is_ok());
+            }
             StatusLine::Error => {
                 let text = " Download Error!".to_string();
 
@@ -1701,31 +2722,44 @@ impl Model {
 
     // update player messages
     pub fn update_player_msg(&mut self) {
-        if let Ok(msg) = self.player.message_rx.try_recv() {
-            match msg {
-                PlayerMsg::Eos => {
-                    // eprintln!("Eos received");
-                    if self.player.playlist.is_empty() {
+        while let Ok(event) = self.player.events.try_recv() {
+            match event {
+                Event::TrackChanged { current, tracks } => {
+                    self.player.set_tracks(&tracks);
+                    self.player.set_current_track(current.as_deref());
+                    if self.player.playlist.current_track.is_some() {
+                        self.player_update_current_track_after();
+                    } else {
                         self.player_stop();
-                        return;
                     }
-                    self.player.start_play();
-                }
-                PlayerMsg::AboutToFinish => {
-                    if self.config.gapless {
-                        // eprintln!("about to finish received");
-                        self.player.enqueue_next();
-                    }
-                }
-                PlayerMsg::CurrentTrackUpdated => {
-                    // eprintln!("current track update received");
-                    self.player_update_current_track_after();
-                    if (self.config.speed - 10).abs() >= 1 {
-                        self.player.set_speed(self.config.speed);
+                    // the episode that ended is played now, or has a new position
+                    if self.layout == TermusicLayout::Podcasts {
+                        self.podcast_reload();
                     }
                 }
-                PlayerMsg::Progress(time_pos, duration) => {
-                    self.progress_update(time_pos, duration);
+                Event::PlaylistChanged { tracks } => {
+                    self.player.set_tracks(&tracks);
+                    self.playlist_sync();
+                }
+                Event::Progress { position, duration } => {
+                    self.progress_update(position, duration);
+                }
+                Event::State(state) => {
+                    self.player.update_state(&state);
+                    self.config.volume = state.volume;
+                    self.config.speed = state.speed;
+                    self.config.gapless = state.gapless;
+                    self.config.loop_mode = state.loop_mode;
+                    self.progress_update_title();
+                    self.progress_update_marks();
+                    self.playlist_update_title();
+                }
+                Event::StreamTitle { title } => {
+                    self.player.stream_title = Some(title);
+                    self.progress_update_title();
+                }
+                Event::Message { title, text } => {
+                    self.show_message_timeout(&title, &text, None);
                 }
             }
         }
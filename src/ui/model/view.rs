@@ -1,39 +1,61 @@
 use crate::config::Settings;
+#[cfg(not(any(feature = "mpv", feature = "gst")))]
+use crate::ui::components::OutputDevicePopup;
 use crate::ui::components::{
     draw_area_in_absolute, draw_area_in_relative, draw_area_top_right_absolute, CEHelpPopup,
     CELibraryBackground, CELibraryBorder, CELibraryForeground, CELibraryHighlight,
     CELibraryHighlightSymbol, CELibraryTitle, CELyricBackground, CELyricBorder, CELyricForeground,
     CELyricTitle, CEPlaylistBackground, CEPlaylistBorder, CEPlaylistForeground,
     CEPlaylistHighlight, CEPlaylistHighlightSymbol, CEPlaylistTitle, CEProgressBackground,
-    CEProgressBorder, CEProgressForeground, CEProgressTitle, CERadioOk, DBListCriteria,
-    DBListSearchResult, DBListSearchTracks, DeleteConfirmInputPopup, DeleteConfirmRadioPopup,
-    ErrorPopup, GSInputPopup, GSTablePopup, GlobalListener, HelpPopup, KEDatabaseAddAll,
-    KEDatabaseAddAllInput, KEGlobalColorEditor, KEGlobalColorEditorInput, KEGlobalDown,
-    KEGlobalDownInput, KEGlobalGotoBottom, KEGlobalGotoBottomInput, KEGlobalGotoTop,
+    CEProgressBorder, CEProgressForeground, CEProgressTitle, CERadioOk, ChapterPopup,
+    DBListCriteria, DBListSearchResult, DBListSearchTracks, DeleteConfirmInputPopup,
+    DeleteConfirmRadioPopup, EqualizerPopup, ErrorPopup, GSInputPopup, GSTablePopup,
+    GlobalListener, HelpPopup, KEDatabaseAddAll, KEDatabaseAddAllInput, KEDatabaseSort,
+    KEDatabaseSortInput, KEGlobalChapterListOpen, KEGlobalChapterListOpenInput,
+    KEGlobalColorEditor, KEGlobalColorEditorInput, KEGlobalDown, KEGlobalDownInput,
+    KEGlobalEqualizerOpen, KEGlobalEqualizerOpenInput, KEGlobalFavouriteToggle,
+    KEGlobalFavouriteToggleInput, KEGlobalGotoBottom, KEGlobalGotoBottomInput, KEGlobalGotoTop,
     KEGlobalGotoTopInput, KEGlobalHelp, KEGlobalHelpInput, KEGlobalKeyEditor,
     KEGlobalKeyEditorInput, KEGlobalLayoutDatabase, KEGlobalLayoutDatabaseInput,
-    KEGlobalLayoutTreeview, KEGlobalLayoutTreeviewInput, KEGlobalLeft, KEGlobalLeftInput,
-    KEGlobalLyricAdjustBackward, KEGlobalLyricAdjustBackwardInput, KEGlobalLyricAdjustForward,
-    KEGlobalLyricAdjustForwardInput, KEGlobalLyricCycle, KEGlobalLyricCycleInput,
-    KEGlobalPlayerNext, KEGlobalPlayerNextInput, KEGlobalPlayerPrevious,
-    KEGlobalPlayerPreviousInput, KEGlobalPlayerSeekBackward, KEGlobalPlayerSeekBackwardInput,
-    KEGlobalPlayerSeekForward, KEGlobalPlayerSeekForwardInput, KEGlobalPlayerSpeedDown,
-    KEGlobalPlayerSpeedDownInput, KEGlobalPlayerSpeedUp, KEGlobalPlayerSpeedUpInput,
-    KEGlobalPlayerToggleGapless, KEGlobalPlayerToggleGaplessInput, KEGlobalPlayerTogglePause,
-    KEGlobalPlayerTogglePauseInput, KEGlobalQuit, KEGlobalQuitInput, KEGlobalRight,
-    KEGlobalRightInput, KEGlobalUp, KEGlobalUpInput, KEGlobalVolumeDown, KEGlobalVolumeDownInput,
-    KEGlobalVolumeUp, KEGlobalVolumeUpInput, KEHelpPopup, KELibraryDelete, KELibraryDeleteInput,
-    KELibraryLoadDir, KELibraryLoadDirInput, KELibraryPaste, KELibraryPasteInput, KELibrarySearch,
+    KEGlobalLayoutPlaylists, KEGlobalLayoutPlaylistsInput, KEGlobalLayoutPodcasts,
+    KEGlobalLayoutPodcastsInput, KEGlobalLayoutTreeview, KEGlobalLayoutTreeviewInput, KEGlobalLeft,
+    KEGlobalLeftInput, KEGlobalLyricAdjustBackward, KEGlobalLyricAdjustBackwardInput,
+    KEGlobalLyricAdjustForward, KEGlobalLyricAdjustForwardInput, KEGlobalLyricCycle,
+    KEGlobalLyricCycleInput, KEGlobalNamedPlaylistAppend, KEGlobalNamedPlaylistAppendInput,
+    KEGlobalOutputDeviceOpen, KEGlobalOutputDeviceOpenInput, KEGlobalPlayerAbLoop,
+    KEGlobalPlayerAbLoopInput, KEGlobalPlayerChapterNext, KEGlobalPlayerChapterNextInput,
+    KEGlobalPlayerChapterPrevious, KEGlobalPlayerChapterPreviousInput, KEGlobalPlayerNext,
+    KEGlobalPlayerNextInput, KEGlobalPlayerPrevious, KEGlobalPlayerPreviousInput,
+    KEGlobalPlayerSeekBackward, KEGlobalPlayerSeekBackwardInput, KEGlobalPlayerSeekForward,
+    KEGlobalPlayerSeekForwardInput, KEGlobalPlayerSpeedDown, KEGlobalPlayerSpeedDownInput,
+    KEGlobalPlayerSpeedUp, KEGlobalPlayerSpeedUpInput, KEGlobalPlayerToggleGapless,
+    KEGlobalPlayerToggleGaplessInput, KEGlobalPlayerTogglePause, KEGlobalPlayerTogglePauseInput,
+    KEGlobalQuit, KEGlobalQuitInput, KEGlobalRadioBrowserOpen, KEGlobalRadioBrowserOpenInput,
+    KEGlobalRatingDown, KEGlobalRatingDownInput, KEGlobalRatingUp, KEGlobalRatingUpInput,
+    KEGlobalRight, KEGlobalRightInput, KEGlobalSleepTimerOpen, KEGlobalSleepTimerOpenInput,
+    KEGlobalStatsOpen, KEGlobalStatsOpenInput, KEGlobalUp, KEGlobalUpInput, KEGlobalVolumeDown,
+    KEGlobalVolumeDownInput, KEGlobalVolumeUp, KEGlobalVolumeUpInput, KEHelpPopup, KELibraryDelete,
+    KELibraryDeleteInput, KELibraryLoadDir, KELibraryLoadDirInput, KELibraryPaste,
+    KELibraryPasteInput, KELibraryReplaygainScan, KELibraryReplaygainScanInput, KELibrarySearch,
     KELibrarySearchInput, KELibrarySearchYoutube, KELibrarySearchYoutubeInput, KELibraryTagEditor,
-    KELibraryTagEditorInput, KELibraryYank, KELibraryYankInput, KEPlaylistAddFront,
-    KEPlaylistAddFrontInput, KEPlaylistDelete, KEPlaylistDeleteAll, KEPlaylistDeleteAllInput,
-    KEPlaylistDeleteInput, KEPlaylistModeCycle, KEPlaylistModeCycleInput, KEPlaylistPlaySelected,
+    KELibraryTagEditorInput, KELibraryYank, KELibraryYankInput, KENamedPlaylistDelete,
+    KENamedPlaylistDeleteInput, KENamedPlaylistDuplicate, KENamedPlaylistDuplicateInput,
+    KENamedPlaylistExport, KENamedPlaylistExportInput, KENamedPlaylistLoad,
+    KENamedPlaylistLoadInput, KENamedPlaylistNew, KENamedPlaylistNewInput, KENamedPlaylistRename,
+    KENamedPlaylistRenameInput, KEPlaylistAddFront, KEPlaylistAddFrontInput, KEPlaylistDelete,
+    KEPlaylistDeleteAll, KEPlaylistDeleteAllInput, KEPlaylistDeleteInput, KEPlaylistExport,
+    KEPlaylistExportInput, KEPlaylistModeCycle, KEPlaylistModeCycleInput, KEPlaylistPlaySelected,
     KEPlaylistPlaySelectedInput, KEPlaylistSearch, KEPlaylistSearchInput, KEPlaylistShuffle,
     KEPlaylistShuffleInput, KEPlaylistSwapDown, KEPlaylistSwapDownInput, KEPlaylistSwapUp,
-    KEPlaylistSwapUpInput, KERadioOk, Label, Lyric, MessagePopup, MusicLibrary, Playlist, Progress,
-    QuitPopup, Source, TECounterDelete, TEHelpPopup, TEInputArtist, TEInputTitle, TERadioTag,
-    TESelectLyric, TETableLyricOptions, TETextareaLyric, ThemeSelectTable, YSInputPopup,
-    YSTablePopup,
+    KEPlaylistSwapUpInput, KEPodcastAdd, KEPodcastAddInput, KEPodcastDelete, KEPodcastDeleteInput,
+    KEPodcastDownload, KEPodcastDownloadInput, KEPodcastMarkPlayed, KEPodcastMarkPlayedInput,
+    KEPodcastRefresh, KEPodcastRefreshAll, KEPodcastRefreshAllInput, KEPodcastRefreshInput,
+    KERadioOk, Label, Lyric, MessagePopup, MusicLibrary, NPDeleteConfirmPopup, NPList,
+    NPNameInputPopup, NPPickPopup, NPTracks, PCAddPopup, PCDeletePopup, PCEpisodes, PCFeeds,
+    Playlist, PlaylistExportPopup, Progress, QuitPopup, RBInputPopup, RBTablePopup,
+    SleepTimerPopup, Source, StatsPopup, TECounterDelete, TEHelpPopup, TEInputArtist, TEInputTitle,
+    TERadioTag, TESelectLyric, TETableLyricOptions, TETextareaLyric, ThemeSelectTable,
+    YSInputPopup, YSTablePopup, KE_SECOND_PAGE, STATS_CRITERIAS, STATS_PERIODS,
 };
 
 use crate::ui::model::{Model, TermusicLayout};
@@ -109,6 +131,26 @@ impl Model {
                 vec![]
             )
             .is_ok());
+        assert!(app
+            .mount(Id::NamedPlaylistList, Box::new(NPList::new(config)), vec![])
+            .is_ok());
+        assert!(app
+            .mount(
+                Id::NamedPlaylistTracks,
+                Box::new(NPTracks::new(config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(app
+            .mount(Id::PodcastFeeds, Box::new(PCFeeds::new(config)), vec![])
+            .is_ok());
+        assert!(app
+            .mount(
+                Id::PodcastEpisodes,
+                Box::new(PCEpisodes::new(config)),
+                vec![]
+            )
+            .is_ok());
         assert!(app
             .mount(Id::Playlist, Box::new(Playlist::new(config)), vec![])
             .is_ok());
@@ -173,6 +215,8 @@ impl Model {
             match self.layout {
                 TermusicLayout::TreeView => self.view_layout_treeview(),
                 TermusicLayout::DataBase => self.view_layout_database(),
+                TermusicLayout::Playlists => self.view_layout_playlists(),
+                TermusicLayout::Podcasts => self.view_layout_podcasts(),
             }
         }
     }
@@ -235,6 +279,103 @@ impl Model {
             .is_ok());
     }
 
+    pub fn view_layout_podcasts(&mut self) {
+        assert!(self
+            .terminal
+            .raw_mut()
+            .draw(|f| {
+                let chunks_main = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([Constraint::Min(2), Constraint::Length(1)].as_ref())
+                    .split(f.size());
+                let chunks_left = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .margin(0)
+                    .constraints([Constraint::Ratio(2, 5), Constraint::Ratio(3, 5)].as_ref())
+                    .split(chunks_main[0]);
+
+                let chunks_left_sections = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([Constraint::Length(10), Constraint::Min(2)].as_ref())
+                    .split(chunks_left[0]);
+                let chunks_right = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints(
+                        [
+                            Constraint::Min(2),
+                            Constraint::Length(3),
+                            Constraint::Length(4),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks_left[1]);
+
+                self.app.view(&Id::PodcastFeeds, f, chunks_left_sections[0]);
+                self.app
+                    .view(&Id::PodcastEpisodes, f, chunks_left_sections[1]);
+
+                self.app.view(&Id::Playlist, f, chunks_right[0]);
+                self.app.view(&Id::Progress, f, chunks_right[1]);
+                self.app.view(&Id::Lyric, f, chunks_right[2]);
+                self.app.view(&Id::Label, f, chunks_main[1]);
+
+                Self::view_layout_commons(f, &mut self.app);
+            })
+            .is_ok());
+    }
+
+    pub fn view_layout_playlists(&mut self) {
+        assert!(self
+            .terminal
+            .raw_mut()
+            .draw(|f| {
+                let chunks_main = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([Constraint::Min(2), Constraint::Length(1)].as_ref())
+                    .split(f.size());
+                let chunks_left = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .margin(0)
+                    .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
+                    .split(chunks_main[0]);
+
+                let chunks_left_sections = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([Constraint::Length(12), Constraint::Min(2)].as_ref())
+                    .split(chunks_left[0]);
+                let chunks_right = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints(
+                        [
+                            Constraint::Min(2),
+                            Constraint::Length(3),
+                            Constraint::Length(4),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks_left[1]);
+
+                self.app
+                    .view(&Id::NamedPlaylistList, f, chunks_left_sections[0]);
+                self.app
+                    .view(&Id::NamedPlaylistTracks, f, chunks_left_sections[1]);
+
+                self.app.view(&Id::Playlist, f, chunks_right[0]);
+                self.app.view(&Id::Progress, f, chunks_right[1]);
+                self.app.view(&Id::Lyric, f, chunks_right[2]);
+                self.app.view(&Id::Label, f, chunks_main[1]);
+
+                Self::view_layout_commons(f, &mut self.app);
+            })
+            .is_ok());
+    }
+
     pub fn view_layout_treeview(&mut self) {
         assert!(self
             .terminal
@@ -284,6 +425,50 @@ impl Model {
             let popup = draw_area_in_relative(f.size(), 60, 91);
             f.render_widget(Clear, popup);
             app.view(&Id::HelpPopup, f, popup);
+        } else if app.mounted(&Id::EqualizerPopup) {
+            let popup = draw_area_in_relative(f.size(), 80, 50);
+            f.render_widget(Clear, popup);
+            app.view(&Id::EqualizerPopup, f, popup);
+        } else if app.mounted(&Id::OutputDevicePopup) {
+            let popup = draw_area_in_relative(f.size(), 50, 40);
+            f.render_widget(Clear, popup);
+            app.view(&Id::OutputDevicePopup, f, popup);
+        } else if app.mounted(&Id::ChapterPopup) {
+            let popup = draw_area_in_relative(f.size(), 60, 50);
+            f.render_widget(Clear, popup);
+            app.view(&Id::ChapterPopup, f, popup);
+        } else if app.mounted(&Id::StatsPopup) {
+            let popup = draw_area_in_relative(f.size(), 70, 60);
+            f.render_widget(Clear, popup);
+            app.view(&Id::StatsPopup, f, popup);
+        } else if app.mounted(&Id::NamedPlaylistPickPopup) {
+            let popup = draw_area_in_relative(f.size(), 50, 40);
+            f.render_widget(Clear, popup);
+            app.view(&Id::NamedPlaylistPickPopup, f, popup);
+        } else if app.mounted(&Id::NamedPlaylistNamePopup) {
+            let popup = draw_area_in_absolute(f.size(), 50, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::NamedPlaylistNamePopup, f, popup);
+        } else if app.mounted(&Id::PlaylistExportPopup) {
+            let popup = draw_area_in_absolute(f.size(), 70, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PlaylistExportPopup, f, popup);
+        } else if app.mounted(&Id::SleepTimerPopup) {
+            let popup = draw_area_in_absolute(f.size(), 75, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::SleepTimerPopup, f, popup);
+        } else if app.mounted(&Id::NamedPlaylistDeletePopup) {
+            let popup = draw_area_in_absolute(f.size(), 40, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::NamedPlaylistDeletePopup, f, popup);
+        } else if app.mounted(&Id::PodcastAddPopup) {
+            let popup = draw_area_in_absolute(f.size(), 80, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastAddPopup, f, popup);
+        } else if app.mounted(&Id::PodcastDeletePopup) {
+            let popup = draw_area_in_absolute(f.size(), 70, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::PodcastDeletePopup, f, popup);
         } else if app.mounted(&Id::DeleteConfirmRadioPopup) {
             let popup = draw_area_in_absolute(f.size(), 30, 3);
             f.render_widget(Clear, popup);
@@ -315,6 +500,14 @@ impl Model {
             let popup = draw_area_in_relative(f.size(), 65, 68);
             f.render_widget(Clear, popup);
             app.view(&Id::YoutubeSearchTablePopup, f, popup);
+        } else if app.mounted(&Id::RadioBrowserInputPopup) {
+            let popup = draw_area_in_absolute(f.size(), 80, 3);
+            f.render_widget(Clear, popup);
+            app.view(&Id::RadioBrowserInputPopup, f, popup);
+        } else if app.mounted(&Id::RadioBrowserTablePopup) {
+            let popup = draw_area_in_relative(f.size(), 80, 68);
+            f.render_widget(Clear, popup);
+            app.view(&Id::RadioBrowserTablePopup, f, popup);
         }
         if app.mounted(&Id::MessagePopup) {
             let popup = draw_area_top_right_absolute(f.size(), 25, 4);
@@ -351,6 +544,179 @@ impl Model {
         self.app.lock_subs();
     }
     /// Mount help popup
+    pub fn mount_equalizer_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::EqualizerPopup,
+                Box::new(EqualizerPopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::EqualizerPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    #[cfg(not(any(feature = "mpv", feature = "gst")))]
+    pub fn mount_output_device_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::OutputDevicePopup,
+                Box::new(OutputDevicePopup::new(
+                    &self.config,
+                    crate::player::GeneralPlayer::output_devices()
+                )),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::OutputDevicePopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_named_playlist_name_popup(&mut self, name: &str) {
+        assert!(self
+            .app
+            .remount(
+                Id::NamedPlaylistNamePopup,
+                Box::new(NPNameInputPopup::new(&self.config, name)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::NamedPlaylistNamePopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_playlist_export_popup(&mut self, path: &str) {
+        assert!(self
+            .app
+            .remount(
+                Id::PlaylistExportPopup,
+                Box::new(PlaylistExportPopup::new(
+                    &self.config.style_color_symbol,
+                    path
+                )),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PlaylistExportPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_chapter_popup(&mut self) {
+        let position = Duration::from_secs(self.time_pos.try_into().unwrap_or(0));
+        let popup = match &self.player.playlist.current_track {
+            Some(track) if !track.chapters().is_empty() => {
+                ChapterPopup::new(&self.config, track, position)
+            }
+            _ => {
+                self.mount_error_popup("The current track has no chapters.");
+                return;
+            }
+        };
+        assert!(self
+            .app
+            .remount(Id::ChapterPopup, Box::new(popup), vec![])
+            .is_ok());
+        assert!(self.app.active(&Id::ChapterPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_sleep_timer_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::SleepTimerPopup,
+                Box::new(SleepTimerPopup::new(&self.config.style_color_symbol)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::SleepTimerPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_named_playlist_delete_popup(&mut self, name: &str) {
+        assert!(self
+            .app
+            .remount(
+                Id::NamedPlaylistDeletePopup,
+                Box::new(NPDeleteConfirmPopup::new(&self.config, name)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::NamedPlaylistDeletePopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_podcast_add_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastAddPopup,
+                Box::new(PCAddPopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PodcastAddPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_podcast_delete_popup(&mut self, title: &str) {
+        assert!(self
+            .app
+            .remount(
+                Id::PodcastDeletePopup,
+                Box::new(PCDeletePopup::new(&self.config, title)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::PodcastDeletePopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_named_playlist_pick_popup(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::NamedPlaylistPickPopup,
+                Box::new(NPPickPopup::new(
+                    &self.config,
+                    &self.np_names,
+                    self.np_append.len()
+                )),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::NamedPlaylistPickPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_stats_popup(&mut self) {
+        let tops = STATS_CRITERIAS
+            .iter()
+            .map(|criteria| {
+                STATS_PERIODS
+                    .iter()
+                    .map(|period| {
+                        self.db
+                            .get_top_played(*criteria, *period, 100)
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+        assert!(self
+            .app
+            .remount(
+                Id::StatsPopup,
+                Box::new(StatsPopup::new(&self.config, tops)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::StatsPopup).is_ok());
+        self.app.lock_subs();
+    }
+
     pub fn mount_help_popup(&mut self) {
         assert!(self
             .app
@@ -478,6 +844,35 @@ impl Model {
         self.app.lock_subs();
     }
 
+    pub fn mount_radio_browser_input(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::RadioBrowserInputPopup,
+                Box::new(RBInputPopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::RadioBrowserInputPopup).is_ok());
+        self.app.lock_subs();
+    }
+
+    pub fn mount_radio_browser_table(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::RadioBrowserTablePopup,
+                Box::new(RBTablePopup::new(&self.config)),
+                vec![]
+            )
+            .is_ok());
+        assert!(self.app.active(&Id::RadioBrowserTablePopup).is_ok());
+        self.app.lock_subs();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("update photo error: {}", e).as_ref());
+        }
+    }
+
     pub fn mount_youtube_search_table(&mut self) {
         assert!(self
             .app
@@ -1484,7 +1879,7 @@ impl Model {
                 Id::KeyEditor(IdKeyEditor::LabelHint),
                 Box::new(
                     Label::default()
-                        .text("  Key Editor. ")
+                        .text("  Key Editor. <TAB> past the last key shows the second page. ")
                         .alignment(Alignment::Left)
                         .background(Color::Reset)
                         .foreground(Color::Magenta)
@@ -2202,22 +2597,562 @@ impl Model {
             )
             .is_ok());
 
-        // focus
         assert!(self
             .app
-            .active(&Id::KeyEditor(IdKeyEditor::GlobalQuit))
+            .remount(
+                Id::KeyEditor(IdKeyEditor::LibraryReplaygainScan),
+                Box::new(KELibraryReplaygainScan::new(&self.config.keys)),
+                vec![],
+            )
             .is_ok());
-        // self.theme_select_sync();
-        self.app.lock_subs();
-        if let Err(e) = self.update_photo() {
-            self.mount_error_popup(format!("clear photo error: {}", e).as_str());
-        }
-    }
 
-    #[allow(clippy::too_many_lines)]
-    pub fn umount_key_editor(&mut self) {
-        self.app.umount(&Id::KeyEditor(IdKeyEditor::LabelHint)).ok();
-        self.app
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::LibraryReplaygainScanInput),
+                Box::new(KELibraryReplaygainScanInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalEqualizerOpen),
+                Box::new(KEGlobalEqualizerOpen::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalEqualizerOpenInput),
+                Box::new(KEGlobalEqualizerOpenInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalOutputDeviceOpen),
+                Box::new(KEGlobalOutputDeviceOpen::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalOutputDeviceOpenInput),
+                Box::new(KEGlobalOutputDeviceOpenInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalStatsOpen),
+                Box::new(KEGlobalStatsOpen::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalStatsOpenInput),
+                Box::new(KEGlobalStatsOpenInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::DatabaseSort),
+                Box::new(KEDatabaseSort::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::DatabaseSortInput),
+                Box::new(KEDatabaseSortInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalRatingUp),
+                Box::new(KEGlobalRatingUp::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalRatingUpInput),
+                Box::new(KEGlobalRatingUpInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalRatingDown),
+                Box::new(KEGlobalRatingDown::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalRatingDownInput),
+                Box::new(KEGlobalRatingDownInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalFavouriteToggle),
+                Box::new(KEGlobalFavouriteToggle::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalFavouriteToggleInput),
+                Box::new(KEGlobalFavouriteToggleInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalLayoutPlaylists),
+                Box::new(KEGlobalLayoutPlaylists::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalLayoutPlaylistsInput),
+                Box::new(KEGlobalLayoutPlaylistsInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalNamedPlaylistAppend),
+                Box::new(KEGlobalNamedPlaylistAppend::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalNamedPlaylistAppendInput),
+                Box::new(KEGlobalNamedPlaylistAppendInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistNew),
+                Box::new(KENamedPlaylistNew::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistNewInput),
+                Box::new(KENamedPlaylistNewInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistRename),
+                Box::new(KENamedPlaylistRename::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistRenameInput),
+                Box::new(KENamedPlaylistRenameInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistDuplicate),
+                Box::new(KENamedPlaylistDuplicate::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistDuplicateInput),
+                Box::new(KENamedPlaylistDuplicateInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistDelete),
+                Box::new(KENamedPlaylistDelete::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistDeleteInput),
+                Box::new(KENamedPlaylistDeleteInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistLoad),
+                Box::new(KENamedPlaylistLoad::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistLoadInput),
+                Box::new(KENamedPlaylistLoadInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PlaylistExport),
+                Box::new(KEPlaylistExport::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PlaylistExportInput),
+                Box::new(KEPlaylistExportInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistExport),
+                Box::new(KENamedPlaylistExport::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::NamedPlaylistExportInput),
+                Box::new(KENamedPlaylistExportInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalRadioBrowserOpen),
+                Box::new(KEGlobalRadioBrowserOpen::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalRadioBrowserOpenInput),
+                Box::new(KEGlobalRadioBrowserOpenInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalLayoutPodcasts),
+                Box::new(KEGlobalLayoutPodcasts::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalLayoutPodcastsInput),
+                Box::new(KEGlobalLayoutPodcastsInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastAdd),
+                Box::new(KEPodcastAdd::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastAddInput),
+                Box::new(KEPodcastAddInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastDelete),
+                Box::new(KEPodcastDelete::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastDeleteInput),
+                Box::new(KEPodcastDeleteInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastRefresh),
+                Box::new(KEPodcastRefresh::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastRefreshInput),
+                Box::new(KEPodcastRefreshInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastRefreshAll),
+                Box::new(KEPodcastRefreshAll::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastRefreshAllInput),
+                Box::new(KEPodcastRefreshAllInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastDownload),
+                Box::new(KEPodcastDownload::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastDownloadInput),
+                Box::new(KEPodcastDownloadInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastMarkPlayed),
+                Box::new(KEPodcastMarkPlayed::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::PodcastMarkPlayedInput),
+                Box::new(KEPodcastMarkPlayedInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalSleepTimerOpen),
+                Box::new(KEGlobalSleepTimerOpen::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalSleepTimerOpenInput),
+                Box::new(KEGlobalSleepTimerOpenInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalPlayerAbLoop),
+                Box::new(KEGlobalPlayerAbLoop::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalPlayerAbLoopInput),
+                Box::new(KEGlobalPlayerAbLoopInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterNext),
+                Box::new(KEGlobalPlayerChapterNext::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterNextInput),
+                Box::new(KEGlobalPlayerChapterNextInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterPrevious),
+                Box::new(KEGlobalPlayerChapterPrevious::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterPreviousInput),
+                Box::new(KEGlobalPlayerChapterPreviousInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalChapterListOpen),
+                Box::new(KEGlobalChapterListOpen::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::GlobalChapterListOpenInput),
+                Box::new(KEGlobalChapterListOpenInput::new(&self.config.keys)),
+                vec![],
+            )
+            .is_ok());
+
+        // focus
+        assert!(self
+            .app
+            .active(&Id::KeyEditor(IdKeyEditor::GlobalQuit))
+            .is_ok());
+        // self.theme_select_sync();
+        self.app.lock_subs();
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("clear photo error: {}", e).as_str());
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    pub fn umount_key_editor(&mut self) {
+        self.app.umount(&Id::KeyEditor(IdKeyEditor::LabelHint)).ok();
+        self.app
             .umount(&Id::KeyEditor(IdKeyEditor::GlobalQuit))
             .ok();
         self.app
@@ -2482,1014 +3417,1281 @@ impl Model {
             .umount(&Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGaplessInput))
             .ok();
 
-        self.app.umount(&Id::KeyEditor(IdKeyEditor::RadioOk)).ok();
-        self.app.unlock_subs();
-        self.library_reload_tree();
-        self.playlist_reload();
-        self.database_reload();
-        self.global_fix_focus();
-        assert!(self
-            .app
-            .remount(
-                Id::GlobalListener,
-                Box::new(GlobalListener::new(&self.config.keys)),
-                Self::subscribe(&self.config.keys),
-            )
-            .is_ok());
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::LibraryReplaygainScan))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::LibraryReplaygainScanInput))
+            .ok();
 
-        assert!(self
-            .app
-            .remount(
-                Id::Label,
-                Box::new(
-                    Label::default()
-                        .text(format!(
-                            "Press <{}> for help. Version: {}",
-                            self.config.keys.global_help, VERSION,
-                        ))
-                        .alignment(Alignment::Left)
-                        .background(Color::Reset)
-                        .foreground(Color::Cyan)
-                        .modifiers(TextModifiers::BOLD),
-                ),
-                Vec::default(),
-            )
-            .is_ok());
-        if let Err(e) = self.update_photo() {
-            self.mount_error_popup(format!("clear photo error: {}", e).as_str());
-        }
-    }
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalEqualizerOpen))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalEqualizerOpenInput))
+            .ok();
 
-    pub fn mount_key_editor_help(&mut self) {
-        assert!(self
-            .app
-            .remount(
-                Id::KeyEditor(IdKeyEditor::HelpPopup),
-                Box::new(KEHelpPopup::new(&self.config.keys)),
-                vec![]
-            )
-            .is_ok());
-        // Active help
-        assert!(self
-            .app
-            .active(&Id::KeyEditor(IdKeyEditor::HelpPopup))
-            .is_ok());
-    }
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalOutputDeviceOpen))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalOutputDeviceOpenInput))
+            .ok();
 
-    #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
-    fn view_key_editor(&mut self) {
-        let select_global_quit_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalQuit)) {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_left_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalLeft)) {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_right_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalRight))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_up_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalUp)) {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_down_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalDown)) {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_goto_top_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalGotoTop)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_global_goto_bottom_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalGotoBottom))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_player_toggle_pause_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerTogglePause))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_player_next_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerNext))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_player_previous_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerPrevious))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalStatsOpen))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalStatsOpenInput))
+            .ok();
 
-        let select_global_help_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalHelp)) {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::DatabaseSort))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::DatabaseSortInput))
+            .ok();
 
-        let select_global_volume_up_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalVolumeUp)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalRatingUp))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalRatingUpInput))
+            .ok();
 
-        let select_global_volume_down_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalVolumeDown))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalRatingDown))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalRatingDownInput))
+            .ok();
 
-        let select_global_player_seek_forward_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekForward))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_player_seek_backward_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekBackward))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_player_speed_up_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedUp))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_player_speed_down_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedDown))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalFavouriteToggle))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalFavouriteToggleInput))
+            .ok();
 
-        let select_global_lyric_adjust_forward_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustForward))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_lyric_adjust_backward_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustBackward))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_lyric_cycle_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalLyricCycle))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_color_editor_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalColorEditor))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_global_key_editor_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalKeyEditor)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPlaylists))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPlaylistsInput))
+            .ok();
 
-        let select_library_delete_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryDelete)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_library_load_dir_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryLoadDir)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_library_yank_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryYank))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_library_paste_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryPaste)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_library_search_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibrarySearch)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_library_search_youtube_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::LibrarySearchYoutube))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_library_tag_editor_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::LibraryTagEditor))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_playlist_delete_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistDelete)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_playlist_delete_all_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::PlaylistDeleteAll))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalNamedPlaylistAppend))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalNamedPlaylistAppendInput))
+            .ok();
 
-        let select_playlist_shuffle_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistShuffle)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistNew))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistNewInput))
+            .ok();
 
-        let select_playlist_mode_cycle_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::PlaylistModeCycle))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_playlist_add_front_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::PlaylistAddFront))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
-        let select_playlist_search_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistSearch)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
-        let select_playlist_play_selected_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::PlaylistPlaySelected))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistRename))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistRenameInput))
+            .ok();
 
-        let select_playlist_swap_down_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::PlaylistSwapDown))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDuplicate))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDuplicateInput))
+            .ok();
 
-        let select_playlist_swap_up_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistSwapUp)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDelete))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistDeleteInput))
+            .ok();
 
-        let select_global_layout_treeview_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalLayoutTreeview))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistLoad))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistLoadInput))
+            .ok();
 
-        let select_global_layout_database_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalLayoutDatabase))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PlaylistExport))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PlaylistExportInput))
+            .ok();
 
-        let select_database_add_all_len =
-            match self.app.state(&Id::KeyEditor(IdKeyEditor::DatabaseAddAll)) {
-                Ok(State::One(_)) => 3,
-                _ => 8,
-            };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistExport))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::NamedPlaylistExportInput))
+            .ok();
 
-        let select_global_player_toggle_gapless_len = match self
-            .app
-            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGapless))
-        {
-            Ok(State::One(_)) => 3,
-            _ => 8,
-        };
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalRadioBrowserOpen))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalRadioBrowserOpenInput))
+            .ok();
+
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPodcasts))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalLayoutPodcastsInput))
+            .ok();
 
-        assert!(self
-            .terminal
-            .raw_mut()
-            .draw(|f| {
-                if self.app.mounted(&Id::KeyEditor(IdKeyEditor::LabelHint)) {
-                    f.render_widget(Clear, f.size());
-                    let chunks_main = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(1),
-                                Constraint::Min(2),
-                                Constraint::Length(3),
-                                Constraint::Length(1),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(f.size());
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastAdd))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastAddInput))
+            .ok();
 
-                    let chunks_middle = Layout::default()
-                        .direction(Direction::Horizontal)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Ratio(1, 7),
-                                Constraint::Ratio(2, 35),
-                                Constraint::Ratio(1, 7),
-                                Constraint::Ratio(2, 35),
-                                Constraint::Ratio(1, 7),
-                                Constraint::Ratio(2, 35),
-                                Constraint::Ratio(1, 7),
-                                Constraint::Ratio(2, 35),
-                                Constraint::Ratio(1, 7),
-                                Constraint::Ratio(2, 35),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_main[1]);
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastDelete))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastDeleteInput))
+            .ok();
 
-                    let chunks_middle_column1 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_global_quit_len),
-                                Constraint::Length(select_global_left_len),
-                                Constraint::Length(select_global_down_len),
-                                Constraint::Length(select_global_up_len),
-                                Constraint::Length(select_global_right_len),
-                                Constraint::Length(select_global_goto_top_len),
-                                Constraint::Length(select_global_goto_bottom_len),
-                                Constraint::Length(select_global_player_toggle_pause_len),
-                                Constraint::Length(select_global_player_next_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[0]);
-                    let chunks_middle_column2 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_global_quit_len),
-                                Constraint::Length(select_global_left_len),
-                                Constraint::Length(select_global_down_len),
-                                Constraint::Length(select_global_up_len),
-                                Constraint::Length(select_global_right_len),
-                                Constraint::Length(select_global_goto_top_len),
-                                Constraint::Length(select_global_goto_bottom_len),
-                                Constraint::Length(select_global_player_toggle_pause_len),
-                                Constraint::Length(select_global_player_next_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[1]);
-                    let chunks_middle_column3 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_global_player_previous_len),
-                                Constraint::Length(select_global_help_len),
-                                Constraint::Length(select_global_volume_up_len),
-                                Constraint::Length(select_global_volume_down_len),
-                                Constraint::Length(select_global_player_seek_forward_len),
-                                Constraint::Length(select_global_player_seek_backward_len),
-                                Constraint::Length(select_global_player_speed_up_len),
-                                Constraint::Length(select_global_player_speed_down_len),
-                                Constraint::Length(select_global_lyric_adjust_forward_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[2]);
-                    let chunks_middle_column4 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_global_player_previous_len),
-                                Constraint::Length(select_global_help_len),
-                                Constraint::Length(select_global_volume_up_len),
-                                Constraint::Length(select_global_volume_down_len),
-                                Constraint::Length(select_global_player_seek_forward_len),
-                                Constraint::Length(select_global_player_seek_backward_len),
-                                Constraint::Length(select_global_player_speed_up_len),
-                                Constraint::Length(select_global_player_speed_down_len),
-                                Constraint::Length(select_global_lyric_adjust_forward_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[3]);
-                    let chunks_middle_column5 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_global_lyric_adjust_backward_len),
-                                Constraint::Length(select_global_lyric_cycle_len),
-                                Constraint::Length(select_global_color_editor_len),
-                                Constraint::Length(select_global_key_editor_len),
-                                Constraint::Length(select_library_tag_editor_len),
-                                Constraint::Length(select_library_delete_len),
-                                Constraint::Length(select_library_load_dir_len),
-                                Constraint::Length(select_library_yank_len),
-                                Constraint::Length(select_library_paste_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[4]);
-                    let chunks_middle_column6 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_global_lyric_adjust_backward_len),
-                                Constraint::Length(select_global_lyric_cycle_len),
-                                Constraint::Length(select_global_color_editor_len),
-                                Constraint::Length(select_global_key_editor_len),
-                                Constraint::Length(select_library_tag_editor_len),
-                                Constraint::Length(select_library_delete_len),
-                                Constraint::Length(select_library_load_dir_len),
-                                Constraint::Length(select_library_yank_len),
-                                Constraint::Length(select_library_paste_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[5]);
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastRefresh))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastRefreshInput))
+            .ok();
 
-                    let chunks_middle_column7 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_library_search_len),
-                                Constraint::Length(select_library_search_youtube_len),
-                                Constraint::Length(select_playlist_delete_len),
-                                Constraint::Length(select_playlist_delete_all_len),
-                                Constraint::Length(select_playlist_search_len),
-                                Constraint::Length(select_playlist_shuffle_len),
-                                Constraint::Length(select_playlist_add_front_len),
-                                Constraint::Length(select_playlist_mode_cycle_len),
-                                Constraint::Length(select_playlist_play_selected_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[6]);
-                    let chunks_middle_column8 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_library_search_len),
-                                Constraint::Length(select_library_search_youtube_len),
-                                Constraint::Length(select_playlist_delete_len),
-                                Constraint::Length(select_playlist_delete_all_len),
-                                Constraint::Length(select_playlist_search_len),
-                                Constraint::Length(select_playlist_shuffle_len),
-                                Constraint::Length(select_playlist_add_front_len),
-                                Constraint::Length(select_playlist_mode_cycle_len),
-                                Constraint::Length(select_playlist_play_selected_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[7]);
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastRefreshAll))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastRefreshAllInput))
+            .ok();
 
-                    let chunks_middle_column9 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_playlist_swap_down_len),
-                                Constraint::Length(select_playlist_swap_up_len),
-                                Constraint::Length(select_global_layout_treeview_len),
-                                Constraint::Length(select_global_layout_database_len),
-                                Constraint::Length(select_database_add_all_len),
-                                Constraint::Length(select_global_player_toggle_gapless_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[8]);
-                    let chunks_middle_column10 = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(0)
-                        .constraints(
-                            [
-                                Constraint::Length(select_playlist_swap_down_len),
-                                Constraint::Length(select_playlist_swap_up_len),
-                                Constraint::Length(select_global_layout_treeview_len),
-                                Constraint::Length(select_global_layout_database_len),
-                                Constraint::Length(select_database_add_all_len),
-                                Constraint::Length(select_global_player_toggle_gapless_len),
-                                Constraint::Min(0),
-                            ]
-                            .as_ref(),
-                        )
-                        .split(chunks_middle[9]);
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastDownload))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastDownloadInput))
+            .ok();
 
-                    self.app
-                        .view(&Id::KeyEditor(IdKeyEditor::LabelHint), f, chunks_main[0]);
-                    self.app
-                        .view(&Id::KeyEditor(IdKeyEditor::RadioOk), f, chunks_main[2]);
-                    self.app.view(&Id::Label, f, chunks_main[3]);
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalQuit),
-                        f,
-                        chunks_middle_column1[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalQuitInput),
-                        f,
-                        chunks_middle_column2[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLeft),
-                        f,
-                        chunks_middle_column1[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLeftInput),
-                        f,
-                        chunks_middle_column2[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalDown),
-                        f,
-                        chunks_middle_column1[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalDownInput),
-                        f,
-                        chunks_middle_column2[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalUp),
-                        f,
-                        chunks_middle_column1[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalUpInput),
-                        f,
-                        chunks_middle_column2[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalRight),
-                        f,
-                        chunks_middle_column1[4],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalRightInput),
-                        f,
-                        chunks_middle_column2[4],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalGotoTop),
-                        f,
-                        chunks_middle_column1[5],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalGotoTopInput),
-                        f,
-                        chunks_middle_column2[5],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalGotoBottom),
-                        f,
-                        chunks_middle_column1[6],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalGotoBottomInput),
-                        f,
-                        chunks_middle_column2[6],
-                    );
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastMarkPlayed))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::PodcastMarkPlayedInput))
+            .ok();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerTogglePause),
-                        f,
-                        chunks_middle_column1[7],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerTogglePauseInput),
-                        f,
-                        chunks_middle_column2[7],
-                    );
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalSleepTimerOpen))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalSleepTimerOpenInput))
+            .ok();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerNext),
-                        f,
-                        chunks_middle_column1[8],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerNextInput),
-                        f,
-                        chunks_middle_column2[8],
-                    );
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalPlayerAbLoop))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalPlayerAbLoopInput))
+            .ok();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerPrevious),
-                        f,
-                        chunks_middle_column3[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerPreviousInput),
-                        f,
-                        chunks_middle_column4[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalHelp),
-                        f,
-                        chunks_middle_column3[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalHelpInput),
-                        f,
-                        chunks_middle_column4[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalVolumeUp),
-                        f,
-                        chunks_middle_column3[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalVolumeUpInput),
-                        f,
-                        chunks_middle_column4[2],
-                    );
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterNext))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterNextInput))
+            .ok();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalVolumeDown),
-                        f,
-                        chunks_middle_column3[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalVolumeDownInput),
-                        f,
-                        chunks_middle_column4[3],
-                    );
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalPlayerChapterPrevious))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(
+                IdKeyEditor::GlobalPlayerChapterPreviousInput,
+            ))
+            .ok();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekForward),
-                        f,
-                        chunks_middle_column3[4],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekForwardInput),
-                        f,
-                        chunks_middle_column4[4],
-                    );
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalChapterListOpen))
+            .ok();
+        self.app
+            .umount(&Id::KeyEditor(IdKeyEditor::GlobalChapterListOpenInput))
+            .ok();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekBackward),
-                        f,
-                        chunks_middle_column3[5],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekBackwardInput),
-                        f,
-                        chunks_middle_column4[5],
-                    );
+        self.app.umount(&Id::KeyEditor(IdKeyEditor::RadioOk)).ok();
+        self.app.unlock_subs();
+        self.library_reload_tree();
+        self.playlist_reload();
+        self.database_reload();
+        self.global_fix_focus();
+        assert!(self
+            .app
+            .remount(
+                Id::GlobalListener,
+                Box::new(GlobalListener::new(&self.config.keys)),
+                Self::subscribe(&self.config.keys),
+            )
+            .is_ok());
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedUp),
-                        f,
-                        chunks_middle_column3[6],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedUpInput),
-                        f,
-                        chunks_middle_column4[6],
-                    );
+        assert!(self
+            .app
+            .remount(
+                Id::Label,
+                Box::new(
+                    Label::default()
+                        .text(format!(
+                            "Press <{}> for help. Version: {}",
+                            self.config.keys.global_help, VERSION,
+                        ))
+                        .alignment(Alignment::Left)
+                        .background(Color::Reset)
+                        .foreground(Color::Cyan)
+                        .modifiers(TextModifiers::BOLD),
+                ),
+                Vec::default(),
+            )
+            .is_ok());
+        if let Err(e) = self.update_photo() {
+            self.mount_error_popup(format!("clear photo error: {}", e).as_str());
+        }
+    }
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedDown),
-                        f,
-                        chunks_middle_column3[7],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedDownInput),
-                        f,
-                        chunks_middle_column4[7],
-                    );
+    pub fn mount_key_editor_help(&mut self) {
+        assert!(self
+            .app
+            .remount(
+                Id::KeyEditor(IdKeyEditor::HelpPopup),
+                Box::new(KEHelpPopup::new(&self.config.keys)),
+                vec![]
+            )
+            .is_ok());
+        // Active help
+        assert!(self
+            .app
+            .active(&Id::KeyEditor(IdKeyEditor::HelpPopup))
+            .is_ok());
+    }
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustForward),
-                        f,
-                        chunks_middle_column3[8],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustForwardInput),
-                        f,
-                        chunks_middle_column4[8],
-                    );
+    #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+    fn view_key_editor(&mut self) {
+        let select_global_quit_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalQuit)) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_left_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalLeft)) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_right_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalRight))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_up_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalUp)) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_down_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalDown)) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_goto_top_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalGotoTop)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_global_goto_bottom_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalGotoBottom))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_toggle_pause_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerTogglePause))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_next_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerNext))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_previous_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerPrevious))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustBackward),
-                        f,
-                        chunks_middle_column5[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustBackwardInput),
-                        f,
-                        chunks_middle_column6[0],
-                    );
+        let select_global_help_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalHelp)) {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLyricCycle),
-                        f,
-                        chunks_middle_column5[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLyricCycleInput),
-                        f,
-                        chunks_middle_column6[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalColorEditor),
-                        f,
-                        chunks_middle_column5[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalColorEditorInput),
-                        f,
-                        chunks_middle_column6[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalKeyEditor),
-                        f,
-                        chunks_middle_column5[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalKeyEditorInput),
-                        f,
-                        chunks_middle_column6[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryTagEditor),
-                        f,
-                        chunks_middle_column5[4],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryTagEditorInput),
-                        f,
-                        chunks_middle_column6[4],
-                    );
+        let select_global_volume_up_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalVolumeUp)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryDelete),
-                        f,
-                        chunks_middle_column5[5],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryDeleteInput),
-                        f,
-                        chunks_middle_column6[5],
-                    );
+        let select_global_volume_down_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalVolumeDown))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryLoadDir),
-                        f,
-                        chunks_middle_column5[6],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryLoadDirInput),
-                        f,
-                        chunks_middle_column6[6],
-                    );
+        let select_global_player_seek_forward_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekForward))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_seek_backward_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekBackward))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_speed_up_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedUp))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_player_speed_down_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedDown))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryYank),
-                        f,
-                        chunks_middle_column5[7],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryYankInput),
-                        f,
-                        chunks_middle_column6[7],
-                    );
+        let select_global_lyric_adjust_forward_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustForward))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_lyric_adjust_backward_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustBackward))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_lyric_cycle_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalLyricCycle))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_color_editor_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalColorEditor))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_global_key_editor_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::GlobalKeyEditor)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryPaste),
-                        f,
-                        chunks_middle_column5[8],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibraryPasteInput),
-                        f,
-                        chunks_middle_column6[8],
-                    );
+        let select_library_delete_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryDelete)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_library_load_dir_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryLoadDir)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_library_yank_len = match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryYank))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_library_paste_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibraryPaste)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_library_search_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::LibrarySearch)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_library_search_youtube_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::LibrarySearchYoutube))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_library_tag_editor_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::LibraryTagEditor))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_delete_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistDelete)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_playlist_delete_all_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::PlaylistDeleteAll))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibrarySearch),
-                        f,
-                        chunks_middle_column7[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibrarySearchInput),
-                        f,
-                        chunks_middle_column8[0],
-                    );
+        let select_playlist_shuffle_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistShuffle)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibrarySearchYoutube),
-                        f,
-                        chunks_middle_column7[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::LibrarySearchYoutubeInput),
-                        f,
-                        chunks_middle_column8[1],
-                    );
+        let select_playlist_mode_cycle_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::PlaylistModeCycle))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_add_front_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::PlaylistAddFront))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
+        let select_playlist_search_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistSearch)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
+        let select_playlist_play_selected_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::PlaylistPlaySelected))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistDelete),
-                        f,
-                        chunks_middle_column7[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistDeleteInput),
-                        f,
-                        chunks_middle_column8[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistDeleteAll),
-                        f,
-                        chunks_middle_column7[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistDeleteAllInput),
-                        f,
-                        chunks_middle_column8[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistSearch),
-                        f,
-                        chunks_middle_column7[4],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistSearchInput),
-                        f,
-                        chunks_middle_column8[4],
-                    );
+        let select_playlist_swap_down_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::PlaylistSwapDown))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistShuffle),
-                        f,
-                        chunks_middle_column7[5],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistShuffleInput),
-                        f,
-                        chunks_middle_column8[5],
-                    );
+        let select_playlist_swap_up_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::PlaylistSwapUp)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistAddFront),
-                        f,
-                        chunks_middle_column7[6],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistAddFrontInput),
-                        f,
-                        chunks_middle_column8[6],
-                    );
+        let select_global_layout_treeview_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalLayoutTreeview))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistModeCycle),
-                        f,
-                        chunks_middle_column7[7],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistModeCycleInput),
-                        f,
-                        chunks_middle_column8[7],
-                    );
+        let select_global_layout_database_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalLayoutDatabase))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistPlaySelected),
-                        f,
-                        chunks_middle_column7[8],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistPlaySelectedInput),
-                        f,
-                        chunks_middle_column8[8],
-                    );
+        let select_database_add_all_len =
+            match self.app.state(&Id::KeyEditor(IdKeyEditor::DatabaseAddAll)) {
+                Ok(State::One(_)) => 3,
+                _ => 8,
+            };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistSwapDown),
-                        f,
-                        chunks_middle_column9[0],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistSwapDownInput),
-                        f,
-                        chunks_middle_column10[0],
-                    );
+        let select_global_player_toggle_gapless_len = match self
+            .app
+            .state(&Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGapless))
+        {
+            Ok(State::One(_)) => 3,
+            _ => 8,
+        };
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistSwapUp),
-                        f,
-                        chunks_middle_column9[1],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::PlaylistSwapUpInput),
-                        f,
-                        chunks_middle_column10[1],
-                    );
+        // the focus tells which page to show, the ok button and the popups keep it
+        if let Some(Id::KeyEditor(id)) = self.app.focus() {
+            if *id != IdKeyEditor::RadioOk && *id != IdKeyEditor::HelpPopup {
+                self.ke_second_page = KE_SECOND_PAGE
+                    .iter()
+                    .any(|(select, input)| select == id || input == id);
+            }
+        }
+        let second_page_lens: Vec<u16> = KE_SECOND_PAGE
+            .iter()
+            .map(
+                |(select, _)| match self.app.state(&Id::KeyEditor(select.clone())) {
+                    Ok(State::One(_)) => 3,
+                    _ => 8,
+                },
+            )
+            .collect();
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLayoutTreeview),
-                        f,
-                        chunks_middle_column9[2],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLayoutTreeviewInput),
-                        f,
-                        chunks_middle_column10[2],
-                    );
+        assert!(self
+            .terminal
+            .raw_mut()
+            .draw(|f| {
+                if self.app.mounted(&Id::KeyEditor(IdKeyEditor::LabelHint)) {
+                    f.render_widget(Clear, f.size());
+                    let chunks_main = Layout::default()
+                        .direction(Direction::Vertical)
+                        .margin(0)
+                        .constraints(
+                            [
+                                Constraint::Length(1),
+                                Constraint::Min(2),
+                                Constraint::Length(3),
+                                Constraint::Length(1),
+                            ]
+                            .as_ref(),
+                        )
+                        .split(f.size());
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLayoutDatabase),
-                        f,
-                        chunks_middle_column9[3],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalLayoutDatabaseInput),
-                        f,
-                        chunks_middle_column10[3],
-                    );
+                    self.app
+                        .view(&Id::KeyEditor(IdKeyEditor::LabelHint), f, chunks_main[0]);
+                    self.app
+                        .view(&Id::KeyEditor(IdKeyEditor::RadioOk), f, chunks_main[2]);
+                    self.app.view(&Id::Label, f, chunks_main[3]);
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::DatabaseAddAll),
-                        f,
-                        chunks_middle_column9[4],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::DatabaseAddAllInput),
-                        f,
-                        chunks_middle_column10[4],
-                    );
+                    let chunks_middle = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .margin(0)
+                        .constraints(
+                            [
+                                Constraint::Ratio(1, 7),
+                                Constraint::Ratio(2, 35),
+                                Constraint::Ratio(1, 7),
+                                Constraint::Ratio(2, 35),
+                                Constraint::Ratio(1, 7),
+                                Constraint::Ratio(2, 35),
+                                Constraint::Ratio(1, 7),
+                                Constraint::Ratio(2, 35),
+                                Constraint::Ratio(1, 7),
+                                Constraint::Ratio(2, 35),
+                            ]
+                            .as_ref(),
+                        )
+                        .split(chunks_main[1]);
 
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGapless),
-                        f,
-                        chunks_middle_column9[5],
-                    );
-                    self.app.view(
-                        &Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGaplessInput),
-                        f,
-                        chunks_middle_column10[5],
-                    );
+                    if self.ke_second_page {
+                        // six keys in each of the five columns
+                        for (column, keys) in KE_SECOND_PAGE.chunks(6).enumerate() {
+                            let constraints: Vec<Constraint> = second_page_lens[column * 6..]
+                                .iter()
+                                .take(keys.len())
+                                .map(|len| Constraint::Length(*len))
+                                .chain(std::iter::once(Constraint::Min(0)))
+                                .collect();
+                            let chunks_select = Layout::default()
+                                .direction(Direction::Vertical)
+                                .margin(0)
+                                .constraints(constraints.clone())
+                                .split(chunks_middle[column * 2]);
+                            let chunks_input = Layout::default()
+                                .direction(Direction::Vertical)
+                                .margin(0)
+                                .constraints(constraints)
+                                .split(chunks_middle[column * 2 + 1]);
+                            for (row, (select, input)) in keys.iter().enumerate() {
+                                self.app.view(
+                                    &Id::KeyEditor(select.clone()),
+                                    f,
+                                    chunks_select[row],
+                                );
+                                self.app
+                                    .view(&Id::KeyEditor(input.clone()), f, chunks_input[row]);
+                            }
+                        }
+                    } else {
+                        let chunks_middle_column1 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_global_quit_len),
+                                    Constraint::Length(select_global_left_len),
+                                    Constraint::Length(select_global_down_len),
+                                    Constraint::Length(select_global_up_len),
+                                    Constraint::Length(select_global_right_len),
+                                    Constraint::Length(select_global_goto_top_len),
+                                    Constraint::Length(select_global_goto_bottom_len),
+                                    Constraint::Length(select_global_player_toggle_pause_len),
+                                    Constraint::Length(select_global_player_next_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[0]);
+                        let chunks_middle_column2 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_global_quit_len),
+                                    Constraint::Length(select_global_left_len),
+                                    Constraint::Length(select_global_down_len),
+                                    Constraint::Length(select_global_up_len),
+                                    Constraint::Length(select_global_right_len),
+                                    Constraint::Length(select_global_goto_top_len),
+                                    Constraint::Length(select_global_goto_bottom_len),
+                                    Constraint::Length(select_global_player_toggle_pause_len),
+                                    Constraint::Length(select_global_player_next_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[1]);
+                        let chunks_middle_column3 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_global_player_previous_len),
+                                    Constraint::Length(select_global_help_len),
+                                    Constraint::Length(select_global_volume_up_len),
+                                    Constraint::Length(select_global_volume_down_len),
+                                    Constraint::Length(select_global_player_seek_forward_len),
+                                    Constraint::Length(select_global_player_seek_backward_len),
+                                    Constraint::Length(select_global_player_speed_up_len),
+                                    Constraint::Length(select_global_player_speed_down_len),
+                                    Constraint::Length(select_global_lyric_adjust_forward_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[2]);
+                        let chunks_middle_column4 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_global_player_previous_len),
+                                    Constraint::Length(select_global_help_len),
+                                    Constraint::Length(select_global_volume_up_len),
+                                    Constraint::Length(select_global_volume_down_len),
+                                    Constraint::Length(select_global_player_seek_forward_len),
+                                    Constraint::Length(select_global_player_seek_backward_len),
+                                    Constraint::Length(select_global_player_speed_up_len),
+                                    Constraint::Length(select_global_player_speed_down_len),
+                                    Constraint::Length(select_global_lyric_adjust_forward_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[3]);
+                        let chunks_middle_column5 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_global_lyric_adjust_backward_len),
+                                    Constraint::Length(select_global_lyric_cycle_len),
+                                    Constraint::Length(select_global_color_editor_len),
+                                    Constraint::Length(select_global_key_editor_len),
+                                    Constraint::Length(select_library_tag_editor_len),
+                                    Constraint::Length(select_library_delete_len),
+                                    Constraint::Length(select_library_load_dir_len),
+                                    Constraint::Length(select_library_yank_len),
+                                    Constraint::Length(select_library_paste_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[4]);
+                        let chunks_middle_column6 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_global_lyric_adjust_backward_len),
+                                    Constraint::Length(select_global_lyric_cycle_len),
+                                    Constraint::Length(select_global_color_editor_len),
+                                    Constraint::Length(select_global_key_editor_len),
+                                    Constraint::Length(select_library_tag_editor_len),
+                                    Constraint::Length(select_library_delete_len),
+                                    Constraint::Length(select_library_load_dir_len),
+                                    Constraint::Length(select_library_yank_len),
+                                    Constraint::Length(select_library_paste_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[5]);
+
+                        let chunks_middle_column7 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_library_search_len),
+                                    Constraint::Length(select_library_search_youtube_len),
+                                    Constraint::Length(select_playlist_delete_len),
+                                    Constraint::Length(select_playlist_delete_all_len),
+                                    Constraint::Length(select_playlist_search_len),
+                                    Constraint::Length(select_playlist_shuffle_len),
+                                    Constraint::Length(select_playlist_add_front_len),
+                                    Constraint::Length(select_playlist_mode_cycle_len),
+                                    Constraint::Length(select_playlist_play_selected_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[6]);
+                        let chunks_middle_column8 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_library_search_len),
+                                    Constraint::Length(select_library_search_youtube_len),
+                                    Constraint::Length(select_playlist_delete_len),
+                                    Constraint::Length(select_playlist_delete_all_len),
+                                    Constraint::Length(select_playlist_search_len),
+                                    Constraint::Length(select_playlist_shuffle_len),
+                                    Constraint::Length(select_playlist_add_front_len),
+                                    Constraint::Length(select_playlist_mode_cycle_len),
+                                    Constraint::Length(select_playlist_play_selected_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[7]);
+
+                        let chunks_middle_column9 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_playlist_swap_down_len),
+                                    Constraint::Length(select_playlist_swap_up_len),
+                                    Constraint::Length(select_global_layout_treeview_len),
+                                    Constraint::Length(select_global_layout_database_len),
+                                    Constraint::Length(select_database_add_all_len),
+                                    Constraint::Length(select_global_player_toggle_gapless_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[8]);
+                        let chunks_middle_column10 = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(0)
+                            .constraints(
+                                [
+                                    Constraint::Length(select_playlist_swap_down_len),
+                                    Constraint::Length(select_playlist_swap_up_len),
+                                    Constraint::Length(select_global_layout_treeview_len),
+                                    Constraint::Length(select_global_layout_database_len),
+                                    Constraint::Length(select_database_add_all_len),
+                                    Constraint::Length(select_global_player_toggle_gapless_len),
+                                    Constraint::Min(0),
+                                ]
+                                .as_ref(),
+                            )
+                            .split(chunks_middle[9]);
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalQuit),
+                            f,
+                            chunks_middle_column1[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalQuitInput),
+                            f,
+                            chunks_middle_column2[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLeft),
+                            f,
+                            chunks_middle_column1[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLeftInput),
+                            f,
+                            chunks_middle_column2[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalDown),
+                            f,
+                            chunks_middle_column1[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalDownInput),
+                            f,
+                            chunks_middle_column2[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalUp),
+                            f,
+                            chunks_middle_column1[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalUpInput),
+                            f,
+                            chunks_middle_column2[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalRight),
+                            f,
+                            chunks_middle_column1[4],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalRightInput),
+                            f,
+                            chunks_middle_column2[4],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalGotoTop),
+                            f,
+                            chunks_middle_column1[5],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalGotoTopInput),
+                            f,
+                            chunks_middle_column2[5],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalGotoBottom),
+                            f,
+                            chunks_middle_column1[6],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalGotoBottomInput),
+                            f,
+                            chunks_middle_column2[6],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerTogglePause),
+                            f,
+                            chunks_middle_column1[7],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerTogglePauseInput),
+                            f,
+                            chunks_middle_column2[7],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerNext),
+                            f,
+                            chunks_middle_column1[8],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerNextInput),
+                            f,
+                            chunks_middle_column2[8],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerPrevious),
+                            f,
+                            chunks_middle_column3[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerPreviousInput),
+                            f,
+                            chunks_middle_column4[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalHelp),
+                            f,
+                            chunks_middle_column3[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalHelpInput),
+                            f,
+                            chunks_middle_column4[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalVolumeUp),
+                            f,
+                            chunks_middle_column3[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalVolumeUpInput),
+                            f,
+                            chunks_middle_column4[2],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalVolumeDown),
+                            f,
+                            chunks_middle_column3[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalVolumeDownInput),
+                            f,
+                            chunks_middle_column4[3],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekForward),
+                            f,
+                            chunks_middle_column3[4],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekForwardInput),
+                            f,
+                            chunks_middle_column4[4],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekBackward),
+                            f,
+                            chunks_middle_column3[5],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSeekBackwardInput),
+                            f,
+                            chunks_middle_column4[5],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedUp),
+                            f,
+                            chunks_middle_column3[6],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedUpInput),
+                            f,
+                            chunks_middle_column4[6],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedDown),
+                            f,
+                            chunks_middle_column3[7],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerSpeedDownInput),
+                            f,
+                            chunks_middle_column4[7],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustForward),
+                            f,
+                            chunks_middle_column3[8],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustForwardInput),
+                            f,
+                            chunks_middle_column4[8],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustBackward),
+                            f,
+                            chunks_middle_column5[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLyricAdjustBackwardInput),
+                            f,
+                            chunks_middle_column6[0],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLyricCycle),
+                            f,
+                            chunks_middle_column5[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLyricCycleInput),
+                            f,
+                            chunks_middle_column6[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalColorEditor),
+                            f,
+                            chunks_middle_column5[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalColorEditorInput),
+                            f,
+                            chunks_middle_column6[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalKeyEditor),
+                            f,
+                            chunks_middle_column5[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalKeyEditorInput),
+                            f,
+                            chunks_middle_column6[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryTagEditor),
+                            f,
+                            chunks_middle_column5[4],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryTagEditorInput),
+                            f,
+                            chunks_middle_column6[4],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryDelete),
+                            f,
+                            chunks_middle_column5[5],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryDeleteInput),
+                            f,
+                            chunks_middle_column6[5],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryLoadDir),
+                            f,
+                            chunks_middle_column5[6],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryLoadDirInput),
+                            f,
+                            chunks_middle_column6[6],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryYank),
+                            f,
+                            chunks_middle_column5[7],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryYankInput),
+                            f,
+                            chunks_middle_column6[7],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryPaste),
+                            f,
+                            chunks_middle_column5[8],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibraryPasteInput),
+                            f,
+                            chunks_middle_column6[8],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibrarySearch),
+                            f,
+                            chunks_middle_column7[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibrarySearchInput),
+                            f,
+                            chunks_middle_column8[0],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibrarySearchYoutube),
+                            f,
+                            chunks_middle_column7[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::LibrarySearchYoutubeInput),
+                            f,
+                            chunks_middle_column8[1],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistDelete),
+                            f,
+                            chunks_middle_column7[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistDeleteInput),
+                            f,
+                            chunks_middle_column8[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistDeleteAll),
+                            f,
+                            chunks_middle_column7[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistDeleteAllInput),
+                            f,
+                            chunks_middle_column8[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistSearch),
+                            f,
+                            chunks_middle_column7[4],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistSearchInput),
+                            f,
+                            chunks_middle_column8[4],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistShuffle),
+                            f,
+                            chunks_middle_column7[5],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistShuffleInput),
+                            f,
+                            chunks_middle_column8[5],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistAddFront),
+                            f,
+                            chunks_middle_column7[6],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistAddFrontInput),
+                            f,
+                            chunks_middle_column8[6],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistModeCycle),
+                            f,
+                            chunks_middle_column7[7],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistModeCycleInput),
+                            f,
+                            chunks_middle_column8[7],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistPlaySelected),
+                            f,
+                            chunks_middle_column7[8],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistPlaySelectedInput),
+                            f,
+                            chunks_middle_column8[8],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistSwapDown),
+                            f,
+                            chunks_middle_column9[0],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistSwapDownInput),
+                            f,
+                            chunks_middle_column10[0],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistSwapUp),
+                            f,
+                            chunks_middle_column9[1],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::PlaylistSwapUpInput),
+                            f,
+                            chunks_middle_column10[1],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLayoutTreeview),
+                            f,
+                            chunks_middle_column9[2],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLayoutTreeviewInput),
+                            f,
+                            chunks_middle_column10[2],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLayoutDatabase),
+                            f,
+                            chunks_middle_column9[3],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalLayoutDatabaseInput),
+                            f,
+                            chunks_middle_column10[3],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::DatabaseAddAll),
+                            f,
+                            chunks_middle_column9[4],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::DatabaseAddAllInput),
+                            f,
+                            chunks_middle_column10[4],
+                        );
+
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGapless),
+                            f,
+                            chunks_middle_column9[5],
+                        );
+                        self.app.view(
+                            &Id::KeyEditor(IdKeyEditor::GlobalPlayerToggleGaplessInput),
+                            f,
+                            chunks_middle_column10[5],
+                        );
+                    }
                     if self.app.mounted(&Id::KeyEditor(IdKeyEditor::HelpPopup)) {
                         let popup = draw_area_in_relative(f.size(), 50, 70);
                         f.render_widget(Clear, popup);
                         self.app
                             .view(&Id::KeyEditor(IdKeyEditor::HelpPopup), f, popup);
                     }
+                    if self.app.mounted(&Id::ErrorPopup) {
+                        let popup = draw_area_in_absolute(f.size(), 50, 4);
+                        f.render_widget(Clear, popup);
+                        self.app.view(&Id::ErrorPopup, f, popup);
+                    }
                 }
             })
             .is_ok());
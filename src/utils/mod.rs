@@ -50,28 +50,17 @@ pub fn parse_hex_color(color: &str) -> Option<Color> {
 pub fn filetype_supported(current_node: &str) -> bool {
     let p = Path::new(current_node);
 
-    #[cfg(any(feature = "mpv", feature = "gst"))]
-    if let Some(ext) = p.extension() {
-        if ext == "opus" {
-            return true;
-        }
-        if ext == "aiff" {
-            return true;
-        }
-        if ext == "webm" {
-            return true;
-        }
-    }
-
     match p.extension() {
         Some(ext) if ext == "mp3" => true,
-        // Some(ext) if ext == "aiff" => true,
+        Some(ext) if ext == "aiff" => true,
+        Some(ext) if ext == "aif" => true,
         Some(ext) if ext == "flac" => true,
         Some(ext) if ext == "m4a" => true,
-        // Some(ext) if ext == "opus" => true,
+        Some(ext) if ext == "m4b" => true,
+        Some(ext) if ext == "opus" => true,
         Some(ext) if ext == "ogg" => true,
         Some(ext) if ext == "wav" => true,
-        // Some(ext) if ext == "webm" => true,
+        Some(ext) if ext == "webm" => true,
         Some(_) | None => false,
     }
 }
@@ -89,6 +78,11 @@ pub fn is_playlist(current_node: &str) -> bool {
     }
 }
 
+/// Whether the location is a url to stream from, rather than a file.
+pub fn is_stream(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
 #[cfg(test)]
 #[allow(clippy::non_ascii_literal)]
 mod tests {